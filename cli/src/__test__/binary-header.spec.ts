@@ -0,0 +1,92 @@
+import test from 'ava'
+
+import { checkBinaryHeader, readBinaryHeader } from '../binary-header'
+import { parseTriple } from '../parse-triple'
+
+function elf(machine: number, osabi = 0, libc = 'libc.so.6') {
+  const content = Buffer.alloc(0x80)
+  content.writeUInt32BE(0x7f454c46, 0)
+  content[4] = 2
+  content[5] = 1
+  content[7] = osabi
+  content.writeUInt16LE(machine, 0x12)
+  content.write(libc, 0x40)
+  return content
+}
+
+function machO(cpuType: number) {
+  const content = Buffer.alloc(0x40)
+  content.writeUInt32LE(0xfeedfacf, 0)
+  content.writeUInt32LE(cpuType, 4)
+  return content
+}
+
+function universal(cpuTypes: number[]) {
+  const content = Buffer.alloc(0x80)
+  content.writeUInt32BE(0xcafebabe, 0)
+  content.writeUInt32BE(cpuTypes.length, 4)
+  cpuTypes.forEach((cpuType, i) => content.writeUInt32BE(cpuType, 8 + i * 20))
+  return content
+}
+
+function pe(machine: number) {
+  const content = Buffer.alloc(0x100)
+  content.write('MZ', 0)
+  content.writeUInt32LE(0x80, 0x3c)
+  content.writeUInt32BE(0x50450000, 0x80)
+  content.writeUInt16LE(machine, 0x84)
+  return content
+}
+
+const matched = [
+  { name: 'x86_64-unknown-linux-gnu', content: elf(0x3e) },
+  {
+    name: 'aarch64-unknown-linux-musl',
+    content: elf(0xb7, 0, 'libc.musl-aarch64.so.1'),
+  },
+  { name: 'x86_64-unknown-freebsd', content: elf(0x3e, 9, '') },
+  { name: 'aarch64-linux-android', content: elf(0xb7, 0, 'libc.so') },
+  { name: 'aarch64-apple-darwin', content: machO(0x0100000c) },
+  {
+    name: 'universal-apple-darwin',
+    content: universal([0x01000007, 0x0100000c]),
+  },
+  { name: 'x86_64-pc-windows-msvc', content: pe(0x8664) },
+  { name: 'i686-pc-windows-msvc', content: pe(0x14c) },
+]
+
+for (const { name, content } of matched) {
+  test(`should accept the binary of ${name}`, (t) => {
+    const header = readBinaryHeader(content)
+    t.truthy(header)
+    t.deepEqual(checkBinaryHeader(header!, parseTriple(name)), [])
+  })
+}
+
+test('should report the architecture mix-up', (t) => {
+  const header = readBinaryHeader(elf(0x3e))!
+  t.deepEqual(
+    checkBinaryHeader(header, parseTriple('aarch64-unknown-linux-gnu')),
+    ['it is built for x64, not arm64'],
+  )
+})
+
+test('should report the platform mix-up', (t) => {
+  const header = readBinaryHeader(machO(0x01000007))!
+  t.deepEqual(
+    checkBinaryHeader(header, parseTriple('x86_64-pc-windows-msvc')),
+    ['it is a Mach-O binary for darwin, not win32'],
+  )
+})
+
+test('should report the libc mix-up', (t) => {
+  const header = readBinaryHeader(elf(0x3e))!
+  t.deepEqual(
+    checkBinaryHeader(header, parseTriple('x86_64-unknown-linux-musl')),
+    ['it is linked against gnu, not musl'],
+  )
+})
+
+test('should not recognize other files', (t) => {
+  t.is(readBinaryHeader(Buffer.from('module.exports = {}'.repeat(8))), null)
+})
@@ -115,6 +115,16 @@ const triples = [
       raw: 'armv7-linux-androideabi',
     },
   } as const,
+  {
+    name: 'wasm32-wasip1-threads',
+    expected: {
+      abi: null,
+      arch: 'wasm32',
+      platform: 'wasi',
+      platformArchABI: 'wasm32-wasi',
+      raw: 'wasm32-wasip1-threads',
+    },
+  } as const,
 ]
 
 for (const triple of triples) {
@@ -0,0 +1,23 @@
+import test from 'ava'
+
+import { bumpPrerelease } from '../version'
+
+const bumps = [
+  { from: '1.2.3', preid: 'beta', expected: '1.2.4-beta.0' },
+  { from: '1.2.4-beta.0', preid: 'beta', expected: '1.2.4-beta.1' },
+  { from: '1.2.4-beta.9', preid: 'beta', expected: '1.2.4-beta.10' },
+  { from: '1.2.4-alpha.3', preid: 'beta', expected: '1.2.4-beta.0' },
+  { from: '1.2.4-canary.1', preid: 'canary', expected: '1.2.4-canary.2' },
+]
+
+for (const { from, preid, expected } of bumps) {
+  test(`should bump ${from} with --preid ${preid}`, (t) => {
+    t.is(bumpPrerelease(from, preid), expected)
+  })
+}
+
+test('should reject invalid versions', (t) => {
+  t.throws(() => bumpPrerelease('latest', 'beta'), {
+    message: /Can not bump the version latest/,
+  })
+})
@@ -4,9 +4,13 @@ import chalk from 'chalk'
 import { Command, Option } from 'clipanion'
 import { fdir } from 'fdir'
 
+import { checkBinaryHeader, readBinaryHeader } from './binary-header'
 import { getNapiConfig } from './consts'
 import { debugFactory } from './debug'
-import { readFileAsync, writeFileAsync } from './utils'
+import { NapiError } from './diagnostics'
+import { download } from './network'
+import { Progress } from './progress'
+import { mkdirAsync, readFileAsync, writeFileAsync } from './utils'
 
 const debug = debugFactory('artifacts')
 
@@ -23,11 +27,73 @@ export class ArtifactsCommand extends Command {
 
   configFileName?: string = Option.String('-c,--config')
 
+  fromRelease?: string = Option.String('--from-release', {
+    description: `Download the artifacts from the assets of the Github release of this tag into ${chalk.green(
+      '--dir',
+    )} first`,
+  })
+
+  repository = Option.String('--repo', process.env.GITHUB_REPOSITORY ?? '', {
+    description: `The ${chalk.green(
+      'owner/repo',
+    )} of the Github release, default to ${chalk.green(
+      'GITHUB_REPOSITORY',
+    )}`,
+  })
+
+  skipHeaderCheck = Option.Boolean('--skip-header-check', false, {
+    description: `Don't verify the platform and the architecture in the headers of the ${chalk.green(
+      '.node',
+    )} files`,
+  })
+
+  offline = Option.Boolean('--offline', false, {
+    description: `Only use the downloads cached by previous runs`,
+  })
+
   async execute() {
-    const { platforms, binaryName, packageJsonPath } = getNapiConfig(
+    const { platforms, binaryName, packageJsonPath, wasm } = getNapiConfig(
       this.configFileName,
     )
 
+    if (this.fromRelease) {
+      if (!this.repository) {
+        throw new NapiError(
+          `${chalk.green('--from-release')} requires the Github repository`,
+          {
+            code: 'napi::artifacts::repository',
+            help: [
+              `Pass ${chalk.green('--repo owner/repo')} or set ${chalk.green(
+                'GITHUB_REPOSITORY',
+              )}`,
+            ],
+          },
+        )
+      }
+      const sourceDir = join(process.cwd(), this.sourceDir)
+      await mkdirAsync(sourceDir, { recursive: true })
+      const filenames = platforms.map(
+        (platform) => `${binaryName}.${platform.platformArchABI}.node`,
+      )
+      const progress = new Progress(`Download ${this.fromRelease}`, filenames)
+      try {
+        await Promise.all(
+          filenames.map((filename) =>
+            progress.track(filename, async () => {
+              const content = await download(
+                `https://github.com/${this.repository}/releases/download/${this.fromRelease}/${filename}`,
+                { offline: this.offline },
+              )
+              debug(`Write downloaded [${chalk.yellowBright(filename)}]`)
+              await writeFileAsync(join(sourceDir, filename), content)
+            }),
+          ),
+        )
+      } finally {
+        progress.finish()
+      }
+    }
+
     const packageJsonDir = parse(packageJsonPath).dir
 
     const sourceApi = new fdir()
@@ -37,6 +103,12 @@ export class ArtifactsCommand extends Command {
     const distDirs = platforms.map((platform) =>
       join(process.cwd(), this.distDir, platform.platformArchABI),
     )
+    // `${binaryName}.wasm32-wasi.wasm` goes into the package created by `create-npm-dir` with `napi.wasm`
+    if (wasm) {
+      distDirs.push(join(process.cwd(), this.distDir, 'wasm32-wasi'))
+    }
+    // reported together after all files are checked, to show the whole mix-up at once
+    const mismatches: string[] = []
 
     await sourceApi.withPromise().then((output) =>
       Promise.all(
@@ -54,7 +126,40 @@ export class ArtifactsCommand extends Command {
           }
           const dir = distDirs.find((dir) => dir.includes(platformArchABI))
           if (!dir) {
-            throw new TypeError(`No dist dir found for ${filePath}`)
+            throw new NapiError(`No dist dir found for ${filePath}`, {
+              code: 'napi::artifacts::dist_dir',
+              causes: [
+                `${platformArchABI} is not in the triples of ${chalk.green(
+                  'napi',
+                )} in package.json`,
+              ],
+              help: [
+                `Add it to ${chalk.green('napi.triples.additional')}, then run ${chalk.green(
+                  'napi create-npm-dir',
+                )}`,
+              ],
+            })
+          }
+          const platform = platforms[distDirs.indexOf(dir)]
+          if (!this.skipHeaderCheck && parsedName.ext === '.node') {
+            const header = readBinaryHeader(sourceContent)
+            if (!header) {
+              debug(
+                `Unknown binary format of [${chalk.yellowBright(filePath)}]`,
+              )
+            } else {
+              const reasons = checkBinaryHeader(header, platform)
+              if (reasons.length) {
+                // never place a binary into the package of another platform
+                mismatches.push(
+                  ...reasons.map(
+                    (reason) =>
+                      `${parsedName.base} for ${platform.platformArchABI}: ${reason}`,
+                  ),
+                )
+                return
+              }
+            }
           }
           const distFilePath = join(dir, parsedName.base)
           debug(`Write file content to [${chalk.yellowBright(distFilePath)}]`)
@@ -67,5 +172,21 @@ export class ArtifactsCommand extends Command {
         }),
       ),
     )
+
+    if (mismatches.length) {
+      throw new NapiError(
+        'The binaries do not match the platforms they are placed in',
+        {
+          code: 'napi::artifacts::binary_header',
+          causes: mismatches,
+          help: [
+            'Check the targets of the CI build matrix and the names of the uploaded artifacts',
+            `Pass ${chalk.green(
+              '--skip-header-check',
+            )} if the binaries are correct`,
+          ],
+        },
+      )
+    }
   }
 }
@@ -0,0 +1,161 @@
+import { NodeJSArch, PlatformDetail } from './parse-triple'
+
+export interface BinaryHeader {
+  format: 'ELF' | 'Mach-O' | 'PE'
+  // ELF doesn't tell linux from android, both are reported as `linux`
+  platform: NodeJS.Platform
+  // more than one for the universal binaries of macOS
+  archs: NodeJSArch[]
+  // `musl` or `gnu` of linux, from the libc the binary is linked against
+  abi: string | null
+}
+
+// https://refspecs.linuxfoundation.org/elf/gabi4+/ch4.eheader.html
+const ElfMachines: Record<number, NodeJSArch> = {
+  0x03: 'ia32',
+  0x08: 'mips',
+  0x14: 'ppc',
+  0x15: 'ppc64',
+  0x16: 's390x',
+  0x28: 'arm',
+  0x3e: 'x64',
+  0xb7: 'arm64',
+}
+
+const ELF_OSABI_FREEBSD = 9
+
+// https://github.com/apple-oss-distributions/xnu/blob/main/osfmk/mach/machine.h
+const MachOCpuTypes: Record<number, NodeJSArch> = {
+  0x7: 'ia32',
+  0x01000007: 'x64',
+  0xc: 'arm',
+  0x0100000c: 'arm64',
+}
+
+// https://docs.microsoft.com/en-us/windows/win32/debug/pe-format#machine-types
+const PeMachines: Record<number, NodeJSArch> = {
+  0x14c: 'ia32',
+  0x8664: 'x64',
+  0x1c4: 'arm',
+  0xaa64: 'arm64',
+}
+
+function readElfHeader(content: Buffer): BinaryHeader | null {
+  const littleEndian = content[5] === 1
+  const machine = littleEndian
+    ? content.readUInt16LE(0x12)
+    : content.readUInt16BE(0x12)
+  const arch = ElfMachines[machine]
+  if (!arch) {
+    return null
+  }
+  const platform = content[7] === ELF_OSABI_FREEBSD ? 'freebsd' : 'linux'
+  let abi: string | null = null
+  if (platform === 'linux') {
+    // the libc is one of the needed libraries in the dynamic section
+    if (content.includes('libc.musl-') || content.includes('ld-musl-')) {
+      abi = 'musl'
+    } else if (content.includes('libc.so.6')) {
+      abi = 'gnu'
+    }
+  }
+  return { format: 'ELF', platform, archs: [arch], abi }
+}
+
+function readMachOHeader(content: Buffer): BinaryHeader | null {
+  const magic = content.readUInt32BE(0)
+  const archs: NodeJSArch[] = []
+  if (magic === 0xcafebabe) {
+    // the universal binary lists a `fat_arch` of 20 bytes for every arch
+    const count = content.readUInt32BE(4)
+    for (let i = 0; i < count; i++) {
+      const offset = 8 + i * 20
+      if (offset + 4 > content.length) {
+        break
+      }
+      const arch = MachOCpuTypes[content.readUInt32BE(offset)]
+      if (arch) {
+        archs.push(arch)
+      }
+    }
+  } else {
+    const arch = MachOCpuTypes[content.readUInt32LE(4)]
+    if (arch) {
+      archs.push(arch)
+    }
+  }
+  return archs.length
+    ? { format: 'Mach-O', platform: 'darwin', archs, abi: null }
+    : null
+}
+
+function readPeHeader(content: Buffer): BinaryHeader | null {
+  const peOffset = content.readUInt32LE(0x3c)
+  if (
+    peOffset + 6 > content.length ||
+    content.readUInt32BE(peOffset) !== 0x50450000
+  ) {
+    return null
+  }
+  const arch = PeMachines[content.readUInt16LE(peOffset + 4)]
+  return arch
+    ? { format: 'PE', platform: 'win32', archs: [arch], abi: null }
+    : null
+}
+
+/**
+ * The platform and the architecture a native addon is built for, null if the format is not recognized
+ */
+export function readBinaryHeader(content: Buffer): BinaryHeader | null {
+  if (content.length < 0x40) {
+    return null
+  }
+  const magic = content.readUInt32BE(0)
+  if (magic === 0x7f454c46) {
+    return readElfHeader(content)
+  }
+  if (magic === 0xcafebabe || magic === 0xcefaedfe || magic === 0xcffaedfe) {
+    return readMachOHeader(content)
+  }
+  if (content.readUInt16BE(0) === 0x4d5a) {
+    return readPeHeader(content)
+  }
+  return null
+}
+
+/**
+ * The reasons `header` doesn't match the platform, empty if it does
+ */
+export function checkBinaryHeader(
+  header: BinaryHeader,
+  platform: PlatformDetail,
+): string[] {
+  const mismatches: string[] = []
+  const headerPlatform =
+    header.platform === 'linux' && platform.platform === 'android'
+      ? 'android'
+      : header.platform
+  if (headerPlatform !== platform.platform) {
+    mismatches.push(
+      `it is a ${header.format} binary for ${header.platform}, not ${platform.platform}`,
+    )
+  }
+  // `universal-apple-darwin` is the fat binary of `lipo`
+  const archMatched =
+    (platform.arch as string) === 'universal'
+      ? header.archs.length > 1
+      : header.archs.includes(platform.arch)
+  if (!archMatched) {
+    mismatches.push(
+      `it is built for ${header.archs.join(', ')}, not ${platform.arch}`,
+    )
+  }
+  if (
+    header.abi &&
+    (platform.abi === 'gnu' || platform.abi === 'musl') &&
+    header.abi !== platform.abi
+  ) {
+    mismatches.push(`it is linked against ${header.abi}, not ${platform.abi}`)
+  }
+  return mismatches
+}
@@ -1,17 +1,20 @@
 import { execSync } from 'child_process'
 import { existsSync, mkdirSync } from 'fs'
-import { join, parse, sep } from 'path'
+import { basename, dirname, join, parse, sep } from 'path'
 
 import { Instance } from 'chalk'
 import { Command, Option } from 'clipanion'
 import envPaths from 'env-paths'
+import inquirer from 'inquirer'
 import { groupBy } from 'lodash-es'
 import toml from 'toml'
 
 import { getNapiConfig } from './consts'
 import { debugFactory } from './debug'
-import { createJsBinding } from './js-binding-template'
+import { NapiError } from './diagnostics'
+import { createEsmJsBinding, createJsBinding } from './js-binding-template'
 import { getDefaultTargetTriple, parseTriple } from './parse-triple'
+import { Progress } from './progress'
 import {
   copyFileAsync,
   mkdirAsync,
@@ -171,7 +174,57 @@ export class BuildCommand extends Command {
     description: `${chalk.green('Strip')} the library for minimum file size`,
   })
 
+  enumType = Option.String('--enum-type', 'const', {
+    description: `Emit enums in d.ts as ${chalk.green(
+      'const enum',
+    )}, ${chalk.green('enum')} or a readonly object with a ${chalk.green(
+      'union',
+    )} type`,
+  })
+
+  dtsHeader?: string = Option.String('--dts-header', {
+    description: `Path to a file which replaces the auto-generated header of the ${chalk.green(
+      '.d.ts',
+    )} file, relative to cwd`,
+  })
+
+  dtsExportDefault = Option.Boolean('--dts-export-default', false, {
+    description: `Declare the whole binding as the ${chalk.green(
+      'export default',
+    )} of the ${chalk.green('.d.ts')} file as well`,
+  })
+
+  dual?: boolean = Option.Boolean('--dual', {
+    description: `Emit ${chalk.green('.d.cts/.d.mts')} typings and ${chalk.green(
+      '.cjs/.mjs',
+    )} js bindings for a dual package. Default to ${chalk.green(
+      'napi.dual',
+    )} in ${chalk.underline(chalk.yellowBright('package.json'))}`,
+  })
+
+  autoInstallTarget = Option.Boolean('--auto-install-target', false, {
+    description: `Run ${chalk.green(
+      'rustup target add',
+    )} without asking if the ${chalk.green(
+      '--target',
+    )} is not installed in the active toolchain`,
+  })
+
+  checkDts = Option.Boolean('--check-dts', false, {
+    description: `Run ${chalk.green(
+      'tsc --noEmit',
+    )} against the generated ${chalk.green(
+      '.d.ts',
+    )} files and fail the build if they are invalid`,
+  })
+
   async execute() {
+    if (!ENUM_TYPES.includes(this.enumType as EnumType)) {
+      throw new NapiError(`Unknown --enum-type: ${this.enumType}`, {
+        code: 'napi::build::enum_type',
+        help: [`Use one of ${ENUM_TYPES.join(', ')}`],
+      })
+    }
     const cwd = this.cargoCwd
       ? join(process.cwd(), this.cargoCwd)
       : process.cwd()
@@ -233,12 +286,26 @@ export class BuildCommand extends Command {
       additionalEnv['RUSTFLAGS'] = rustflags.join(' ')
     }
 
+    if (triple.platform === 'wasi' && !process.env.EMNAPI_LINK_DIR) {
+      // `napi-build` links the static libraries of the emnapi installed in the project
+      additionalEnv['EMNAPI_LINK_DIR'] = join(
+        resolveEmnapi(cwd),
+        'lib',
+        'wasm32-wasi-threads',
+      )
+    }
+
     if (this.useZig) {
       const zigTarget = `${ZIG_PLATFORM_TARGET_MAP[triple.raw]}${
         this.zigABIVersion ? `.${this.zigABIVersion}` : ''
       }`
       if (!zigTarget) {
-        throw new Error(`${triple.raw} can not be cross compiled by zig`)
+        throw new NapiError(`${triple.raw} can not be cross compiled by zig`, {
+          code: 'napi::build::zig_target',
+          help: [
+            `zig supports ${Object.keys(ZIG_PLATFORM_TARGET_MAP).join(', ')}`,
+          ],
+        })
       }
       const paths = envPaths('napi-rs')
       const linkerWrapperShell = join(
@@ -279,16 +346,38 @@ export class BuildCommand extends Command {
       additionalEnv[`CARGO_TARGET_${envTarget}_LINKER`] = linkerWrapperShell
     }
 
-    execSync(cargoCommand, {
-      env: {
-        ...process.env,
-        ...additionalEnv,
-        TYPE_DEF_TMP_PATH: intermediateTypeFile,
-      },
-      stdio: 'inherit',
-      cwd,
-    })
-    const { binaryName, packageName } = getNapiConfig(this.configFileName)
+    if (this.targetTripleDir) {
+      await ensureTargetInstalled(triple.raw, this.autoInstallTarget, cwd)
+    }
+
+    const progress = new Progress(`Build ${triple.raw}`, [
+      'cargo build',
+      'copy binary',
+      'type definitions',
+      'js binding',
+    ])
+    progress.start('cargo build')
+    progress.suspend()
+    try {
+      execSync(cargoCommand, {
+        env: {
+          ...process.env,
+          ...additionalEnv,
+          TYPE_DEF_TMP_PATH: intermediateTypeFile,
+        },
+        stdio: 'inherit',
+        cwd,
+      })
+    } catch (e) {
+      progress.fail('cargo build')
+      progress.finish()
+      throw e
+    }
+    progress.succeed('cargo build')
+    const { binaryName, packageName, dual, wasm } = getNapiConfig(
+      this.configFileName,
+    )
+    const isDual = this.dual ?? dual
     let dylibName = this.cargoName
     if (!dylibName) {
       let tomlContentString: string
@@ -300,27 +389,53 @@ export class BuildCommand extends Command {
           'utf-8',
         )
       } catch {
-        throw new TypeError(`Could not find Cargo.toml in ${cwd}`)
+        throw new NapiError(`Could not find Cargo.toml in ${cwd}`, {
+          code: 'napi::build::cargo_toml',
+          help: [
+            `Run it in the directory of the crate, or pass ${chalk.green(
+              '--cargo-cwd',
+            )} or ${chalk.green('--cargo-name')}`,
+          ],
+        })
       }
 
       try {
         debug('Start parse toml')
         tomlContent = toml.parse(tomlContentString)
-      } catch {
-        throw new TypeError('Could not parse the Cargo.toml')
+      } catch (e: any) {
+        throw new NapiError('Could not parse the Cargo.toml', {
+          code: 'napi::build::cargo_toml',
+          causes: [
+            e?.line
+              ? `line ${e.line}, column ${e.column}: ${e.message}`
+              : `${e}`,
+          ],
+        })
       }
 
       if (tomlContent.package?.name) {
         dylibName = tomlContent.package.name.replace(/-/g, '_')
       } else {
-        throw new TypeError('No package.name field in Cargo.toml')
+        throw new NapiError('No package.name field in Cargo.toml', {
+          code: 'napi::build::cargo_toml',
+          help: [
+            `Add ${chalk.green('name')} to ${chalk.green(
+              '[package]',
+            )}, or pass ${chalk.green('--cargo-name')}`,
+          ],
+        })
       }
 
       if (!tomlContent.lib?.['crate-type']?.includes?.('cdylib')) {
-        throw new TypeError(
+        throw new NapiError(
           `Missing ${chalk.green('crate-type = ["cdylib"]')} in ${chalk.green(
             '[lib]',
           )}`,
+          {
+            code: 'napi::build::crate_type',
+            causes: ['Node.js can only load a dynamic library'],
+            help: [`Add to Cargo.toml:\n[lib]\ncrate-type = ["cdylib"]`],
+          },
         )
       }
     }
@@ -349,6 +464,9 @@ export class BuildCommand extends Command {
         dylibName = `lib${dylibName}`
         libExt = '.so'
         break
+      case 'wasi':
+        libExt = '.wasm'
+        break
       default:
         throw new TypeError(
           'Operating system not currently supported or recognized by the build script',
@@ -358,7 +476,17 @@ export class BuildCommand extends Command {
     const targetRootDir = await findUp(cwd)
 
     if (!targetRootDir) {
-      throw new TypeError('No target dir found')
+      throw new NapiError('No target dir found', {
+        code: 'napi::build::target_dir',
+        causes: [
+          `No ${chalk.green('target')} directory in ${cwd} or its parents`,
+        ],
+        help: [
+          `A custom ${chalk.green(
+            'CARGO_TARGET_DIR',
+          )} is not supported, build into the default target dir`,
+        ],
+      })
     }
 
     const targetDir = join(
@@ -371,7 +499,9 @@ export class BuildCommand extends Command {
       : ''
 
     debug(`Platform name: ${platformName || chalk.green('[Empty]')}`)
-    const distFileName = `${binaryName}${platformName}.node`
+    const distFileName = `${binaryName}${platformName}${
+      platform === 'wasi' ? '.wasm' : '.node'
+    }`
 
     const distModulePath = join(this.destDir ?? '.', distFileName)
 
@@ -400,7 +530,9 @@ export class BuildCommand extends Command {
     }
 
     debug(`Write binary content to [${chalk.yellowBright(distModulePath)}]`)
-    await copyFileAsync(sourcePath, distModulePath)
+    await progress.track('copy binary', () =>
+      copyFileAsync(sourcePath, distModulePath),
+    )
 
     const dtsFilePath = join(
       process.cwd(),
@@ -408,12 +540,25 @@ export class BuildCommand extends Command {
       this.dts ?? 'index.d.ts',
     )
 
+    progress.start('type definitions')
     const idents = await processIntermediateTypeFile(
       intermediateTypeFile,
       dtsFilePath,
+      {
+        enumType: this.enumType as EnumType,
+        header: this.dtsHeader
+          ? await readFileAsync(join(process.cwd(), this.dtsHeader), 'utf8')
+          : undefined,
+        exportDefault: this.dtsExportDefault,
+        dual: isDual,
+      },
     )
+    const dtsFiles = isDual
+      ? [dtsFilePath, ...Object.values(getDualDtsPaths(dtsFilePath))]
+      : [dtsFilePath]
     if (this.pipe) {
-      const pipeCommand = `${this.pipe} ${dtsFilePath}`
+      progress.suspend()
+      const pipeCommand = `${this.pipe} ${dtsFiles.join(' ')}`
       console.info(`Run ${chalk.green(pipeCommand)}`)
       try {
         execSync(pipeCommand, { stdio: 'inherit', env: process.env })
@@ -424,20 +569,41 @@ export class BuildCommand extends Command {
         )
       }
     }
+    if (this.checkDts) {
+      progress.suspend()
+      try {
+        checkDtsFiles(dtsFiles)
+      } catch (e) {
+        progress.fail('type definitions')
+        progress.finish()
+        throw e
+      }
+    }
+    progress.succeed('type definitions')
     const jsBindingFilePath =
       this.jsBinding &&
       this.jsBinding !== 'false' &&
       this.appendPlatformToFilename
         ? join(process.cwd(), this.jsBinding)
         : null
+    progress.start('js binding')
     await writeJsBinding(
       binaryName,
       this.jsPackageName ?? packageName,
       jsBindingFilePath,
       idents,
+      isDual,
+      wasm,
     )
     if (this.pipe && jsBindingFilePath) {
-      const pipeCommand = `${this.pipe} ${jsBindingFilePath}`
+      progress.suspend()
+      const jsFiles = isDual
+        ? [
+            jsBindingFilePath,
+            ...Object.values(getDualJsPaths(jsBindingFilePath)),
+          ]
+        : [jsBindingFilePath]
+      const pipeCommand = `${this.pipe} ${jsFiles.join(' ')}`
       console.info(`Run ${chalk.green(pipeCommand)}`)
       try {
         execSync(pipeCommand, { stdio: 'inherit', env: process.env })
@@ -448,7 +614,149 @@ export class BuildCommand extends Command {
         )
       }
     }
+    progress.succeed('js binding', jsBindingFilePath ? undefined : 'skipped')
+    progress.finish()
+  }
+}
+
+function resolveEmnapi(cwd: string) {
+  try {
+    return dirname(require.resolve('emnapi/package.json', { paths: [cwd] }))
+  } catch {
+    throw new NapiError(
+      `Building for ${chalk.green('wasm32-wasi')} requires ${chalk.green(
+        'emnapi',
+      )}`,
+      {
+        code: 'napi::build::emnapi',
+        help: [
+          `Install it into the devDependencies of your project: ${chalk.green(
+            'yarn add -D emnapi',
+          )}`,
+          `Or set ${chalk.green(
+            'EMNAPI_LINK_DIR',
+          )} to the directory of its static libraries`,
+        ],
+      },
+    )
+  }
+}
+
+function resolveTsc() {
+  // prefer the TypeScript of the project, fallback to the one resolvable from the cli
+  for (const paths of [[process.cwd()], [__dirname]]) {
+    try {
+      return require.resolve('typescript/bin/tsc', { paths })
+    } catch {
+      continue
+    }
+  }
+  throw new NapiError(
+    `${chalk.green('--check-dts')} requires ${chalk.green('typescript')}`,
+    {
+      code: 'napi::build::typescript',
+      help: [
+        `Install it into the devDependencies of your project: ${chalk.green(
+          'yarn add -D typescript',
+        )}`,
+      ],
+    },
+  )
+}
+
+function checkDtsFiles(dtsFiles: string[]) {
+  const tscCommand = [
+    'node',
+    JSON.stringify(resolveTsc()),
+    '--noEmit',
+    '--strict',
+    ...dtsFiles.map((file) => JSON.stringify(file)),
+  ].join(' ')
+  debug(`Check dts files: [${chalk.green(tscCommand)}]`)
+  try {
+    execSync(tscCommand, { stdio: 'inherit', env: process.env })
+  } catch {
+    throw new NapiError(
+      `Generated ${dtsFiles
+        .map((file) => chalk.yellowBright(file))
+        .join(', ')} failed the TypeScript check`,
+      {
+        code: 'napi::build::check_dts',
+        causes: [
+          `A ${chalk.green('ts_type')}, ${chalk.green(
+            'ts_args_type',
+          )} or ${chalk.green('ts_return_type')} override is not valid TypeScript`,
+          'Or the type generation of napi-rs has a bug',
+        ],
+        help: ['See the errors reported by tsc above'],
+      },
+    )
+  }
+}
+
+// null when rustup is not available or does not know the target, like a custom target json,
+// it runs in the cargo cwd so the toolchain of its `rust-toolchain` file is checked
+function isTargetInstalled(target: string, cwd: string): boolean | null {
+  let targets: string
+  try {
+    targets = execSync('rustup target list', {
+      cwd,
+      env: process.env,
+      stdio: ['ignore', 'pipe', 'ignore'],
+    }).toString('utf8')
+  } catch {
+    return null
+  }
+  // installed targets are listed as `x86_64-unknown-linux-gnu (installed)`
+  for (const line of targets.split('\n')) {
+    const [name, status] = line.trim().split(/\s+/)
+    if (name === target) {
+      return status === '(installed)'
+    }
+  }
+  return null
+}
+
+async function ensureTargetInstalled(
+  target: string,
+  autoInstall: boolean,
+  cwd: string,
+) {
+  if (isTargetInstalled(target, cwd) !== false) {
+    return
+  }
+  let install = autoInstall
+  if (!install && process.stdin.isTTY && !process.env.CI) {
+    const answer = await inquirer.prompt([
+      {
+        type: 'confirm',
+        name: 'install',
+        message: `Target ${target} is not installed, run ${chalk.green(
+          `rustup target add ${target}`,
+        )}?`,
+        default: true,
+      },
+    ])
+    install = answer.install
   }
+  if (!install) {
+    throw new NapiError(`Target ${target} is not installed`, {
+      code: 'napi::build::target_not_installed',
+      causes: [
+        `The standard library of ${target} is missing in the active toolchain`,
+      ],
+      help: [
+        `Run ${chalk.green(`rustup target add ${target}`)}`,
+        `Or pass ${chalk.green('--auto-install-target')} to install it`,
+      ],
+    })
+  }
+  console.info(`Run ${chalk.green(`rustup target add ${target}`)}`)
+  execSync(`rustup target add ${target}`, {
+    cwd,
+    stdio: 'inherit',
+    env: process.env,
+  })
 }
 
 async function findUp(dir = process.cwd()): Promise<string | null> {
@@ -464,18 +772,70 @@ async function findUp(dir = process.cwd()): Promise<string | null> {
   return findUp(dirs.join(sep))
 }
 
+const ENUM_TYPES = ['const', 'enum', 'union'] as const
+
+type EnumType = typeof ENUM_TYPES[number]
+
 interface TypeDef {
-  kind: 'fn' | 'struct' | 'impl' | 'enum' | 'interface'
+  kind:
+    | 'fn'
+    | 'const'
+    | 'struct'
+    | 'impl'
+    | 'enum'
+    | 'interface'
+    | 'type'
+    | 'extra'
   name: string
   original_name?: string
   def: string
   js_mod?: string
   js_doc: string
+  extends?: string
+  ts_module?: string
+  /** Type parameters of a generic class, e.g. `<T>` */
+  generics?: string
+}
+
+interface DtsOptions {
+  enumType?: EnumType
+  /** Replaces the auto-generated header */
+  header?: string
+  /** Declares the whole binding as the default export too */
+  exportDefault?: boolean
+  /** Writes `.d.cts` and `.d.mts` flavors next to the `.d.ts` file */
+  dual?: boolean
+}
+
+const DEFAULT_DTS_HEADER = `/* tslint:disable */
+/* eslint-disable */
+
+/* auto-generated by NAPI-RS */\n
+`
+
+function compareStrings(a = '', b = '') {
+  return a < b ? -1 : a > b ? 1 : 0
+}
+
+// the intermediate file is appended in the order the items are compiled,
+// which changes between incremental builds
+function compareDefs(a: TypeDef, b: TypeDef) {
+  return (
+    compareStrings(a.name, b.name) ||
+    compareStrings(a.kind, b.kind) ||
+    compareStrings(a.def, b.def)
+  )
 }
 
 async function processIntermediateTypeFile(
   source: string,
   target: string,
+  {
+    enumType = 'const',
+    header,
+    exportDefault = false,
+    dual = false,
+  }: DtsOptions = {},
 ): Promise<string[]> {
   const idents: string[] = []
   if (!existsSync(source)) {
@@ -493,12 +853,23 @@ async function processIntermediateTypeFile(
     return idents
   }
 
-  const allDefs = lines.map((line) => JSON.parse(line) as TypeDef)
+  // the same item is written again if its crate is expanded more than once
+  const allDefs = Array.from(new Set(lines))
+    .map((line) => JSON.parse(line) as TypeDef)
+    .sort(compareDefs)
+  // `module:Export` parents of classes are imported from the builtin modules
+  const imports = new Map<string, Set<string>>()
 
   function convertDefs(defs: TypeDef[], nested = false): string {
     const classes = new Map<
       string,
-      { def: string; js_doc: string; original_name?: string }
+      {
+        def: string
+        js_doc: string
+        original_name?: string
+        extends?: string
+        generics?: string
+      }
     >()
     const impls = new Map<string, string>()
     let dts = ''
@@ -514,10 +885,18 @@ async function processIntermediateTypeFile(
             original_name: def.original_name,
             def: def.def,
             js_doc: def.js_doc,
+            extends: def.extends,
+            generics: def.generics,
           })
           break
         case 'impl':
-          impls.set(def.name, `${def.js_doc}${def.def}`)
+          // a class could have several `#[napi] impl` blocks
+          impls.set(
+            def.name,
+            [impls.get(def.name), `${def.js_doc}${def.def}`]
+              .filter(Boolean)
+              .join('\n'),
+          )
           break
         case 'interface':
           dts +=
@@ -530,11 +909,22 @@ async function processIntermediateTypeFile(
           if (!nested) {
             idents.push(def.name)
           }
+          dts += convertEnum(def, enumType, nest)
+          break
+        case 'type':
           dts +=
-            indentLines(`${def.js_doc}export const enum ${def.name} {`, nest) +
-            '\n'
-          dts += indentLines(def.def, nest + 2) + '\n'
-          dts += indentLines(`}`, nest) + '\n'
+            indentLines(
+              `${def.js_doc}export type ${def.name} = ${def.def}`,
+              nest,
+            ) + '\n'
+          break
+        case 'extra':
+          // hand-written definitions keep their own indentation
+          dts +=
+            def.def
+              .split('\n')
+              .map((line) => (line ? ''.padEnd(nest, ' ') + line : line))
+              .join('\n') + '\n'
           break
         default:
           if (!nested) {
@@ -544,14 +934,34 @@ async function processIntermediateTypeFile(
       }
     })
 
-    for (const [name, { js_doc, def, original_name }] of classes.entries()) {
+    for (const [
+      name,
+      { js_doc, def, original_name, extends: parent, generics = '' },
+    ] of classes.entries()) {
       const implDef = impls.get(name)
 
       if (original_name && name !== original_name) {
-        dts += indentLines(`export type ${original_name} = ${name}\n`, nest)
+        dts += indentLines(
+          `export type ${original_name}${generics} = ${name}${generics}\n`,
+          nest,
+        )
       }
 
-      dts += indentLines(`${js_doc}export class ${name} {`, nest)
+      let extendsClause = ''
+      if (parent) {
+        const [mod, exported] = parent.includes(':')
+          ? parent.split(':')
+          : [null, parent]
+        if (mod) {
+          imports.set(mod, (imports.get(mod) ?? new Set()).add(exported))
+        }
+        extendsClause = ` extends ${exported}`
+      }
+
+      dts += indentLines(
+        `${js_doc}export class ${name}${generics}${extendsClause} {`,
+        nest,
+      )
 
       if (def) {
         dts += '\n' + indentLines(def, nest + 2)
@@ -573,27 +983,57 @@ async function processIntermediateTypeFile(
     return dts
   }
 
-  const topLevelDef = convertDefs(allDefs.filter((def) => !def.js_mod))
+  const topLevelDef = convertDefs(
+    allDefs.filter((def) => !def.js_mod && !def.ts_module),
+  )
 
   const namespaceDefs = Object.entries(
     groupBy(
       allDefs.filter((def) => def.js_mod),
       'js_mod',
     ),
-  ).reduce((acc, [mod, defs]) => {
-    idents.push(mod)
-    return acc + `export namespace ${mod} {\n${convertDefs(defs, true)}}\n`
-  }, '')
+  )
+    .sort(([a], [b]) => compareStrings(a, b))
+    .reduce((acc, [mod, defs]) => {
+      idents.push(mod)
+      return acc + `export namespace ${mod} {\n${convertDefs(defs, true)}}\n`
+    }, '')
+
+  // interfaces augmenting other modules are imported back for the top level
+  const moduleDefs = Object.entries(
+    groupBy(
+      allDefs.filter((def) => def.ts_module),
+      'ts_module',
+    ),
+  )
+    .sort(([a], [b]) => compareStrings(a, b))
+    .reduce((acc, [mod, defs]) => {
+      const exported = imports.get(mod) ?? new Set()
+      defs.forEach((def) => exported.add(def.name))
+      imports.set(mod, exported)
+      return acc + `declare module '${mod}' {\n${convertDefs(defs, true)}}\n`
+    }, '')
+
+  let dtsHeader = DEFAULT_DTS_HEADER
+  if (header !== undefined) {
+    dtsHeader = header.trim() ? header.replace(/\s*$/, '\n') : ''
+  }
 
-  const dtsHeader = `/* tslint:disable */
-/* eslint-disable */
+  const importDef = Array.from(imports.entries())
+    .sort(([a], [b]) => compareStrings(a, b))
+    .map(
+      ([mod, exported]) =>
+        `import { ${Array.from(exported)
+          .sort(compareStrings)
+          .join(', ')} } from '${mod}'\n`,
+    )
+    .join('')
 
-/* auto-generated by NAPI-RS */\n
-`
 
   const externalDef =
     topLevelDef.indexOf('ExternalObject<') > -1 ||
-    namespaceDefs.indexOf('ExternalObject<') > -1
+    namespaceDefs.indexOf('ExternalObject<') > -1 ||
+    moduleDefs.indexOf('ExternalObject<') > -1
       ? `export class ExternalObject<T> {
   readonly '': {
     readonly '': unique symbol
@@ -603,14 +1043,85 @@ async function processIntermediateTypeFile(
       : ''
 
   await unlinkAsync(source)
+  const dts =
+    dtsHeader +
+    importDef +
+    externalDef +
+    topLevelDef +
+    namespaceDefs +
+    moduleDefs
+
+  const dtsName = basename(target).replace(/\.d\.ts$/, '')
   await writeFileAsync(
     target,
-    dtsHeader + externalDef + topLevelDef + namespaceDefs,
+    dts + (exportDefault ? declareDefaultExport(dtsName) : ''),
     'utf8',
   )
+  if (dual) {
+    // `moduleResolution: node16` resolves a relative import with its extension
+    const { cjs, esm } = getDualDtsPaths(target)
+    await writeFileAsync(
+      cjs,
+      dts + (exportDefault ? declareDefaultExport(`${dtsName}.cjs`) : ''),
+      'utf8',
+    )
+    // the `.mjs` binding always has the default export
+    await writeFileAsync(
+      esm,
+      dts + declareDefaultExport(`${dtsName}.mjs`),
+      'utf8',
+    )
+  }
   return idents
 }
 
+// `import binding from './index'` is typed as the module itself
+function declareDefaultExport(specifier: string) {
+  return `declare const __napiBinding: typeof import('./${specifier}')\nexport default __napiBinding\n`
+}
+
+function getDualDtsPaths(dtsFilePath: string) {
+  const base = dtsFilePath.replace(/\.d\.ts$/, '')
+  return { cjs: `${base}.d.cts`, esm: `${base}.d.mts` }
+}
+
+function getDualJsPaths(jsFilePath: string) {
+  const { dir, name } = parse(jsFilePath)
+  return { cjs: join(dir, `${name}.cjs`), esm: join(dir, `${name}.mjs`) }
+}
+
+function convertEnum(def: TypeDef, enumType: EnumType, nest: number) {
+  if (enumType === 'union') {
+    // `Name = value` members become readonly properties of the declared object
+    const members = def.def
+      .split('\n')
+      .map((line) => line.replace(/^\s*(\w+) = (.+?),?$/, 'readonly $1: $2'))
+      .join('\n')
+    return (
+      indentLines(`${def.js_doc}export const ${def.name}: {`, nest) +
+      '\n' +
+      indentLines(members, nest + 2) +
+      '\n' +
+      indentLines(`}`, nest) +
+      '\n' +
+      indentLines(
+        `export type ${def.name} = (typeof ${def.name})[keyof typeof ${def.name}]`,
+        nest,
+      ) +
+      '\n'
+    )
+  }
+  const keyword = enumType === 'const' ? 'const enum' : 'enum'
+  return (
+    indentLines(`${def.js_doc}export ${keyword} ${def.name} {`, nest) +
+    '\n' +
+    indentLines(def.def, nest + 2) +
+    '\n' +
+    indentLines(`}`, nest) +
+    '\n'
+  )
+}
+
 function indentLines(input: string, spaces: number) {
   return input
     .split('\n')
@@ -627,18 +1138,28 @@ async function writeJsBinding(
   packageName: string,
   distFileName: string | null,
   idents: string[],
+  dual = false,
+  wasm = false,
 ) {
   if (distFileName && idents.length) {
-    const template = createJsBinding(localName, packageName)
+    const template = createJsBinding(localName, packageName, wasm)
     const declareCodes = `const { ${idents.join(', ')} } = nativeBinding\n`
     const exportsCode = idents.reduce(
       (acc, cur) => `${acc}\nmodule.exports.${cur} = ${cur}`,
       '',
     )
-    await writeFileAsync(
-      distFileName,
-      template + declareCodes + exportsCode + '\n',
-      'utf8',
-    )
+    const cjsCode = template + declareCodes + exportsCode + '\n'
+    await writeFileAsync(distFileName, cjsCode, 'utf8')
+    if (dual) {
+      // the `.js` file is ESM in a `"type": "module"` package
+      const { cjs, esm } = getDualJsPaths(distFileName)
+      await writeFileAsync(cjs, cjsCode, 'utf8')
+      await writeFileAsync(
+        esm,
+        createEsmJsBinding(basename(cjs)) +
+          `export const { ${idents.join(', ')} } = nativeBinding\n`,
+        'utf8',
+      )
+    }
   }
 }
@@ -1,5 +1,6 @@
 import { join } from 'path'
 
+import { NapiError } from './diagnostics'
 import { DefaultPlatforms, PlatformDetail, parseTriple } from './parse-triple'
 
 export function getNapiConfig(
@@ -8,7 +9,18 @@ export function getNapiConfig(
 ) {
   const packageJsonPath = join(cwd, packageJson)
 
-  const pkgJson = require(packageJsonPath)
+  let pkgJson: any
+  try {
+    pkgJson = require(packageJsonPath)
+  } catch (e: any) {
+    throw new NapiError(`Could not read ${packageJsonPath}`, {
+      code: 'napi::config::package_json',
+      causes: [e?.code === 'MODULE_NOT_FOUND' ? 'It does not exist' : `${e}`],
+      help: [
+        'Run the command in the directory of the package, or pass the path of package.json with -c',
+      ],
+    })
+  }
   const { version: packageVersion, napi, name } = pkgJson
   const additionPlatforms: PlatformDetail[] = (
     napi?.triples?.additional ?? []
@@ -24,12 +36,18 @@ export function getNapiConfig(
   const packageName = napi?.package?.name ?? name
 
   const binaryName: string = napi?.name ?? 'index'
+  // ships both CommonJS and ESM entries through the `exports` conditions
+  const dual: boolean = napi?.dual === true
+  // falls back to the `wasm32-wasi` package on platforms without a native binary
+  const wasm: boolean = napi?.wasm === true
 
   return {
     platforms,
     version,
     packageName,
     binaryName,
+    dual,
+    wasm,
     packageJsonPath,
     content: pkgJson,
   }
@@ -22,10 +22,11 @@ export class CreateNpmDirCommand extends Command {
     config: string,
     targetDirPath: string,
     cwd: string,
+    wasmFallback = false,
   ) => {
     const pkgJsonDir = config
     debug(`Read content from [${chalk.yellowBright(pkgJsonDir)}]`)
-    const { platforms, packageName, version, binaryName, content } =
+    const { platforms, packageName, version, binaryName, content, wasm } =
       getNapiConfig(pkgJsonDir, cwd)
 
     for (const platformDetail of platforms) {
@@ -72,21 +73,147 @@ export class CreateNpmDirCommand extends Command {
       debug(`Write target README.md [${chalk.yellowBright(targetReadme)}]`)
       await writeFileAsync(targetReadme, readme(packageName, platformDetail))
     }
+
+    if (wasm || wasmFallback) {
+      await createWasmDir(
+        targetDirPath,
+        packageName,
+        version,
+        binaryName,
+        content,
+      )
+    }
   }
 
   targetDir: string = Option.String('-t,--target')!
 
   config = Option.String('-c,--config', 'package.json')
 
+  wasm = Option.Boolean('--wasm', false, {
+    description: `Create the ${chalk.green(
+      'wasm32-wasi',
+    )} fallback package even without ${chalk.green(
+      'napi.wasm',
+    )} in package.json`,
+  })
+
   async execute() {
     await CreateNpmDirCommand.create(
       this.config,
       join(process.cwd(), this.targetDir),
       process.cwd(),
+      this.wasm,
     )
   }
 }
 
+async function createWasmDir(
+  targetDirPath: string,
+  packageName: string,
+  version: string,
+  binaryName: string,
+  content: any,
+) {
+  const targetDir = join(targetDirPath, 'npm', 'wasm32-wasi')
+  mkdirSync(targetDir, {
+    recursive: true,
+  })
+  const wasmFileName = `${binaryName}.wasm32-wasi.wasm`
+  const loaderFileName = `${binaryName}.wasi.cjs`
+  const targetPackageJson = join(targetDir, 'package.json')
+  debug(`Write file [${chalk.yellowBright(targetPackageJson)}]`)
+  await writeFileAsync(
+    targetPackageJson,
+    JSON.stringify(
+      {
+        name: `${packageName}-wasm32-wasi`,
+        version,
+        // no `os` and `cpu`, it is installed on every platform
+        main: loaderFileName,
+        files: [wasmFileName, loaderFileName],
+        dependencies: {
+          '@emnapi/core': '^1.0.0',
+          '@emnapi/runtime': '^1.0.0',
+        },
+        ...pick(
+          content,
+          'description',
+          'keywords',
+          'author',
+          'authors',
+          'homepage',
+          'license',
+          'publishConfig',
+          'repository',
+          'bugs',
+        ),
+        engines: {
+          node: '>= 14',
+        },
+      },
+      null,
+      2,
+    ),
+  )
+  const loaderPath = join(targetDir, loaderFileName)
+  debug(`Write wasm loader [${chalk.yellowBright(loaderPath)}]`)
+  await writeFileAsync(loaderPath, createWasmLoader(wasmFileName))
+  const targetReadme = join(targetDir, 'README.md')
+  debug(`Write target README.md [${chalk.yellowBright(targetReadme)}]`)
+  await writeFileAsync(
+    targetReadme,
+    `# \`${packageName}-wasm32-wasi\`
+
+This is the **wasm32-wasi** fallback for \`${packageName}\`, loaded on platforms without a native binary
+`,
+  )
+}
+
+// instantiates the Node-API module with the Node-API implementation of emnapi
+const createWasmLoader = (wasmFileName: string) => `const { readFileSync } = require('fs')
+const { join } = require('path')
+const { WASI } = require('wasi')
+
+const { instantiateNapiModuleSync } = require('@emnapi/core')
+const { getDefaultContext } = require('@emnapi/runtime')
+
+const wasi = new WASI({ version: 'preview1', env: process.env, preopens: {} })
+
+// \`napi-build\` links the module with \`--import-memory\` for \`wasm32-wasip1-threads\`
+const memory = new WebAssembly.Memory({
+  initial: 4000,
+  maximum: 65536,
+  shared: true,
+})
+
+const { napiModule } = instantiateNapiModuleSync(
+  readFileSync(join(__dirname, '${wasmFileName}')),
+  {
+    context: getDefaultContext(),
+    wasi,
+    overwriteImports(importObject) {
+      importObject.env = {
+        ...importObject.env,
+        ...importObject.napi,
+        ...importObject.emnapi,
+        memory,
+      }
+      return importObject
+    },
+    // \`ctor\` does not run on WebAssembly, the exports are collected by the exported register functions
+    beforeInit({ instance }) {
+      for (const name of Object.keys(instance.exports)) {
+        if (name.startsWith('__napi_register__')) {
+          instance.exports[name]()
+        }
+      }
+    },
+  },
+)
+
+module.exports = napiModule.exports
+`
+
 function readme(packageName: string, platformDetail: PlatformDetail) {
   return `# \`${packageName}-${platformDetail.platformArchABI}\`
 
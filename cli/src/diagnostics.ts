@@ -0,0 +1,49 @@
+import chalk from 'chalk'
+
+export interface DiagnosticOptions {
+  // `napi::<command>::<reason>`, stable for searching issues and docs
+  code: string
+  // what probably went wrong
+  causes?: string[]
+  // how to fix it, usually a command to run or a config to change
+  help?: string[]
+}
+
+/**
+ * An error of the cli rendered as a report with its probable causes and fix suggestions
+ */
+export class NapiError extends Error {
+  readonly code: string
+  readonly causes: string[]
+  readonly help: string[]
+
+  constructor(message: string, { code, causes, help }: DiagnosticOptions) {
+    super(message)
+    this.name = 'NapiError'
+    this.code = code
+    this.causes = causes ?? []
+    this.help = help ?? []
+  }
+}
+
+const indent = (text: string, prefix: string) =>
+  text.split('\n').join(`\n${prefix}`)
+
+export function renderDiagnostic(error: NapiError): string {
+  const lines = [
+    `${chalk.red(error.code)}`,
+    '',
+    `  ${chalk.red('×')} ${indent(error.message, '    ')}`,
+  ]
+  error.causes.forEach((cause, index) => {
+    const branch = index === error.causes.length - 1 ? '╰─▶' : '├─▶'
+    lines.push(`  ${chalk.red(branch)} ${indent(cause, '      ')}`)
+  })
+  if (error.help.length) {
+    lines.push('')
+    for (const help of error.help) {
+      lines.push(`  ${chalk.cyan('help:')} ${indent(help, '        ')}`)
+    }
+  }
+  return lines.join('\n') + '\n'
+}
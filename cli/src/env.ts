@@ -0,0 +1,129 @@
+import { execSync } from 'child_process'
+import { existsSync } from 'fs'
+import { release } from 'os'
+
+import chalk from 'chalk'
+import { Command, Option } from 'clipanion'
+
+import { version as cliVersion } from '../package.json'
+
+import { getNapiConfig } from './consts'
+import { debugFactory } from './debug'
+
+const debug = debugFactory('env')
+
+// the output of `command`, or null if it is not installed or failed
+function probe(command: string): string | null {
+  try {
+    return execSync(command, {
+      env: process.env,
+      stdio: ['ignore', 'pipe', 'ignore'],
+    })
+      .toString('utf8')
+      .trim()
+  } catch (e) {
+    debug(`${chalk.green(command)} failed: ${e}`)
+    return null
+  }
+}
+
+function findAndroidNdk(): string | null {
+  for (const name of [
+    'ANDROID_NDK_LATEST_HOME',
+    'ANDROID_NDK_HOME',
+    'ANDROID_NDK_ROOT',
+  ]) {
+    const path = process.env[name]
+    if (path) {
+      return `${path} (${name}${existsSync(path) ? '' : ', not existed'})`
+    }
+  }
+  return null
+}
+
+function napiConfigReport(configFileName?: string) {
+  try {
+    const { packageName, binaryName, version, platforms, dual } =
+      getNapiConfig(configFileName)
+    return {
+      packageName,
+      binaryName,
+      version,
+      dual,
+      triples: platforms.map((platform) => platform.raw),
+    }
+  } catch (e: any) {
+    return { error: e?.message ?? `${e}` }
+  }
+}
+
+export class EnvCommand extends Command {
+  static usage = Command.Usage({
+    description:
+      'Print the versions of the toolchains and the napi config, for pasting into bug reports',
+  })
+
+  static paths = [['env']]
+
+  configFileName?: string = Option.String('-c,--config')
+
+  json = Option.Boolean('--json', false, {
+    description: `Print the report as ${chalk.green('JSON')}`,
+  })
+
+  async execute() {
+    const installedTargets = probe('rustup target list --installed')
+    const report = {
+      cli: cliVersion,
+      os: `${process.platform} ${process.arch} ${release()}`,
+      node: process.version,
+      npm: probe('npm --version'),
+      yarn: probe('yarn --version'),
+      rustc: probe('rustc --version'),
+      cargo: probe('cargo --version'),
+      toolchain: probe('rustup show active-toolchain'),
+      targets: installedTargets ? installedTargets.split(/\s+/) : null,
+      zig: probe('zig version'),
+      androidNdk: findAndroidNdk(),
+      napi: napiConfigReport(this.configFileName),
+    }
+
+    if (this.json) {
+      this.context.stdout.write(JSON.stringify(report, null, 2) + '\n')
+      return
+    }
+
+    const value = (value: string | null) => value ?? 'not found'
+    const lines = [
+      '### Environment',
+      '',
+      `- @napi-rs/cli: ${report.cli}`,
+      `- OS: ${report.os}`,
+      `- Node.js: ${report.node}`,
+      `- npm: ${value(report.npm)}`,
+      `- yarn: ${value(report.yarn)}`,
+      `- rustc: ${value(report.rustc)}`,
+      `- cargo: ${value(report.cargo)}`,
+      `- rustup toolchain: ${value(report.toolchain)}`,
+      `- installed targets: ${value(report.targets?.join(', ') ?? null)}`,
+      `- zig: ${value(report.zig)}`,
+      `- Android NDK: ${value(report.androidNdk)}`,
+      '',
+      '### napi config',
+      '',
+    ]
+    const { napi } = report
+    if ('error' in napi) {
+      lines.push(`- not found: ${napi.error}`)
+    } else {
+      lines.push(
+        `- package name: ${napi.packageName}`,
+        `- binary name: ${napi.binaryName}`,
+        `- version: ${napi.version}`,
+        `- dual: ${napi.dual}`,
+        `- triples: ${napi.triples.join(', ')}`,
+      )
+    }
+    this.context.stdout.write(lines.join('\n') + '\n')
+  }
+}
@@ -1,18 +1,32 @@
 import 'core-js/es/string/replace-all'
 
-import { Cli } from 'clipanion'
+import { BaseContext, Cli, Command } from 'clipanion'
 
 import { version } from '../package.json'
 
 import { ArtifactsCommand } from './artifacts'
 import { BuildCommand } from './build'
 import { CreateNpmDirCommand } from './create-npm-dir'
+import { NapiError, renderDiagnostic } from './diagnostics'
+import { EnvCommand } from './env'
 import { NewProjectCommand } from './new'
 import { PrePublishCommand } from './pre-publish'
 import { RenameCommand } from './rename'
 import { VersionCommand } from './version'
 
-const cli = new Cli({
+class NapiCli extends Cli {
+  error(
+    error: Error | any,
+    options?: { command?: Command<BaseContext> | null },
+  ): string {
+    if (error instanceof NapiError) {
+      return renderDiagnostic(error)
+    }
+    return super.error(error, options)
+  }
+}
+
+const cli = new NapiCli({
   binaryName: 'napi',
   binaryVersion: version,
 })
@@ -20,6 +34,7 @@ const cli = new Cli({
 cli.register(ArtifactsCommand)
 cli.register(BuildCommand)
 cli.register(CreateNpmDirCommand)
+cli.register(EnvCommand)
 cli.register(PrePublishCommand)
 cli.register(VersionCommand)
 cli.register(NewProjectCommand)
@@ -33,6 +48,6 @@ cli
     process.exit(status)
   })
   .catch((e) => {
-    console.error(e)
+    console.error(e instanceof NapiError ? renderDiagnostic(e) : e)
     process.exit(1)
   })
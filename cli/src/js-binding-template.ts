@@ -1,6 +1,40 @@
+// the ESM entry of a dual package loads the CommonJS binding
+export const createEsmJsBinding = (cjsFileName: string) => `import { createRequire } from 'module'
+
+const require = createRequire(import.meta.url)
+const nativeBinding = require('./${cjsFileName}')
+
+export default nativeBinding
+`
+
+// loads the `wasm32-wasi` package if no native binary matches the platform
+const wasmFallback = (localName: string, pkgName: string) => `
+if (!nativeBinding) {
+  try {
+    if (existsSync(join(__dirname, '${localName}.wasi.cjs'))) {
+      nativeBinding = require('./${localName}.wasi.cjs')
+    } else {
+      nativeBinding = require('${pkgName}-wasm32-wasi')
+    }
+  } catch (e) {
+    // report the error of the native binary, the wasm package is only a fallback
+    if (!loadError) {
+      loadError = e
+    }
+  }
+}
+`
+
+// with the wasm fallback an unsupported platform is only reported if the fallback fails too
+const unsupported = (message: string, wasm: boolean) =>
+  wasm
+    ? `loadError = new Error(\`${message}\`)`
+    : `throw new Error(\`${message}\`)`
+
 export const createJsBinding = (
   localName: string,
   pkgName: string,
+  wasm = false,
 ) => `const { existsSync, readFileSync } = require('fs')
 const { join } = require('path')
 
@@ -52,7 +86,7 @@ switch (platform) {
         }
         break
       default:
-        throw new Error(\`Unsupported architecture on Android \${arch}\`)
+        ${unsupported('Unsupported architecture on Android ${arch}', wasm)}
     }
     break
   case 'win32':
@@ -100,7 +134,7 @@ switch (platform) {
         }
         break
       default:
-        throw new Error(\`Unsupported architecture on Windows: \${arch}\`)
+        ${unsupported('Unsupported architecture on Windows: ${arch}', wasm)}
     }
     break
   case 'darwin':
@@ -132,12 +166,14 @@ switch (platform) {
         }
         break
       default:
-        throw new Error(\`Unsupported architecture on macOS: \${arch}\`)
+        ${unsupported('Unsupported architecture on macOS: ${arch}', wasm)}
     }
     break
   case 'freebsd':
     if (arch !== 'x64') {
-      throw new Error(\`Unsupported architecture on FreeBSD: \${arch}\`)
+      ${unsupported('Unsupported architecture on FreeBSD: ${arch}', wasm)}${
+  wasm ? '\n      break' : ''
+}
     }
     localFileExisted = existsSync(join(__dirname, '${localName}.freebsd-x64.node'))
     try {
@@ -225,13 +261,13 @@ switch (platform) {
         }
         break
       default:
-        throw new Error(\`Unsupported architecture on Linux: \${arch}\`)
+        ${unsupported('Unsupported architecture on Linux: ${arch}', wasm)}
     }
     break
   default:
-    throw new Error(\`Unsupported OS: \${platform}, architecture: \${arch}\`)
+    ${unsupported('Unsupported OS: ${platform}, architecture: ${arch}', wasm)}
 }
-
+${wasm ? wasmFallback(localName, pkgName) : ''}
 if (!nativeBinding) {
   if (loadError) {
     throw loadError
@@ -0,0 +1,335 @@
+import { createHash } from 'crypto'
+import { existsSync } from 'fs'
+import * as http from 'http'
+import { ClientRequest, IncomingMessage, OutgoingHttpHeaders } from 'http'
+import * as https from 'https'
+import { Agent as HttpsAgent, AgentOptions } from 'https'
+import { Socket } from 'net'
+import { join } from 'path'
+import { connect } from 'tls'
+
+import chalk from 'chalk'
+import envPaths from 'env-paths'
+
+import { debugFactory } from './debug'
+import { NapiError } from './diagnostics'
+import {
+  mkdirAsync,
+  readFileAsync,
+  renameAsync,
+  writeFileAsync,
+} from './utils'
+
+const debug = debugFactory('network')
+
+const MAX_REDIRECTS = 10
+
+export class OfflineError extends NapiError {
+  constructor(url: string, reason: string) {
+    super(
+      `Can not download ${chalk.yellowBright(
+        url,
+      )} with ${chalk.green('--offline')}`,
+      {
+        code: 'napi::network::offline',
+        causes: [reason],
+        help: [
+          `Run it once without ${chalk.green(
+            '--offline',
+          )} to fill the download cache`,
+        ],
+      },
+    )
+  }
+}
+
+// `NO_PROXY` entries are hosts, domain suffixes like `.example.com`, optionally with a port, or `*`
+function isProxyBypassed(url: URL) {
+  const noProxy = process.env.NO_PROXY ?? process.env.no_proxy
+  if (!noProxy) {
+    return false
+  }
+  const port = defaultPort(url)
+  return noProxy
+    .split(/[\s,]+/)
+    .filter(Boolean)
+    .some((entry) => {
+      if (entry === '*') {
+        return true
+      }
+      const [host, entryPort] = entry.toLowerCase().split(':')
+      if (entryPort && entryPort !== port) {
+        return false
+      }
+      const domain = host.replace(/^\*?\./, '')
+      return url.hostname === domain || url.hostname.endsWith(`.${domain}`)
+    })
+}
+
+export function getProxyUrl(url: URL): URL | null {
+  if (isProxyBypassed(url)) {
+    return null
+  }
+  const proxy =
+    url.protocol === 'https:'
+      ? process.env.HTTPS_PROXY ??
+        process.env.https_proxy ??
+        process.env.HTTP_PROXY ??
+        process.env.http_proxy
+      : process.env.HTTP_PROXY ?? process.env.http_proxy
+  return proxy
+    ? new URL(proxy.includes('://') ? proxy : `http://${proxy}`)
+    : null
+}
+
+// the client of the protocol of `url`, plain http urls must not be requested with `https`
+function clientOf(url: URL): Pick<typeof http, 'get' | 'request'> {
+  return url.protocol === 'https:' ? https : http
+}
+
+function defaultPort(url: URL) {
+  return url.port || (url.protocol === 'https:' ? '443' : '80')
+}
+
+function proxyAuthorization(proxy: URL): Record<string, string> {
+  if (!proxy.username) {
+    return {}
+  }
+  return {
+    'proxy-authorization': `Basic ${Buffer.from(
+      `${decodeURIComponent(proxy.username)}:${decodeURIComponent(
+        proxy.password,
+      )}`,
+    ).toString('base64')}`,
+  }
+}
+
+// tunnels the TLS connections through the `CONNECT` method of the proxy
+class HttpsProxyAgent extends HttpsAgent {
+  constructor(private readonly proxy: URL, options?: AgentOptions) {
+    super({ keepAlive: false, ...options })
+  }
+
+  createConnection(
+    options: { host?: string; port?: number; servername?: string },
+    callback: (err: Error | null, socket?: Socket) => void,
+  ) {
+    const target = `${options.host}:${options.port ?? 443}`
+    clientOf(this.proxy)
+      .request({
+        host: this.proxy.hostname,
+        port: defaultPort(this.proxy),
+        method: 'CONNECT',
+        path: target,
+        headers: { host: target, ...proxyAuthorization(this.proxy) },
+      })
+      .once('connect', (res: IncomingMessage, socket: Socket) => {
+        if (res.statusCode !== 200) {
+          socket.destroy()
+          callback(
+            new Error(
+              `Proxy ${this.proxy.host} refused to connect to ${target}: ${res.statusCode}`,
+            ),
+          )
+          return
+        }
+        callback(
+          null,
+          connect({
+            socket,
+            servername: options.servername ?? options.host,
+          }),
+        )
+      })
+      .once('error', (err) => callback(err))
+      .end()
+    return undefined
+  }
+}
+
+/**
+ * The agent sending the requests to the `https` url through the proxy, if there is one.
+ *
+ * Plain http urls need no agent, they are requested from the proxy itself, see `get`
+ */
+export function createAgent(url: URL): HttpsAgent | undefined {
+  const proxy = getProxyUrl(url)
+  if (!proxy || url.protocol !== 'https:') {
+    return undefined
+  }
+  debug(`Connect to ${url.host} through proxy ${chalk.green(proxy.host)}`)
+  return new HttpsProxyAgent(proxy)
+}
+
+function get(
+  url: URL,
+  headers: OutgoingHttpHeaders,
+  callback: (res: IncomingMessage) => void,
+): ClientRequest {
+  const proxy = getProxyUrl(url)
+  if (proxy && url.protocol !== 'https:') {
+    debug(`Request ${url.href} from proxy ${chalk.green(proxy.host)}`)
+    // plain http requests are sent to the proxy with the absolute url as the path
+    return clientOf(proxy).get(
+      {
+        host: proxy.hostname,
+        port: defaultPort(proxy),
+        path: url.href,
+        headers: {
+          ...headers,
+          host: url.host,
+          ...proxyAuthorization(proxy),
+        },
+      },
+      callback,
+    )
+  }
+  return clientOf(url).get(url, { agent: createAgent(url), headers }, callback)
+}
+
+interface CacheValidators {
+  etag?: string
+  lastModified?: string
+}
+
+type FetchResult =
+  | { notModified: true }
+  | { notModified: false; content: Buffer; validators: CacheValidators }
+
+function fetchBuffer(
+  url: URL,
+  validators: CacheValidators = {},
+  redirects = 0,
+): Promise<FetchResult> {
+  const requestHeaders: OutgoingHttpHeaders = {}
+  if (validators.etag) {
+    requestHeaders['if-none-match'] = validators.etag
+  }
+  if (validators.lastModified) {
+    requestHeaders['if-modified-since'] = validators.lastModified
+  }
+  return new Promise((resolve, reject) => {
+    get(url, requestHeaders, (res) => {
+      const { statusCode = 0, headers } = res
+      if (statusCode === 304) {
+        res.resume()
+        resolve({ notModified: true })
+        return
+      }
+      if (statusCode >= 300 && statusCode < 400 && headers.location) {
+        res.resume()
+        if (redirects >= MAX_REDIRECTS) {
+          reject(
+            new NapiError(`Too many redirects while downloading ${url}`, {
+              code: 'napi::network::redirects',
+            }),
+          )
+          return
+        }
+        fetchBuffer(
+          new URL(headers.location, url),
+          validators,
+          redirects + 1,
+        ).then(resolve, reject)
+        return
+      }
+      if (statusCode !== 200) {
+        res.resume()
+        reject(
+          new NapiError(`Download ${url} failed: ${statusCode}`, {
+            code: 'napi::network::status',
+            causes:
+              statusCode === 404
+                ? ['The release or the asset of this platform does not exist']
+                : [],
+            help: getProxyUrl(url)
+              ? []
+              : [
+                  `Set ${chalk.green(
+                    'HTTPS_PROXY',
+                  )} if the network is only reachable through a proxy`,
+                ],
+          }),
+        )
+        return
+      }
+      const chunks: Buffer[] = []
+      res.on('data', (chunk) => chunks.push(chunk))
+      res.once('end', () =>
+        resolve({
+          notModified: false,
+          content: Buffer.concat(chunks),
+          validators: {
+            etag: headers.etag,
+            lastModified: headers['last-modified'],
+          },
+        }),
+      )
+      res.once('error', reject)
+    }).once('error', reject)
+  })
+}
+
+async function readValidators(metaPath: string): Promise<CacheValidators> {
+  try {
+    return JSON.parse(await readFileAsync(metaPath, 'utf8'))
+  } catch {
+    return {}
+  }
+}
+
+/**
+ * Download `url` into the napi-rs cache dir.
+ *
+ * The cached copy is revalidated with its `ETag` or `Last-Modified` header, a re-uploaded asset replaces it.
+ * It's used as is with `--offline` or when the network is unreachable.
+ */
+export async function download(
+  url: string,
+  { offline = false }: { offline?: boolean } = {},
+): Promise<Buffer> {
+  const cacheDir = join(envPaths('napi-rs').cache, 'downloads')
+  const cachePath = join(
+    cacheDir,
+    createHash('sha256').update(url).digest('hex').slice(0, 16) +
+      '-' +
+      decodeURIComponent(new URL(url).pathname.split('/').pop() ?? ''),
+  )
+  const metaPath = `${cachePath}.json`
+  const cached = existsSync(cachePath)
+  if (offline) {
+    if (!cached) {
+      throw new OfflineError(url, `No cached copy in ${cacheDir}`)
+    }
+    debug(`Use cached [${chalk.yellowBright(cachePath)}] of ${url}`)
+    return readFileAsync(cachePath)
+  }
+  // without validators the cached copy can't be revalidated, download it again
+  const validators = cached ? await readValidators(metaPath) : {}
+  debug(`Download ${chalk.green(url)}`)
+  let result: FetchResult
+  try {
+    result = await fetchBuffer(new URL(url), validators)
+  } catch (e) {
+    if (!cached || e instanceof NapiError) {
+      throw e
+    }
+    debug(
+      `Use cached [${chalk.yellowBright(cachePath)}] of ${url}, ${
+        (e as Error).message
+      }`,
+    )
+    return readFileAsync(cachePath)
+  }
+  if (result.notModified) {
+    debug(`Cached [${chalk.yellowBright(cachePath)}] of ${url} is up to date`)
+    return readFileAsync(cachePath)
+  }
+  await mkdirAsync(cacheDir, { recursive: true })
+  // write to a temporary file first, an interrupted download must not leave a broken cache entry
+  const tmpPath = `${cachePath}.${process.pid}.tmp`
+  await writeFileAsync(tmpPath, result.content)
+  await renameAsync(tmpPath, cachePath)
+  await writeFileAsync(metaPath, JSON.stringify(result.validators))
+  return result.content
+}
@@ -1,15 +1,26 @@
-export const createCargoContent = (name: string) => `[package]
-edition = "2021"
+// `--compat-mode` registers the exports with `#[module_exports]`, which needs the `compat-mode` features
+const NapiDependencies = `napi = { version = "NAPI_VERSION", default-features = false, features = ["napi4"] }
+napi-derive = "NAPI_DERIVE_VERSION"`
+
+const CompatNapiDependencies = `napi = { version = "NAPI_VERSION", default-features = false, features = ["compat-mode", "napi4"] }
+napi-derive = { version = "NAPI_DERIVE_VERSION", default-features = false, features = ["compat-mode"] }`
+
+export const createCargoContent = (
+  name: string,
+  compatMode = false,
+  edition = '2021',
+  msrv?: string,
+) => `[package]
+edition = "${edition}"
 name = "${name.replace('@', '').replace('/', '_').toLowerCase()}"
-version = "0.0.0"
+version = "0.0.0"${msrv ? `\nrust-version = "${msrv}"` : ''}
 
 [lib]
 crate-type = ["cdylib"]
 
 [dependencies]
 # Default enable napi4 feature, see https://nodejs.org/api/n-api.html#node-api-version-matrix
-napi = { version = "NAPI_VERSION", default-features = false, features = ["napi4"] }
-napi-derive = "NAPI_DERIVE_VERSION"
+${compatMode ? CompatNapiDependencies : NapiDependencies}
 
 [build-dependencies]
 napi-build = "NAPI_BUILD_VERSION"
@@ -1,4 +1,4 @@
-export const YAML = (app: string) => `
+export const YAML = (app: string, toolchain = 'stable') => `
 name: CI
 
 env:
@@ -116,7 +116,7 @@ jobs:
             target: 'aarch64-pc-windows-msvc'
             build: yarn build --target aarch64-pc-windows-msvc
 
-    name: stable - \${{ matrix.settings.target }} - node@16
+    name: ${toolchain} - \${{ matrix.settings.target }} - node@16
     runs-on: \${{ matrix.settings.host }}
 
     steps:
@@ -135,7 +135,7 @@ jobs:
         with:
           profile: minimal
           override: true
-          toolchain: stable
+          toolchain: ${toolchain}
           target: \${{ matrix.settings.target }}
 
       - name: Generate Cargo.lock
@@ -208,7 +208,7 @@ jobs:
             curl -qL https://www.npmjs.com/install.sh | sh
             npm install -g yarn
             curl https://sh.rustup.rs -sSf --output rustup.sh
-            sh rustup.sh -y --profile minimal --default-toolchain stable
+            sh rustup.sh -y --profile minimal --default-toolchain ${toolchain}
             export PATH="/usr/local/cargo/bin:$PATH"
             echo "~~~~ rustc --version ~~~~"
             rustc --version
@@ -499,6 +499,10 @@ jobs:
   publish:
     name: Publish
     runs-on: ubuntu-latest
+    # creating the Github release, and the OIDC token if `--provenance` is added to `npm publish`
+    permissions:
+      contents: write
+      id-token: write
     needs:
       - test-linux-x64-gnu-binding
       - test-linux-x64-musl-binding
@@ -13,8 +13,10 @@ const TEST_LINUX_ARM_GNUEABIHF = 'test-linux-arm-gnueabihf-binding'
 export const createGithubActionsCIYml = (
   binaryName: string,
   targets: string[],
+  toolchain = 'stable',
+  wasm = false,
 ) => {
-  const fullTemplate = load(YAML(binaryName)) as any
+  const fullTemplate = load(YAML(binaryName, toolchain)) as any
   const requiredSteps = []
   const enableWindowsX86 = targets.includes('x86_64-pc-windows-msvc')
   const enableMacOSX86 = targets.includes('x86_64-apple-darwin')
@@ -29,6 +31,19 @@ export const createGithubActionsCIYml = (
     fullTemplate.jobs.build.strategy.matrix.settings.filter(
       ({ target }: { target: string }) => targets.includes(target),
     )
+  if (wasm) {
+    // the `wasm32-wasi` fallback package of `napi.wasm`
+    fullTemplate.jobs.build.strategy.matrix.settings.push({
+      host: 'ubuntu-latest',
+      target: 'wasm32-wasip1-threads',
+      architecture: 'x64',
+      build: 'yarn build --target wasm32-wasip1-threads',
+    })
+    const uploadStep = fullTemplate.jobs.build.steps.find(
+      ({ name }: { name?: string }) => name === 'Upload artifact',
+    )
+    uploadStep.with.path = `${uploadStep.with.path}\n\${{ env.APP_NAME }}.*.wasm`
+  }
   if (!fullTemplate.jobs.build.strategy.matrix.settings.length) {
     delete fullTemplate.jobs.build.strategy.matrix
   }
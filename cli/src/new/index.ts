@@ -12,7 +12,12 @@ import { DefaultPlatforms } from '../parse-triple'
 import { createCargoContent } from './cargo'
 import { createCargoConfig } from './cargo-config'
 import { createGithubActionsCIYml } from './ci-yml'
-import { LibRs } from './lib-rs'
+import {
+  CompatIndexDts,
+  CompatLibRs,
+  createCompatIndexJs,
+  LibRs,
+} from './lib-rs'
 import { NPMIgnoreFiles } from './npmignore'
 import { createPackageJson } from './package'
 
@@ -29,6 +34,19 @@ fn main() {
 }
 `
 
+// the first Rust version supporting each edition
+const RustEditions: Record<string, string> = {
+  '2015': '1.0',
+  '2018': '1.31',
+  '2021': '1.56',
+}
+
+const compareRustVersions = (a: string, b: string) => {
+  const [aMajor, aMinor = 0, aPatch = 0] = a.split('.').map(Number)
+  const [bMajor, bMinor = 0, bPatch = 0] = b.split('.').map(Number)
+  return aMajor - bMajor || aMinor - bMinor || aPatch - bPatch
+}
+
 const SupportedPlatforms: string[] = [
   'aarch64-apple-darwin',
   'aarch64-linux-android',
@@ -68,7 +86,35 @@ export class NewProjectCommand extends Command {
 
   enableGithubActions?: boolean = Option.Boolean(`--enable-github-actions`)
 
+  rustEdition = Option.String('--rust-edition', '2021', {
+    description: `The ${chalk.green('edition')} of the generated crate`,
+  })
+
+  msrv?: string = Option.String('--msrv', {
+    description: `The minimum supported Rust version, written to ${chalk.green(
+      'rust-version',
+    )} and used as the toolchain of the CI workflow`,
+  })
+
+  compatMode = Option.Boolean(`--compat-mode`, false, {
+    description: `Generate a napi v1 style project registering its exports with ${chalk.green(
+      '#[module_exports]',
+    )}, without the ${chalk.green('#[napi]')} macro and type generation`,
+  })
+
+  wasmFallback = Option.Boolean('--wasm-fallback', false, {
+    description: `Generate a ${chalk.green(
+      'wasm32-wasi',
+    )} package loaded on platforms without a native binary`,
+  })
+
   async execute() {
+    if (this.compatMode && this.wasmFallback) {
+      throw new TypeError(
+        '--wasm-fallback needs the generated js binding, it can not be used with --compat-mode',
+      )
+    }
+    this.validateRustVersions()
     await this.getName()
     if (!this.dirname) {
       const [scope, name] = this.name?.split('/') ?? []
@@ -122,18 +168,37 @@ export class NewProjectCommand extends Command {
     const [s, pkgName] = this.name!.split('/')
     const binaryName = pkgName ?? s
 
-    this.writeFile('Cargo.toml', createCargoContent(this.name!))
+    this.writeFile(
+      'Cargo.toml',
+      createCargoContent(
+        this.name!,
+        this.compatMode,
+        this.rustEdition,
+        this.msrv,
+      ),
+    )
     this.writeFile('.npmignore', NPMIgnoreFiles)
     this.writeFile('build.rs', BUILD_RS)
     this.writeFile(
       'package.json',
       JSON.stringify(
-        createPackageJson(this.name!, binaryName, this.targets!),
+        createPackageJson(
+          this.name!,
+          binaryName,
+          this.targets!,
+          this.wasmFallback,
+        ),
         null,
         2,
       ),
     )
-    this.writeFile('src/lib.rs', LibRs)
+    if (this.compatMode) {
+      this.writeFile('src/lib.rs', CompatLibRs)
+      this.writeFile('index.js', createCompatIndexJs(binaryName, this.name!))
+      this.writeFile('index.d.ts', CompatIndexDts)
+    } else {
+      this.writeFile('src/lib.rs', LibRs)
+    }
 
     if (this.enableGithubActions) {
       const githubDir = join(process.cwd(), this.dirname!, '.github')
@@ -144,7 +209,12 @@ export class NewProjectCommand extends Command {
       }
       this.writeFile(
         join('.github', 'workflows', 'CI.yml'),
-        createGithubActionsCIYml(binaryName, this.targets!),
+        createGithubActionsCIYml(
+          binaryName,
+          this.targets!,
+          this.msrv,
+          this.wasmFallback,
+        ),
       )
     }
 
@@ -178,11 +248,34 @@ export class NewProjectCommand extends Command {
     this.writeFile(
       'rustfmt.toml',
       `tab_spaces = 2
-edition = "2021"
+edition = "${this.rustEdition}"
 `,
     )
   }
 
+  private validateRustVersions() {
+    const editionSince = RustEditions[this.rustEdition]
+    if (!editionSince) {
+      throw new TypeError(
+        `Unknown Rust edition ${this.rustEdition}, expected one of ${Object.keys(
+          RustEditions,
+        ).join(', ')}`,
+      )
+    }
+    if (this.msrv) {
+      if (!/^\d+\.\d+(\.\d+)?$/.test(this.msrv)) {
+        throw new TypeError(
+          `Invalid --msrv ${this.msrv}, expected a Rust version like 1.56`,
+        )
+      }
+      if (compareRustVersions(this.msrv, editionSince) < 0) {
+        throw new TypeError(
+          `Rust edition ${this.rustEdition} needs Rust ${editionSince}, but --msrv is ${this.msrv}`,
+        )
+      }
+    }
+  }
+
   private writeFile(path: string, content: string) {
     const distDir = join(process.cwd(), this.dirname!)
     this.context.stdout.write(chalk.green(`Writing ${chalk.blue(path)}\n`))
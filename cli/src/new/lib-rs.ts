@@ -1,3 +1,5 @@
+import { createJsBinding } from '../js-binding-template'
+
 export const LibRs = `#![deny(clippy::all)]
 
 #[macro_use]
@@ -8,3 +10,36 @@ fn sum(a: i32, b: i32) -> i32 {
   a + b
 }
 `
+
+// `--compat-mode` registers the exports like napi v1, the typings are written by hand
+export const CompatLibRs = `#![deny(clippy::all)]
+
+#[macro_use]
+extern crate napi_derive;
+
+use napi::{CallContext, JsNumber, JsObject, Result};
+
+#[js_function(2)]
+fn sum(ctx: CallContext) -> Result<JsNumber> {
+  let a = ctx.get::<JsNumber>(0)?.get_int32()?;
+  let b = ctx.get::<JsNumber>(1)?.get_int32()?;
+  ctx.env.create_int32(a + b)
+}
+
+#[module_exports]
+fn init(mut exports: JsObject) -> Result<()> {
+  exports.create_named_method("sum", sum)?;
+  Ok(())
+}
+`
+
+// there are no `#[napi]` exports for `napi build` to generate the loader from,
+// it loads the `.node` file of the platform and exports it as a whole
+export const createCompatIndexJs = (
+  binaryName: string,
+  packageName: string,
+) => `${createJsBinding(binaryName, packageName)}module.exports = nativeBinding
+`
+
+export const CompatIndexDts = `export function sum(a: number, b: number): number
+`
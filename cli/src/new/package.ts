@@ -5,6 +5,7 @@ export const createPackageJson = (
   name: string,
   binaryName: string,
   targets: string[],
+  wasmFallback = false,
 ) => {
   const pkgContent = {
     name,
@@ -54,5 +55,13 @@ export const createPackageJson = (
   // @ts-expect-error
   pkgContent.napi.triples = triples
 
+  if (wasmFallback) {
+    // @ts-expect-error
+    pkgContent.napi.wasm = true
+    // `napi build --target wasm32-wasip1-threads` links its static libraries
+    // @ts-expect-error
+    pkgContent.devDependencies.emnapi = '^1.0.0'
+  }
+
   return pkgContent
 }
@@ -1,7 +1,7 @@
 import { execSync } from 'child_process'
 
 // https://nodejs.org/api/process.html#process_process_arch
-type NodeJSArch =
+export type NodeJSArch =
   | 'arm'
   | 'arm64'
   | 'ia32'
@@ -29,7 +29,8 @@ const SysToNodePlatform: { [index: string]: NodeJS.Platform } = {
 }
 
 export interface PlatformDetail {
-  platform: NodeJS.Platform
+  // `wasi` is the `wasm32-wasi` fallback, Node.js never reports it
+  platform: NodeJS.Platform | 'wasi'
   platformArchABI: string
   arch: NodeJSArch
   raw: string
@@ -71,6 +72,16 @@ export const DefaultPlatforms: PlatformDetail[] = [
  *   - `abi` = The ABI, for example `gnu`, `android`, `eabi`, etc.
  */
 export function parseTriple(rawTriple: string): PlatformDetail {
+  // `wasm32-wasi`, `wasm32-wasip1` and `wasm32-wasip1-threads` all build the `wasm32-wasi` fallback
+  if (rawTriple.startsWith('wasm32-wasi')) {
+    return {
+      platform: 'wasi',
+      arch: 'wasm32' as NodeJSArch,
+      abi: null,
+      platformArchABI: 'wasm32-wasi',
+      raw: rawTriple,
+    }
+  }
   const triple = rawTriple.endsWith('eabi')
     ? `${rawTriple.slice(0, -4)}-eabi`
     : rawTriple
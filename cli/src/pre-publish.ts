@@ -1,4 +1,5 @@
-import { createReadStream, existsSync, statSync } from 'fs'
+import { execSync } from 'child_process'
+import { createReadStream, existsSync, readFileSync, statSync } from 'fs'
 import { join } from 'path'
 
 import { Octokit } from '@octokit/rest'
@@ -7,9 +8,13 @@ import { Command, Option } from 'clipanion'
 
 import { getNapiConfig } from './consts'
 import { debugFactory } from './debug'
+import { NapiError } from './diagnostics'
+import { createAgent } from './network'
+import { Progress } from './progress'
 import { spawn } from './spawn'
 import { updatePackageJson } from './update-package'
-import { VersionCommand } from './version'
+import { mkdirAsync } from './utils'
+import { isPrerelease, VersionCommand } from './version'
 
 const debug = debugFactory('prepublish')
 
@@ -37,91 +42,209 @@ export class PrePublishCommand extends Command {
 
   skipGHRelease = Option.Boolean('--skip-gh-release', false)
 
+  ghReleaseOnly = Option.Boolean('--gh-release-only', false, {
+    description: `Only create or update the Github release and upload the binaries, without ${chalk.green(
+      'npm publish',
+    )}`,
+  })
+
+  offline = Option.Boolean('--offline', false)
+
+  // `npm publish --provenance` of the main package passes it to the lifecycle scripts
+  provenance = Option.Boolean(
+    '--provenance',
+    process.env.npm_config_provenance === 'true',
+    {
+      description: `Publish the platform packages with ${chalk.green(
+        'npm publish --provenance',
+      )}, only supported in Github Actions and GitLab CI`,
+    },
+  )
+
+  otp?: string = Option.String('--otp', {
+    description:
+      'The one-time password of npm 2FA, not needed with an automation token',
+  })
+
+  packDestination?: string = Option.String('--pack', {
+    description: `Run ${chalk.green(
+      'npm pack',
+    )} for the root and every platform package into this directory instead of publishing them`,
+  })
+
   async execute() {
-    const { packageJsonPath, platforms, version, packageName, binaryName } =
-      getNapiConfig(this.configFileName)
+    if (this.packDestination && this.ghReleaseOnly) {
+      throw new NapiError(
+        `${chalk.green('--pack')} conflicts with ${chalk.green(
+          '--gh-release-only',
+        )}`,
+        {
+          code: 'napi::prepublish::pack',
+          help: ['Pass only one of them'],
+        },
+      )
+    }
+    if (this.ghReleaseOnly && this.skipGHRelease) {
+      throw new NapiError(
+        `${chalk.green('--gh-release-only')} conflicts with ${chalk.green(
+          '--skip-gh-release',
+        )}`,
+        {
+          code: 'napi::prepublish::gh_release',
+          help: ['Pass only one of them'],
+        },
+      )
+    }
+    if (this.offline && !this.isDryRun && !this.packDestination) {
+      throw new NapiError(
+        `prepublish can not run with ${chalk.green('--offline')}`,
+        {
+          code: 'napi::prepublish::offline',
+          causes: ['Publishing to npm and Github needs the network'],
+          help: [`Add ${chalk.green('--dry-run')} to only check the packages`],
+        },
+      )
+    }
+    if (this.provenance && !this.isDryRun && !this.packDestination) {
+      checkProvenanceSupport()
+    }
+    const {
+      packageJsonPath,
+      platforms,
+      version,
+      packageName,
+      binaryName,
+      wasm,
+    } = getNapiConfig(this.configFileName)
+    const optionalDependencies = platforms.reduce(
+      (acc: Record<string, string>, cur) => {
+        acc[`${packageName}-${cur.platformArchABI}`] = `${version}`
+        return acc
+      },
+      wasm ? { [`${packageName}-wasm32-wasi`]: `${version}` } : {},
+    )
+    const pkgDirs = [
+      ...platforms.map((platform) => platform.platformArchABI),
+      ...(wasm ? ['wasm32-wasi'] : []),
+    ]
+    if (this.isDryRun) {
+      await this.printDryRun(
+        packageJsonPath,
+        packageName,
+        version,
+        optionalDependencies,
+        pkgDirs,
+      )
+      return
+    }
     debug(`Update optionalDependencies in [${packageJsonPath}]`)
-    if (!this.isDryRun) {
+    if (!this.ghReleaseOnly) {
       await VersionCommand.updatePackageJson(this.prefix, this.configFileName)
-      await updatePackageJson(packageJsonPath, {
-        optionalDependencies: platforms.reduce(
-          (acc: Record<string, string>, cur) => {
-            acc[`${packageName}-${cur.platformArchABI}`] = `${version}`
-            return acc
-          },
-          {},
-        ),
-      })
+      await updatePackageJson(packageJsonPath, { optionalDependencies })
     }
 
-    const { owner, repo, pkgInfo, octokit } = await this.createGhRelease(
-      packageName,
-      version,
-    )
+    if (this.packDestination) {
+      await this.pack(packageName, version, pkgDirs)
+      return
+    }
 
+    const { owner, repo, pkgInfo, octokit, releaseId } =
+      await this.createGhRelease(packageName, version)
+
+    const progress = new Progress(`Publish ${packageName}@${version}`, pkgDirs)
     for (const platformDetail of platforms) {
-      const pkgDir = join(
-        process.cwd(),
-        this.prefix,
-        `${platformDetail.platformArchABI}`,
-      )
-      const filename = `${binaryName}.${platformDetail.platformArchABI}.node`
+      const { platformArchABI } = platformDetail
+      const pkgDir = join(process.cwd(), this.prefix, `${platformArchABI}`)
+      const filename = `${binaryName}.${platformArchABI}.node`
       const dstPath = join(pkgDir, filename)
 
-      if (!this.isDryRun) {
-        if (!existsSync(dstPath)) {
-          console.warn(`[${chalk.yellowBright(dstPath)}] is not existed`)
-          continue
-        }
-        await spawn('npm publish', {
-          cwd: pkgDir,
-          env: process.env,
-        })
-        if (!this.skipGHRelease) {
-          debug(
-            `Start upload [${chalk.greenBright(
-              dstPath,
-            )}] to Github release, [${chalk.greenBright(pkgInfo.tag)}]`,
-          )
-          try {
-            const releaseInfo = await octokit!.repos.getReleaseByTag({
-              repo: repo!,
-              owner: owner!,
-              tag: pkgInfo.tag!,
-            })
-            const dstFileStats = statSync(dstPath)
-            const assetInfo = await octokit!.repos.uploadReleaseAsset({
+      if (!existsSync(dstPath)) {
+        progress.suspend()
+        console.warn(`[${chalk.yellowBright(dstPath)}] is not existed`)
+        progress.fail(platformArchABI, 'binary not found')
+        continue
+      }
+      if (!this.ghReleaseOnly) {
+        progress.start(platformArchABI, 'npm publish')
+        progress.suspend()
+        await this.npmPublish(pkgDir)
+      }
+      if (!this.skipGHRelease) {
+        progress.start(platformArchABI, 'upload to Github release')
+        progress.suspend()
+        debug(
+          `Start upload [${chalk.greenBright(
+            dstPath,
+          )}] to Github release, [${chalk.greenBright(pkgInfo.tag)}]`,
+        )
+        try {
+          // re-running replaces the assets uploaded before
+          const { data: assets } = await octokit!.repos.listReleaseAssets({
+            owner: owner!,
+            repo: repo!,
+            release_id: releaseId!,
+            per_page: 100,
+          })
+          const existed = assets.find((asset) => asset.name === filename)
+          if (existed) {
+            debug(`Delete existed asset [${chalk.yellowBright(filename)}]`)
+            await octokit!.repos.deleteReleaseAsset({
               owner: owner!,
               repo: repo!,
-              name: filename,
-              release_id: releaseInfo.data.id,
-              mediaType: { format: 'raw' },
-              headers: {
-                'content-length': dstFileStats.size,
-                'content-type': 'application/octet-stream',
-              },
-              // @ts-expect-error
-              data: createReadStream(dstPath),
+              asset_id: existed.id,
             })
-            console.info(`${chalk.green(dstPath)} upload success`)
-            console.info(
-              `Download url: ${chalk.blueBright(
-                assetInfo.data.browser_download_url,
-              )}`,
-            )
-          } catch (e) {
-            debug(
-              `Param: ${JSON.stringify(
-                { owner, repo, tag: pkgInfo.tag, filename: dstPath },
-                null,
-                2,
-              )}`,
-            )
-            console.error(e)
           }
+          const dstFileStats = statSync(dstPath)
+          const assetInfo = await octokit!.repos.uploadReleaseAsset({
+            owner: owner!,
+            repo: repo!,
+            name: filename,
+            release_id: releaseId!,
+            mediaType: { format: 'raw' },
+            headers: {
+              'content-length': dstFileStats.size,
+              'content-type': 'application/octet-stream',
+            },
+            // @ts-expect-error
+            data: createReadStream(dstPath),
+          })
+          console.info(`${chalk.green(dstPath)} upload success`)
+          console.info(
+            `Download url: ${chalk.blueBright(
+              assetInfo.data.browser_download_url,
+            )}`,
+          )
+        } catch (e) {
+          debug(
+            `Param: ${JSON.stringify(
+              { owner, repo, tag: pkgInfo.tag, filename: dstPath },
+              null,
+              2,
+            )}`,
+          )
+          console.error(e)
+          progress.fail(platformArchABI, 'upload failed')
+          continue
         }
       }
+      progress.succeed(platformArchABI)
+    }
+    if (wasm && !this.ghReleaseOnly) {
+      // the fallback is only published to npm, it is not a Github release asset
+      const pkgDir = join(process.cwd(), this.prefix, 'wasm32-wasi')
+      const wasmPath = join(pkgDir, `${binaryName}.wasm32-wasi.wasm`)
+      if (!existsSync(wasmPath)) {
+        progress.suspend()
+        console.warn(`[${chalk.yellowBright(wasmPath)}] is not existed`)
+        progress.fail('wasm32-wasi', 'binary not found')
+      } else {
+        progress.start('wasm32-wasi', 'npm publish')
+        progress.suspend()
+        await this.npmPublish(pkgDir)
+        progress.succeed('wasm32-wasi')
+      }
     }
+    progress.finish()
   }
 
   private async createGhRelease(packageName: string, version: string) {
@@ -130,17 +253,222 @@ export class PrePublishCommand extends Command {
         owner: null,
         repo: null,
         pkgInfo: { name: null, version: null, tag: null },
+        releaseId: null,
       }
     }
-    const headCommit = (await spawn('git log -1 --pretty=%B'))
-      .toString('utf8')
-      .trim()
+    const pkgInfo = await this.getPkgInfo(packageName, version)
 
     debug(`Github repository: ${process.env.GITHUB_REPOSITORY}`)
     const [owner, repo] = process.env.GITHUB_REPOSITORY!.split('/')
     const octokit = new Octokit({
       auth: process.env.GITHUB_TOKEN,
+      request: { agent: createAgent(new URL('https://api.github.com')) },
+    })
+    let releaseId: number
+    const prerelease = isPrerelease(version)
+    try {
+      const existed = await octokit.repos
+        .getReleaseByTag({ owner, repo, tag: pkgInfo.tag })
+        .catch((e) => {
+          if (e.status === 404) {
+            return null
+          }
+          throw e
+        })
+      if (existed) {
+        debug(`Update existed Github release [${pkgInfo.tag}]`)
+        await octokit.repos.updateRelease({
+          owner,
+          repo,
+          release_id: existed.data.id,
+          prerelease,
+        })
+        releaseId = existed.data.id
+      } else {
+        const { data } = await octokit.repos.createRelease({
+          owner,
+          repo,
+          tag_name: pkgInfo.tag,
+          prerelease,
+        })
+        releaseId = data.id
+      }
+    } catch (e) {
+      debug(
+        `Params: ${JSON.stringify(
+          { owner, repo, tag_name: pkgInfo.tag },
+          null,
+          2,
+        )}`,
+      )
+      // the assets can't be uploaded without the release, stop before anything is published
+      throw new NapiError(
+        `Can not create or update the Github release ${chalk.green(
+          pkgInfo.tag,
+        )} of ${chalk.green(`${owner}/${repo}`)}`,
+        {
+          code: 'napi::prepublish::gh_release_create',
+          causes: [(e as Error).message],
+          help: [
+            `Check that ${chalk.green(
+              'GITHUB_TOKEN',
+            )} is allowed to write the contents of the repository`,
+            `Add ${chalk.green('--skip-gh-release')} to only publish to npm`,
+          ],
+        },
+      )
+    }
+    return { owner, repo, pkgInfo, octokit, releaseId }
+  }
+
+  // what `prepublish` would do, the tarballs are listed by `npm pack --dry-run`
+  private async printDryRun(
+    packageJsonPath: string,
+    packageName: string,
+    version: string,
+    optionalDependencies: Record<string, string>,
+    pkgDirs: string[],
+  ) {
+    const { optionalDependencies: current = {}, publishConfig } = JSON.parse(
+      readFileSync(packageJsonPath, 'utf8'),
+    )
+    const lines = [chalk.bold('Dry run, nothing is published'), '']
+    // `publishConfig.tag` is set by `napi version --tag`, it wins over the `npm publish --tag` of the main package
+    const distTag = publishConfig?.tag ?? process.env.npm_config_tag ?? 'latest'
+    lines.push(`npm dist-tag: ${chalk.green(distTag)}`)
+    if (this.skipGHRelease) {
+      lines.push(`Github release: ${chalk.gray('skipped')}`)
+    } else {
+      const { tag } = await this.getPkgInfo(packageName, version)
+      lines.push(
+        `Github release: ${chalk.green(tag)} of ${chalk.green(
+          process.env.GITHUB_REPOSITORY ?? '(GITHUB_REPOSITORY is not set)',
+        )}`,
+      )
+    }
+
+    lines.push(
+      '',
+      `${chalk.bold('optionalDependencies')} of ${packageJsonPath}`,
+      ...diffDependencies(current, optionalDependencies),
+    )
+
+    for (const dir of pkgDirs) {
+      const pkgDir = join(process.cwd(), this.prefix, dir)
+      lines.push('')
+      if (!existsSync(join(pkgDir, 'package.json'))) {
+        lines.push(`${chalk.red('✖')} ${pkgDir} is not existed`)
+        continue
+      }
+      const pkgJson = JSON.parse(
+        readFileSync(join(pkgDir, 'package.json'), 'utf8'),
+      )
+      const versionChange =
+        pkgJson.version === version
+          ? chalk.green(version)
+          : `${chalk.red(pkgJson.version)} → ${chalk.green(version)}`
+      lines.push(`${chalk.bold(pkgJson.name)}@${versionChange} (${pkgDir})`)
+      let packed: any
+      try {
+        ;[packed] = JSON.parse(
+          execSync('npm pack --dry-run --json', {
+            cwd: pkgDir,
+            env: process.env,
+            stdio: ['ignore', 'pipe', 'ignore'],
+          }).toString('utf8'),
+        )
+      } catch (e) {
+        debug(`npm pack failed in [${pkgDir}]: ${e}`)
+        lines.push(`  ${chalk.red('npm pack failed')}`)
+        continue
+      }
+      for (const file of packed.files) {
+        lines.push(`  ${formatSize(file.size).padStart(9)}  ${file.path}`)
+      }
+      const hasBinary = packed.files.some((file: any) =>
+        /\.(node|wasm)$/.test(file.path),
+      )
+      if (!hasBinary) {
+        lines.push(`  ${chalk.red('✖ no binary in the tarball')}`)
+      }
+      lines.push(
+        `  ${chalk.gray(
+          `${packed.entryCount} files, ${formatSize(
+            packed.size,
+          )} packed, ${formatSize(packed.unpackedSize)} unpacked`,
+        )}`,
+      )
+    }
+    this.context.stdout.write(lines.join('\n') + '\n')
+  }
+
+  private async pack(packageName: string, version: string, pkgDirs: string[]) {
+    const destination = join(process.cwd(), this.packDestination!)
+    await mkdirAsync(destination, { recursive: true })
+    const progress = new Progress(`Pack ${packageName}@${version}`, [
+      ...pkgDirs,
+      packageName,
+    ])
+    // the platform packages first, the root package depends on them
+    const targets = [
+      ...pkgDirs.map((dir) => ({
+        name: dir,
+        cwd: join(process.cwd(), this.prefix, dir),
+      })),
+      { name: packageName, cwd: process.cwd() },
+    ]
+    const tarballs: string[] = []
+    for (const { name, cwd } of targets) {
+      if (!existsSync(join(cwd, 'package.json'))) {
+        progress.fail(name, 'package.json not found')
+        continue
+      }
+      progress.start(name, 'npm pack')
+      progress.suspend()
+      try {
+        const [packed] = JSON.parse(
+          execSync(
+            `npm pack --json --pack-destination ${JSON.stringify(destination)}`,
+            { cwd, env: process.env, stdio: ['ignore', 'pipe', 'inherit'] },
+          ).toString('utf8'),
+        )
+        tarballs.push(join(destination, packed.filename))
+        progress.succeed(name, packed.filename)
+      } catch (e) {
+        progress.fail(name, 'npm pack failed')
+        progress.finish()
+        throw e
+      }
+    }
+    progress.finish()
+    for (const tarball of tarballs) {
+      this.context.stdout.write(`${tarball}\n`)
+    }
+  }
+
+  private async npmPublish(pkgDir: string) {
+    if (this.provenance) {
+      checkProvenanceRepository(pkgDir)
+    }
+    const flags = [
+      this.provenance ? '--provenance' : '',
+      this.otp ? `--otp ${this.otp}` : '',
+    ].filter(Boolean)
+    await spawn(['npm publish', ...flags].join(' '), {
+      cwd: pkgDir,
+      env: process.env,
     })
+  }
+
+  // the released package and its tag, from the head commit with `--tagstyle lerna`
+  private async getPkgInfo(
+    packageName: string,
+    version: string,
+  ): Promise<PackageInfo> {
+    const headCommit = (await spawn('git log -1 --pretty=%B'))
+      .toString('utf8')
+      .trim()
+
     let pkgInfo: PackageInfo | undefined
     if (this.tagStyle === 'lerna') {
       const packagesToPublish = headCommit
@@ -153,9 +481,18 @@ export class PrePublishCommand extends Command {
         (pkgInfo) => pkgInfo.name === packageName,
       )
       if (!pkgInfo) {
-        throw new TypeError(
-          `No release commit found with ${packageName}, original commit info: ${headCommit}`,
-        )
+        throw new NapiError(`No release commit found with ${packageName}`, {
+          code: 'napi::prepublish::release_commit',
+          causes: [`The head commit is:\n${headCommit}`],
+          help: [
+            `With ${chalk.green(
+              '--tagstyle lerna',
+            )}, the lines after the subject must list the released ${chalk.green(
+              '- package@version',
+            )}`,
+            `Or use ${chalk.green('--tagstyle npm')} to release the package.json version`,
+          ],
+        })
       }
     } else {
       pkgInfo = {
@@ -164,29 +501,7 @@ export class PrePublishCommand extends Command {
         name: packageName,
       }
     }
-    if (!this.isDryRun) {
-      try {
-        await octokit.repos.createRelease({
-          owner,
-          repo,
-          tag_name: pkgInfo.tag,
-          prerelease:
-            version.includes('alpha') ||
-            version.includes('beta') ||
-            version.includes('rc'),
-        })
-      } catch (e) {
-        debug(
-          `Params: ${JSON.stringify(
-            { owner, repo, tag_name: pkgInfo.tag },
-            null,
-            2,
-          )}`,
-        )
-        console.error(e)
-      }
-    }
-    return { owner, repo, pkgInfo, octokit }
+    return pkgInfo
   }
 
   private parseTag(tag: string) {
@@ -201,3 +516,109 @@ export class PrePublishCommand extends Command {
     }
   }
 }
+
+// npm signs the provenance with the OIDC token of the CI
+function checkProvenanceSupport() {
+  if (process.env.GITHUB_ACTIONS) {
+    if (!process.env.ACTIONS_ID_TOKEN_REQUEST_URL) {
+      throw new NapiError('No OIDC token for npm provenance', {
+        code: 'napi::prepublish::provenance',
+        causes: ['The workflow is not allowed to request an id token'],
+        help: [
+          `Add to the publish job:\npermissions:\n  contents: write\n  id-token: write`,
+        ],
+      })
+    }
+    return
+  }
+  if (process.env.GITLAB_CI) {
+    if (!process.env.SIGSTORE_ID_TOKEN) {
+      throw new NapiError('No OIDC token for npm provenance', {
+        code: 'napi::prepublish::provenance',
+        help: [
+          `Add to the publish job:\nid_tokens:\n  SIGSTORE_ID_TOKEN:\n    aud: sigstore`,
+        ],
+      })
+    }
+    return
+  }
+  throw new NapiError(
+    `${chalk.green('--provenance')} is not supported in this environment`,
+    {
+      code: 'napi::prepublish::provenance',
+      causes: ['npm only generates provenance in Github Actions and GitLab CI'],
+      help: [
+        `Publish from the CI, or drop ${chalk.green(
+          '--provenance',
+        )} to publish from this machine`,
+      ],
+    },
+  )
+}
+
+// the attestation is only accepted if the package declares the repository it was built from
+function checkProvenanceRepository(pkgDir: string) {
+  const packageJsonPath = join(pkgDir, 'package.json')
+  const { name, repository } = JSON.parse(readFileSync(packageJsonPath, 'utf8'))
+  const url: string | undefined =
+    typeof repository === 'string' ? repository : repository?.url
+  if (!url) {
+    throw new NapiError(`No repository in ${packageJsonPath}`, {
+      code: 'napi::prepublish::provenance',
+      causes: [`npm rejects the provenance of ${name} without it`],
+      help: [
+        `Add ${chalk.green(
+          'repository',
+        )} to the package.json of the project, then run ${chalk.green(
+          'napi create-npm-dir',
+        )} again`,
+      ],
+    })
+  }
+  const expected = process.env.GITHUB_REPOSITORY ?? process.env.CI_PROJECT_PATH
+  if (expected && !url.toLowerCase().includes(expected.toLowerCase())) {
+    throw new NapiError(`Repository of ${name} does not match the CI`, {
+      code: 'napi::prepublish::provenance',
+      causes: [
+        `${chalk.yellowBright(url)} is not the repository ${chalk.green(
+          expected,
+        )} running the build`,
+      ],
+      help: [`Point ${chalk.green('repository')} to ${expected}`],
+    })
+  }
+  debug(`Provenance repository of ${name}: ${chalk.green(url)}`)
+}
+
+function diffDependencies(
+  current: Record<string, string>,
+  next: Record<string, string>,
+) {
+  const names = Array.from(
+    new Set([...Object.keys(current), ...Object.keys(next)]),
+  ).sort()
+  const lines = names.map((name) => {
+    // the whole field is replaced, dependencies of removed platforms are dropped
+    if (!(name in next)) {
+      return `  ${chalk.red(`- ${name}@${current[name]}`)}`
+    }
+    if (!(name in current)) {
+      return `  ${chalk.green(`+ ${name}@${next[name]}`)}`
+    }
+    if (current[name] !== next[name]) {
+      return `  ${chalk.yellow(`~ ${name}@${current[name]} → ${next[name]}`)}`
+    }
+    return `    ${name}@${next[name]}`
+  })
+  return lines.length ? lines : [`  ${chalk.gray('(none)')}`]
+}
+
+function formatSize(bytes: number) {
+  if (bytes < 1024) {
+    return `${bytes} B`
+  }
+  if (bytes < 1024 * 1024) {
+    return `${(bytes / 1024).toFixed(1)} kB`
+  }
+  return `${(bytes / 1024 / 1024).toFixed(1)} MB`
+}
@@ -0,0 +1,145 @@
+import chalk from 'chalk'
+
+const SPINNER_FRAMES = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏']
+
+// redrawing lines only works on an interactive terminal, CI logs get plain lines instead
+export const isProgressEnabled = () =>
+  Boolean(process.stderr.isTTY) &&
+  !process.env.CI &&
+  process.env.NAPI_PROGRESS !== 'false'
+
+type TaskStatus = 'pending' | 'running' | 'done' | 'failed'
+
+interface Task {
+  name: string
+  status: TaskStatus
+  detail?: string
+}
+
+/**
+ * Status lines of a group of tasks, drawn on stderr so the piped stdout is untouched
+ */
+export class Progress {
+  private readonly tasks: Task[]
+  private readonly enabled = isProgressEnabled()
+  private frame = 0
+  private drawnLines = 0
+  private timer?: NodeJS.Timeout
+  private finished = false
+
+  constructor(private readonly title: string, names: string[]) {
+    this.tasks = names.map((name) => ({ name, status: 'pending' }))
+    if (this.enabled) {
+      this.startSpinner()
+      this.draw()
+    }
+  }
+
+  start(name: string, detail?: string) {
+    this.update(name, 'running', detail)
+  }
+
+  succeed(name: string, detail?: string) {
+    this.update(name, 'done', detail)
+  }
+
+  fail(name: string, detail?: string) {
+    this.update(name, 'failed', detail)
+  }
+
+  /**
+   * Run `task` while showing its status, the rejection is rethrown after marking it as failed
+   */
+  async track<T>(name: string, task: () => Promise<T>): Promise<T> {
+    this.start(name)
+    try {
+      const result = await task()
+      this.succeed(name)
+      return result
+    } catch (e) {
+      this.fail(name)
+      throw e
+    }
+  }
+
+  /**
+   * Erase the status lines so other output, like the one of cargo, is not overdrawn,
+   * they are drawn again below it on the next update
+   */
+  suspend() {
+    if (this.enabled) {
+      this.stopSpinner()
+      if (this.drawnLines) {
+        process.stderr.write(`\x1b[${this.drawnLines}A\x1b[0J`)
+        this.drawnLines = 0
+      }
+    }
+  }
+
+  finish() {
+    if (this.enabled && !this.finished) {
+      this.stopSpinner()
+      this.draw()
+    }
+    this.finished = true
+  }
+
+  private startSpinner() {
+    this.timer = setInterval(() => {
+      this.frame = (this.frame + 1) % SPINNER_FRAMES.length
+      this.draw()
+    }, 80)
+    this.timer.unref()
+  }
+
+  private stopSpinner() {
+    if (this.timer) {
+      clearInterval(this.timer)
+      this.timer = undefined
+    }
+  }
+
+  private update(name: string, status: TaskStatus, detail?: string) {
+    const task = this.tasks.find((task) => task.name === name)
+    if (!task) {
+      return
+    }
+    task.status = status
+    task.detail = detail
+    if (this.enabled) {
+      if (!this.timer && !this.finished) {
+        this.startSpinner()
+      }
+      this.draw()
+    } else if (status !== 'pending') {
+      process.stderr.write(`${this.formatTask(task)}\n`)
+    }
+  }
+
+  private formatTask(task: Task) {
+    const icon = {
+      pending: chalk.gray('·'),
+      running: chalk.cyan(SPINNER_FRAMES[this.frame]),
+      done: chalk.green('✔'),
+      failed: chalk.red('✖'),
+    }[task.status]
+    const detail = task.detail ? ` ${chalk.gray(task.detail)}` : ''
+    return `${icon} ${task.name}${detail}`
+  }
+
+  private draw() {
+    const done = this.tasks.filter((task) => task.status === 'done').length
+    const lines = [
+      `${chalk.bold(this.title)} ${chalk.gray(
+        `${done}/${this.tasks.length}`,
+      )}`,
+      ...this.tasks.map((task) => `  ${this.formatTask(task)}`),
+    ]
+    // move back to the first line drawn last time and clear everything below it
+    if (this.drawnLines) {
+      process.stderr.write(`\x1b[${this.drawnLines}A`)
+    }
+    process.stderr.write(`\x1b[0J${lines.join('\n')}\n`)
+    this.drawnLines = lines.length
+  }
+}
@@ -1,4 +1,4 @@
-import { readFile, writeFile, copyFile, mkdir, unlink } from 'fs'
+import { readFile, writeFile, copyFile, mkdir, rename, unlink } from 'fs'
 import { promisify } from 'util'
 
 export const readFileAsync = promisify(readFile)
@@ -6,6 +6,7 @@ export const writeFileAsync = promisify(writeFile)
 export const unlinkAsync = promisify(unlink)
 export const copyFileAsync = promisify(copyFile)
 export const mkdirAsync = promisify(mkdir)
+export const renameAsync = promisify(rename)
 
 export function pick<O, K extends keyof O>(o: O, ...keys: K[]): Pick<O, K> {
   return keys.reduce((acc, key) => {
@@ -1,3 +1,4 @@
+import { readFileSync } from 'fs'
 import { join } from 'path'
 
 import chalk from 'chalk'
@@ -5,6 +6,7 @@ import { Command, Option } from 'clipanion'
 
 import { getNapiConfig } from './consts'
 import { debugFactory } from './debug'
+import { NapiError } from './diagnostics'
 import { spawn } from './spawn'
 import { updatePackageJson } from './update-package'
 
@@ -17,17 +19,31 @@ export class VersionCommand extends Command {
 
   static paths = [['version']]
 
-  static async updatePackageJson(prefix: string, configFileName?: string) {
-    const { version, platforms } = getNapiConfig(configFileName)
-    for (const platformDetail of platforms) {
-      const pkgDir = join(process.cwd(), prefix, platformDetail.platformArchABI)
+  static async updatePackageJson(
+    prefix: string,
+    configFileName?: string,
+    { version: nextVersion, tag }: { version?: string; tag?: string } = {},
+  ) {
+    const { platforms, wasm, ...config } = getNapiConfig(configFileName)
+    const version = nextVersion ?? config.version
+    const dirs = platforms.map((platform) => platform.platformArchABI)
+    if (wasm) {
+      dirs.push('wasm32-wasi')
+    }
+    for (const dir of dirs) {
+      const pkgDir = join(process.cwd(), prefix, dir)
       debug(
         `Update version to ${chalk.greenBright(
           version,
         )} in [${chalk.yellowBright(pkgDir)}]`,
       )
-      await updatePackageJson(join(pkgDir, 'package.json'), {
+      const pkgJsonPath = join(pkgDir, 'package.json')
+      // `require` would cache the package.json, read it as it's on disk now
+      const { publishConfig } = JSON.parse(readFileSync(pkgJsonPath, 'utf8'))
+      await updatePackageJson(pkgJsonPath, {
         version,
+        // npm publish reads the dist-tag from `publishConfig`
+        ...(tag ? { publishConfig: { ...publishConfig, tag } } : {}),
       })
     }
   }
@@ -36,8 +52,80 @@ export class VersionCommand extends Command {
 
   configFileName?: string = Option.String('-c,--config')
 
+  preid?: string = Option.String('--preid', {
+    description: `Bump to the next pre-release with this identifier, like ${chalk.green(
+      '1.2.3',
+    )} → ${chalk.green('1.2.4-beta.0')} → ${chalk.green('1.2.4-beta.1')}`,
+  })
+
+  tag?: string = Option.String('--tag', {
+    description: `The npm dist-tag the packages are published with, default to ${chalk.green(
+      '--preid',
+    )}. Pass ${chalk.green('latest')} to go back to stable releases`,
+  })
+
   async execute() {
-    await VersionCommand.updatePackageJson(this.prefix, this.configFileName)
+    const { packageJsonPath, packageName, content } = getNapiConfig(
+      this.configFileName,
+    )
+    const version = this.preid
+      ? bumpPrerelease(content.version, this.preid)
+      : undefined
+    const tag = this.tag ?? this.preid
+    if (version || tag) {
+      debug(
+        `Update [${chalk.yellowBright(packageJsonPath)}] to ${chalk.greenBright(
+          version ?? content.version,
+        )}, dist-tag ${chalk.greenBright(tag ?? 'unchanged')}`,
+      )
+      const optionalDependencies = content.optionalDependencies
+      await updatePackageJson(packageJsonPath, {
+        ...(version ? { version } : {}),
+        ...(tag ? { publishConfig: { ...content.publishConfig, tag } } : {}),
+        // keep the platform packages of the root package on the same version
+        ...(version && optionalDependencies
+          ? {
+              optionalDependencies: Object.fromEntries(
+                Object.entries(optionalDependencies).map(([name, range]) => [
+                  name,
+                  name.startsWith(`${packageName}-`) ? version : range,
+                ]),
+              ),
+            }
+          : {}),
+      })
+    }
+    await VersionCommand.updatePackageJson(this.prefix, this.configFileName, {
+      version,
+      tag,
+    })
     await spawn('git add .')
   }
 }
+
+const PRERELEASE_VERSION = /^(\d+)\.(\d+)\.(\d+)(?:-([0-9A-Za-z.-]+))?$/
+
+// whether `version` has a pre-release component, like `1.2.3-beta.0` or `1.2.3-next.1+build.5`
+export function isPrerelease(version: string) {
+  return /^\d+\.\d+\.\d+-[0-9A-Za-z.-]+(?:\+[0-9A-Za-z.-]+)?$/.test(version)
+}
+
+// the same as `npm version prerelease --preid <preid>`
+export function bumpPrerelease(version: string, preid: string) {
+  const matched = PRERELEASE_VERSION.exec(version)
+  if (!matched) {
+    throw new NapiError(`Can not bump the version ${version}`, {
+      code: 'napi::version::invalid',
+      causes: ['It is not a semver version like 1.2.3 or 1.2.3-beta.0'],
+    })
+  }
+  const [, major, minor, patch, prerelease] = matched
+  if (!prerelease) {
+    return `${major}.${minor}.${Number(patch) + 1}-${preid}.0`
+  }
+  const counter = new RegExp(`^${preid.replace(/\./g, '\\.')}\\.(\\d+)$`).exec(
+    prerelease,
+  )
+  const next = counter ? Number(counter[1]) + 1 : 0
+  return `${major}.${minor}.${patch}-${preid}.${next}`
+}
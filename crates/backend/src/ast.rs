@@ -6,7 +6,7 @@ pub struct NapiFn {
   pub name: Ident,
   pub js_name: String,
   pub attrs: Vec<Attribute>,
-  pub args: Vec<NapiFnArgKind>,
+  pub args: Vec<NapiFnArg>,
   pub ret: Option<syn::Type>,
   pub is_ret_result: bool,
   pub is_async: bool,
@@ -20,6 +20,27 @@ pub struct NapiFn {
   pub ts_return_type: Option<String>,
   pub skip_typescript: bool,
   pub comments: Vec<String>,
+  /// Only export the function if the runtime supports at least this Node-API version
+  pub napi_version: Option<u32>,
+  /// Convert panics into JavaScript errors instead of aborting the process
+  pub catch_unwind: bool,
+  /// Called with the `Env` and exports object when the module is registered, instead of being exported
+  pub module_init: bool,
+  /// Type parameters other than callbacks, instantiated as `JsUnknown` and emitted as TypeScript generics
+  pub generics: Vec<Ident>,
+  /// Type parameters of the generic class the method belongs to, instantiated as `UnknownRef`
+  pub parent_generics: Vec<Ident>,
+  /// Returns the `HashMap` or `BTreeMap` as a JavaScript `Map` instead of a plain object
+  pub js_map: bool,
+  /// How `None` of the returned `Option<T>` is represented in JavaScript
+  pub none: Option<NoneValue>,
+  /// How the `i64`, `u64`, `isize` and `usize` arguments and return value are converted
+  pub large_int: Option<LargeIntMode>,
+  /// `#[napi(strict_numbers)]`, the 8 to 32-bit integer arguments reject `NaN`, `Infinity`,
+  /// fractions and numbers out of their range
+  pub strict_numbers: bool,
+  /// How `NaN` and the infinities of the `f32` and `f64` arguments and return value are converted
+  pub non_finite: Option<NonFiniteMode>,
 }
 
 #[derive(Debug, Clone)]
@@ -29,10 +50,60 @@ pub struct CallbackArg {
   pub ret: Option<syn::Type>,
 }
 
+#[derive(Debug, Clone)]
+pub struct NapiFnArg {
+  pub kind: NapiFnArgKind,
+  /// Overrides the generated TypeScript type of the argument
+  pub ts_arg_type: Option<String>,
+  /// Value used when the argument is omitted or `undefined`
+  pub default: Option<syn::Expr>,
+  /// Collects all the remaining JavaScript arguments into a `Vec`
+  pub variadic: bool,
+  /// Receives the `HashMap` or `BTreeMap` from a JavaScript `Map` instead of a plain object
+  pub js_map: bool,
+  /// How `None` of the `Option<T>` argument is represented in JavaScript
+  pub none: Option<NoneValue>,
+}
+
+/// JavaScript value of `None`, chosen with `#[napi(none = "null")]` or `#[napi(none = "undefined")]`.
+///
+/// Only this value is accepted as `None` when converted from JavaScript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoneValue {
+  Null,
+  Undefined,
+}
+
+/// Conversion of `i64`, `u64`, `isize` and `usize`, chosen with `#[napi(large_int = "checked")]`
+/// or for the whole crate with the `large-int-checked`, `large-int-saturate` or `large-int-bigint` feature of `napi-derive`.
+///
+/// Without it `i64` is converted from and into a lossy `number` and `u64` into a `bigint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LargeIntMode {
+  /// `number`, a `RangeError` is thrown beyond `Number.MAX_SAFE_INTEGER` or the range of the Rust type
+  Checked,
+  /// `number`, clamped into `Number.MAX_SAFE_INTEGER` and the range of the Rust type
+  Saturate,
+  /// `bigint`
+  BigInt,
+}
+
+/// Conversion of `NaN`, `Infinity` and `-Infinity`, chosen with `#[napi(non_finite = "null")]`
+/// or `#[napi(non_finite = "error")]`, they're passed through without it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonFiniteMode {
+  /// `null` like `JSON.stringify`, `null` is converted back into `NaN`
+  Null,
+  /// A `TypeError` is thrown
+  Error,
+}
+
 #[derive(Debug, Clone)]
 pub enum NapiFnArgKind {
   PatType(Box<syn::PatType>),
   Callback(Box<CallbackArg>),
+  /// `This<T>`, receives the JavaScript `this` of the call
+  This(Box<syn::PatType>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -60,7 +131,30 @@ pub struct NapiStruct {
   pub is_tuple: bool,
   pub kind: NapiStructKind,
   pub js_mod: Option<String>,
+  /// Module augmented by the TypeScript interface of a `#[napi(object)]`
+  pub ts_module: Option<String>,
   pub comments: Vec<String>,
+  pub extends: Option<NapiClassParent>,
+  pub skip_typescript: bool,
+  /// Converting a `#[napi(object)]` from JavaScript reports the errors of all the fields at once
+  pub aggregate_errors: bool,
+  /// How the `i64`, `u64`, `isize` and `usize` fields of a `#[napi(object)]` are converted
+  pub large_int: Option<LargeIntMode>,
+  /// The 8 to 32-bit integer fields of a `#[napi(object)]` reject `NaN`, `Infinity`,
+  /// fractions and numbers out of their range
+  pub strict_numbers: bool,
+  /// How `NaN` and the infinities of the `f32` and `f64` fields of a `#[napi(object)]` are converted
+  pub non_finite: Option<NonFiniteMode>,
+  /// Type parameters of a class, instantiated as `UnknownRef` and emitted as TypeScript generics
+  pub generics: Vec<Ident>,
+}
+
+#[derive(Debug, Clone)]
+pub enum NapiClassParent {
+  /// Another `#[napi]` class, embedded as the first field of the struct
+  Class(Ident),
+  /// A JavaScript constructor, e.g. `Error` or `events:EventEmitter`
+  Js(String),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -80,6 +174,12 @@ pub struct NapiStructField {
   pub comments: Vec<String>,
   pub skip_typescript: bool,
   pub ts_type: Option<String>,
+  /// Converts the `HashMap` or `BTreeMap` field from and into a JavaScript `Map`
+  pub js_map: bool,
+  /// Collects the properties without a field of their own, `[key: string]: T` in TypeScript
+  pub index_signature: bool,
+  /// How `None` of the `Option<T>` field is represented in JavaScript
+  pub none: Option<NoneValue>,
 }
 
 #[derive(Debug, Clone)]
@@ -87,9 +187,14 @@ pub struct NapiImpl {
   pub name: Ident,
   pub js_name: String,
   pub items: Vec<NapiFn>,
+  /// Associated constants, exposed as static readonly class properties
+  pub consts: Vec<NapiConst>,
   pub task_output_type: Option<Type>,
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
+  pub skip_typescript: bool,
+  /// Type parameters of the generic class, instantiated as `UnknownRef`
+  pub generics: Vec<Ident>,
 }
 
 #[derive(Debug, Clone)]
@@ -97,6 +202,10 @@ pub struct NapiEnum {
   pub name: Ident,
   pub js_name: String,
   pub variants: Vec<NapiEnumVariant>,
+  pub is_string_enum: bool,
+  pub is_tagged_union: bool,
+  /// `#[napi(error)]` enums are converted into `napi::Error` instead of JavaScript values
+  pub is_error: bool,
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
   pub skip_typescript: bool,
@@ -105,7 +214,21 @@ pub struct NapiEnum {
 #[derive(Debug, Clone)]
 pub struct NapiEnumVariant {
   pub name: Ident,
+  pub js_name: String,
   pub val: i32,
+  pub fields: Vec<NapiEnumVariantField>,
+  /// `code` of the JavaScript error created from the variant of an error enum
+  pub error_code: Option<String>,
+  /// Constructor of the JavaScript error, one of `napi::ErrorClass`
+  pub error_class: Option<Ident>,
+  pub comments: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NapiEnumVariantField {
+  pub name: Ident,
+  pub js_name: String,
+  pub ty: Type,
   pub comments: Vec<String>,
 }
 
@@ -125,3 +248,28 @@ pub struct NapiMod {
   pub name: Ident,
   pub js_name: String,
 }
+
+/// A trait annotated with `#[napi(interface)]`, implemented by JavaScript objects
+#[derive(Debug, Clone)]
+pub struct NapiTrait {
+  pub name: Ident,
+  pub js_name: String,
+  pub methods: Vec<NapiTraitMethod>,
+  pub js_mod: Option<String>,
+  /// Module augmented by the TypeScript interface
+  pub ts_module: Option<String>,
+  pub comments: Vec<String>,
+  pub skip_typescript: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NapiTraitMethod {
+  pub name: Ident,
+  pub js_name: String,
+  /// Signature of the method in the trait, reused by the implementation calling into JavaScript
+  pub sig: syn::Signature,
+  pub args: Vec<(Ident, syn::Type)>,
+  /// `T` of the returned `Result<T>`
+  pub ret: syn::Type,
+  pub comments: Vec<String>,
+}
@@ -1,11 +1,16 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use proc_macro2::{Ident, Span, TokenStream};
+use quote::ToTokens;
+use syn::fold::{self, Fold};
 
-use crate::BindgenResult;
+use crate::{BindgenResult, LargeIntMode, NonFiniteMode, NoneValue};
 
 mod r#const;
 mod r#enum;
 mod r#fn;
 mod r#struct;
+mod r#trait;
 
 pub trait TryToTokens {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()>;
@@ -28,6 +33,216 @@ fn get_register_ident(name: &str) -> Ident {
   Ident::new(&new_name, Span::call_site())
 }
 
+static REGISTER_INDEX: AtomicUsize = AtomicUsize::new(0);
+
+/// Function running `body` to register an export before the module is loaded.
+///
+/// It is a `ctor` natively. `ctor` does not support WebAssembly, there it is exported with a unique
+/// `__napi_register__` name instead, and the loader calls all of them before `napi_register_wasm_v1`.
+fn gen_register_fn(register_name: &Ident, body: TokenStream) -> TokenStream {
+  let wasm_export_name = format!(
+    "{}_{}",
+    register_name,
+    REGISTER_INDEX.fetch_add(1, Ordering::SeqCst)
+  );
+  quote! {
+    #[allow(non_snake_case)]
+    #[allow(clippy::all)]
+    #[cfg(all(not(test), not(feature = "noop"), not(target_family = "wasm")))]
+    #[napi::bindgen_prelude::ctor]
+    fn #register_name() {
+      #body
+    }
+
+    #[allow(non_snake_case)]
+    #[allow(clippy::all)]
+    #[cfg(all(not(test), not(feature = "noop"), target_family = "wasm"))]
+    #[export_name = #wasm_export_name]
+    extern "C" fn #register_name() {
+      #body
+    }
+  }
+}
+
+fn is_optional_ty(ty: &syn::Type) -> bool {
+  if let syn::Type::Path(syn::TypePath {
+    path: syn::Path { segments, .. },
+    ..
+  }) = ty
+  {
+    segments
+      .last()
+      .map(|last_path| last_path.ident == "Option")
+      .unwrap_or(false)
+  } else {
+    false
+  }
+}
+
+/// `Option<T>` => `T`
+fn option_inner_ty(ty: &syn::Type) -> Option<&syn::Type> {
+  match ty {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => match path.segments.last() {
+      Some(syn::PathSegment {
+        ident,
+        arguments: syn::PathArguments::AngleBracketed(arguments),
+      }) if ident == "Option" => arguments.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(inner_ty) => Some(inner_ty),
+        _ => None,
+      }),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/// Type converting `Option<T>` with the `None` represented by `none`
+fn none_wrapper(none: NoneValue, ty: &syn::Type) -> Option<(TokenStream, TokenStream)> {
+  let inner_ty = option_inner_ty(ty)?;
+  let wrapper = match none {
+    NoneValue::Null => quote! { napi::bindgen_prelude::Nullable },
+    NoneValue::Undefined => quote! { napi::bindgen_prelude::Undefinable },
+  };
+  Some((quote! { #wrapper<#inner_ty> }, wrapper))
+}
+
+/// Number, alone or in an `Option<T>`, converted through the type of `#[napi(large_int)]`, `#[napi(strict_numbers)]`
+/// or `#[napi(non_finite)]`
+pub(crate) struct NumberWrapper {
+  /// `CheckedInt<T>` or `Option<CheckedInt<T>>`
+  pub(crate) ty: syn::Type,
+  wrapper: TokenStream,
+  is_option: bool,
+}
+
+impl NumberWrapper {
+  /// The Rust `value` into the wrapped type
+  fn wrap(&self, value: TokenStream) -> TokenStream {
+    let wrapper = &self.wrapper;
+    if self.is_option {
+      quote! { #value.map(#wrapper) }
+    } else {
+      quote! { #wrapper(#value) }
+    }
+  }
+
+  /// Appended to a value of the wrapped type to get the Rust value back
+  fn unwrap(&self) -> TokenStream {
+    if self.is_option {
+      quote! { .map(|value| value.0) }
+    } else {
+      quote! { .0 }
+    }
+  }
+}
+
+/// `i64`, `u64`, `isize` and `usize` with `#[napi(large_int)]` => `CheckedInt<T>`, `SaturatingInt<T>` or `AsBigInt<T>`,
+/// the 8 to 32-bit integers with `#[napi(strict_numbers)]` => `CheckedU32` and friends,
+/// `f32` and `f64` with `#[napi(non_finite)]` => `NonFiniteAsNull<T>` or `Finite<T>`
+pub(crate) fn number_wrapper(
+  large_int: Option<LargeIntMode>,
+  strict_numbers: bool,
+  non_finite: Option<NonFiniteMode>,
+  ty: &syn::Type,
+) -> Option<NumberWrapper> {
+  let (number_ty, is_option) = match option_inner_ty(ty) {
+    Some(inner_ty) => (inner_ty, true),
+    None => (ty, false),
+  };
+  let type_name = number_ty.to_token_stream().to_string();
+  let (wrapper, wrapped_ty) = match type_name.as_str() {
+    "i64" | "u64" | "isize" | "usize" => {
+      let wrapper = match large_int? {
+        LargeIntMode::Checked => quote! { napi::bindgen_prelude::CheckedInt },
+        LargeIntMode::Saturate => quote! { napi::bindgen_prelude::SaturatingInt },
+        LargeIntMode::BigInt => quote! { napi::bindgen_prelude::AsBigInt },
+      };
+      let wrapped_ty = quote! { #wrapper<#number_ty> };
+      (wrapper, wrapped_ty)
+    }
+    "i8" | "u8" | "i16" | "u16" | "i32" | "u32" if strict_numbers => {
+      let checked = Ident::new(
+        &format!("Checked{}", type_name.to_uppercase()),
+        Span::call_site(),
+      );
+      let wrapper = quote! { napi::bindgen_prelude::#checked };
+      (wrapper.clone(), wrapper)
+    }
+    "f32" | "f64" => {
+      let wrapper = match non_finite? {
+        NonFiniteMode::Null => quote! { napi::bindgen_prelude::NonFiniteAsNull },
+        NonFiniteMode::Error => quote! { napi::bindgen_prelude::Finite },
+      };
+      let wrapped_ty = quote! { #wrapper<#number_ty> };
+      (wrapper, wrapped_ty)
+    }
+    _ => return None,
+  };
+  let ty = if is_option {
+    syn::parse_quote! { Option<#wrapped_ty> }
+  } else {
+    syn::parse_quote! { #wrapped_ty }
+  };
+  Some(NumberWrapper {
+    ty,
+    wrapper,
+    is_option,
+  })
+}
+
+/// `AsBigInt<T>` exists with the `napi6` feature of `napi` only, the item reports it
+/// rather than an unresolved path once one of `tys` is converted through it
+pub(crate) fn large_int_requirement<'a>(
+  large_int: Option<LargeIntMode>,
+  mut tys: impl Iterator<Item = &'a syn::Type>,
+) -> TokenStream {
+  if large_int == Some(LargeIntMode::BigInt)
+    && tys.any(|ty| number_wrapper(large_int, false, None, ty).is_some())
+  {
+    quote! { napi::__large_int_bigint!(); }
+  } else {
+    quote! {}
+  }
+}
+
+/// Replaces the type parameters with a concrete type, `JsUnknown` for functions and `UnknownRef` for classes
+struct InstantiateGenerics<'a>(&'a [Ident], syn::Type);
+
+impl Fold for InstantiateGenerics<'_> {
+  fn fold_type(&mut self, ty: syn::Type) -> syn::Type {
+    match ty {
+      syn::Type::Path(syn::TypePath { qself: None, path })
+        if path.segments.len() == 1
+          && path.segments[0].arguments.is_empty()
+          && self.0.contains(&path.segments[0].ident) =>
+      {
+        self.1.clone()
+      }
+      ty => fold::fold_type(self, ty),
+    }
+  }
+}
+
+fn instantiate_class_generics(generics: &[Ident], ty: syn::Type) -> syn::Type {
+  InstantiateGenerics(
+    generics,
+    syn::parse_quote! { napi::bindgen_prelude::UnknownRef },
+  )
+  .fold_type(ty)
+}
+
+/// The type of the class `name`, its type parameters are instantiated with `UnknownRef`
+fn class_ty(name: &Ident, generics: &[Ident]) -> TokenStream {
+  if generics.is_empty() {
+    quote! { #name }
+  } else {
+    let args = generics
+      .iter()
+      .map(|_| quote! { napi::bindgen_prelude::UnknownRef });
+    quote! { #name::<#(#args),*> }
+  }
+}
+
 fn js_mod_to_token_stream(js_mod: Option<&String>) -> TokenStream {
   js_mod
     .map(|i| {
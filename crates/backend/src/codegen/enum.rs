@@ -2,14 +2,28 @@ use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 
 use crate::{
-  codegen::{get_register_ident, js_mod_to_token_stream},
+  codegen::{gen_register_fn, get_register_ident, is_optional_ty, js_mod_to_token_stream},
   BindgenResult, NapiEnum, TryToTokens,
 };
 
 impl TryToTokens for NapiEnum {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
-    let register = self.gen_module_register();
-    let napi_value_conversion = self.gen_napi_value_map_impl();
+    if self.is_error {
+      self.gen_error_impl().to_tokens(tokens);
+      return Ok(());
+    }
+
+    // string enums and tagged unions only exist as a type on the JavaScript side
+    let register = if self.is_string_enum || self.is_tagged_union {
+      quote! {}
+    } else {
+      self.gen_module_register()
+    };
+    let napi_value_conversion = if self.is_tagged_union {
+      self.gen_tagged_union_impl()?
+    } else {
+      self.gen_napi_value_map_impl()
+    };
 
     (quote! {
       #napi_value_conversion
@@ -22,6 +36,52 @@ impl TryToTokens for NapiEnum {
 }
 
 impl NapiEnum {
+  /// The message of the error comes from the `Display` implementation of the enum,
+  /// and the fields of the variant become properties of the JavaScript error
+  fn gen_error_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let branches = self.variants.iter().map(|v| {
+      let v_name = &v.name;
+      let code = v.error_code.as_deref().unwrap_or_default();
+      let class = v.error_class.as_ref().map(|class| {
+        // `SyntaxError` needs Node-API 9, the macro fails to compile without the `napi9` feature
+        if class == "SyntaxError" {
+          quote! { .with_class(napi::__syntax_error_class!()) }
+        } else {
+          quote! { .with_class(napi::bindgen_prelude::ErrorClass::#class) }
+        }
+      });
+      let field_names = v.fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+      let properties = v.fields.iter().map(|f| {
+        let field_name = &f.name;
+        let js_name = &f.js_name;
+        quote! { .with_property(#js_name, #field_name) }
+      });
+      let pattern = if v.fields.is_empty() {
+        quote! { #name::#v_name }
+      } else {
+        quote! { #name::#v_name { #(#field_names),* } }
+      };
+      quote! {
+        #pattern => napi::bindgen_prelude::Error::new(napi::bindgen_prelude::Status::GenericFailure, reason)
+          .with_code(#code)
+          #class
+          #(#properties)*
+      }
+    });
+
+    quote! {
+      impl From<#name> for napi::bindgen_prelude::Error {
+        fn from(err: #name) -> Self {
+          let reason = err.to_string();
+          match err {
+            #(#branches),*
+          }
+        }
+      }
+    }
+  }
+
   fn gen_napi_value_map_impl(&self) -> TokenStream {
     let name = &self.name;
     let name_str = self.name.to_string();
@@ -29,13 +89,32 @@ impl NapiEnum {
     let mut to_napi_branches = vec![];
 
     self.variants.iter().for_each(|v| {
-      let val = Literal::i32_unsuffixed(v.val);
+      let val = if self.is_string_enum {
+        Literal::string(&v.js_name)
+      } else {
+        Literal::i32_unsuffixed(v.val)
+      };
       let v_name = &v.name;
 
       from_napi_branches.push(quote! { #val => Ok(#name::#v_name) });
       to_napi_branches.push(quote! { #name::#v_name => #val });
     });
 
+    let (value_ty, value_type, match_val) = if self.is_string_enum {
+      (
+        quote! { String },
+        quote! { String },
+        quote! { val.as_str() },
+      )
+    } else {
+      (quote! { i32 }, quote! { Number }, quote! { val })
+    };
+    let to_napi_ty = if self.is_string_enum {
+      quote! { &str }
+    } else {
+      quote! { i32 }
+    };
+
     quote! {
       impl napi::bindgen_prelude::TypeName for #name {
         fn type_name() -> &'static str {
@@ -52,7 +131,7 @@ impl NapiEnum {
           env: napi::bindgen_prelude::sys::napi_env,
           napi_val: napi::bindgen_prelude::sys::napi_value
         ) -> napi::bindgen_prelude::Result<()> {
-          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::Number)
+          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::#value_type)
         }
       }
 
@@ -61,7 +140,7 @@ impl NapiEnum {
           env: napi::bindgen_prelude::sys::napi_env,
           napi_val: napi::bindgen_prelude::sys::napi_value
         ) -> napi::bindgen_prelude::Result<Self> {
-          let val = i32::from_napi_value(env, napi_val).map_err(|e| {
+          let val = <#value_ty>::from_napi_value(env, napi_val).map_err(|e| {
             napi::bindgen_prelude::error!(
               e.status,
               "Failed to convert napi value into enum `{}`. {}",
@@ -70,7 +149,7 @@ impl NapiEnum {
             )
           })?;
 
-          match val {
+          match #match_val {
             #(#from_napi_branches,)*
             _ => {
               Err(napi::bindgen_prelude::error!(
@@ -93,12 +172,140 @@ impl NapiEnum {
             #(#to_napi_branches,)*
           };
 
-          i32::to_napi_value(env, val)
+          <#to_napi_ty>::to_napi_value(env, val)
         }
       }
     }
   }
 
+  fn gen_tagged_union_impl(&self) -> BindgenResult<TokenStream> {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let mut from_napi_branches = vec![];
+    let mut to_napi_branches = vec![];
+
+    for v in self.variants.iter() {
+      let v_name = &v.name;
+      let tag = &v.js_name;
+      let mut field_idents = vec![];
+      let mut obj_field_setters = vec![];
+      let mut obj_field_getters = vec![];
+
+      for field in v.fields.iter() {
+        let ident = &field.name;
+        let field_js_name = &field.js_name;
+        if field_js_name == "type" {
+          bail_span!(
+            ident,
+            "the `type` field of the `{}` variant would overwrite the tag of the tagged union, rename it with #[napi(js_name)]",
+            v_name
+          );
+        }
+        let key = quote! { &napi::bindgen_prelude::PropertyKey::new(#field_js_name) };
+        let ty = &field.ty;
+        field_idents.push(ident);
+
+        if is_optional_ty(ty) {
+          obj_field_setters.push(quote! {
+            if #ident.is_some() {
+              obj.set_by_key(#key, #ident)?;
+            }
+          });
+          obj_field_getters.push(quote! { let #ident: #ty = obj.get_by_key(#key)?; });
+        } else {
+          obj_field_setters.push(quote! { obj.set_by_key(#key, #ident)?; });
+          obj_field_getters.push(quote! {
+            let #ident: #ty = obj.get_by_key(#key)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+              napi::bindgen_prelude::Status::InvalidArg,
+              format!("Missing field `{}` of `{}` variant", #field_js_name, #tag),
+            ))?;
+          });
+        }
+      }
+
+      let variant = if v.fields.is_empty() {
+        quote! { #name::#v_name }
+      } else {
+        quote! { #name::#v_name { #(#field_idents),* } }
+      };
+
+      from_napi_branches.push(quote! {
+        #tag => {
+          #(#obj_field_getters)*
+          Ok(#variant)
+        }
+      });
+      to_napi_branches.push(quote! {
+        #variant => {
+          obj.set_by_key(&napi::bindgen_prelude::PropertyKey::new("type"), #tag)?;
+          #(#obj_field_setters)*
+        }
+      });
+    }
+
+    Ok(quote! {
+      impl napi::bindgen_prelude::TypeName for #name {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for #name {
+        unsafe fn validate(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<()> {
+          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::Object)
+        }
+      }
+
+      impl napi::bindgen_prelude::FromNapiValue for #name {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<Self> {
+          let obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
+          let tag: String = obj.get_by_key(&napi::bindgen_prelude::PropertyKey::new("type"))?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+            napi::bindgen_prelude::Status::InvalidArg,
+            format!("Missing field `type` of enum `{}`", #name_str),
+          ))?;
+
+          match tag.as_str() {
+            #(#from_napi_branches,)*
+            _ => {
+              Err(napi::bindgen_prelude::error!(
+                napi::bindgen_prelude::Status::InvalidArg,
+                "value `{}` does not match any variant of enum `{}`",
+                tag,
+                #name_str
+              ))
+            }
+          }
+        }
+      }
+
+      impl napi::bindgen_prelude::ToNapiValue for #name {
+        unsafe fn to_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          val: Self
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          let env_wrapper = napi::bindgen_prelude::Env::from(env);
+          let mut obj = env_wrapper.create_object()?;
+
+          match val {
+            #(#to_napi_branches,)*
+          };
+
+          <napi::bindgen_prelude::Object as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, obj)
+        }
+      }
+    })
+  }
+
   fn gen_module_register(&self) -> TokenStream {
     let name_str = self.name.to_string();
     let js_name_lit = Literal::string(&format!("{}\0", &self.js_name));
@@ -114,7 +321,7 @@ impl NapiEnum {
         {
           let name = std::ffi::CStr::from_bytes_with_nul_unchecked(#name_lit.as_bytes());
           napi::bindgen_prelude::check_status!(
-            napi::bindgen_prelude::sys::napi_set_named_property(env, obj_ptr, name.as_ptr(), i32::to_napi_value(env, #val_lit)?),
+            napi::bindgen_prelude::sys::napi_set_named_property(env, obj_ptr, name.as_ptr(), <i32 as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #val_lit)?),
             "Failed to defined enum `{}`",
             #js_name_lit
           )?;
@@ -129,6 +336,13 @@ impl NapiEnum {
 
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
 
+    let register_fn = gen_register_fn(
+      &register_name,
+      quote! {
+        napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name_lit, #callback_name);
+      },
+    );
+
     quote! {
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
@@ -147,13 +361,7 @@ impl NapiEnum {
 
         Ok(obj_ptr)
       }
-      #[allow(non_snake_case)]
-      #[allow(clippy::all)]
-      #[cfg(all(not(test), not(feature = "noop")))]
-      #[napi::bindgen_prelude::ctor]
-      fn #register_name() {
-        napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name_lit, #callback_name);
-      }
+      #register_fn
     }
   }
 }
@@ -1,13 +1,48 @@
+use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
+use syn::fold::Fold;
 
 use crate::{
-  codegen::{get_intermediate_ident, get_register_ident, js_mod_to_token_stream},
-  BindgenResult, CallbackArg, FnKind, FnSelf, NapiFn, NapiFnArgKind, TryToTokens,
+  codegen::{
+    class_ty, gen_register_fn, get_intermediate_ident, get_register_ident,
+    instantiate_class_generics, is_optional_ty, js_mod_to_token_stream, large_int_requirement,
+    none_wrapper, number_wrapper, InstantiateGenerics,
+  },
+  BindgenResult, CallbackArg, FnKind, FnSelf, NapiFn, NapiFnArg, NapiFnArgKind, NoneValue,
+  TryToTokens,
 };
 
 impl TryToTokens for NapiFn {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    if self.generics.is_empty() && self.parent_generics.is_empty() {
+      self.gen_fn(tokens)
+    } else {
+      self.instantiate_generics().gen_fn(tokens)
+    }
+  }
+}
+
+/// Replaces the named lifetimes, which aren't in scope in the generated callback, with `'_`
+struct ElideLifetimes;
+
+impl Fold for ElideLifetimes {
+  fn fold_lifetime(&mut self, lifetime: syn::Lifetime) -> syn::Lifetime {
+    if lifetime.ident == "static" {
+      lifetime
+    } else {
+      syn::Lifetime::new("'_", lifetime.apostrophe)
+    }
+  }
+}
+
+impl NapiFn {
+  fn gen_fn(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    if self.module_init {
+      self.gen_module_init().to_tokens(tokens);
+      return Ok(());
+    }
+
     let name_str = self.name.to_string();
     let intermediate_ident = get_intermediate_ident(&name_str);
     let args_len = self.args.len();
@@ -17,18 +52,33 @@ impl TryToTokens for NapiFn {
     let receiver_ret_name = Ident::new("_ret", Span::call_site());
     let ret = self.gen_fn_return(&receiver_ret_name);
     let register = self.gen_fn_register();
+    let arg_tys = self.args.iter().filter_map(|arg| match &arg.kind {
+      NapiFnArgKind::PatType(path) => Some(&*path.ty),
+      _ => None,
+    });
+    let large_int_requirement = large_int_requirement(self.large_int, arg_tys.chain(&self.ret));
     let attrs = &self.attrs;
+    let span_name = match &self.parent {
+      Some(parent) => format!("{}.{}", parent, self.js_name),
+      None => self.js_name.clone(),
+    };
 
+    // the error type of the returned `Result` only needs to be convertible into `napi::Error`
+    let map_err = if self.is_ret_result {
+      quote! { .map_err(napi::bindgen_prelude::Error::from) }
+    } else {
+      quote! {}
+    };
     let native_call = if !self.is_async {
       quote! {
         let #receiver_ret_name = {
-          #receiver(#(#arg_names),*)
+          #receiver(#(#arg_names),*) #map_err
         };
         #ret
       }
     } else {
       let call = if self.is_ret_result {
-        quote! { #receiver(#(#arg_names),*).await }
+        quote! { #receiver(#(#arg_names),*).await #map_err }
       } else {
         quote! { Ok(#receiver(#(#arg_names),*).await) }
       };
@@ -39,31 +89,42 @@ impl TryToTokens for NapiFn {
       }
     };
 
+    let catch_unwind = |call: TokenStream| {
+      if self.catch_unwind {
+        quote! { napi::bindgen_prelude::catch_unwind(move || { #call }) }
+      } else {
+        call
+      }
+    };
+
     let function_call = if args_len == 0
       && self.fn_self.is_none()
       && self.kind != FnKind::Constructor
       && self.kind != FnKind::Factory
     {
-      quote! { #native_call }
+      catch_unwind(quote! { #native_call })
     } else if self.kind == FnKind::Constructor {
+      let call = catch_unwind(quote! {
+        napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
+          #(#arg_conversions)*
+          #native_call
+        })
+      });
       quote! {
         // constructor function is called from class `factory`
         // so we should skip the original `constructor` logic
-        if napi::bindgen_prelude::___CALL_FROM_FACTORY.load(std::sync::atomic::Ordering::Relaxed) {
+        if napi::bindgen_prelude::___CALL_FROM_FACTORY.with(|f| f.get()) {
           return std::ptr::null_mut();
         }
-        napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
-          #(#arg_conversions)*
-          #native_call
-        })
+        #call
       }
     } else {
-      quote! {
+      catch_unwind(quote! {
         napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
           #(#arg_conversions)*
           #native_call
         })
-      }
+      })
     };
 
     (quote! {
@@ -75,6 +136,7 @@ impl TryToTokens for NapiFn {
         env: napi::bindgen_prelude::sys::napi_env,
         cb: napi::bindgen_prelude::sys::napi_callback_info
       ) -> napi::bindgen_prelude::sys::napi_value {
+        let _span = napi::bindgen_prelude::CallSpan::enter(#span_name);
         unsafe {
           #function_call.unwrap_or_else(|e| {
             napi::bindgen_prelude::JsError::from(e).throw_into(env);
@@ -83,21 +145,44 @@ impl TryToTokens for NapiFn {
         }
       }
 
+      #large_int_requirement
       #register
     })
     .to_tokens(tokens);
 
     Ok(())
   }
-}
 
-impl NapiFn {
+  fn instantiate_generics(&self) -> NapiFn {
+    let mut folder = InstantiateGenerics(&self.generics, syn::parse_quote! { napi::JsUnknown });
+    let parent_generics = self.parent_generics.clone();
+    let mut instantiate =
+      |ty: syn::Type| instantiate_class_generics(&parent_generics, folder.fold_type(ty));
+    let mut func = self.clone();
+    // `parent_generics` are kept for the type of the class
+    func.generics = vec![];
+    func.ret = func.ret.map(&mut instantiate);
+    for arg in func.args.iter_mut() {
+      match &mut arg.kind {
+        NapiFnArgKind::PatType(path) | NapiFnArgKind::This(path) => {
+          *path.ty = instantiate(*path.ty.clone());
+        }
+        NapiFnArgKind::Callback(cb) => {
+          cb.args = cb.args.drain(..).map(&mut instantiate).collect();
+          cb.ret = cb.ret.take().map(&mut instantiate);
+        }
+      }
+    }
+    func
+  }
+
   fn gen_arg_conversions(&self) -> (Vec<TokenStream>, Vec<TokenStream>) {
     let mut arg_conversions = vec![];
     let mut args = vec![];
 
     // fetch this
     if let Some(parent) = &self.parent {
+      let parent = class_ty(parent, &self.parent_generics);
       match self.fn_self {
         Some(FnSelf::Ref) => {
           arg_conversions.push(quote! { let this = cb.unwrap_borrow::<#parent>()?; });
@@ -109,18 +194,36 @@ impl NapiFn {
       };
     }
 
+    if self.strict {
+      let required_argc = self.required_js_argc();
+      if required_argc > 0 {
+        let js_name = &self.js_name;
+        arg_conversions.push(quote! { cb.check_argc(#required_argc, #js_name)?; });
+      }
+    }
+
     let mut skipped_arg_count = 0;
     self.args.iter().enumerate().for_each(|(i, arg)| {
       let i = i - skipped_arg_count;
       let ident = Ident::new(&format!("arg{}", i), Span::call_site());
 
-      match arg {
+      match &arg.kind {
         NapiFnArgKind::PatType(path) => {
           if &path.ty.to_token_stream().to_string() == "Env" {
             args.push(quote! { napi::bindgen_prelude::Env::from(env) });
             skipped_arg_count += 1;
+          } else if arg.variadic {
+            let ty = &path.ty;
+            arg_conversions.push(quote! {
+              let #ident = cb
+                .get_rest_args(#i)?
+                .into_iter()
+                .map(|arg| napi::bindgen_prelude::FromNapiValue::from_napi_value(env, arg))
+                .collect::<napi::bindgen_prelude::Result<#ty>>()?;
+            });
+            args.push(quote! { #ident });
           } else {
-            arg_conversions.push(self.gen_ty_arg_conversion(&ident, i, path));
+            arg_conversions.push(self.gen_ty_arg_conversion(&ident, i, path, arg));
             args.push(quote! { #ident });
           }
         }
@@ -128,6 +231,11 @@ impl NapiFn {
           arg_conversions.push(self.gen_cb_arg_conversion(&ident, i, cb));
           args.push(quote! { #ident });
         }
+        NapiFnArgKind::This(path) => {
+          arg_conversions.push(self.gen_this_arg_conversion(path));
+          args.push(quote! { napi_this });
+          skipped_arg_count += 1;
+        }
       }
     });
 
@@ -139,6 +247,7 @@ impl NapiFn {
     arg_name: &Ident,
     index: usize,
     path: &syn::PatType,
+    arg: &NapiFnArg,
   ) -> TokenStream {
     let ty = &*path.ty;
     match ty {
@@ -157,21 +266,118 @@ impl NapiFn {
         }
       }
       _ => {
+        // `Map` arguments are received through `EsMap` and unwrapped into the `HashMap` or `BTreeMap`,
+        // `Option<T>` arguments with `#[napi(none)]` through `Nullable<T>` or `Undefinable<T>`,
+        // numbers with `#[napi(large_int)]`, `#[napi(strict_numbers)]` or `#[napi(non_finite)]` through `CheckedInt<T>` and friends
+        let number_wrapper =
+          number_wrapper(self.large_int, self.strict_numbers, self.non_finite, ty);
+        let (ty, unwrap_number) = match &number_wrapper {
+          Some(wrapper) => (&wrapper.ty, wrapper.unwrap()),
+          None => (ty, quote! {}),
+        };
+        let none_wrapper = arg.none.and_then(|none| none_wrapper(none, ty));
+        let (ty, unwrap_value) = if arg.js_map {
+          (quote! { napi::bindgen_prelude::EsMap<#ty> }, quote! { .0 })
+        } else if let Some((wrapped_ty, _)) = none_wrapper {
+          (wrapped_ty, quote! { .0 #unwrap_number })
+        } else {
+          (quote! { #ty }, unwrap_number)
+        };
+        // the property path of a conversion error starts at the argument, `options.retry.maxAttempts: ...`
+        let at_arg = match &*path.pat {
+          syn::Pat::Ident(pat) => {
+            let js_arg_name = pat.ident.to_string().to_case(Case::Camel);
+            quote! { .map_err(|e| e.at_argument(#js_arg_name)) }
+          }
+          _ => quote! {},
+        };
         let type_check = if self.strict {
+          let js_name = &self.js_name;
           quote! {
-            <#ty as napi::bindgen_prelude::ValidateNapiValue>::validate(env, cb.get_arg(#index))?;
+            napi::bindgen_prelude::validate_strict_arg::<#ty>(env, cb.get_arg(#index), #index, #js_name)?;
           }
         } else {
           quote! {}
         };
 
+        if let Some(default) = &arg.default {
+          quote! {
+            let #arg_name = if napi::bindgen_prelude::type_of!(env, cb.get_arg(#index))? == napi::bindgen_prelude::ValueType::Undefined {
+              #default
+            } else {
+              #type_check
+              <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.get_arg(#index)) #at_arg? #unwrap_value
+            };
+          }
+        } else {
+          quote! {
+            let #arg_name = {
+              #type_check
+              <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.get_arg(#index)) #at_arg? #unwrap_value
+            };
+          }
+        }
+      }
+    }
+  }
+
+  /// Count of JavaScript arguments up to the last one which can't be omitted
+  fn required_js_argc(&self) -> usize {
+    let mut js_argc = 0;
+    let mut required_argc = 0;
+    for arg in self.args.iter() {
+      let is_optional = match &arg.kind {
+        NapiFnArgKind::PatType(path) => {
+          if path.ty.to_token_stream().to_string() == "Env" {
+            continue;
+          }
+          arg.default.is_some()
+            || arg.variadic
+            || (is_optional_ty(&path.ty) && arg.none != Some(NoneValue::Null))
+        }
+        NapiFnArgKind::Callback(_) => false,
+        NapiFnArgKind::This(_) => continue,
+      };
+      js_argc += 1;
+      if !is_optional {
+        required_argc = js_argc;
+      }
+    }
+    required_argc
+  }
+
+  fn gen_this_arg_conversion(&self, path: &syn::PatType) -> TokenStream {
+    let ty = &*path.ty;
+    let elem = match ty {
+      syn::Type::Path(syn::TypePath { path, .. }) => match &path.segments.last().unwrap().arguments
+      {
+        syn::PathArguments::AngleBracketed(args) => args.args.first(),
+        _ => None,
+      },
+      _ => None,
+    };
+    match elem {
+      Some(syn::GenericArgument::Type(syn::Type::Reference(syn::TypeReference {
+        mutability: Some(_),
+        elem,
+        ..
+      }))) => quote! {
+        let napi_this = napi::bindgen_prelude::This::from(
+          <#elem as napi::bindgen_prelude::FromNapiMutRef>::from_napi_mut_ref(env, cb.this())?
+        );
+      },
+      Some(syn::GenericArgument::Type(syn::Type::Reference(syn::TypeReference {
+        elem, ..
+      }))) => {
         quote! {
-          let #arg_name = {
-            #type_check
-            <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.get_arg(#index))?
-          };
+          let napi_this = napi::bindgen_prelude::This::from(
+            <#elem as napi::bindgen_prelude::FromNapiRef>::from_napi_ref(env, cb.this())?
+          );
         }
       }
+      _ => quote! {
+        let napi_this = <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.this())?;
+      },
     }
   }
 
@@ -234,41 +440,80 @@ impl NapiFn {
       }
       Some(FnSelf::Ref) | Some(FnSelf::MutRef) => quote! { this.#name },
       None => match &self.parent {
-        Some(class) => quote! { #class::#name },
+        Some(class) => {
+          let class = class_ty(class, &self.parent_generics);
+          quote! { #class::#name }
+        }
         None => quote! { #name },
       },
     }
   }
 
   fn gen_fn_return(&self, ret: &Ident) -> TokenStream {
-    let js_name = &self.js_name;
+    let class_name = self
+      .parent
+      .as_ref()
+      .map(|parent| parent.to_string())
+      .unwrap_or_default();
 
     if let Some(ty) = &self.ret {
+      // borrowed return values like `&str` or `Cow<'a, str>` are copied into JavaScript values
+      let ty = &ElideLifetimes.fold_type(ty.clone());
       let ty_string = ty.into_token_stream().to_string();
       let is_return_self = ty_string == "& Self" || ty_string == "&mut Self";
+      // `HashMap` and `BTreeMap` returned with `#[napi(js_map)]` are wrapped into `EsMap`,
+      // `Option<T>` returned with `#[napi(none)]` into `Nullable<T>` or `Undefinable<T>`,
+      // numbers returned with `#[napi(large_int)]`, `#[napi(strict_numbers)]` or `#[napi(non_finite)]` into `CheckedInt<T>` and friends
+      let number_wrapper = number_wrapper(self.large_int, self.strict_numbers, self.non_finite, ty);
+      let (ty, number_ret, number_ok) = match &number_wrapper {
+        Some(wrapper) => (
+          &wrapper.ty,
+          wrapper.wrap(quote! { #ret }),
+          wrapper.wrap(quote! { value }),
+        ),
+        None => (ty, quote! { #ret }, quote! { value }),
+      };
+      let none_wrapper = self.none.and_then(|none| none_wrapper(none, ty));
+      let (ty, ret_value, ok_value) = if self.js_map {
+        (
+          quote! { napi::bindgen_prelude::EsMap<#ty> },
+          quote! { napi::bindgen_prelude::EsMap(#ret) },
+          quote! { napi::bindgen_prelude::EsMap(value) },
+        )
+      } else if let Some((wrapped_ty, wrapper)) = none_wrapper {
+        (
+          wrapped_ty,
+          quote! { #wrapper(#number_ret) },
+          quote! { #wrapper(#number_ok) },
+        )
+      } else {
+        (quote! { #ty }, number_ret, number_ok)
+      };
       if self.kind == FnKind::Constructor {
         if self.is_ret_result {
-          quote! { cb.construct(#js_name, #ret?) }
+          quote! { cb.construct(#class_name, #ret?) }
         } else {
-          quote! { cb.construct(#js_name, #ret) }
+          quote! { cb.construct(#class_name, #ret) }
         }
       } else if self.kind == FnKind::Factory {
-        if self.is_ret_result {
-          quote! { cb.factory(#js_name, #ret?) }
+        if self.is_async {
+          quote! { napi::bindgen_prelude::new_class_instance(env, #class_name, #ret) }
+        } else if self.is_ret_result {
+          quote! { cb.factory(#class_name, #ret?) }
         } else {
-          quote! { cb.factory(#js_name, #ret) }
+          quote! { cb.factory(#class_name, #ret) }
         }
       } else if self.is_ret_result {
         if self.is_async {
           quote! {
-            <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret)
+            <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret_value)
           }
         } else if is_return_self {
           quote! { #ret.map(|_| cb.this) }
         } else {
           quote! {
             match #ret {
-              Ok(value) => napi::bindgen_prelude::ToNapiValue::to_napi_value(env, value),
+              Ok(value) => napi::bindgen_prelude::ToNapiValue::to_napi_value(env, #ok_value),
               Err(err) => {
                 napi::bindgen_prelude::JsError::from(err).throw_into(env);
                 Ok(std::ptr::null_mut())
@@ -280,7 +525,7 @@ impl NapiFn {
         quote! { Ok(cb.this) }
       } else {
         quote! {
-          <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret)
+          <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret_value)
         }
       }
     } else {
@@ -290,6 +535,54 @@ impl NapiFn {
     }
   }
 
+  fn gen_module_init(&self) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let register_name = get_register_ident(&name_str);
+    let cb_name = Ident::new(&format!("{}_module_init", name_str), Span::call_site());
+    // callback and `This` args are rejected by the parser
+    let args = self.args.iter().filter_map(|arg| match &arg.kind {
+      NapiFnArgKind::PatType(path) if path.ty.to_token_stream().to_string() == "Env" => {
+        Some(quote! { napi::bindgen_prelude::Env::from(env) })
+      }
+      NapiFnArgKind::PatType(path) => {
+        let ty = &path.ty;
+        Some(
+          quote! { <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, exports)? },
+        )
+      }
+      NapiFnArgKind::Callback(_) | NapiFnArgKind::This(_) => None,
+    });
+    let call = if self.is_ret_result {
+      quote! { #name(#(#args),*).map(|_| ()).map_err(napi::bindgen_prelude::Error::from) }
+    } else {
+      quote! {
+        #name(#(#args),*);
+        Ok(())
+      }
+    };
+
+    let register_fn = gen_register_fn(
+      &register_name,
+      quote! {
+        napi::bindgen_prelude::register_module_init(#cb_name);
+      },
+    );
+
+    quote! {
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      unsafe fn #cb_name(
+        env: napi::bindgen_prelude::sys::napi_env,
+        exports: napi::bindgen_prelude::sys::napi_value
+      ) -> napi::bindgen_prelude::Result<()> {
+        #call
+      }
+
+      #register_fn
+    }
+  }
+
   fn gen_fn_register(&self) -> TokenStream {
     if self.parent.is_some() {
       quote! {}
@@ -301,6 +594,15 @@ impl NapiFn {
       let intermediate_ident = get_intermediate_ident(&name_str);
       let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
       let cb_name = Ident::new(&format!("{}_js_function", name_str), Span::call_site());
+      let register_module_export = match self.napi_version {
+        Some(napi_version) => quote! {
+          napi::bindgen_prelude::register_module_export_with_napi_version(#js_mod_ident, #js_name, #cb_name, #napi_version);
+        },
+        None => quote! {
+          napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name, #cb_name);
+        },
+      };
+      let register_fn = gen_register_fn(&module_register_name, register_module_export);
       quote! {
         #[allow(non_snake_case)]
         #[allow(clippy::all)]
@@ -323,13 +625,7 @@ impl NapiFn {
           Ok(fn_ptr)
         }
 
-        #[allow(clippy::all)]
-        #[allow(non_snake_case)]
-        #[cfg(all(not(test), not(feature = "noop")))]
-        #[napi::bindgen_prelude::ctor]
-        fn #module_register_name() {
-          napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name, #cb_name);
-        }
+        #register_fn
       }
     }
   }
@@ -5,15 +5,25 @@ use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 
 use crate::{
-  codegen::{get_intermediate_ident, get_register_ident, js_mod_to_token_stream},
-  BindgenResult, FnKind, NapiImpl, NapiStruct, NapiStructKind, TryToTokens,
+  codegen::{
+    class_ty, gen_register_fn, get_intermediate_ident, get_register_ident,
+    instantiate_class_generics, js_mod_to_token_stream, large_int_requirement, none_wrapper,
+    number_wrapper,
+  },
+  BindgenResult, FnKind, NapiClassParent, NapiImpl, NapiStruct, NapiStructKind, NoneValue,
+  TryToTokens,
 };
 
 static NAPI_IMPL_ID: AtomicU32 = AtomicU32::new(0);
 
 // Generate trait implementations for given Struct.
-fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> TokenStream {
+fn gen_napi_value_map_impl(
+  name: &Ident,
+  generics: &[Ident],
+  to_napi_val_impl: TokenStream,
+) -> TokenStream {
   let name_str = name.to_string();
+  let name = class_ty(name, generics);
   quote! {
     impl napi::bindgen_prelude::TypeName for #name {
       fn type_name() -> &'static str {
@@ -59,6 +69,7 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
           "Failed to recover `{}` type from napi value",
           #name_str,
         )?;
+        napi::bindgen_prelude::check_class_instance::<#name>(env, napi_val, wrapped_val, #name_str)?;
 
         Ok(&*(wrapped_val as *const #name))
       }
@@ -76,6 +87,7 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
           "Failed to recover `{}` type from napi value",
           #name_str,
         )?;
+        napi::bindgen_prelude::check_class_instance::<#name>(env, napi_val, wrapped_val, #name_str)?;
 
         Ok(&mut *(wrapped_val as *mut #name))
       }
@@ -115,7 +127,28 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
 
 impl TryToTokens for NapiStruct {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    if self.generics.is_empty() {
+      self.gen_struct(tokens)
+    } else {
+      self.instantiate_generics().gen_struct(tokens)
+    }
+  }
+}
+
+impl NapiStruct {
+  /// Instantiates the type parameters of the fields, `generics` are kept for the type of the class
+  fn instantiate_generics(&self) -> NapiStruct {
+    let mut instantiated = self.clone();
+    for field in instantiated.fields.iter_mut() {
+      field.ty = instantiate_class_generics(&self.generics, field.ty.clone());
+    }
+    instantiated
+  }
+
+  fn gen_struct(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
     let napi_value_map_impl = self.gen_napi_value_map_impl();
+    let large_int_requirement =
+      large_int_requirement(self.large_int, self.fields.iter().map(|field| &field.ty));
 
     let class_helper_mod = if self.kind == NapiStructKind::Object {
       quote! {}
@@ -126,14 +159,13 @@ impl TryToTokens for NapiStruct {
     (quote! {
       #napi_value_map_impl
       #class_helper_mod
+      #large_int_requirement
     })
     .to_tokens(tokens);
 
     Ok(())
   }
-}
 
-impl NapiStruct {
   fn gen_helper_mod(&self) -> TokenStream {
     let mod_name = Ident::new(&format!("__napi_helper__{}", self.name), Span::call_site());
 
@@ -164,8 +196,8 @@ impl NapiStruct {
   }
 
   fn gen_default_ctor(&self) -> TokenStream {
-    let name = &self.name;
-    let js_name_str = &self.js_name;
+    let name = class_ty(&self.name, &self.generics);
+    let name_str = self.name.to_string();
     let fields_len = self.fields.len();
     let mut fields = vec![];
 
@@ -192,7 +224,7 @@ impl NapiStruct {
         cb: napi::bindgen_prelude::sys::napi_callback_info
       ) -> napi::bindgen_prelude::sys::napi_value {
         napi::bindgen_prelude::CallbackInfo::<#fields_len>::new(env, cb, None)
-          .and_then(|cb| unsafe { cb.construct(#js_name_str, #construct) })
+          .and_then(|cb| unsafe { cb.construct(#name_str, #construct) })
           .unwrap_or_else(|e| {
             unsafe { napi::bindgen_prelude::JsError::from(e).throw_into(env) };
             std::ptr::null_mut::<napi::bindgen_prelude::sys::napi_value__>()
@@ -205,24 +237,28 @@ impl NapiStruct {
     match self.kind {
       NapiStructKind::None => gen_napi_value_map_impl(
         &self.name,
+        &self.generics,
         self.gen_to_napi_value_ctor_impl_for_non_default_constructor_struct(),
       ),
-      NapiStructKind::Constructor => {
-        gen_napi_value_map_impl(&self.name, self.gen_to_napi_value_ctor_impl())
-      }
+      NapiStructKind::Constructor => gen_napi_value_map_impl(
+        &self.name,
+        &self.generics,
+        self.gen_to_napi_value_ctor_impl(),
+      ),
       NapiStructKind::Object => self.gen_to_napi_value_obj_impl(),
     }
   }
 
   fn gen_to_napi_value_ctor_impl_for_non_default_constructor_struct(&self) -> TokenStream {
-    let name = &self.name;
+    let name = class_ty(&self.name, &self.generics);
+    let name_str = self.name.to_string();
     let js_name_str = format!("{}\0", &self.js_name);
     quote! {
       impl napi::bindgen_prelude::ToNapiValue for #name {
         unsafe fn to_napi_value(
           env: napi::sys::napi_env, val: #name
         ) -> napi::Result<napi::bindgen_prelude::sys::napi_value> {
-          if let Some(ctor_ref) = napi::bindgen_prelude::get_class_constructor(#js_name_str) {
+          if let Some(ctor_ref) = napi::bindgen_prelude::get_class_constructor(env, #js_name_str) {
             let mut ctor = std::ptr::null_mut();
 
             napi::check_status!(
@@ -231,27 +267,7 @@ impl NapiStruct {
               #js_name_str
             )?;
 
-            let mut result = std::ptr::null_mut();
-            napi::bindgen_prelude::___CALL_FROM_FACTORY.store(true, std::sync::atomic::Ordering::Relaxed);
-            napi::check_status!(
-              napi::sys::napi_new_instance(env, ctor, 0, std::ptr::null_mut(), &mut result),
-              "Failed to construct class `{}`",
-              #js_name_str
-            )?;
-            napi::check_status!(
-              napi::sys::napi_wrap(
-                env,
-                result,
-                Box::into_raw(Box::new(val)) as *mut std::ffi::c_void,
-                Some(napi::bindgen_prelude::raw_finalize_unchecked::<#name>),
-                std::ptr::null_mut(),
-                std::ptr::null_mut(),
-              ),
-              "Failed to wrap native object of class `{}`",
-              #js_name_str
-            )?;
-            napi::bindgen_prelude::___CALL_FROM_FACTORY.store(false, std::sync::atomic::Ordering::Relaxed);
-            Ok(result)
+            napi::bindgen_prelude::new_instance_from_constructor(env, ctor, #name_str, val)
           } else {
             Err(napi::bindgen_prelude::Error::new(
               napi::bindgen_prelude::Status::InvalidArg, format!("Failed to get constructor of class `{}`", #js_name_str))
@@ -263,7 +279,7 @@ impl NapiStruct {
   }
 
   fn gen_to_napi_value_ctor_impl(&self) -> TokenStream {
-    let name = &self.name;
+    let name = class_ty(&self.name, &self.generics);
     let js_name_str = format!("{}\0", &self.js_name);
 
     let mut field_conversions = vec![];
@@ -303,7 +319,7 @@ impl NapiStruct {
         unsafe fn to_napi_value(
           env: napi::bindgen_prelude::sys::napi_env, val: #name
         ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
-          if let Some(ctor_ref) = napi::bindgen_prelude::get_class_constructor(#js_name_str) {
+          if let Some(ctor_ref) = napi::bindgen_prelude::get_class_constructor(env, #js_name_str) {
             let mut ctor = std::ptr::null_mut();
 
             napi::bindgen_prelude::check_status!(
@@ -340,10 +356,101 @@ impl NapiStruct {
     let mut obj_field_setters = vec![];
     let mut obj_field_getters = vec![];
     let mut field_destructions = vec![];
+    let fields_len = self.fields.len();
+    // the properties without a field of their own go into the `#[napi(index_signature)]` field
+    let known_keys = self
+      .fields
+      .iter()
+      .filter(|f| !f.index_signature)
+      .map(|f| &f.js_name)
+      .collect::<Vec<_>>();
+    let known_keys_const = if self.fields.iter().any(|f| f.index_signature) {
+      quote! { const KNOWN_KEYS: &[&str] = &[#(#known_keys),*]; }
+    } else {
+      quote! {}
+    };
+
+    // `#[napi(aggregate_errors)]` converts all the fields before reporting the errors of every one of them
+    let field_getter = |binding: TokenStream, ty: &syn::Type, value: TokenStream| {
+      if self.aggregate_errors {
+        quote! {
+          let #binding: Option<#ty> = napi::bindgen_prelude::collect_field_error(&mut __napi_errors, || Ok(#value));
+        }
+      } else {
+        quote! { let #binding: #ty = #value; }
+      }
+    };
 
     for field in self.fields.iter() {
+      if field.index_signature {
+        let ident = match &field.name {
+          syn::Member::Named(ident) => quote! { #ident },
+          syn::Member::Unnamed(i) => quote! { arg #i },
+        };
+        let ty = &field.ty;
+        field_destructions.push(ident.clone());
+        // entries clashing with a field are skipped, the field wins
+        obj_field_setters.push(quote! {
+          for (key, value) in #ident {
+            let key: &str = key.as_ref();
+            if !KNOWN_KEYS.contains(&key) {
+              obj.set_named(key, value)?;
+            }
+          }
+        });
+        obj_field_getters.push(field_getter(
+          ident.clone(),
+          ty,
+          quote! {{
+            let mut #ident: #ty = Default::default();
+            for key in napi::bindgen_prelude::Object::keys(&obj)? {
+              if !KNOWN_KEYS.contains(&key.as_str()) {
+                let value = obj.get_value(&key).map_err(|e| e.at_property(&key))?;
+                #ident.insert(key.into(), value);
+              }
+            }
+            #ident
+          }},
+        ));
+        continue;
+      }
+
       let field_js_name = &field.js_name;
+      // conversion errors of the field are prefixed with its name, `retry.maxAttempts: ...`
+      let at_field = quote! { .map_err(|e| e.at_property(#field_js_name)) };
+      // keys are created once per `Env` rather than for every object
+      let key = quote! { &napi::bindgen_prelude::PropertyKey::new(#field_js_name) };
       let ty = &field.ty;
+      // `#[napi(readonly)]` fields are defined as non-writable properties
+      let set = if field.getter && !field.setter {
+        quote! { set_readonly }
+      } else {
+        quote! { set }
+      };
+      // number fields with `#[napi(large_int)]`, `#[napi(strict_numbers)]` or `#[napi(non_finite)]` are
+      // converted through `CheckedInt<T>` and friends
+      let number_wrapper = number_wrapper(self.large_int, self.strict_numbers, self.non_finite, ty);
+      let (number_ty, unwrap_number) = match &number_wrapper {
+        Some(wrapper) => (&wrapper.ty, wrapper.unwrap()),
+        None => (ty, quote! {}),
+      };
+      // `#[napi(js_map)]` fields are converted from and into a `Map` through `EsMap`
+      let (field_ty, unwrap_value) = if field.js_map {
+        (quote! { napi::bindgen_prelude::EsMap<#ty> }, quote! { .0 })
+      } else {
+        (quote! { #number_ty }, unwrap_number.clone())
+      };
+      let wrap_value = |value: TokenStream| {
+        if field.js_map {
+          quote! { napi::bindgen_prelude::EsMap(#value) }
+        } else if let Some(wrapper) = &number_wrapper {
+          wrapper.wrap(value)
+        } else {
+          value
+        }
+      };
+      // `Option<T>` fields with `#[napi(none)]` are converted through `Nullable<T>` or `Undefinable<T>`
+      let none_wrapper = field.none.and_then(|none| none_wrapper(none, number_ty));
       let is_optional_field = if let syn::Type::Path(syn::TypePath {
         path: syn::Path { segments, .. },
         ..
@@ -360,51 +467,123 @@ impl NapiStruct {
       match &field.name {
         syn::Member::Named(ident) => {
           field_destructions.push(quote! { #ident });
-          if is_optional_field {
+          let value = wrap_value(quote! { #ident });
+          if let Some((_, wrapper)) = &none_wrapper {
+            if field.none == Some(NoneValue::Null) {
+              obj_field_setters.push(quote! { obj.#set(#key, #wrapper(#value))?; });
+            } else {
+              obj_field_setters.push(quote! {
+                if #ident.is_some() {
+                  obj.#set(#key, #wrapper(#value))?;
+                }
+              });
+            }
+          } else if is_optional_field {
             obj_field_setters.push(quote! {
               if #ident.is_some() {
-                obj.set(#field_js_name, #ident)?;
+                obj.#set(#key, #value)?;
               }
             });
           } else {
-            obj_field_setters.push(quote! { obj.set(#field_js_name, #ident)?; });
+            obj_field_setters.push(quote! { obj.#set(#key, #value)?; });
           }
-          if is_optional_field {
-            obj_field_getters.push(quote! { let #ident: #ty = obj.get(#field_js_name)?; });
+          if let Some((wrapped_ty, _)) = &none_wrapper {
+            obj_field_getters.push(field_getter(
+              quote! { #ident },
+              ty,
+              quote! { obj.get_value_by_key::<#wrapped_ty>(#key) #at_field?.0 #unwrap_number },
+            ));
+          } else if is_optional_field {
+            obj_field_getters.push(field_getter(
+              quote! { #ident },
+              ty,
+              if number_wrapper.is_some() {
+                quote! { obj.get_value_by_key::<#number_ty>(#key) #at_field? #unwrap_number }
+              } else {
+                quote! { obj.get_by_key(#key) #at_field? }
+              },
+            ));
           } else {
-            obj_field_getters.push(quote! {
-              let #ident: #ty = obj.get(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
-                napi::bindgen_prelude::Status::InvalidArg,
-                format!("Missing field `{}`", #field_js_name),
-              ))?;
-            });
+            obj_field_getters.push(field_getter(
+              quote! { #ident },
+              ty,
+              quote! {
+                obj.get_by_key::<#field_ty>(#key) #at_field?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+                  napi::bindgen_prelude::Status::InvalidArg,
+                  format!("Missing field `{}`", #field_js_name),
+                ))? #unwrap_value
+              },
+            ));
           }
         }
         syn::Member::Unnamed(i) => {
           field_destructions.push(quote! { arg #i });
-          if is_optional_field {
+          let value = wrap_value(quote! { arg #i });
+          if let Some((_, wrapper)) = &none_wrapper {
+            if field.none == Some(NoneValue::Null) {
+              obj_field_setters.push(quote! { obj.#set(#key, #wrapper(#value))?; });
+            } else {
+              obj_field_setters.push(quote! {
+                if arg #i.is_some() {
+                  obj.#set(#key, #wrapper(#value))?;
+                }
+              });
+            }
+          } else if is_optional_field {
             obj_field_setters.push(quote! {
               if arg #1.is_some() {
-                obj.set(#field_js_name, arg #i)?;
+                obj.#set(#key, #value)?;
               }
             });
           } else {
-            obj_field_setters.push(quote! { obj.set(#field_js_name, arg #1)?; });
+            obj_field_setters.push(quote! { obj.#set(#key, #value)?; });
           }
-          if is_optional_field {
-            obj_field_getters.push(quote! { let arg #i: #ty = obj.get(#field_js_name)?; });
+          if let Some((wrapped_ty, _)) = &none_wrapper {
+            obj_field_getters.push(field_getter(
+              quote! { arg #i },
+              ty,
+              quote! { obj.get_value_by_key::<#wrapped_ty>(#key) #at_field?.0 #unwrap_number },
+            ));
+          } else if is_optional_field {
+            obj_field_getters.push(field_getter(
+              quote! { arg #i },
+              ty,
+              if number_wrapper.is_some() {
+                quote! { obj.get_value_by_key::<#number_ty>(#key) #at_field? #unwrap_number }
+              } else {
+                quote! { obj.get_by_key(#key) #at_field? }
+              },
+            ));
           } else {
-            obj_field_getters.push(quote! {
-              let arg #i: #ty = obj.get(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
-                napi::bindgen_prelude::Status::InvalidArg,
-                format!("Missing field `{}`", #field_js_name),
-              ))?;
-            });
+            obj_field_getters.push(field_getter(
+              quote! { arg #i },
+              ty,
+              quote! {
+                obj.get_by_key::<#field_ty>(#key) #at_field?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+                  napi::bindgen_prelude::Status::InvalidArg,
+                  format!("Missing field `{}`", #field_js_name),
+                ))? #unwrap_value
+              },
+            ));
           }
         }
       }
     }
 
+    let (collect_errors, report_errors) = if self.aggregate_errors {
+      (
+        quote! { let mut __napi_errors = vec![]; },
+        quote! {
+          if !__napi_errors.is_empty() {
+            return Err(napi::bindgen_prelude::Error::aggregate(__napi_errors));
+          }
+          #(let #field_destructions = #field_destructions.unwrap();)*
+        },
+      )
+    } else {
+      (quote! {}, quote! {})
+    };
+
     let destructed_fields = if self.is_tuple {
       quote! {
         Self (#(#field_destructions),*)
@@ -429,12 +608,14 @@ impl NapiStruct {
       impl napi::bindgen_prelude::ToNapiValue for #name {
         unsafe fn to_napi_value(env: napi::bindgen_prelude::sys::napi_env, val: #name) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
           let env_wrapper = napi::bindgen_prelude::Env::from(env);
-          let mut obj = env_wrapper.create_object()?;
+          // all the fields are defined at once when the object is built
+          let mut obj = napi::bindgen_prelude::ObjectBuilder::with_capacity(&env_wrapper, #fields_len);
+          #known_keys_const
 
           let #destructed_fields = val;
           #(#obj_field_setters)*
 
-          napi::bindgen_prelude::Object::to_napi_value(env, obj)
+          napi::bindgen_prelude::Object::to_napi_value(env, obj.build()?)
         }
       }
 
@@ -445,8 +626,11 @@ impl NapiStruct {
         ) -> napi::bindgen_prelude::Result<Self> {
           let env_wrapper = napi::bindgen_prelude::Env::from(env);
           let mut obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
+          #known_keys_const
+          #collect_errors
 
           #(#obj_field_getters)*
+          #report_errors
 
           let val = #destructed_fields;
 
@@ -458,7 +642,7 @@ impl NapiStruct {
 
   fn gen_default_getters_setters(&self) -> Vec<(String, TokenStream)> {
     let mut getters_setters = vec![];
-    let struct_name = &self.name;
+    let struct_name = class_ty(&self.name, &self.generics);
 
     for field in self.fields.iter() {
       let field_ident = &field.name;
@@ -565,15 +749,25 @@ impl NapiStruct {
       props.push(prop);
     }
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
-    quote! {
-      #[allow(non_snake_case)]
-      #[allow(clippy::all)]
-      #[cfg(all(not(test), not(feature = "noop")))]
-      #[napi::bindgen_prelude::ctor]
-      fn #struct_register_name() {
-        napi::bindgen_prelude::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
+    let register_parent = match &self.extends {
+      Some(NapiClassParent::Class(parent)) => {
+        let parent_str = parent.to_string();
+        quote! {
+          napi::bindgen_prelude::register_class_parent(#name_str, napi::bindgen_prelude::ClassParent::Class(#parent_str));
+        }
       }
-    }
+      Some(NapiClassParent::Js(parent)) => quote! {
+        napi::bindgen_prelude::register_class_parent(#name_str, napi::bindgen_prelude::ClassParent::Js(#parent));
+      },
+      None => quote! {},
+    };
+    gen_register_fn(
+      &struct_register_name,
+      quote! {
+        napi::bindgen_prelude::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
+        #register_parent
+      },
+    )
   }
 }
 
@@ -617,6 +811,13 @@ impl NapiImpl {
 
       let appendix = match item.kind {
         FnKind::Constructor => quote! { .with_ctor(#intermediate_name) },
+        // accessors without `self` are defined on the class itself
+        FnKind::Getter if item.fn_self.is_none() => {
+          quote! { .with_getter(#intermediate_name).with_property_attributes(napi::bindgen_prelude::PropertyAttributes::Static) }
+        }
+        FnKind::Setter if item.fn_self.is_none() => {
+          quote! { .with_setter(#intermediate_name).with_property_attributes(napi::bindgen_prelude::PropertyAttributes::Static) }
+        }
         FnKind::Getter => quote! { .with_getter(#intermediate_name) },
         FnKind::Setter => quote! { .with_setter(#intermediate_name) },
         _ => {
@@ -631,10 +832,48 @@ impl NapiImpl {
       appendix.to_tokens(prop);
     }
 
+    for item in self.consts.iter() {
+      let js_name = Literal::string(&item.js_name);
+      let name = &item.name;
+      let type_name = &item.type_name;
+      let struct_name = class_ty(&self.name, &self.generics);
+      let intermediate_name = get_intermediate_ident(&format!("const_{}", name));
+      methods.push(quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        #[allow(clippy::all)]
+        extern "C" fn #intermediate_name(
+          env: napi::bindgen_prelude::sys::napi_env,
+          _cb: napi::bindgen_prelude::sys::napi_callback_info
+        ) -> napi::bindgen_prelude::sys::napi_value {
+          unsafe {
+            <#type_name as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #struct_name::#name).unwrap_or_else(|e| {
+              napi::bindgen_prelude::JsError::from(e).throw_into(env);
+              std::ptr::null_mut::<napi::bindgen_prelude::sys::napi_value__>()
+            })
+          }
+        }
+      });
+      props.insert(
+        &item.js_name,
+        quote! {
+          napi::bindgen_prelude::Property::new(#js_name).unwrap()
+            .with_getter(#intermediate_name)
+            .with_property_attributes(napi::bindgen_prelude::PropertyAttributes::Static)
+        },
+      );
+    }
+
     let mut props: Vec<_> = props.into_iter().collect();
     props.sort_by_key(|(_, prop)| prop.to_string());
     let props = props.into_iter().map(|(_, prop)| prop);
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let register_fn = gen_register_fn(
+      &register_name,
+      quote! {
+        napi::bindgen_prelude::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
+      },
+    );
     Ok(quote! {
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
@@ -642,11 +881,7 @@ impl NapiImpl {
         use super::*;
         #(#methods)*
 
-        #[cfg(all(not(test), not(feature = "noop")))]
-        #[napi::bindgen_prelude::ctor]
-        fn #register_name() {
-          napi::bindgen_prelude::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
-        }
+        #register_fn
       }
     })
   }
@@ -0,0 +1,129 @@
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::ToTokens;
+
+use crate::{BindgenResult, NapiTrait, TryToTokens};
+
+impl TryToTokens for NapiTrait {
+  fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    let impls = self.gen_js_object_impl();
+    let conversions = self.gen_conversions();
+    (quote! {
+      #impls
+      #conversions
+    })
+    .to_tokens(tokens);
+
+    Ok(())
+  }
+}
+
+impl NapiTrait {
+  fn js_object_ident(&self) -> Ident {
+    Ident::new(
+      &format!("__napi_interface__{}", self.name),
+      self.name.span(),
+    )
+  }
+
+  /// The JavaScript object implementing the interface,
+  /// every trait method looks up the same named property and calls it.
+  ///
+  /// The object is held by a reference, the `napi_value` is only valid in the scope of the call which received it.
+  fn gen_js_object_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let js_object_ident = self.js_object_ident();
+    let trait_js_name = &self.js_name;
+    let methods = self.methods.iter().map(|method| {
+      let sig = &method.sig;
+      let js_name = Literal::string(&method.js_name);
+      let not_fn_message = format!("{}.{} is not a function", trait_js_name, method.js_name);
+      let args = method.args.iter().map(|(arg, _)| arg);
+      let ret = &method.ret;
+      let call = if is_unit_ty(ret) {
+        quote! {
+          method
+            .typed_call::<_, napi::JsUnknown>(Some(&object), (#(#args,)*))
+            .map(|_| ())
+        }
+      } else {
+        quote! {
+          method.typed_call::<_, #ret>(Some(&object), (#(#args,)*))
+        }
+      };
+      quote! {
+        #sig {
+          let object: napi::JsObject = self.env.get_reference_value(&self.object)?;
+          let method: napi::JsUnknown = object.get_named_property(#js_name)?;
+          if method.get_type()? != napi::ValueType::Function {
+            return Err(napi::Error::new(
+              napi::Status::FunctionExpected,
+              #not_fn_message.to_owned(),
+            ));
+          }
+          let method: napi::JsFunction = unsafe { method.cast() };
+          #call
+        }
+      }
+    });
+
+    quote! {
+      #[doc(hidden)]
+      #[allow(non_camel_case_types)]
+      pub struct #js_object_ident {
+        env: napi::Env,
+        object: napi::Ref<()>,
+      }
+
+      #[allow(clippy::all)]
+      impl #name for #js_object_ident {
+        #(#methods)*
+      }
+
+      impl Drop for #js_object_ident {
+        fn drop(&mut self) {
+          let _ = self.object.unref(self.env);
+        }
+      }
+    }
+  }
+
+  fn gen_conversions(&self) -> TokenStream {
+    let name = &self.name;
+    let js_name = Literal::string(&self.js_name);
+    let js_object_ident = self.js_object_ident();
+
+    quote! {
+      impl napi::bindgen_prelude::TypeName for Box<dyn #name> {
+        fn type_name() -> &'static str {
+          #js_name
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for Box<dyn #name> {
+        fn type_of() -> Vec<napi::ValueType> {
+          vec![napi::ValueType::Object]
+        }
+      }
+
+      impl napi::bindgen_prelude::FromNapiValue for Box<dyn #name> {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<Self> {
+          let obj = <napi::JsObject as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)?;
+          let env = napi::Env::from(env);
+          let object = env.create_reference(obj)?;
+          Ok(Box::new(#js_object_ident { env, object }))
+        }
+      }
+    }
+  }
+}
+
+fn is_unit_ty(ty: &syn::Type) -> bool {
+  matches!(ty, syn::Type::Tuple(tuple) if tuple.elems.is_empty())
+}
@@ -28,6 +28,15 @@ macro_rules! napi_ast_impl {
       $($v($ast)),*
     }
 
+    impl Napi {
+      /// Name of the item in JavaScript
+      pub fn js_name(&self) -> &str {
+        match self.item {
+          $( NapiItem::$v(ref ast) => &ast.js_name ),*
+        }
+      }
+    }
+
     impl TryToTokens for Napi {
       fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
         match self.item {
@@ -53,4 +62,5 @@ napi_ast_impl! {
  (Impl, NapiImpl),
  (Enum, NapiEnum),
  (Const, NapiConst),
+ (Trait, NapiTrait),
 }
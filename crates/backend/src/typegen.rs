@@ -2,10 +2,12 @@ mod r#const;
 mod r#enum;
 mod r#fn;
 pub(crate) mod r#struct;
+mod r#trait;
 
 use std::{cell::RefCell, collections::HashMap};
 
 use once_cell::sync::Lazy;
+use proc_macro2::Ident;
 use syn::Type;
 
 #[derive(Default, Debug)]
@@ -16,6 +18,12 @@ pub struct TypeDef {
   pub def: String,
   pub js_mod: Option<String>,
   pub js_doc: String,
+  /// Parent class of a `struct`
+  pub extends: Option<String>,
+  /// Module augmented by the type, e.g. `declare module "pkg"`
+  pub ts_module: Option<String>,
+  /// Type parameters of a generic `struct`, e.g. `<T>`
+  pub generics: Option<String>,
 }
 
 thread_local! {
@@ -28,11 +36,32 @@ fn add_alias(name: String, alias: String) {
   });
 }
 
+/// TypeScript type parameters, e.g. `<T, U>`, empty without any
+fn ts_generics(generics: &[Ident]) -> String {
+  if generics.is_empty() {
+    return "".to_owned();
+  }
+  format!(
+    "<{}>",
+    generics
+      .iter()
+      .map(|ident| ident.to_string())
+      .collect::<Vec<_>>()
+      .join(", ")
+  )
+}
+
 pub fn js_doc_from_comments(comments: &[String]) -> String {
   if comments.is_empty() {
     return "".to_owned();
   }
 
+  // `*/` in the doc comments would terminate the JSDoc block early
+  let comments = comments
+    .iter()
+    .map(|c| c.replace("*/", "*\\/"))
+    .collect::<Vec<_>>();
+
   if comments.len() == 1 {
     return format!("/**{} */\n", comments[0]);
   }
@@ -102,14 +131,32 @@ impl ToString for TypeDef {
     } else {
       "".to_owned()
     };
+    let extends = if let Some(extends) = &self.extends {
+      format!(", \"extends\": \"{}\"", extends)
+    } else {
+      "".to_owned()
+    };
+    let ts_module = if let Some(ts_module) = &self.ts_module {
+      format!(", \"ts_module\": \"{}\"", ts_module)
+    } else {
+      "".to_owned()
+    };
+    let generics = if let Some(generics) = &self.generics {
+      format!(", \"generics\": \"{}\"", generics)
+    } else {
+      "".to_owned()
+    };
     format!(
-      r#"{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}}}"#,
+      r#"{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}{}{}{}}}"#,
       self.kind,
       self.name,
       escape_json(&self.js_doc),
       escape_json(&self.def),
       original_name,
       js_mod,
+      extends,
+      ts_module,
+      generics,
     )
   }
 }
@@ -141,13 +188,26 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("isize", "bigint"),
     ("JsBigInt", "bigint"),
     ("BigInt", "bigint"),
+    ("CheckedInt", "number"),
+    ("SaturatingInt", "number"),
+    ("AsBigInt", "bigint"),
+    ("CheckedI8", "number"),
+    ("CheckedU8", "number"),
+    ("CheckedI16", "number"),
+    ("CheckedU16", "number"),
+    ("CheckedI32", "number"),
+    ("CheckedU32", "number"),
+    ("Finite", "number"),
+    ("NonFiniteAsNull", "number | null"),
     ("JsBoolean", "boolean"),
     ("bool", "boolean"),
     ("JsString", "string"),
     ("String", "string"),
     ("str", "string"),
+    ("StaticStr", "string"),
     ("Latin1String", "string"),
     ("Utf16String", "string"),
+    ("Utf16Lossy", "string"),
     ("char", "string"),
     ("JsObject", "object"),
     ("Object", "object"),
@@ -155,6 +215,7 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("Value", "any"),
     ("Map", "Record<string, any>"),
     ("HashMap", "Record<{}, {}>"),
+    ("BTreeMap", "Record<{}, {}>"),
     ("ArrayBuffer", "ArrayBuffer"),
     ("Int8Array", "Int8Array"),
     ("Uint8Array", "Uint8Array"),
@@ -171,8 +232,23 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("DateTime", "Date"),
     ("Date", "Date"),
     ("JsDate", "Date"),
+    ("Decimal", "string"),
+    ("BigDecimal", "string"),
+    ("DecimalNumber", "number"),
+    ("ScaledDecimal", "bigint"),
+    ("OffsetDateTime", "Date"),
+    ("PrimitiveDateTime", "Date"),
+    ("Bytes", "Buffer"),
+    ("BytesMut", "Buffer"),
+    ("Uuid", "string"),
+    ("UuidBytes", "Uint8Array"),
+    ("Url", "URL"),
+    ("UrlOrString", "string | URL"),
+    ("SearchParams", "URLSearchParams"),
     ("JsBuffer", "Buffer"),
     ("Buffer", "Buffer"),
+    ("PooledBuffer", "Buffer"),
+    ("PooledArrayBuffer", "ArrayBuffer"),
     ("Vec", "Array<{}>"),
     ("Result", "Error | {}"),
     ("Either", "{} | {}"),
@@ -180,15 +256,20 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("Either4", "{} | {} | {} | {}"),
     ("Either5", "{} | {} | {} | {} | {}"),
     ("unknown", "unknown"),
+    ("JsUnknown", "unknown"),
     ("Null", "null"),
     ("null", "null"),
     ("Symbol", "symbol"),
     ("JsSymbol", "symbol"),
     ("external", "object"),
     ("AbortSignal", "AbortSignal"),
+    ("Blob", "Blob"),
+    ("FileBlob", "File"),
     ("JsFunction", "(...args: any[]) => any"),
     ("JsGlobal", "typeof global"),
     ("External", "ExternalObject<{}>"),
+    ("Cow", "{}"),
+    ("ClassInstance", "{}"),
   ]);
 
   map
@@ -212,6 +293,140 @@ fn fill_ty(template: &str, args: Vec<String>) -> String {
   ret
 }
 
+/// `Option<T>` with `#[napi(none = "null")]` => `T | null`
+pub fn none_ts_type(ty: &Type, none: crate::NoneValue) -> (String, bool) {
+  let inner_ty = match ty {
+    Type::Path(syn::TypePath { qself: None, path }) => match path.segments.last() {
+      Some(syn::PathSegment {
+        arguments: syn::PathArguments::AngleBracketed(arguments),
+        ..
+      }) => arguments.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(generic_ty) => Some(ty_to_ts_type(generic_ty, false).0),
+        _ => None,
+      }),
+      _ => None,
+    },
+    _ => None,
+  }
+  .unwrap_or_else(|| "unknown".to_owned());
+  match none {
+    crate::NoneValue::Null => (format!("{} | null", inner_ty), false),
+    crate::NoneValue::Undefined => (format!("{} | undefined", inner_ty), true),
+  }
+}
+
+/// `EsMap<HashMap<K, V>>` => `Map<K, V>`
+pub fn es_map_ts_type(map_ty: &Type) -> String {
+  match map_entry_ts_types(map_ty).as_slice() {
+    [key, value, ..] => format!("Map<{}, {}>", key, value),
+    _ => "Map<unknown, unknown>".to_owned(),
+  }
+}
+
+/// The value type of a `#[napi(index_signature)]` field, `HashMap<String, V>` => `V`
+pub fn index_signature_ts_type(map_ty: &Type) -> String {
+  match map_entry_ts_types(map_ty).as_slice() {
+    [_, value, ..] => value.to_owned(),
+    _ => "unknown".to_owned(),
+  }
+}
+
+fn map_entry_ts_types(map_ty: &Type) -> Vec<String> {
+  match map_ty {
+    Type::Path(syn::TypePath { qself: None, path }) => match path.segments.last() {
+      Some(syn::PathSegment {
+        arguments: syn::PathArguments::AngleBracketed(arguments),
+        ..
+      }) => arguments
+        .args
+        .iter()
+        .filter_map(|arg| match arg {
+          syn::GenericArgument::Type(generic_ty) => Some(ty_to_ts_type(generic_ty, false).0),
+          _ => None,
+        })
+        .collect::<Vec<_>>(),
+      _ => vec![],
+    },
+    _ => vec![],
+  }
+}
+
+/// `Array2<f64>` or `NdArrayView<f32>` => `{ data: Float64Array, shape: Array<number> }`,
+/// `None` for the other types and for the `Array` of napi which has no generic arguments
+fn nd_array_ts_type(rust_ty: &str, arguments: &syn::PathArguments) -> Option<String> {
+  let dimension = rust_ty
+    .strip_prefix("ArrayView")
+    .or_else(|| rust_ty.strip_prefix("Array"));
+  let is_nd_array = rust_ty == "NdArrayView"
+    || matches!(
+      dimension,
+      Some("" | "0" | "1" | "2" | "3" | "4" | "5" | "6" | "D")
+    );
+  if !is_nd_array {
+    return None;
+  }
+  let elem_ty = match arguments {
+    syn::PathArguments::AngleBracketed(arguments) => {
+      arguments.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(Type::Path(p)) => p.path.get_ident().map(|i| i.to_string()),
+        _ => None,
+      })?
+    }
+    _ => return None,
+  };
+  let typed_array = match elem_ty.as_str() {
+    "i8" => "Int8Array",
+    "u8" => "Uint8Array",
+    "i16" => "Int16Array",
+    "u16" => "Uint16Array",
+    "i32" => "Int32Array",
+    "u32" => "Uint32Array",
+    "f32" => "Float32Array",
+    "f64" => "Float64Array",
+    "i64" => "BigInt64Array",
+    "u64" => "BigUint64Array",
+    _ => return None,
+  };
+  Some(format!("{{ data: {}, shape: Array<number> }}", typed_array))
+}
+
+/// `Function<(A, B), R>` => `(arg0: A, arg1: B) => R`
+fn typed_function_ts_type(arguments: &syn::PathArguments) -> String {
+  let generic_tys = match arguments {
+    syn::PathArguments::AngleBracketed(arguments) => arguments
+      .args
+      .iter()
+      .filter_map(|arg| match arg {
+        syn::GenericArgument::Type(generic_ty) => Some(generic_ty),
+        _ => None,
+      })
+      .collect::<Vec<_>>(),
+    _ => vec![],
+  };
+  let args = match generic_tys.first() {
+    Some(Type::Tuple(tuple)) => tuple.elems.iter().collect::<Vec<_>>(),
+    Some(ty) => vec![*ty],
+    None => vec![],
+  };
+  let ret = match generic_tys.get(1) {
+    Some(ty) => match ty_to_ts_type(ty, true).0.as_str() {
+      "undefined" => "void".to_owned(),
+      ret => ret.to_owned(),
+    },
+    None => "unknown".to_owned(),
+  };
+  format!(
+    "({}) => {}",
+    args
+      .into_iter()
+      .enumerate()
+      .map(|(i, arg)| format!("arg{}: {}", i, ty_to_ts_type(arg, false).0))
+      .collect::<Vec<_>>()
+      .join(", "),
+    ret
+  )
+}
+
 pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool) -> (String, bool) {
   match ty {
     Type::Reference(r) => ty_to_ts_type(&r.elem, is_return_ty),
@@ -257,6 +472,14 @@ pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool) -> (String, bool) {
           ts_ty = args
             .first()
             .map(|(arg, _)| (format!("{} | undefined | null", arg), true));
+        } else if rust_ty == "Nullable" {
+          ts_ty = args
+            .first()
+            .map(|(arg, _)| (format!("{} | null", arg), false));
+        } else if rust_ty == "Undefinable" {
+          ts_ty = args
+            .first()
+            .map(|(arg, _)| (format!("{} | undefined", arg), true));
         } else if rust_ty == "AsyncTask" {
           ts_ty = r#struct::TASK_STRUCTS.with(|t| {
             let (output_type, _) = args.first().unwrap().to_owned();
@@ -266,6 +489,35 @@ pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool) -> (String, bool) {
               Some(("Promise<unknown>".to_owned(), false))
             }
           });
+        } else if rust_ty == "Box" {
+          ts_ty = args.into_iter().next();
+        } else if rust_ty == "EsMap" {
+          ts_ty = match arguments {
+            syn::PathArguments::AngleBracketed(arguments) => {
+              arguments.args.iter().find_map(|arg| match arg {
+                syn::GenericArgument::Type(map_ty) => Some((es_map_ts_type(map_ty), false)),
+                _ => None,
+              })
+            }
+            _ => None,
+          };
+        } else if rust_ty == "Function" {
+          ts_ty = Some((typed_function_ts_type(arguments), false));
+        } else if let Some(nd_array_ty) = nd_array_ts_type(&rust_ty, arguments) {
+          ts_ty = Some((nd_array_ty, false));
+        } else if rust_ty == "ThreadsafeFunction" {
+          let value_ty = args
+            .first()
+            .map(|(arg, _)| arg.to_owned())
+            .unwrap_or_else(|| "unknown".to_owned());
+          ts_ty = Some((
+            if matches!(args.get(1), Some((strategy, _)) if strategy == "Fatal") {
+              format!("(value: {}) => any", value_ty)
+            } else {
+              format!("(err: Error | null, value: {}) => any", value_ty)
+            },
+            false,
+          ));
         } else if let Some(&known_ty) = KNOWN_TYPES.get(rust_ty.as_str()) {
           if known_ty.contains("{}") {
             ts_ty = Some((
@@ -299,6 +551,25 @@ pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool) -> (String, bool) {
       ts_ty.unwrap_or_else(|| ("any".to_owned(), false))
     }
     Type::Group(g) => ty_to_ts_type(&g.elem, is_return_ty),
+    // `&[u8]` is returned as a copy in a `Buffer`
+    Type::Slice(s) => match &*s.elem {
+      Type::Path(p) if p.path.is_ident("u8") => ("Buffer".to_owned(), false),
+      _ => ("any".to_owned(), false),
+    },
+    // `dyn Trait` of the `#[napi(interface)]` traits
+    Type::TraitObject(t) => t
+      .bounds
+      .iter()
+      .find_map(|bound| match bound {
+        syn::TypeParamBound::Trait(t) => t.path.segments.last(),
+        _ => None,
+      })
+      .map(|segment| {
+        let rust_ty = segment.ident.to_string();
+        let ts_ty = ALIAS.with(|aliases| aliases.borrow().get(rust_ty.as_str()).cloned());
+        (ts_ty.unwrap_or(rust_ty), false)
+      })
+      .unwrap_or_else(|| ("any".to_owned(), false)),
     _ => ("any".to_owned(), false),
   }
 }
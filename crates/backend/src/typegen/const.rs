@@ -21,6 +21,9 @@ impl ToTypeDef for NapiConst {
       ),
       js_mod: self.js_mod.to_owned(),
       js_doc: js_doc_from_comments(&self.comments),
+      extends: None,
+      ts_module: None,
+      generics: None,
     })
   }
 }
@@ -1,20 +1,42 @@
 use super::{add_alias, ToTypeDef, TypeDef};
-use crate::{js_doc_from_comments, NapiEnum};
+use crate::{js_doc_from_comments, ty_to_ts_type, NapiEnum};
 
 impl ToTypeDef for NapiEnum {
   fn to_type_def(&self) -> Option<TypeDef> {
-    if self.skip_typescript {
+    // error enums are only thrown, they never cross the boundary as values
+    if self.skip_typescript || self.is_error {
       return None;
     }
 
     add_alias(self.name.to_string(), self.js_name.to_string());
 
+    if self.is_string_enum || self.is_tagged_union {
+      return Some(TypeDef {
+        kind: "type".to_owned(),
+        name: self.js_name.to_owned(),
+        original_name: Some(self.name.to_string()),
+        def: if self.is_tagged_union {
+          self.gen_ts_tagged_union()
+        } else {
+          self.gen_ts_string_union()
+        },
+        js_doc: js_doc_from_comments(&self.comments),
+        extends: None,
+        ts_module: None,
+        generics: None,
+        js_mod: self.js_mod.to_owned(),
+      });
+    }
+
     Some(TypeDef {
       kind: "enum".to_owned(),
       name: self.js_name.to_owned(),
       original_name: Some(self.name.to_string()),
       def: self.gen_ts_variants(),
       js_doc: js_doc_from_comments(&self.comments),
+      extends: None,
+      ts_module: None,
+      generics: None,
       js_mod: self.js_mod.to_owned(),
     })
   }
@@ -36,4 +58,54 @@ impl NapiEnum {
       .collect::<Vec<_>>()
       .join(",\n ")
   }
+
+  fn gen_ts_string_union(&self) -> String {
+    self
+      .variants
+      .iter()
+      .map(|v| format!("'{}'", v.js_name))
+      .collect::<Vec<_>>()
+      .join(" | ")
+  }
+
+  fn gen_ts_tagged_union(&self) -> String {
+    self
+      .variants
+      .iter()
+      .map(|v| {
+        let fields = v
+          .fields
+          .iter()
+          .map(|f| {
+            let (ty, is_optional) = ty_to_ts_type(&f.ty, false);
+            let sep = if is_optional { "?" } else { "" };
+            format!(
+              ", {}{}{}: {}",
+              inline_js_doc(&f.comments),
+              f.js_name,
+              sep,
+              ty
+            )
+          })
+          .collect::<String>();
+        format!(
+          "{{ {}type: '{}'{} }}",
+          inline_js_doc(&v.comments),
+          v.js_name,
+          fields
+        )
+      })
+      .collect::<Vec<_>>()
+      .join(" | ")
+  }
+}
+
+/// JSDoc placed in front of a property of an inline object type
+fn inline_js_doc(comments: &[String]) -> String {
+  let js_doc = js_doc_from_comments(comments);
+  if js_doc.is_empty() {
+    js_doc
+  } else {
+    format!("{} ", js_doc.trim_end())
+  }
 }
@@ -3,16 +3,17 @@ use quote::ToTokens;
 use std::fmt::{Display, Formatter};
 use syn::Pat;
 
-use super::{ty_to_ts_type, ToTypeDef, TypeDef};
-use crate::{js_doc_from_comments, CallbackArg, FnKind, NapiFn};
+use super::{es_map_ts_type, none_ts_type, ts_generics, ty_to_ts_type, ToTypeDef, TypeDef};
+use crate::{codegen::number_wrapper, js_doc_from_comments, CallbackArg, FnKind, NapiFn};
 
-struct FnArg {
-  arg: String,
-  ts_type: String,
-  is_optional: bool,
+pub(crate) struct FnArg {
+  pub(crate) arg: String,
+  pub(crate) ts_type: String,
+  pub(crate) is_optional: bool,
+  pub(crate) is_rest: bool,
 }
 
-struct FnArgList {
+pub(crate) struct FnArgList {
   args: Vec<FnArg>,
   last_required: Option<usize>,
 }
@@ -27,7 +28,9 @@ impl Display for FnArgList {
         && self
           .last_required
           .map_or(true, |last_required| i > last_required);
-      if is_optional {
+      if arg.is_rest {
+        write!(f, "...{}: {}", arg.arg, arg.ts_type)?;
+      } else if is_optional {
         write!(f, "{}?: {}", arg.arg, arg.ts_type)?;
       } else {
         write!(f, "{}: {}", arg.arg, arg.ts_type)?;
@@ -54,14 +57,15 @@ impl FromIterator<FnArg> for FnArgList {
 
 impl ToTypeDef for NapiFn {
   fn to_type_def(&self) -> Option<TypeDef> {
-    if self.skip_typescript {
+    if self.skip_typescript || self.module_init {
       return None;
     }
 
     let def = format!(
-      r#"{prefix} {name}({args}){ret}"#,
+      r#"{prefix} {name}{generics}({args}){ret}"#,
       prefix = self.gen_ts_func_prefix(),
       name = &self.js_name,
+      generics = ts_generics(&self.generics),
       args = self
         .ts_args_type
         .clone()
@@ -79,11 +83,29 @@ impl ToTypeDef for NapiFn {
       original_name: None,
       def,
       js_mod: self.js_mod.to_owned(),
-      js_doc: js_doc_from_comments(&self.comments),
+      js_doc: js_doc_from_comments(&self.gen_ts_comments()),
+      extends: None,
+      ts_module: None,
+      generics: None,
     })
   }
 }
 
+fn first_generic_ty(ty: &syn::Type) -> Option<&syn::Type> {
+  match ty {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => {
+      match &path.segments.last()?.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+          syn::GenericArgument::Type(elem) => Some(elem),
+          _ => None,
+        },
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
 fn gen_callback_type(callback: &CallbackArg) -> String {
   format!(
     "({args}) => {ret}",
@@ -97,6 +119,7 @@ fn gen_callback_type(callback: &CallbackArg) -> String {
           arg: format!("arg{}", i),
           ts_type,
           is_optional,
+          is_rest: false,
         }
       })
       .collect::<FnArgList>(),
@@ -109,12 +132,20 @@ fn gen_callback_type(callback: &CallbackArg) -> String {
 
 impl NapiFn {
   fn gen_ts_func_args(&self) -> String {
-    format!(
+    // `this` must be the first parameter in TypeScript
+    let this_ts_type = self.args.iter().find_map(|fn_arg| match &fn_arg.kind {
+      crate::NapiFnArgKind::This(path) => Some(fn_arg.ts_arg_type.clone().unwrap_or_else(|| {
+        first_generic_ty(&path.ty)
+          .map_or_else(|| "object".to_owned(), |elem| ty_to_ts_type(elem, false).0)
+      })),
+      _ => None,
+    });
+    let args = format!(
       "{}",
       self
         .args
         .iter()
-        .filter_map(|arg| match arg {
+        .filter_map(|fn_arg| match &fn_arg.kind {
           crate::NapiFnArgKind::PatType(path) => {
             if path.ty.to_token_stream().to_string() == "Env" {
               return None;
@@ -125,27 +156,91 @@ impl NapiFn {
               i.mutability = None;
             }
             let arg = path.pat.to_token_stream().to_string().to_case(Case::Camel);
-            let (ts_type, is_optional) = ty_to_ts_type(&path.ty, false);
+            if fn_arg.variadic {
+              let ts_type = fn_arg.ts_arg_type.clone().unwrap_or_else(|| {
+                let elem_ts_type = match first_generic_ty(&path.ty) {
+                  Some(elem) => ty_to_ts_type(elem, false).0,
+                  None => "unknown".to_owned(),
+                };
+                if elem_ts_type.contains(' ') {
+                  format!("({})[]", elem_ts_type)
+                } else {
+                  format!("{}[]", elem_ts_type)
+                }
+              });
+              // rest parameters never make the preceding optional parameters required
+              return Some(FnArg {
+                arg,
+                ts_type,
+                is_optional: true,
+                is_rest: true,
+              });
+            }
+            // `i64` with `#[napi(large_int = "checked")]` is typed as `CheckedInt<i64>`
+            let number_wrapper = number_wrapper(
+              self.large_int,
+              self.strict_numbers,
+              self.non_finite,
+              &path.ty,
+            );
+            let ty = number_wrapper
+              .as_ref()
+              .map_or(&*path.ty, |wrapper| &wrapper.ty);
+            let (ts_type, is_optional) = if fn_arg.js_map {
+              (es_map_ts_type(ty), false)
+            } else if let Some(none) = fn_arg.none {
+              none_ts_type(ty, none)
+            } else {
+              ty_to_ts_type(ty, false)
+            };
+            let ts_type = fn_arg.ts_arg_type.clone().unwrap_or(ts_type);
 
             Some(FnArg {
               arg,
               ts_type,
-              is_optional,
+              is_optional: is_optional || fn_arg.default.is_some(),
+              is_rest: false,
             })
           }
           crate::NapiFnArgKind::Callback(cb) => {
             let arg = cb.pat.to_token_stream().to_string().to_case(Case::Camel);
-            let ts_type = gen_callback_type(cb);
+            let ts_type = fn_arg
+              .ts_arg_type
+              .clone()
+              .unwrap_or_else(|| gen_callback_type(cb));
 
             Some(FnArg {
               arg,
               ts_type,
               is_optional: false,
+              is_rest: false,
             })
           }
+          crate::NapiFnArgKind::This(_) => None,
         })
         .collect::<FnArgList>()
-    )
+    );
+
+    match this_ts_type {
+      Some(this_ts_type) if args.is_empty() => format!("this: {}", this_ts_type),
+      Some(this_ts_type) => format!("this: {}, {}", this_ts_type, args),
+      None => args,
+    }
+  }
+
+  /// Functions with `#[napi(napi_version)]` are missing from the exports of older runtimes
+  fn gen_ts_comments(&self) -> Vec<String> {
+    let mut comments = self.comments.clone();
+    if let Some(napi_version) = self.napi_version {
+      if !comments.is_empty() {
+        comments.push("".to_owned());
+      }
+      comments.push(format!(
+        " Only exported if the runtime supports Node-API version {}, check that it's defined before calling it",
+        napi_version
+      ));
+    }
+    comments
   }
 
   fn gen_ts_func_prefix(&self) -> &'static str {
@@ -157,8 +252,14 @@ impl NapiFn {
         },
         crate::FnKind::Factory => "static",
         crate::FnKind::Constructor => "",
-        crate::FnKind::Getter => "get",
-        crate::FnKind::Setter => "set",
+        crate::FnKind::Getter => match self.fn_self {
+          Some(_) => "get",
+          None => "static get",
+        },
+        crate::FnKind::Setter => match self.fn_self {
+          Some(_) => "set",
+          None => "static set",
+        },
       }
     } else {
       "export function"
@@ -171,11 +272,27 @@ impl NapiFn {
       FnKind::Factory => self
         .parent
         .clone()
-        .map(|i| format!(": {}", i.to_string().to_case(Case::Pascal)))
+        .map(|i| {
+          let class = i.to_string().to_case(Case::Pascal);
+          if self.is_async {
+            format!(": Promise<{}>", class)
+          } else {
+            format!(": {}", class)
+          }
+        })
         .unwrap_or_else(|| "".to_owned()),
       _ => {
         let ret = if let Some(ret) = &self.ret {
-          let (ts_type, _) = ty_to_ts_type(ret, true);
+          let number_wrapper =
+            number_wrapper(self.large_int, self.strict_numbers, self.non_finite, ret);
+          let ret = number_wrapper.as_ref().map_or(ret, |wrapper| &wrapper.ty);
+          let (ts_type, _) = if self.js_map {
+            (es_map_ts_type(ret), false)
+          } else if let Some(none) = self.none {
+            none_ts_type(ret, none)
+          } else {
+            ty_to_ts_type(ret, true)
+          };
           if ts_type == "undefined" {
             "void".to_owned()
           } else if ts_type == "Self" {
@@ -1,8 +1,12 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 
-use super::{add_alias, ToTypeDef, TypeDef};
-use crate::{js_doc_from_comments, ty_to_ts_type, NapiImpl, NapiStruct, NapiStructKind};
+use super::{add_alias, ts_generics, ToTypeDef, TypeDef};
+use crate::{
+  codegen::number_wrapper, es_map_ts_type, index_signature_ts_type, js_doc_from_comments,
+  none_ts_type, ty_to_ts_type, NapiClassParent, NapiImpl, NapiStruct, NapiStructField,
+  NapiStructKind,
+};
 
 thread_local! {
   pub(crate) static TASK_STRUCTS: RefCell<HashMap<String, String>> = Default::default();
@@ -17,6 +21,11 @@ impl ToTypeDef for NapiStruct {
     });
     add_alias(self.name.to_string(), self.js_name.to_string());
 
+    // still registered above, so the types referring to it are generated as usual
+    if self.skip_typescript {
+      return None;
+    }
+
     Some(TypeDef {
       kind: String::from(if self.kind == NapiStructKind::Object {
         "interface"
@@ -28,6 +37,9 @@ impl ToTypeDef for NapiStruct {
       def: self.gen_ts_class(),
       js_mod: self.js_mod.to_owned(),
       js_doc: js_doc_from_comments(&self.comments),
+      extends: self.gen_ts_extends(),
+      ts_module: self.ts_module.to_owned(),
+      generics: Some(ts_generics(&self.generics)).filter(|generics| !generics.is_empty()),
     })
   }
 }
@@ -41,14 +53,27 @@ impl ToTypeDef for NapiImpl {
       });
     }
 
+    if self.skip_typescript {
+      return None;
+    }
+
     Some(TypeDef {
       kind: "impl".to_owned(),
       name: self.js_name.to_owned(),
       original_name: None,
       def: self
-        .items
+        .consts
         .iter()
-        .filter_map(|f| {
+        .filter(|c| !c.skip_typescript)
+        .map(|c| {
+          format!(
+            "{}static readonly {}: {}",
+            js_doc_from_comments(&c.comments),
+            c.js_name,
+            ty_to_ts_type(&c.type_name, false).0
+          )
+        })
+        .chain(self.items.iter().filter_map(|f| {
           if f.skip_typescript {
             None
           } else {
@@ -59,16 +84,82 @@ impl ToTypeDef for NapiImpl {
                 .map_or(String::default(), |type_def| type_def.def)
             ))
           }
-        })
+        }))
         .collect::<Vec<_>>()
         .join("\\n"),
       js_mod: self.js_mod.to_owned(),
       js_doc: "".to_string(),
+      extends: None,
+      ts_module: None,
+      generics: None,
     })
   }
 }
 
 impl NapiStruct {
+  fn gen_ts_extends(&self) -> Option<String> {
+    self.extends.as_ref().map(|parent| match parent {
+      NapiClassParent::Class(ident) => CLASS_STRUCTS
+        .with(|c| c.borrow().get(&ident.to_string()).cloned())
+        .unwrap_or_else(|| ident.to_string()),
+      NapiClassParent::Js(name) => name.to_owned(),
+    })
+  }
+
+  /// The TypeScript type of a field and whether it's optional
+  fn gen_ts_field_type(&self, f: &NapiStructField) -> (String, bool) {
+    let (arg, is_optional) = if f.js_map {
+      (es_map_ts_type(&f.ty), false)
+    } else if let Some(wrapper) =
+      number_wrapper(self.large_int, self.strict_numbers, self.non_finite, &f.ty)
+    {
+      match f.none {
+        Some(none) => none_ts_type(&wrapper.ty, none),
+        None => ty_to_ts_type(&wrapper.ty, false),
+      }
+    } else if let Some(none) = f.none {
+      none_ts_type(&f.ty, none)
+    } else {
+      ty_to_ts_type(&f.ty, false)
+    };
+    (f.ts_type.clone().unwrap_or(arg), is_optional)
+  }
+
+  /// The values of the map, joined with the types of the named fields,
+  /// TypeScript requires every property to match the index signature
+  fn gen_ts_index_signature_type(&self, index_signature: &NapiStructField) -> String {
+    let mut types = vec![index_signature_ts_type(&index_signature.ty)];
+    let mut has_optional = false;
+    for f in &self.fields {
+      if !f.getter || f.skip_typescript || f.index_signature {
+        continue;
+      }
+      let (ty, is_optional) = self.gen_ts_field_type(f);
+      has_optional |= is_optional;
+      types.push(ty);
+    }
+    if has_optional {
+      types.push("undefined".to_owned());
+    }
+    let mut union: Vec<String> = vec![];
+    for ty in types {
+      // `string | null` is merged member by member, function types need parentheses in a union
+      let members = if ty.contains("=>") {
+        vec![format!("({})", ty)]
+      } else if ty.contains(['<', '(', '{', '[']) {
+        vec![ty]
+      } else {
+        ty.split(" | ").map(|member| member.to_owned()).collect()
+      };
+      for member in members {
+        if !union.contains(&member) {
+          union.push(member);
+        }
+      }
+    }
+    union.join(" | ")
+  }
+
   fn gen_ts_class(&self) -> String {
     let mut ctor_args = vec![];
     let def = self
@@ -90,11 +181,17 @@ impl NapiStruct {
           field_str.push_str("readonly ")
         }
 
-        let (arg, is_optional) = ty_to_ts_type(&f.ty, false);
-        let arg = f.ts_type.as_ref().map(|ty| ty.to_string()).unwrap_or(arg);
-
-        let sep = if is_optional { "?" } else { "" };
-        let arg = format!("{}{}: {}", &f.js_name, sep, arg);
+        let arg = if f.index_signature {
+          let arg = f
+            .ts_type
+            .clone()
+            .unwrap_or_else(|| self.gen_ts_index_signature_type(f));
+          format!("[key: string]: {}", arg)
+        } else {
+          let (arg, is_optional) = self.gen_ts_field_type(f);
+          let sep = if is_optional { "?" } else { "" };
+          format!("{}{}: {}", &f.js_name, sep, arg)
+        };
         if self.kind == NapiStructKind::Constructor {
           ctor_args.push(arg.clone());
         }
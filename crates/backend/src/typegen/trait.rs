@@ -0,0 +1,62 @@
+use convert_case::{Case, Casing};
+
+use super::r#fn::{FnArg, FnArgList};
+use super::{add_alias, ty_to_ts_type, ToTypeDef, TypeDef};
+use crate::{js_doc_from_comments, NapiTrait, NapiTraitMethod};
+
+impl ToTypeDef for NapiTrait {
+  fn to_type_def(&self) -> Option<TypeDef> {
+    add_alias(self.name.to_string(), self.js_name.to_string());
+
+    if self.skip_typescript {
+      return None;
+    }
+
+    Some(TypeDef {
+      kind: "interface".to_owned(),
+      name: self.js_name.to_owned(),
+      original_name: Some(self.name.to_string()),
+      def: self
+        .methods
+        .iter()
+        .map(|method| method.gen_ts_method())
+        .collect::<Vec<_>>()
+        .join("\\n"),
+      js_mod: self.js_mod.to_owned(),
+      js_doc: js_doc_from_comments(&self.comments),
+      extends: None,
+      ts_module: self.ts_module.to_owned(),
+      generics: None,
+    })
+  }
+}
+
+impl NapiTraitMethod {
+  fn gen_ts_method(&self) -> String {
+    let args = self
+      .args
+      .iter()
+      .map(|(arg, ty)| {
+        let (ts_type, is_optional) = ty_to_ts_type(ty, false);
+        FnArg {
+          arg: arg.to_string().to_case(Case::Camel),
+          ts_type,
+          is_optional,
+          is_rest: false,
+        }
+      })
+      .collect::<FnArgList>();
+    let ret = match &self.ret {
+      syn::Type::Tuple(tuple) if tuple.elems.is_empty() => "void".to_owned(),
+      ty => ty_to_ts_type(ty, true).0,
+    };
+
+    format!(
+      "{}{}({}): {}",
+      js_doc_from_comments(&self.comments),
+      self.js_name,
+      args,
+      ret
+    )
+  }
+}
@@ -0,0 +1,207 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const DEFAULT_DIST_URL: &str = "https://electronjs.org/headers";
+
+/// Configure the build of an addon loaded by Electron, see [`Electron::setup`].
+///
+/// ```no_run
+/// let headers = napi_build::Electron::new("28.0.0").setup();
+/// println!("cargo:include={}", headers.include_dir.display());
+/// ```
+pub struct Electron {
+  version: String,
+  win_delay_load_hook: bool,
+}
+
+/// The Node.js headers of an Electron version, to compile C/C++ code of the addon, e.g. with the `cc` crate.
+pub struct ElectronHeaders {
+  /// Directory of `node.h`, `node_api.h`, `v8.h` and `uv.h`
+  pub include_dir: PathBuf,
+  /// Preprocessor definitions Electron's Node.js and V8 are built with, the layout of V8 objects depends on them
+  pub defines: Vec<(&'static str, Option<&'static str>)>,
+}
+
+impl Electron {
+  /// `version` of Electron without the `v` prefix, e.g. `"28.0.0"`
+  pub fn new(version: impl Into<String>) -> Self {
+    Electron {
+      version: version.into().trim_start_matches('v').to_owned(),
+      win_delay_load_hook: true,
+    }
+  }
+
+  /// Link a delay-load hook on Windows with the MSVC toolchain, enabled by default.
+  ///
+  /// `node.lib` of Electron imports the Node.js symbols from `node.exe`,
+  /// the hook resolves them from the host process instead, e.g. `electron.exe` or a renamed app executable.
+  pub fn win_delay_load_hook(mut self, enabled: bool) -> Self {
+    self.win_delay_load_hook = enabled;
+    self
+  }
+
+  /// Emit the link arguments of [`setup`](crate::setup) and locate the Node.js headers of Electron.
+  ///
+  /// The headers are taken from `ELECTRON_HEADERS_DIR` if it's set, then from the `~/.electron-gyp` cache of `node-gyp`,
+  /// otherwise they're downloaded from `ELECTRON_DIST_URL`, which defaults to `https://electronjs.org/headers`.
+  /// On Windows `node.lib` is linked too, the Node-API bindings of `napi-sys` don't need it, but C/C++ code does.
+  pub fn setup(self) -> ElectronHeaders {
+    crate::setup();
+    println!("cargo:rerun-if-env-changed=ELECTRON_HEADERS_DIR");
+    println!("cargo:rerun-if-env-changed=ELECTRON_DIST_URL");
+    let dir = self.headers_dir();
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("windows") {
+      self.link_node_lib(&dir);
+    }
+    ElectronHeaders {
+      include_dir: dir.join("include").join("node"),
+      defines: defines(&dir),
+    }
+  }
+
+  fn dist_url(&self) -> String {
+    let dist_url = env::var("ELECTRON_DIST_URL").unwrap_or_else(|_| DEFAULT_DIST_URL.to_owned());
+    format!("{}/v{}", dist_url.trim_end_matches('/'), self.version)
+  }
+
+  /// Same layout as the `node-gyp` cache: `include/node/*.h` and `<arch>/node.lib`
+  fn headers_dir(&self) -> PathBuf {
+    if let Ok(dir) = env::var("ELECTRON_HEADERS_DIR") {
+      return PathBuf::from(dir);
+    }
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+    if let Some(home) = home {
+      let dir = Path::new(&home).join(".electron-gyp").join(&self.version);
+      if dir.join("include").join("node").join("node_api.h").exists() {
+        return dir;
+      }
+    }
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+    let dir = out_dir.join(format!("electron-v{}", self.version));
+    if !dir.join("include").join("node").join("node_api.h").exists() {
+      fs::create_dir_all(&dir).expect("Failed to create the Electron headers directory");
+      let tarball = dir.join("headers.tar.gz");
+      download(
+        &format!("{}/node-v{}-headers.tar.gz", self.dist_url(), self.version),
+        &tarball,
+      );
+      // the tarball holds `node_headers/include/node`
+      run(
+        Command::new("tar")
+          .arg("-xzf")
+          .arg(&tarball)
+          .arg("--strip-components=1")
+          .arg("-C")
+          .arg(&dir),
+      );
+      fs::remove_file(&tarball).ok();
+    }
+    dir
+  }
+
+  fn link_node_lib(&self, dir: &Path) {
+    let (gyp_arch, dist_arch) = match env::var("CARGO_CFG_TARGET_ARCH").as_deref() {
+      Ok("x86") => ("ia32", "win-x86"),
+      Ok("aarch64") => ("arm64", "win-arm64"),
+      _ => ("x64", "win-x64"),
+    };
+    let lib_dir = dir.join(gyp_arch);
+    if !lib_dir.join("node.lib").exists() {
+      fs::create_dir_all(&lib_dir).expect("Failed to create the node.lib directory");
+      download(
+        &format!("{}/{}/node.lib", self.dist_url(), dist_arch),
+        &lib_dir.join("node.lib"),
+      );
+    }
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    println!("cargo:rustc-link-lib=dylib=node");
+    if self.win_delay_load_hook && env::var("CARGO_CFG_TARGET_ENV").as_deref() == Ok("msvc") {
+      win_delay_load_hook();
+    }
+  }
+}
+
+/// Delay loading `node.exe` and link the hook which hands out the host process instead
+fn win_delay_load_hook() {
+  let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+  let source = out_dir.join("win_delay_load_hook.rs");
+  let object = out_dir.join("win_delay_load_hook.obj");
+  fs::write(&source, include_str!("win_delay_load_hook.rs"))
+    .expect("Failed to write the delay-load hook");
+  // compiled on its own so the hook is linked even though nothing references it,
+  // `no_std` keeps a second copy of `std` out of the addon
+  let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+  run(
+    Command::new(rustc)
+      .args(["--crate-type", "lib", "--emit", "obj", "--edition", "2021"])
+      .args(["-C", "opt-level=2", "-C", "panic=abort"])
+      .arg("--target")
+      .arg(env::var("TARGET").expect("TARGET is not set"))
+      .arg("-o")
+      .arg(&object)
+      .arg(&source),
+  );
+  println!("cargo:rustc-cdylib-link-arg={}", object.display());
+  println!("cargo:rustc-cdylib-link-arg=/DELAYLOAD:node.exe");
+  println!("cargo:rustc-link-lib=dylib=delayimp");
+}
+
+/// The defines `common.gypi` of Node.js derives from the `config.gypi` Electron ships with its headers
+fn defines(dir: &Path) -> Vec<(&'static str, Option<&'static str>)> {
+  let mut defines = vec![
+    ("BUILDING_NODE_EXTENSION", None),
+    ("USING_UV_SHARED", Some("1")),
+    ("USING_V8_SHARED", Some("1")),
+    ("V8_DEPRECATION_WARNINGS", Some("1")),
+  ];
+  let config =
+    fs::read_to_string(dir.join("include").join("node").join("config.gypi")).unwrap_or_default();
+  let pointer_compression = gypi_flag(&config, "v8_enable_pointer_compression");
+  if pointer_compression {
+    defines.push(("V8_COMPRESS_POINTERS", None));
+    defines.push(("V8_COMPRESS_POINTERS_IN_ISOLATE_CAGE", None));
+  }
+  if pointer_compression || gypi_flag(&config, "v8_enable_31bit_smis_on_64bit_arch") {
+    defines.push(("V8_31BIT_SMIS_ON_64BIT_ARCH", None));
+  }
+  if gypi_flag(&config, "v8_enable_sandbox") {
+    defines.push(("V8_ENABLE_SANDBOX", None));
+  }
+  defines
+}
+
+/// Whether `'name': 1` is set in `config`
+fn gypi_flag(config: &str, name: &str) -> bool {
+  let key = format!("'{}':", name);
+  config
+    .find(&key)
+    .map(|start| config[start + key.len()..].trim_start().starts_with('1'))
+    .unwrap_or(false)
+}
+
+fn download(url: &str, to: &Path) {
+  // `curl` ships with Windows 10 and later, macOS and most Linux distributions
+  run(
+    Command::new("curl")
+      .args([
+        "--silent",
+        "--show-error",
+        "--fail",
+        "--location",
+        "--output",
+      ])
+      .arg(to)
+      .arg(url),
+  );
+}
+
+fn run(command: &mut Command) {
+  let status = command
+    .status()
+    .unwrap_or_else(|e| panic!("Failed to run {:?}: {}", command, e));
+  if !status.success() {
+    panic!("{:?} failed with {}", command, status);
+  }
+}
@@ -1,11 +1,36 @@
+mod electron;
+mod linux;
 mod macos;
-mod windows;
+mod symbols;
+mod wasi;
 
+pub use electron::{Electron, ElectronHeaders};
+
+/// Emit the link arguments every Node.js addon needs for the target platform, so no `.cargo/config.toml` is required.
 pub fn setup() {
   println!("cargo:rerun-if-env-changed=DEBUG_GENERATED_CODE");
+  let target_family = std::env::var("CARGO_CFG_TARGET_FAMILY").unwrap_or_default();
+  if target_family.split(',').any(|family| family == "wasm") {
+    wasi::setup();
+    return;
+  }
   match std::env::var("CARGO_CFG_TARGET_OS").as_deref() {
     Ok("macos") => macos::setup(),
-    Ok("windows") => windows::setup(),
+    Ok("linux") | Ok("android") | Ok("freebsd") => linux::setup(),
+    // Windows needs no import library, Node-API symbols are resolved from the host process at runtime by `napi-sys`
     _ => {}
   }
 }
+
+/// Hide every symbol of the addon except `napi_register_module_v1` on macOS, with an exported symbols list.
+///
+/// On Linux, Android and FreeBSD rustc already links the cdylib with a version script which only exports the `#[no_mangle]` symbols.
+/// It shrinks the binary and prevents symbol collisions between addons loaded into the same process, call it after [`setup`]:
+///
+/// ```no_run
+/// napi_build::setup();
+/// napi_build::hide_symbols();
+/// ```
+pub fn hide_symbols() {
+  symbols::hide_symbols();
+}
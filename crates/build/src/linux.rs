@@ -0,0 +1,5 @@
+pub fn setup() {
+  // Node-API symbols are provided by the host executable.
+  // Some toolchains, e.g. the Android NDK, link shared libraries with `--no-undefined` by default.
+  println!("cargo:rustc-cdylib-link-arg=-Wl,-z,undefs");
+}
@@ -1,5 +1,4 @@
 pub fn setup() {
-  println!("cargo:rustc-cdylib-link-arg=-Wl");
-  println!("cargo:rustc-cdylib-link-arg=-undefined");
-  println!("cargo:rustc-cdylib-link-arg=dynamic_lookup");
+  // Node-API symbols are provided by the host executable, leave them to be resolved when the addon is loaded
+  println!("cargo:rustc-cdylib-link-arg=-Wl,-undefined,dynamic_lookup");
 }
@@ -0,0 +1,22 @@
+use std::env;
+use std::fs::write;
+use std::path::PathBuf;
+
+pub fn hide_symbols() {
+  let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+  match env::var("CARGO_CFG_TARGET_OS").as_deref() {
+    Ok("macos") | Ok("ios") => {
+      let exported_symbols_list = out_dir.join("napi-exported-symbols.txt");
+      write(&exported_symbols_list, "_napi_register_module_v1\n")
+        .expect("Failed to write exported symbols list");
+      println!(
+        "cargo:rustc-cdylib-link-arg=-Wl,-exported_symbols_list,{}",
+        exported_symbols_list.display()
+      );
+    }
+    // rustc links ELF cdylibs with its own version script which only exports the `#[no_mangle]` symbols,
+    // a second one would conflict with it. DLLs only export what is marked with `dllexport`,
+    // and WebAssembly exports are listed explicitly
+    _ => {}
+  }
+}
@@ -0,0 +1,18 @@
+use std::env;
+
+pub fn setup() {
+  // Directory of the emnapi static libraries, e.g. `node_modules/emnapi/lib/wasm32-wasi-threads`
+  println!("cargo:rerun-if-env-changed=EMNAPI_LINK_DIR");
+  if let Ok(link_dir) = env::var("EMNAPI_LINK_DIR") {
+    println!("cargo:rustc-link-search=native={}", link_dir);
+    println!("cargo:rustc-link-lib=static=emnapi-basic-mt");
+  }
+  // Node-API functions are imported from the `napi` module which is provided by the emnapi runtime
+  println!("cargo:rustc-cdylib-link-arg=--import-undefined");
+  println!("cargo:rustc-cdylib-link-arg=--import-memory");
+  println!("cargo:rustc-cdylib-link-arg=--export-table");
+  println!("cargo:rustc-cdylib-link-arg=--export=napi_register_wasm_v1");
+  println!("cargo:rustc-cdylib-link-arg=--export=malloc");
+  println!("cargo:rustc-cdylib-link-arg=--export=free");
+  println!("cargo:rustc-cdylib-link-arg=--max-memory=4294967296");
+}
@@ -0,0 +1,46 @@
+// Compiled and linked by `Electron::setup` on Windows, like `win_delay_load_hook.cc` of node-gyp.
+// `node.lib` of Electron imports from `node.exe`, which is resolved to the host process instead.
+#![no_std]
+
+use core::ffi::{c_char, c_void};
+use core::ptr;
+
+const DLI_NOTE_PRE_LOAD_LIBRARY: u32 = 1;
+
+#[repr(C)]
+pub struct DelayLoadInfo {
+  cb: u32,
+  pidd: *const c_void,
+  ppfn: *mut c_void,
+  sz_dll: *const c_char,
+}
+
+extern "system" {
+  fn GetModuleHandleW(module_name: *const u16) -> *mut c_void;
+}
+
+unsafe extern "system" fn load_exe_hook(event: u32, info: *const DelayLoadInfo) -> *mut c_void {
+  if event != DLI_NOTE_PRE_LOAD_LIBRARY {
+    return ptr::null_mut();
+  }
+  let mut dll = unsafe { (*info).sz_dll } as *const u8;
+  for expected in b"node.exe\0" {
+    let byte = unsafe { *dll };
+    if !byte.eq_ignore_ascii_case(expected) {
+      return ptr::null_mut();
+    }
+    if byte == 0 {
+      break;
+    }
+    dll = unsafe { dll.add(1) };
+  }
+  unsafe { GetModuleHandleW(ptr::null()) }
+}
+
+#[allow(non_upper_case_globals)]
+#[no_mangle]
+#[used]
+pub static __pfnDliNotifyHook2: unsafe extern "system" fn(
+  u32,
+  *const DelayLoadInfo,
+) -> *mut c_void = load_exe_hook;
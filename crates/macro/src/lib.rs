@@ -84,6 +84,7 @@ fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStream> {
             syn::Item::Enum(ref mut enum_) => &mut enum_.attrs,
             syn::Item::Const(ref mut const_) => &mut const_.attrs,
             syn::Item::Impl(ref mut impl_) => &mut impl_.attrs,
+            syn::Item::Trait(ref mut trait_) => &mut trait_.attrs,
             syn::Item::Mod(mod_) => {
               let mod_in_mod = mod_
                 .attrs
@@ -102,11 +103,17 @@ fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStream> {
             _ => &mut empty_attrs,
           },
         ) {
+          #[cfg_attr(not(feature = "type-def"), allow(unused_variables))]
+          let ts_extra = get_ts_extra(&item_opts);
           let napi = item.parse_napi(&mut tokens, item_opts)?;
           napi.try_to_tokens(&mut tokens)?;
           #[cfg(feature = "type-def")]
           if let Ok(type_def_file) = env::var("TYPE_DEF_TMP_PATH") {
-            if let Err(e) = output_type_def(type_def_file, napi.to_type_def()) {
+            let type_defs = napi
+              .to_type_def()
+              .into_iter()
+              .chain(ts_extra.map(|ts_extra| ts_extra.into_type_def(&napi)));
+            if let Err(e) = output_type_def(type_def_file, type_defs) {
               println!("Failed to write type def file: {:?}", e);
             };
           }
@@ -127,12 +134,18 @@ fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStream> {
     let mod_tokens = quote! { #(#js_mod_attrs)* #visible mod #mod_name { #tokens } };
     Ok(mod_tokens)
   } else {
+    #[cfg_attr(not(feature = "type-def"), allow(unused_variables))]
+    let ts_extra = get_ts_extra(&opts);
     let napi = item.parse_napi(&mut tokens, opts)?;
     napi.try_to_tokens(&mut tokens)?;
 
     #[cfg(feature = "type-def")]
     if let Ok(type_def_file) = env::var("TYPE_DEF_TMP_PATH") {
-      if let Err(e) = output_type_def(type_def_file, napi.to_type_def()) {
+      let type_defs = napi
+        .to_type_def()
+        .into_iter()
+        .chain(ts_extra.map(|ts_extra| ts_extra.into_type_def(&napi)));
+      if let Err(e) = output_type_def(type_def_file, type_defs) {
         println!("Failed to write type def file: {:?}", e);
       };
     }
@@ -141,21 +154,58 @@ fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStream> {
 }
 
 #[cfg(all(feature = "type-def", not(feature = "noop")))]
-fn output_type_def(type_def_file: String, type_def: Option<TypeDef>) -> IOResult<()> {
-  if type_def.is_some() {
+fn output_type_def(
+  type_def_file: String,
+  type_defs: impl IntoIterator<Item = TypeDef>,
+) -> IOResult<()> {
+  let mut type_defs = type_defs.into_iter().peekable();
+  if type_defs.peek().is_some() {
     let file = fs::OpenOptions::new()
       .append(true)
       .create(true)
       .open(type_def_file)?;
 
     let mut writer = BufWriter::<fs::File>::new(file);
-    writer.write_all(type_def.unwrap().to_string().as_bytes())?;
-    writer.write_all("\n".as_bytes())
+    for type_def in type_defs {
+      writer.write_all(type_def.to_string().as_bytes())?;
+      writer.write_all("\n".as_bytes())?;
+    }
+    writer.flush()
   } else {
     IOResult::Ok(())
   }
 }
 
+/// Hand-written TypeScript of `#[napi(ts_extra = "...")]`, emitted as is next to the generated definitions
+#[cfg(not(feature = "noop"))]
+#[cfg_attr(not(feature = "type-def"), allow(dead_code))]
+struct TsExtra {
+  def: String,
+  js_mod: Option<String>,
+}
+
+#[cfg(all(feature = "type-def", not(feature = "noop")))]
+impl TsExtra {
+  /// Named after the item, so it's sorted together with the item's own definitions
+  fn into_type_def(self, napi: &napi_derive_backend::Napi) -> TypeDef {
+    TypeDef {
+      kind: "extra".to_owned(),
+      name: napi.js_name().to_owned(),
+      def: self.def,
+      js_mod: self.js_mod,
+      ..Default::default()
+    }
+  }
+}
+
+#[cfg(not(feature = "noop"))]
+fn get_ts_extra(opts: &BindgenAttrs) -> Option<TsExtra> {
+  opts.ts_extra().map(|(def, _)| TsExtra {
+    def: def.to_owned(),
+    js_mod: opts.namespace().map(|(js_mod, _)| js_mod.to_owned()),
+  })
+}
+
 #[cfg(feature = "compat-mode")]
 #[proc_macro_attribute]
 pub fn contextless_function(_attr: RawStream, input: RawStream) -> RawStream {
@@ -267,19 +317,31 @@ pub fn module_exports(_attr: RawStream, input: RawStream) -> RawStream {
     panic!("Arguments length of #[module_exports] function must be 1 or 2");
   };
 
+  let register_body = quote! {
+    unsafe fn register(raw_env: napi::sys::napi_env, raw_exports: napi::sys::napi_value) -> napi::Result<()> {
+      use napi::{Env, JsObject, NapiValue};
+
+      let env = Env::from_raw(raw_env);
+      let exports = JsObject::from_raw_unchecked(raw_env, raw_exports);
+
+      #call_expr
+    }
+
+    napi::bindgen_prelude::register_module_exports(register)
+  };
+
+  // `ctor` does not support WebAssembly, the loader calls the exported function there
   let register = quote! {
+    #[cfg(not(target_family = "wasm"))]
     #[napi::bindgen_prelude::ctor]
     fn __napi__explicit_module_register() {
-      unsafe fn register(raw_env: napi::sys::napi_env, raw_exports: napi::sys::napi_value) -> napi::Result<()> {
-        use napi::{Env, JsObject, NapiValue};
-
-        let env = Env::from_raw(raw_env);
-        let exports = JsObject::from_raw_unchecked(raw_env, raw_exports);
-
-        #call_expr
-      }
+      #register_body
+    }
 
-      napi::bindgen_prelude::register_module_exports(register)
+    #[cfg(target_family = "wasm")]
+    #[export_name = "__napi_register__explicit_module_register"]
+    extern "C" fn __napi__explicit_module_register() {
+      #register_body
     }
   };
 
@@ -50,16 +50,37 @@ macro_rules! attrgen {
       (readonly, Readonly(Span)),
       (skip, Skip(Span)),
       (strict, Strict(Span)),
+      (catch_unwind, CatchUnwind(Span)),
+      (module_init, ModuleInit(Span)),
       (object, Object(Span)),
+      (interface, Interface(Span)),
+      (error, Error(Span)),
+      (code, Code(Span, String, Span)),
+      (class, Class(Span, String, Span)),
+      (extends, Extends(Span, syn::Expr)),
       (namespace, Namespace(Span, String, Span)),
+      (ts_module, TsModule(Span, String, Span)),
       (ts_args_type, TsArgsType(Span, String, Span)),
       (ts_return_type, TsReturnType(Span, String, Span)),
       (ts_type, TsType(Span, String, Span)),
+      (default, Default(Span, syn::Expr)),
+      (variadic, Variadic(Span)),
+      (js_map, JsMap(Span)),
+      (index_signature, IndexSignature(Span)),
+      (aggregate_errors, AggregateErrors(Span)),
+      (strict_numbers, StrictNumbers(Span)),
+      (none, NoneValue(Span, String, Span)),
+      (large_int, LargeInt(Span, String, Span)),
+      (non_finite, NonFinite(Span, String, Span)),
+      (napi_version, NapiVersion(Span, u32)),
+      (string_enum, StringEnum(Span, Option<(String, Span)>)),
+      (rename_all, RenameAll(Span, String, Span)),
 
       // impl later
       // (inspectable, Inspectable(Span)),
       // (typescript_custom_section, TypescriptCustomSection(Span)),
       (skip_typescript, SkipTypescript(Span)),
+      (ts_extra, TsExtra(Span, String, Span)),
       // (getter_with_clone, GetterWithClone(Span)),
 
       // For testing purposes only.
@@ -164,7 +185,7 @@ macro_rules! methods {
 impl BindgenAttrs {
   /// Find and parse the napi attributes.
   pub fn find(attrs: &mut Vec<syn::Attribute>) -> Result<BindgenAttrs, Diagnostic> {
-    let mut ret = BindgenAttrs::default();
+    let mut ret = <BindgenAttrs as Default>::default();
     loop {
       let napi_attr = attrs
         .iter()
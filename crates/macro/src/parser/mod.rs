@@ -9,11 +9,12 @@ use attrs::{BindgenAttr, BindgenAttrs};
 
 use convert_case::{Case, Casing};
 use napi_derive_backend::{
-  BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, Napi, NapiConst, NapiEnum,
-  NapiEnumVariant, NapiFn, NapiFnArgKind, NapiImpl, NapiItem, NapiStruct, NapiStructField,
-  NapiStructKind,
+  BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, LargeIntMode, Napi, NapiClassParent,
+  NapiConst, NapiEnum, NapiEnumVariant, NapiEnumVariantField, NapiFn, NapiFnArg, NapiFnArgKind,
+  NapiImpl, NapiItem, NapiStruct, NapiStructField, NapiStructKind, NapiTrait, NapiTraitMethod,
+  NonFiniteMode, NoneValue,
 };
-use proc_macro2::{Ident, TokenStream, TokenTree};
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::ToTokens;
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream, Result as SynResult};
@@ -34,7 +35,7 @@ impl Parse for AnyIdent {
 
 impl Parse for BindgenAttrs {
   fn parse(input: ParseStream) -> SynResult<Self> {
-    let mut attrs = BindgenAttrs::default();
+    let mut attrs = <BindgenAttrs as Default>::default();
     if input.is_empty() {
       return Ok(attrs);
     }
@@ -95,6 +96,21 @@ impl Parse for BindgenAttr {
             return Ok(BindgenAttr::$variant(attr_span, input.parse()?));
         });
 
+        (@parser $variant:ident(Span, u32)) => ({
+          input.parse::<Token![=]>()?;
+          let val = input.parse::<syn::LitInt>()?.base10_parse::<u32>()?;
+          return Ok(BindgenAttr::$variant(attr_span, val))
+        });
+
+        (@parser $variant:ident(Span, Option<(String, Span)>)) => ({
+          if input.parse::<Token![=]>().is_ok() {
+            let str = input.parse::<syn::LitStr>()?;
+            return Ok(BindgenAttr::$variant(attr_span, Some((str.value(), str.span()))))
+          } else {
+            return Ok(BindgenAttr::$variant(attr_span, None));
+          }
+        });
+
         (@parser $variant:ident(Span, String, Span)) => ({
           input.parse::<Token![=]>()?;
           let (val, span) = match input.parse::<syn::LitStr>() {
@@ -159,7 +175,7 @@ fn get_ty(mut ty: &syn::Type) -> &syn::Type {
   ty
 }
 
-fn replace_self(ty: syn::Type, self_ty: Option<&Ident>) -> syn::Type {
+fn replace_self(ty: syn::Type, self_ty: Option<&Ident>, self_generics: &[Ident]) -> syn::Type {
   let self_ty = match self_ty {
     Some(i) => i,
     None => return ty,
@@ -169,7 +185,11 @@ fn replace_self(ty: syn::Type, self_ty: Option<&Ident>) -> syn::Type {
     other => return other.clone(),
   };
   let new_path = if path.segments.len() == 1 && path.segments[0].ident == "Self" {
-    self_ty.clone().into()
+    if self_generics.is_empty() {
+      self_ty.clone().into()
+    } else {
+      syn::parse_quote! { #self_ty<#(#self_generics),*> }
+    }
   } else {
     path
   };
@@ -196,6 +216,48 @@ fn extract_path_ident(path: &syn::Path) -> BindgenResult<Ident> {
   }
 }
 
+/// Extracts the struct ident of `impl<T> Struct<T>`, along with the type parameters of the generic class
+fn extract_impl_self_ident(
+  path: &syn::Path,
+  generics: &syn::Generics,
+) -> BindgenResult<(Ident, Vec<Ident>)> {
+  let last = match path.segments.last() {
+    Some(last) => last,
+    None => bail_span!(path, "empty idents are not supported"),
+  };
+  let args = match &last.arguments {
+    syn::PathArguments::AngleBracketed(args) => args,
+    _ => return Ok((extract_path_ident(path)?, vec![])),
+  };
+  let type_params = generics
+    .type_params()
+    .map(|param| &param.ident)
+    .collect::<Vec<_>>();
+  let mut self_generics = vec![];
+  for arg in args.args.iter() {
+    let ident = match arg {
+      syn::GenericArgument::Type(syn::Type::Path(syn::TypePath { qself: None, path })) => {
+        path.get_ident()
+      }
+      _ => None,
+    };
+    match ident {
+      Some(ident) if type_params.contains(&ident) => self_generics.push(ident.clone()),
+      _ => bail_span!(
+        arg,
+        "#[napi] impl of a generic class must be generic over all its type parameters"
+      ),
+    }
+  }
+  if self_generics.len() != type_params.len() || generics.lifetimes().next().is_some() {
+    bail_span!(
+      generics,
+      "#[napi] impl of a generic class only supports the type parameters of the class"
+    );
+  }
+  Ok((last.ident.clone(), self_generics))
+}
+
 fn extract_callback_trait_types(
   arguments: &syn::PathArguments,
 ) -> BindgenResult<(Vec<syn::Type>, Option<syn::Type>)> {
@@ -234,6 +296,18 @@ fn extract_callback_trait_types(
   }
 }
 
+/// Interfaces augmenting a module are types only, they can't be exported under a namespace
+fn parse_ts_module(opts: &BindgenAttrs) -> BindgenResult<Option<String>> {
+  match (opts.ts_module(), opts.namespace()) {
+    (Some((_, span)), Some(_)) => Err(Diagnostic::span_error(
+      span,
+      "#[napi(ts_module)] can't be used together with #[napi(namespace)]",
+    )),
+    (Some((ts_module, _)), None) => Ok(Some(ts_module.to_owned())),
+    _ => Ok(None),
+  }
+}
+
 fn extract_result_ty(ty: &syn::Type) -> BindgenResult<Option<syn::Type>> {
   match ty {
     syn::Type::Path(syn::TypePath { qself: None, path }) => {
@@ -261,6 +335,45 @@ fn extract_result_ty(ty: &syn::Type) -> BindgenResult<Option<syn::Type>> {
   }
 }
 
+fn is_vec_ty(ty: &syn::Type) -> bool {
+  is_ty_named(ty, "Vec")
+}
+
+fn is_map_ty(ty: &syn::Type) -> bool {
+  is_ty_named(ty, "HashMap") || is_ty_named(ty, "BTreeMap")
+}
+
+fn is_this_ty(ty: &syn::Type) -> bool {
+  is_ty_named(ty, "This")
+}
+
+/// Whether `This<&mut T>` or `This<&T>` borrows the class instance mutably, `None` for `This<JsObject>` and alike
+fn this_ref_mutability(ty: &syn::Type) -> Option<bool> {
+  match get_ty(ty) {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => {
+      match &path.segments.last()?.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+          syn::GenericArgument::Type(syn::Type::Reference(reference)) => {
+            Some(reference.mutability.is_some())
+          }
+          _ => None,
+        }),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+fn is_ty_named(ty: &syn::Type, name: &str) -> bool {
+  match get_ty(ty) {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => {
+      matches!(path.segments.last(), Some(segment) if segment.ident == name)
+    }
+    _ => false,
+  }
+}
+
 fn get_expr(mut expr: &syn::Expr) -> &syn::Expr {
   while let syn::Expr::Group(g) = expr {
     expr = &g.expr;
@@ -269,6 +382,103 @@ fn get_expr(mut expr: &syn::Expr) -> &syn::Expr {
   expr
 }
 
+/// Map a serde style casing name, e.g. `"camelCase"`, to a [`Case`]
+fn parse_none_value(opts: &BindgenAttrs) -> BindgenResult<Option<NoneValue>> {
+  match opts.none() {
+    Some(("null", _)) => Ok(Some(NoneValue::Null)),
+    Some(("undefined", _)) => Ok(Some(NoneValue::Undefined)),
+    Some((none, span)) => Err(Diagnostic::span_error(
+      span,
+      format!(
+        "unknown value `{}` of #[napi(none)], expected `null` or `undefined`",
+        none
+      ),
+    )),
+    None => Ok(None),
+  }
+}
+
+/// `#[napi(large_int)]`, falling back to the `large-int-*` feature of `napi-derive` for the whole crate
+fn parse_large_int_mode(opts: &BindgenAttrs) -> BindgenResult<Option<LargeIntMode>> {
+  let (mode, span) = match opts.large_int() {
+    Some((mode, span)) => (mode, span),
+    None => return crate_large_int_mode(),
+  };
+  match mode {
+    "checked" => Ok(Some(LargeIntMode::Checked)),
+    "saturate" => Ok(Some(LargeIntMode::Saturate)),
+    "bigint" => Ok(Some(LargeIntMode::BigInt)),
+    _ => Err(Diagnostic::span_error(
+      span,
+      format!(
+        "unknown value `{}` of #[napi(large_int)], expected `checked`, `saturate` or `bigint`",
+        mode
+      ),
+    )),
+  }
+}
+
+/// The `large-int-checked`, `large-int-saturate` or `large-int-bigint` feature
+fn crate_large_int_mode() -> BindgenResult<Option<LargeIntMode>> {
+  let modes = [
+    (cfg!(feature = "large-int-checked"), LargeIntMode::Checked),
+    (cfg!(feature = "large-int-saturate"), LargeIntMode::Saturate),
+    (cfg!(feature = "large-int-bigint"), LargeIntMode::BigInt),
+  ];
+  let mut enabled = modes.iter().filter(|(enabled, _)| *enabled);
+  match (enabled.next(), enabled.next()) {
+    (Some((_, mode)), None) => Ok(Some(*mode)),
+    (None, _) => Ok(None),
+    (Some(_), Some(_)) => Err(Diagnostic::span_error(
+      Span::call_site(),
+      "only one of the `large-int-checked`, `large-int-saturate` and `large-int-bigint` features of `napi-derive` can be enabled".to_owned(),
+    )),
+  }
+}
+
+fn parse_non_finite_mode(opts: &BindgenAttrs) -> BindgenResult<Option<NonFiniteMode>> {
+  match opts.non_finite() {
+    Some(("null", _)) => Ok(Some(NonFiniteMode::Null)),
+    Some(("error", _)) => Ok(Some(NonFiniteMode::Error)),
+    Some((mode, span)) => Err(Diagnostic::span_error(
+      span,
+      format!(
+        "unknown value `{}` of #[napi(non_finite)], expected `null` or `error`",
+        mode
+      ),
+    )),
+    None => Ok(None),
+  }
+}
+
+fn is_option_ty(ty: &syn::Type) -> bool {
+  is_ty_named(ty, "Option")
+}
+
+fn parse_case(case: &str, span: Span) -> BindgenResult<Case> {
+  Ok(match case {
+    "lowercase" => Case::Flat,
+    "UPPERCASE" => Case::UpperFlat,
+    "camelCase" => Case::Camel,
+    "PascalCase" => Case::Pascal,
+    "snake_case" => Case::Snake,
+    "SCREAMING_SNAKE_CASE" => Case::ScreamingSnake,
+    "kebab-case" => Case::Kebab,
+    "SCREAMING-KEBAB-CASE" => Case::Cobol,
+    _ => {
+      return Err(Diagnostic::span_error(
+        span,
+        format!(
+          "unknown case `{}`, expected one of `lowercase`, `UPPERCASE`, `camelCase`, \
+            `PascalCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` or \
+            `SCREAMING-KEBAB-CASE`",
+          case
+        ),
+      ))
+    }
+  })
+}
+
 /// Extract the documentation comments from a Vec of attributes
 fn extract_doc_comments(attrs: &[syn::Attribute]) -> Vec<String> {
   attrs
@@ -448,6 +658,8 @@ fn extract_fn_closure_generics(
           }
         }
       }
+      // lifetimes of borrowed arguments and return values are elided in the generated callback
+      syn::GenericParam::Lifetime(_) => {}
       _ => {
         errors.push(err_span!(param, "unsupported napi generic param for fn"));
       }
@@ -463,6 +675,7 @@ fn napi_fn_from_decl(
   attrs: Vec<Attribute>,
   vis: Visibility,
   parent: Option<&Ident>,
+  parent_generics: &[Ident],
 ) -> BindgenResult<NapiFn> {
   let mut errors = vec![];
 
@@ -475,31 +688,122 @@ fn napi_fn_from_decl(
     ..
   } = sig;
 
+  if let Some(span) = opts.module_init() {
+    if parent.is_some() || asyncness.is_some() {
+      errors.push(Diagnostic::span_error(
+        *span,
+        "#[napi(module_init)] can only be applied to a sync free function",
+      ));
+    }
+  }
+
+  if let (Some(span), Some(_)) = (opts.constructor(), &asyncness) {
+    errors.push(Diagnostic::span_error(
+      *span,
+      "#[napi(constructor)] can not be async, use an async #[napi(factory)] instead",
+    ));
+  }
+
+  // applies to all the `Option<T>` arguments and the returned `Option<T>`
+  let fn_none = parse_none_value(opts)?;
+  let large_int = parse_large_int_mode(opts)?;
+  let non_finite = parse_non_finite_mode(opts)?;
+
   let mut fn_self = None;
   let callback_traits = extract_fn_closure_generics(&generics)?;
+  let type_generics = generics
+    .type_params()
+    .map(|param| param.ident.clone())
+    .filter(|ident| !callback_traits.contains_key(&ident.to_string()))
+    .collect::<Vec<_>>();
 
   let args = inputs
     .into_iter()
     .filter_map(|arg| match arg {
       syn::FnArg::Typed(mut p) => {
+        let arg_opts = match BindgenAttrs::find(&mut p.attrs) {
+          Ok(arg_opts) => arg_opts,
+          Err(e) => {
+            errors.push(e);
+            return None;
+          }
+        };
+        let ts_arg_type = arg_opts.ts_type().map(|(ty, _)| ty.to_owned());
+        let default = arg_opts.default().cloned();
+        let variadic = arg_opts.variadic().is_some();
+        let js_map = arg_opts.js_map().is_some();
         let ty_str = p.ty.to_token_stream().to_string();
-        if let Some(path_arguments) = callback_traits.get(&ty_str) {
+        if default.is_some() && (callback_traits.contains_key(&ty_str) || ty_str.starts_with('&')) {
+          errors.push(err_span!(
+            p.ty,
+            "#[napi(default)] is only supported on arguments of owned types"
+          ));
+          return None;
+        }
+        if variadic && (default.is_some() || !is_vec_ty(&p.ty)) {
+          errors.push(err_span!(
+            p.ty,
+            "#[napi(variadic)] is only supported on arguments of type `Vec<T>` without default value"
+          ));
+          return None;
+        }
+        let none = match parse_none_value(&arg_opts) {
+          Ok(Some(_)) if !is_option_ty(&p.ty) => {
+            errors.push(err_span!(
+              p.ty,
+              "#[napi(none)] is only supported on arguments of type `Option<T>`"
+            ));
+            return None;
+          }
+          Ok(none) => none.or(fn_none).filter(|_| is_option_ty(&p.ty)),
+          Err(e) => {
+            errors.push(e);
+            return None;
+          }
+        };
+        if js_map && (variadic || ty_str.starts_with('&') || !is_map_ty(&p.ty)) {
+          errors.push(err_span!(
+            p.ty,
+            "#[napi(js_map)] is only supported on arguments of type `HashMap<K, V>` or `BTreeMap<K, V>`"
+          ));
+          return None;
+        }
+        let kind = if let Some(path_arguments) = callback_traits.get(&ty_str) {
           match extract_callback_trait_types(path_arguments) {
-            Ok((fn_args, fn_ret)) => Some(NapiFnArgKind::Callback(Box::new(CallbackArg {
+            Ok((fn_args, fn_ret)) => NapiFnArgKind::Callback(Box::new(CallbackArg {
               pat: p.pat,
               args: fn_args,
               ret: fn_ret,
-            }))),
+            })),
             Err(e) => {
               errors.push(e);
-              None
+              return None;
             }
           }
+        } else if is_this_ty(&p.ty) {
+          if default.is_some() || variadic {
+            errors.push(err_span!(
+              p.ty,
+              "#[napi(default)] and #[napi(variadic)] can't be applied to `This`"
+            ));
+            return None;
+          }
+          let ty = replace_self(*p.ty, parent, parent_generics);
+          p.ty = Box::new(ty);
+          NapiFnArgKind::This(Box::new(p))
         } else {
-          let ty = replace_self(*p.ty, parent);
+          let ty = replace_self(*p.ty, parent, parent_generics);
           p.ty = Box::new(ty);
-          Some(NapiFnArgKind::PatType(Box::new(p)))
-        }
+          NapiFnArgKind::PatType(Box::new(p))
+        };
+        Some(NapiFnArg {
+          kind,
+          ts_arg_type,
+          default,
+          variadic,
+          js_map,
+          none,
+        })
       }
       syn::FnArg::Receiver(r) => {
         if parent.is_some() {
@@ -522,6 +826,48 @@ fn napi_fn_from_decl(
     })
     .collect::<Vec<_>>();
 
+  // `This<&mut T>` borrows the same class instance as the receiver
+  if let Some(fn_self) = &fn_self {
+    for arg in &args {
+      if let NapiFnArgKind::This(this) = &arg.kind {
+        match this_ref_mutability(&this.ty) {
+          Some(true) => errors.push(err_span!(
+            this.ty,
+            "`This<&mut T>` borrows the same instance as the `&self` or `&mut self` receiver, use the receiver instead"
+          )),
+          Some(false) if matches!(fn_self, FnSelf::MutRef) => errors.push(err_span!(
+            this.ty,
+            "`This<&T>` borrows the same instance as the `&mut self` receiver, use the receiver instead"
+          )),
+          _ => {}
+        }
+      }
+    }
+  }
+
+  if let Some(pos) = args.iter().position(|arg| arg.variadic) {
+    if pos != args.len() - 1 {
+      errors.push(err_span!(
+        ident,
+        "#[napi(variadic)] can only be applied to the last argument"
+      ));
+    }
+  }
+
+  if opts.module_init().is_some()
+    && args.iter().any(|arg| {
+      matches!(
+        arg.kind,
+        NapiFnArgKind::Callback(_) | NapiFnArgKind::This(_)
+      )
+    })
+  {
+    errors.push(err_span!(
+      ident,
+      "#[napi(module_init)] functions only accept `Env` and the exports object as arguments"
+    ));
+  }
+
   let (ret, is_ret_result) = match output {
     syn::ReturnType::Default => (None, false),
     syn::ReturnType::Type(_, ty) => {
@@ -529,11 +875,23 @@ fn napi_fn_from_decl(
       if result_ty.is_some() {
         (result_ty, true)
       } else {
-        (Some(replace_self(*ty, parent)), false)
+        (Some(replace_self(*ty, parent, parent_generics)), false)
       }
     }
   };
 
+  if let Some(span) = opts.js_map() {
+    let is_map_ret = matches!(&ret, Some(ret) if is_map_ty(ret));
+    if !is_map_ret || matches!(fn_kind(opts), FnKind::Constructor | FnKind::Factory) {
+      errors.push(Diagnostic::span_error(
+        *span,
+        "#[napi(js_map)] is only supported on functions returning `HashMap<K, V>` or `BTreeMap<K, V>`",
+      ));
+    }
+  }
+
+  let ret_none = fn_none.filter(|_| matches!(&ret, Some(ret) if is_option_ty(ret)));
+
   Diagnostic::from_vec(errors).map(|_| {
     let js_name = if let Some(prop_name) = opts.getter() {
       if let Some(ident) = prop_name {
@@ -580,6 +938,16 @@ fn napi_fn_from_decl(
       ts_args_type: opts.ts_args_type().map(|(m, _)| m.to_owned()),
       ts_return_type: opts.ts_return_type().map(|(m, _)| m.to_owned()),
       skip_typescript: opts.skip_typescript().is_some(),
+      napi_version: opts.napi_version().copied(),
+      catch_unwind: opts.catch_unwind().is_some(),
+      module_init: opts.module_init().is_some(),
+      generics: type_generics,
+      parent_generics: parent_generics.to_vec(),
+      js_map: opts.js_map().is_some(),
+      none: ret_none,
+      large_int,
+      strict_numbers: opts.strict_numbers().is_some(),
+      non_finite,
     }
   })
 }
@@ -592,9 +960,10 @@ impl ParseNapi for syn::Item {
       syn::Item::Impl(i) => i.parse_napi(tokens, opts),
       syn::Item::Enum(e) => e.parse_napi(tokens, opts),
       syn::Item::Const(c) => c.parse_napi(tokens, opts),
+      syn::Item::Trait(t) => t.parse_napi(tokens, opts),
       _ => bail_span!(
         self,
-        "#[napi] can only be applied to a function, struct, enum, const, mod or impl."
+        "#[napi] can only be applied to a function, struct, enum, const, mod, impl or trait."
       ),
     }
   }
@@ -608,20 +977,20 @@ impl ParseNapi for syn::ItemFn {
         "#[napi] can't be applied to a function with #[napi(ts_type)]"
       );
     }
+    // `#[napi]` attributes on arguments will be removed after converted to ast
+    let napi = self.convert_to_ast(opts);
     self.to_tokens(tokens);
-    self.convert_to_ast(opts)
+
+    napi
   }
 }
 impl ParseNapi for syn::ItemStruct {
   fn parse_napi(&mut self, tokens: &mut TokenStream, opts: BindgenAttrs) -> BindgenResult<Napi> {
-    if opts.ts_args_type().is_some()
-      || opts.ts_return_type().is_some()
-      || opts.skip_typescript().is_some()
-      || opts.ts_type().is_some()
+    if opts.ts_args_type().is_some() || opts.ts_return_type().is_some() || opts.ts_type().is_some()
     {
       bail_span!(
         self,
-        "#[napi] can't be applied to a struct with #[napi(ts_args_type)], #[napi(ts_return_type)], #[napi(skip_typescript)] or #[napi(ts_type)]"
+        "#[napi] can't be applied to a struct with #[napi(ts_args_type)], #[napi(ts_return_type)] or #[napi(ts_type)]"
       );
     }
     let napi = self.convert_to_ast(opts);
@@ -632,14 +1001,11 @@ impl ParseNapi for syn::ItemStruct {
 }
 impl ParseNapi for syn::ItemImpl {
   fn parse_napi(&mut self, tokens: &mut TokenStream, opts: BindgenAttrs) -> BindgenResult<Napi> {
-    if opts.ts_args_type().is_some()
-      || opts.ts_return_type().is_some()
-      || opts.skip_typescript().is_some()
-      || opts.ts_type().is_some()
+    if opts.ts_args_type().is_some() || opts.ts_return_type().is_some() || opts.ts_type().is_some()
     {
       bail_span!(
         self,
-        "#[napi] can't be applied to impl with #[napi(ts_args_type)], #[napi(ts_return_type)], #[napi(skip_typescript)] or #[napi(ts_type)]"
+        "#[napi] can't be applied to impl with #[napi(ts_args_type)], #[napi(ts_return_type)] or #[napi(ts_type)]"
       );
     }
     // #[napi] macro will be remove from impl items after converted to ast
@@ -664,6 +1030,20 @@ impl ParseNapi for syn::ItemEnum {
     napi
   }
 }
+impl ParseNapi for syn::ItemTrait {
+  fn parse_napi(&mut self, tokens: &mut TokenStream, opts: BindgenAttrs) -> BindgenResult<Napi> {
+    if opts.interface().is_none() {
+      bail_span!(
+        self,
+        "#[napi] can only be applied to a trait with #[napi(interface)]"
+      );
+    }
+    // `#[napi]` attributes on methods will be removed after converted to ast
+    let napi = self.convert_to_ast(opts);
+    self.to_tokens(tokens);
+    napi
+  }
+}
 impl ParseNapi for syn::ItemConst {
   fn parse_napi(&mut self, tokens: &mut TokenStream, opts: BindgenAttrs) -> BindgenResult<Napi> {
     if opts.ts_args_type().is_some() || opts.ts_return_type().is_some() || opts.ts_type().is_some()
@@ -679,6 +1059,15 @@ impl ParseNapi for syn::ItemConst {
   }
 }
 
+/// Strip the `#[napi(..)]` attributes of arguments, they are not valid outside of the macro
+fn remove_napi_arg_attrs(sig: &mut Signature) {
+  for arg in sig.inputs.iter_mut() {
+    if let syn::FnArg::Typed(p) = arg {
+      p.attrs.retain(|attr| !attr.path.is_ident("napi"));
+    }
+  }
+}
+
 fn fn_kind(opts: &BindgenAttrs) -> FnKind {
   let mut kind = FnKind::Normal;
 
@@ -709,7 +1098,9 @@ impl ConvertToAST for syn::ItemFn {
       self.attrs.clone(),
       self.vis.clone(),
       None,
+      &[],
     )?;
+    remove_napi_arg_attrs(&mut self.sig);
 
     Ok(Napi {
       item: NapiItem::Fn(func),
@@ -721,14 +1112,32 @@ impl ConvertToAST for syn::ItemStruct {
   fn convert_to_ast(&mut self, opts: BindgenAttrs) -> BindgenResult<Napi> {
     let mut errors = vec![];
 
+    let mut generics = vec![];
+    for param in self.generics.params.iter() {
+      match param {
+        syn::GenericParam::Type(ty) => generics.push(ty.ident.clone()),
+        _ => bail_span!(
+          param,
+          "#[napi] classes only support type parameters, wrap a concrete instantiation of the struct instead"
+        ),
+      }
+    }
+
     let vis = self.vis.clone();
     let struct_name = self.ident.clone();
     let js_name = opts.js_name().map_or_else(
       || self.ident.to_string().to_case(Case::Pascal),
       |(js_name, _)| js_name.to_owned(),
     );
+    let rename_all = opts
+      .rename_all()
+      .map(|(case, span)| parse_case(case, span))
+      .transpose()?
+      .unwrap_or(Case::Camel);
     let mut fields = vec![];
     let mut is_tuple = false;
+    // applies to all the `Option<T>` fields of the object
+    let struct_none = parse_none_value(&opts)?;
     let struct_kind = if opts.constructor().is_some() {
       NapiStructKind::Constructor
     } else if opts.object().is_some() {
@@ -756,7 +1165,7 @@ impl ConvertToAST for syn::ItemStruct {
       let (js_name, name) = match &field.ident {
         Some(ident) => (
           field_opts.js_name().map_or_else(
-            || ident.unraw().to_string().to_case(Case::Camel),
+            || ident.unraw().to_string().to_case(rename_all),
             |(js_name, _)| js_name.to_owned(),
           ),
           syn::Member::Named(ident.clone()),
@@ -771,6 +1180,37 @@ impl ConvertToAST for syn::ItemStruct {
       let readonly = field_opts.readonly().is_some();
       let skip_typescript = field_opts.skip_typescript().is_some();
       let ts_type = field_opts.ts_type().map(|e| e.0.to_string());
+      let js_map = field_opts.js_map().is_some();
+      let index_signature = field_opts.index_signature().is_some();
+      let none = parse_none_value(&field_opts)?;
+      if none.is_some() && (struct_kind != NapiStructKind::Object || !is_option_ty(&field.ty)) {
+        errors.push(err_span!(
+          field,
+          "#[napi(none)] is only supported on fields of type `Option<T>` in #[napi(object)] structs"
+        ));
+      }
+      let none = none
+        .or(struct_none)
+        .filter(|_| struct_kind == NapiStructKind::Object && is_option_ty(&field.ty));
+      if js_map && (struct_kind != NapiStructKind::Object || !is_map_ty(&field.ty)) {
+        errors.push(err_span!(
+          field,
+          "#[napi(js_map)] is only supported on fields of type `HashMap<K, V>` or `BTreeMap<K, V>` in #[napi(object)] structs"
+        ));
+      }
+      if index_signature {
+        if struct_kind != NapiStructKind::Object || !is_map_ty(&field.ty) || js_map {
+          errors.push(err_span!(
+            field,
+            "#[napi(index_signature)] is only supported on fields of type `HashMap<String, V>` or `BTreeMap<String, V>` in #[napi(object)] structs"
+          ));
+        } else if fields.iter().any(|f: &NapiStructField| f.index_signature) {
+          errors.push(err_span!(
+            field,
+            "#[napi(index_signature)] can only be used on one field of a struct"
+          ));
+        }
+      }
 
       fields.push(NapiStructField {
         name,
@@ -781,9 +1221,101 @@ impl ConvertToAST for syn::ItemStruct {
         comments: extract_doc_comments(&field.attrs),
         skip_typescript,
         ts_type,
+        js_map,
+        index_signature,
+        none,
       })
     }
 
+    let extends = match opts.extends() {
+      Some(syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(parent),
+        ..
+      })) => Some(NapiClassParent::Js(parent.value())),
+      Some(syn::Expr::Path(syn::ExprPath { path, .. })) => {
+        let parent = extract_path_ident(path)?;
+        // `&Child` is reinterpreted as `&Parent` when methods of the parent class are called
+        // on instances of the child class, so the parent must be at the start of the struct
+        let first_field_is_parent = match self.fields.iter().next().map(|f| get_ty(&f.ty)) {
+          Some(syn::Type::Path(syn::TypePath { qself: None, path })) => {
+            path.segments.last().map(|s| &s.ident) == Some(&parent)
+          }
+          _ => false,
+        };
+        if !first_field_is_parent {
+          bail_span!(
+            self.ident,
+            "#[napi(extends = {})] requires the first field of the struct to be `{}`",
+            parent,
+            parent
+          );
+        }
+        self.attrs.push(syn::parse_quote! { #[repr(C)] });
+        Some(NapiClassParent::Class(parent))
+      }
+      Some(expr) => bail_span!(
+        expr,
+        "#[napi(extends)] expects a #[napi] class or the name of a JavaScript constructor"
+      ),
+      None => None,
+    };
+    if !generics.is_empty() && struct_kind == NapiStructKind::Object {
+      bail_span!(
+        self.generics,
+        "#[napi(object)] can't be applied to a generic struct, wrap a concrete instantiation of it instead"
+      );
+    }
+    if extends.is_some() && struct_kind == NapiStructKind::Object {
+      bail_span!(
+        self.ident,
+        "#[napi(extends)] can't be applied to a struct with #[napi(object)]"
+      );
+    }
+    let ts_module = parse_ts_module(&opts)?;
+    if ts_module.is_some() && struct_kind != NapiStructKind::Object {
+      bail_span!(
+        self.ident,
+        "#[napi(ts_module)] can only be applied to a struct with #[napi(object)]"
+      );
+    }
+    if struct_none.is_some() && struct_kind != NapiStructKind::Object {
+      bail_span!(
+        self.ident,
+        "#[napi(none)] can only be applied to a struct with #[napi(object)]"
+      );
+    }
+    let large_int = if struct_kind == NapiStructKind::Object {
+      parse_large_int_mode(&opts)?
+    } else if opts.large_int().is_some() {
+      bail_span!(
+        self.ident,
+        "#[napi(large_int)] can only be applied to a struct with #[napi(object)]"
+      );
+    } else {
+      None
+    };
+    let aggregate_errors = opts.aggregate_errors().is_some();
+    if aggregate_errors && struct_kind != NapiStructKind::Object {
+      bail_span!(
+        self.ident,
+        "#[napi(aggregate_errors)] can only be applied to a struct with #[napi(object)]"
+      );
+    }
+    let strict_numbers = opts.strict_numbers().is_some();
+    if strict_numbers && struct_kind != NapiStructKind::Object {
+      bail_span!(
+        self.ident,
+        "#[napi(strict_numbers)] can only be applied to a struct with #[napi(object)]"
+      );
+    }
+    let non_finite = parse_non_finite_mode(&opts)?;
+    if non_finite.is_some() && struct_kind != NapiStructKind::Object {
+      bail_span!(
+        self.ident,
+        "#[napi(non_finite)] can only be applied to a struct with #[napi(object)]"
+      );
+    }
+
     record_struct(&struct_name, js_name.clone(), &opts);
 
     Diagnostic::from_vec(errors).map(|()| Napi {
@@ -795,7 +1327,15 @@ impl ConvertToAST for syn::ItemStruct {
         is_tuple,
         kind: struct_kind,
         js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
+        ts_module,
         comments: extract_doc_comments(&self.attrs),
+        extends,
+        skip_typescript: opts.skip_typescript().is_some(),
+        aggregate_errors,
+        large_int,
+        strict_numbers,
+        non_finite,
+        generics,
       }),
     })
   }
@@ -813,10 +1353,11 @@ impl ConvertToAST for syn::ItemImpl {
       }
     };
 
-    let struct_name = extract_path_ident(struct_name)?;
+    let (struct_name, generics) = extract_impl_self_ident(struct_name, &self.generics)?;
 
     let mut struct_js_name = struct_name.to_string();
     let mut items = vec![];
+    let mut consts = vec![];
     let mut task_output_type = None;
     for item in self.items.iter_mut() {
       if let Some(method) = match item {
@@ -829,6 +1370,27 @@ impl ConvertToAST for syn::ItemImpl {
           }
           None
         }
+        syn::ImplItem::Const(c) => {
+          let opts = BindgenAttrs::find(&mut c.attrs)?;
+          if opts.exists {
+            match c.vis {
+              Visibility::Public(_) => {}
+              _ => bail_span!(c.ident, "only pub const supported by #[napi]."),
+            }
+            consts.push(NapiConst {
+              name: c.ident.clone(),
+              js_name: opts
+                .js_name()
+                .map_or_else(|| c.ident.to_string(), |(s, _)| s.to_string()),
+              type_name: c.ty.clone(),
+              value: c.expr.clone(),
+              js_mod: None,
+              comments: extract_doc_comments(&c.attrs),
+              skip_typescript: opts.skip_typescript().is_some(),
+            });
+          }
+          None
+        }
         _ => {
           bail_span!(item, "unsupported impl item in #[napi]")
         }
@@ -859,7 +1421,9 @@ impl ConvertToAST for syn::ItemImpl {
           method.attrs.clone(),
           vis,
           Some(&struct_name),
+          &generics,
         )?;
+        remove_napi_arg_attrs(&mut method.sig);
 
         items.push(func);
       }
@@ -870,9 +1434,12 @@ impl ConvertToAST for syn::ItemImpl {
         name: struct_name,
         js_name: struct_js_name,
         items,
+        consts,
         task_output_type,
         js_mod: impl_opts.namespace().map(|(m, _)| m.to_owned()),
         comments: extract_doc_comments(&self.attrs),
+        skip_typescript: impl_opts.skip_typescript().is_some(),
+        generics,
       }),
     })
   }
@@ -888,26 +1455,116 @@ impl ConvertToAST for syn::ItemEnum {
       bail_span!(self, "cannot export empty enum to JS");
     }
 
-    self.attrs.push(Attribute {
-      pound_token: Default::default(),
-      style: syn::AttrStyle::Outer,
-      bracket_token: Default::default(),
-      path: syn::parse_quote! { derive },
-      tokens: quote! { (Copy, Clone) },
-    });
+    let is_tagged_union = self
+      .variants
+      .iter()
+      .any(|v| !matches!(v.fields, syn::Fields::Unit));
+
+    let is_error = opts.error().is_some();
+
+    // data carrying variants can't be `Copy` in general,
+    // error enums are never converted from JavaScript values so leave their derives alone
+    if !is_tagged_union && !is_error {
+      self.attrs.push(Attribute {
+        pound_token: Default::default(),
+        style: syn::AttrStyle::Outer,
+        bracket_token: Default::default(),
+        path: syn::parse_quote! { derive },
+        tokens: quote! { (Copy, Clone) },
+      });
+    }
 
     let js_name = opts
       .js_name()
       .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string());
 
+    let string_enum_case = match opts.string_enum() {
+      Some(Some((case, span))) => Some(Some(parse_case(case, *span)?)),
+      Some(None) => Some(None),
+      None => None,
+    };
+
+    if is_tagged_union && string_enum_case.is_some() {
+      bail_span!(
+        self.ident,
+        "#[napi(string_enum)] can only be used on enums without data carrying variants"
+      );
+    }
+
+    if is_error && string_enum_case.is_some() {
+      bail_span!(
+        self.ident,
+        "#[napi(string_enum)] can't be used together with #[napi(error)]"
+      );
+    }
+
+    let rename_all = opts
+      .rename_all()
+      .map(|(case, span)| parse_case(case, span))
+      .transpose()?
+      .unwrap_or(Case::Camel);
+
     let mut last_variant_val: i32 = -1;
     let variants = self
       .variants
-      .iter()
+      .iter_mut()
       .map(|v| {
-        match v.fields {
-          syn::Fields::Unit => {}
-          _ => bail_span!(v.fields, "Structured enum is not supported in #[napi]"),
+        let variant_opts = BindgenAttrs::find(&mut v.attrs)?;
+        let (error_code, error_class) = if is_error {
+          let error_class = match variant_opts.class() {
+            Some((class, span)) => match class {
+              "Error" | "TypeError" | "RangeError" | "SyntaxError" => {
+                Some(Ident::new(class, span))
+              }
+              _ => {
+                return Err(Diagnostic::span_error(
+                  span,
+                  format!(
+                    "unknown error class `{}`, expected one of `Error`, `TypeError`, `RangeError` or `SyntaxError`",
+                    class
+                  ),
+                ))
+              }
+            },
+            None => None,
+          };
+          let error_code = variant_opts
+            .code()
+            .map_or_else(|| v.ident.to_string(), |(code, _)| code.to_owned());
+          (Some(error_code), error_class)
+        } else {
+          if let Some((_, span)) = variant_opts.code().or_else(|| variant_opts.class()) {
+            return Err(Diagnostic::span_error(
+              span,
+              "#[napi(code)] and #[napi(class)] can only be used in #[napi(error)] enums",
+            ));
+          }
+          (None, None)
+        };
+
+        let fields = match &mut v.fields {
+          syn::Fields::Unit => vec![],
+          syn::Fields::Named(named) => named
+            .named
+            .iter_mut()
+            .map(|field| {
+              let field_opts = BindgenAttrs::find(&mut field.attrs)?;
+              let ident = field.ident.clone().unwrap();
+              Ok(NapiEnumVariantField {
+                js_name: field_opts.js_name().map_or_else(
+                  || ident.unraw().to_string().to_case(rename_all),
+                  |(js_name, _)| js_name.to_owned(),
+                ),
+                name: ident,
+                ty: field.ty.clone(),
+                comments: extract_doc_comments(&field.attrs),
+              })
+            })
+            .collect::<BindgenResult<Vec<_>>>()?,
+          syn::Fields::Unnamed(_) => bail_span!(
+            v.fields,
+            "tuple variants are not supported in #[napi] enums, use named fields instead"
+          ),
         };
 
         let val = match &v.discriminant {
@@ -950,9 +1607,18 @@ impl ConvertToAST for syn::ItemEnum {
 
         last_variant_val = val;
 
+        let js_name = match string_enum_case {
+          Some(Some(case)) => v.ident.to_string().to_case(case),
+          _ => v.ident.to_string(),
+        };
+
         Ok(NapiEnumVariant {
           name: v.ident.clone(),
+          js_name,
           val,
+          fields,
+          error_code,
+          error_class,
           comments: extract_doc_comments(&v.attrs),
         })
       })
@@ -963,6 +1629,9 @@ impl ConvertToAST for syn::ItemEnum {
         name: self.ident.clone(),
         js_name,
         variants,
+        is_string_enum: string_enum_case.is_some(),
+        is_tagged_union,
+        is_error,
         js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
         comments: extract_doc_comments(&self.attrs),
         skip_typescript: opts.skip_typescript().is_some(),
@@ -991,3 +1660,112 @@ impl ConvertToAST for syn::ItemConst {
     }
   }
 }
+
+impl ConvertToAST for syn::ItemTrait {
+  fn convert_to_ast(&mut self, opts: BindgenAttrs) -> BindgenResult<Napi> {
+    if !self.generics.params.is_empty() {
+      bail_span!(
+        self.generics,
+        "#[napi(interface)] can't be applied to a generic trait"
+      );
+    }
+
+    let ts_module = parse_ts_module(&opts)?;
+    let mut errors = vec![];
+    let mut methods = vec![];
+    for item in self.items.iter_mut() {
+      let method = match item {
+        syn::TraitItem::Method(method) => method,
+        _ => {
+          errors.push(err_span!(
+            item,
+            "only methods are supported in #[napi(interface)] traits"
+          ));
+          continue;
+        }
+      };
+      let method_opts = BindgenAttrs::find(&mut method.attrs)?;
+      let sig = &method.sig;
+
+      if !sig.generics.params.is_empty() || sig.asyncness.is_some() {
+        errors.push(err_span!(
+          sig,
+          "methods of #[napi(interface)] can't be generic or async"
+        ));
+        continue;
+      }
+
+      let mut args = vec![];
+      let mut has_receiver = false;
+      for input in sig.inputs.iter() {
+        match input {
+          syn::FnArg::Receiver(r) if r.reference.is_some() && r.mutability.is_none() => {
+            has_receiver = true;
+          }
+          syn::FnArg::Receiver(r) => {
+            errors.push(err_span!(
+              r,
+              "methods of #[napi(interface)] must take `&self`"
+            ));
+          }
+          syn::FnArg::Typed(p) => match &*p.pat {
+            syn::Pat::Ident(pat) => args.push((pat.ident.clone(), (*p.ty).clone())),
+            _ => errors.push(err_span!(
+              p.pat,
+              "only named arguments are supported in #[napi(interface)]"
+            )),
+          },
+        }
+      }
+      if !has_receiver {
+        errors.push(err_span!(
+          sig,
+          "methods of #[napi(interface)] must take `&self`"
+        ));
+        continue;
+      }
+
+      // calling into JavaScript could always fail
+      let ret = match &sig.output {
+        syn::ReturnType::Type(_, ty) => extract_result_ty(ty)?,
+        syn::ReturnType::Default => None,
+      };
+      let ret = match ret {
+        Some(ret) => ret,
+        None => {
+          errors.push(err_span!(
+            sig,
+            "methods of #[napi(interface)] must return `Result<T>`"
+          ));
+          continue;
+        }
+      };
+
+      methods.push(NapiTraitMethod {
+        name: sig.ident.clone(),
+        js_name: method_opts.js_name().map_or_else(
+          || sig.ident.to_string().to_case(Case::Camel),
+          |(js_name, _)| js_name.to_owned(),
+        ),
+        sig: sig.clone(),
+        args,
+        ret,
+        comments: extract_doc_comments(&method.attrs),
+      });
+    }
+
+    Diagnostic::from_vec(errors).map(|_| Napi {
+      item: NapiItem::Trait(NapiTrait {
+        name: self.ident.clone(),
+        js_name: opts
+          .js_name()
+          .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string()),
+        methods,
+        js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
+        ts_module,
+        comments: extract_doc_comments(&self.attrs),
+        skip_typescript: opts.skip_typescript().is_some(),
+      }),
+    })
+  }
+}
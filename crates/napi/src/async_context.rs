@@ -0,0 +1,149 @@
+#[cfg(feature = "napi3")]
+use std::marker::PhantomData;
+use std::ptr;
+
+use crate::{check_status, sys, JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue, Result};
+
+/// An async resource, created by [`Env::create_async_context`](crate::Env::create_async_context).
+///
+/// Callbacks invoked through [`AsyncContext::make_callback`] or inside an [`AsyncContext::enter`] scope run in the async context captured at creation time, so `async_hooks` and `AsyncLocalStorage` see them as descendants of the JavaScript call which created the `AsyncContext`.
+///
+/// The async resource is destroyed when the `AsyncContext` is dropped.
+pub struct AsyncContext {
+  env: sys::napi_env,
+  raw: sys::napi_async_context,
+  resource: sys::napi_ref,
+}
+
+impl AsyncContext {
+  pub(crate) fn new(
+    env: sys::napi_env,
+    resource: Option<JsObject>,
+    resource_name: &str,
+  ) -> Result<Self> {
+    let raw_resource = match resource {
+      Some(resource) => resource.0.value,
+      None => {
+        let mut raw_resource = ptr::null_mut();
+        check_status!(unsafe { sys::napi_create_object(env, &mut raw_resource) })?;
+        raw_resource
+      }
+    };
+    let mut raw_name = ptr::null_mut();
+    check_status!(unsafe {
+      sys::napi_create_string_utf8(
+        env,
+        resource_name.as_ptr() as *const _,
+        resource_name.len(),
+        &mut raw_name,
+      )
+    })?;
+    let mut raw = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_async_init(env, raw_resource, raw_name, &mut raw) },
+      "Failed to init async context `{}`",
+      resource_name
+    )?;
+    let mut resource = ptr::null_mut();
+    check_status!(unsafe { sys::napi_create_reference(env, raw_resource, 1, &mut resource) })?;
+    Ok(AsyncContext { env, raw, resource })
+  }
+
+  /// The `resource_object` passed to `async_hooks` `init` callbacks.
+  pub fn resource(&self) -> Result<JsObject> {
+    let mut raw_resource = ptr::null_mut();
+    check_status!(unsafe {
+      sys::napi_get_reference_value(self.env, self.resource, &mut raw_resource)
+    })?;
+    Ok(unsafe { JsObject::from_raw_unchecked(self.env, raw_resource) })
+  }
+
+  /// <https://nodejs.org/api/n-api.html#napi_make_callback>
+  ///
+  /// Call `func` in this async context, this should be used instead of [`JsFunction::call`] when calling back into JavaScript from native code which is not itself called from JavaScript, for example from an event loop callback.
+  ///
+  /// The microtask queue and `process.nextTick` callbacks are drained after `func` returns if there is no other JavaScript on the stack.
+  pub fn make_callback<V>(
+    &self,
+    this: Option<&JsObject>,
+    func: &JsFunction,
+    args: &[V],
+  ) -> Result<JsUnknown>
+  where
+    V: NapiRaw,
+  {
+    let raw_this = match this {
+      Some(this) => this.0.value,
+      None => self.resource()?.0.value,
+    };
+    let raw_args = args
+      .iter()
+      .map(|arg| unsafe { arg.raw() })
+      .collect::<Vec<sys::napi_value>>();
+    let mut return_value = ptr::null_mut();
+    check_status!(unsafe {
+      sys::napi_make_callback(
+        self.env,
+        self.raw,
+        raw_this,
+        func.0.value,
+        raw_args.len(),
+        raw_args.as_ptr(),
+        &mut return_value,
+      )
+    })?;
+    Ok(unsafe { JsUnknown::from_raw_unchecked(self.env, return_value) })
+  }
+
+  /// <https://nodejs.org/api/n-api.html#napi_open_callback_scope>
+  ///
+  /// Enter this async context until the returned [`CallbackScope`] is dropped, every JavaScript call in between runs in it.
+  #[cfg(feature = "napi3")]
+  pub fn enter(&self) -> Result<CallbackScope<'_>> {
+    let resource = self.resource()?;
+    let mut raw = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_open_callback_scope(self.env, resource.0.value, self.raw, &mut raw) },
+      "Failed to open callback scope"
+    )?;
+    Ok(CallbackScope {
+      env: self.env,
+      raw,
+      _context: PhantomData,
+    })
+  }
+}
+
+impl Drop for AsyncContext {
+  fn drop(&mut self) {
+    let status = unsafe { sys::napi_async_destroy(self.env, self.raw) };
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Destroy async context failed"
+    );
+    let status = unsafe { sys::napi_delete_reference(self.env, self.resource) };
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Delete async resource reference failed"
+    );
+  }
+}
+
+/// Created by [`AsyncContext::enter`], closes the callback scope when dropped.
+#[cfg(feature = "napi3")]
+pub struct CallbackScope<'a> {
+  env: sys::napi_env,
+  raw: sys::napi_callback_scope,
+  _context: PhantomData<&'a AsyncContext>,
+}
+
+#[cfg(feature = "napi3")]
+impl Drop for CallbackScope<'_> {
+  fn drop(&mut self) {
+    let status = unsafe { sys::napi_close_callback_scope(self.env, self.raw) };
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Close callback scope failed"
+    );
+  }
+}
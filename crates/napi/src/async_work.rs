@@ -4,11 +4,17 @@ use std::os::raw::c_void;
 use std::ptr;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU8, Ordering};
+#[cfg(feature = "napi4")]
+use std::sync::{mpsc, Arc, Mutex};
+#[cfg(feature = "napi4")]
+use std::thread;
 
 use crate::{
   bindgen_runtime::ToNapiValue, check_status, js_values::NapiValue, sys, Env, JsError, JsObject,
-  Result, Task,
+  Result, Task, TaskPool,
 };
+#[cfg(feature = "napi4")]
+use crate::{Error, Status};
 
 struct AsyncWork<T: Task> {
   inner_task: T,
@@ -35,17 +41,165 @@ impl AsyncWorkPromise {
     unsafe { JsObject::from_raw_unchecked(self.env, self.raw_promise) }
   }
 
+  /// Cancel the task, the promise is never settled afterwards.
+  ///
+  /// Tasks queued into the dedicated pool can't be removed from its queue, their `compute` still runs
+  /// but the result is dropped.
   pub fn cancel(&self) -> Result<()> {
     // must be happened in the main thread, relaxed is enough
     self.status.store(2, Ordering::Relaxed);
+    // Tasks run in the dedicated pool can't be removed from the queue, the result will be dropped
+    if self.napi_async_work.is_null() {
+      return Ok(());
+    }
     check_status!(unsafe { sys::napi_cancel_async_work(self.env, self.napi_async_work) })
   }
 }
 
-pub fn run<T: Task>(
+/// The thread pool which runs [`Task::compute`](crate::Task::compute).
+///
+/// By default tasks are queued into the libuv thread pool, which is shared with Node.js I/O like `fs` and `dns`,
+/// and is sized by the `UV_THREADPOOL_SIZE` environment variable.
+/// CPU heavy addons could move their tasks into a dedicated pool instead, so that they don't starve Node.js I/O.
+///
+/// The default pool could also be chosen by the `NAPI_RS_ASYNC_WORK_THREADS` environment variable,
+/// any positive number means a `Dedicated` pool with that many threads.
+///
+/// Tasks with [`TaskPool::Cpu`](crate::TaskPool) always run in the dedicated pool, it's started with
+/// one thread per CPU when the default pool is `Libuv`.
+#[cfg(feature = "napi4")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsyncWorkPool {
+  Libuv,
+  Dedicated { threads: usize },
+}
+
+#[cfg(feature = "napi4")]
+type PoolJob = Box<dyn FnOnce() + Send>;
+
+#[cfg(feature = "napi4")]
+struct PoolState {
+  /// The pool of the tasks with `TaskPool::Default`
+  default: AsyncWorkPool,
+  /// The dedicated pool once it has started
+  dedicated: Option<Mutex<mpsc::Sender<PoolJob>>>,
+}
+
+#[cfg(feature = "napi4")]
+lazy_static::lazy_static! {
+  static ref ASYNC_WORK_POOL: Mutex<PoolState> = {
+    let pool = std::env::var("NAPI_RS_ASYNC_WORK_THREADS")
+      .ok()
+      .and_then(|threads| threads.parse::<usize>().ok())
+      .filter(|threads| *threads > 0)
+      .map(|threads| AsyncWorkPool::Dedicated { threads })
+      .unwrap_or(AsyncWorkPool::Libuv);
+    Mutex::new(PoolState {
+      default: pool,
+      dedicated: None,
+    })
+  };
+}
+
+/// Choose the thread pool for [`Task`](crate::Task)s spawned afterwards.
+///
+/// Returns an error once a `Dedicated` pool has started, because its threads can't be resized.
+#[cfg(feature = "napi4")]
+pub fn set_async_work_pool(pool: AsyncWorkPool) -> Result<()> {
+  if let AsyncWorkPool::Dedicated { threads: 0 } = pool {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "Dedicated async work pool needs at least one thread".to_owned(),
+    ));
+  }
+  let mut state = ASYNC_WORK_POOL.lock().map_err(|_| {
+    Error::new(
+      Status::GenericFailure,
+      "Async work pool state is poisoned".to_owned(),
+    )
+  })?;
+  if state.dedicated.is_some() && pool != AsyncWorkPool::Libuv {
+    return Err(Error::new(
+      Status::GenericFailure,
+      "Dedicated async work pool has already started".to_owned(),
+    ));
+  }
+  state.default = pool;
+  Ok(())
+}
+
+/// Whether a task with `pool` runs in the libuv thread pool
+#[cfg(feature = "napi4")]
+fn use_libuv_pool(pool: TaskPool) -> bool {
+  match pool {
+    TaskPool::Io => true,
+    TaskPool::Cpu => false,
+    TaskPool::Default => ASYNC_WORK_POOL
+      .lock()
+      .map(|state| state.default == AsyncWorkPool::Libuv)
+      .unwrap_or(true),
+  }
+}
+
+/// The sender of the dedicated pool, starting it on first use. `None` if the pool is not available.
+#[cfg(feature = "napi4")]
+fn dedicated_pool() -> Option<mpsc::Sender<PoolJob>> {
+  let mut state = ASYNC_WORK_POOL.lock().ok()?;
+  if state.dedicated.is_none() {
+    let threads = match state.default {
+      AsyncWorkPool::Dedicated { threads } => threads,
+      AsyncWorkPool::Libuv => thread::available_parallelism()
+        .map(|threads| threads.get())
+        .unwrap_or(4),
+    };
+    let (sender, receiver) = mpsc::channel::<PoolJob>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    let mut spawned = 0;
+    for index in 0..threads {
+      let receiver = receiver.clone();
+      let spawn_result = thread::Builder::new()
+        .name(format!("napi-rs-async-work-{}", index))
+        .spawn(move || loop {
+          let job = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => break,
+          };
+          match job {
+            Ok(job) => job(),
+            Err(_) => break,
+          }
+        });
+      if spawn_result.is_ok() {
+        spawned += 1;
+      }
+    }
+    if spawned == 0 {
+      return None;
+    }
+    state.dedicated = Some(Mutex::new(sender));
+  }
+  state
+    .dedicated
+    .as_ref()
+    .and_then(|sender| sender.lock().ok())
+    .map(|sender| sender.clone())
+}
+
+pub fn run<T: Task + 'static>(
+  env: sys::napi_env,
+  task: T,
+  abort_status: Option<Rc<AtomicU8>>,
+) -> Result<AsyncWorkPromise> {
+  run_in_pool(env, task, abort_status, T::POOL)
+}
+
+/// Without `napi4` all tasks run in the libuv thread pool
+#[cfg_attr(not(feature = "napi4"), allow(unused_variables))]
+pub fn run_in_pool<T: Task + 'static>(
   env: sys::napi_env,
   task: T,
   abort_status: Option<Rc<AtomicU8>>,
+  pool: TaskPool,
 ) -> Result<AsyncWorkPromise> {
   let mut raw_resource = ptr::null_mut();
   check_status!(unsafe { sys::napi_create_object(env, &mut raw_resource) })?;
@@ -53,6 +207,8 @@ pub fn run<T: Task>(
   let mut deferred = ptr::null_mut();
   check_status!(unsafe { sys::napi_create_promise(env, &mut deferred, &mut raw_promise) })?;
   let task_status = abort_status.unwrap_or_else(|| Rc::new(AtomicU8::new(0)));
+  #[cfg(feature = "tracing")]
+  tracing::debug!(task = std::any::type_name::<T>(), pool = ?pool, "napi task queued");
   let result = Box::leak(Box::new(AsyncWork {
     inner_task: task,
     deferred,
@@ -60,6 +216,11 @@ pub fn run<T: Task>(
     napi_async_work: ptr::null_mut(),
     status: task_status.clone(),
   }));
+  // Fallback to the libuv thread pool if the dedicated pool can't be started
+  #[cfg(feature = "napi4")]
+  if let Some(sender) = (!use_libuv_pool(pool)).then(dedicated_pool).flatten() {
+    return run_in_dedicated_pool(env, sender, result, raw_promise, task_status);
+  }
   let async_work_name = unsafe { CStr::from_bytes_with_nul_unchecked(b"napi_rs_async_work\0") };
   check_status!(unsafe {
     sys::napi_create_async_work(
@@ -85,6 +246,81 @@ pub fn run<T: Task>(
   })
 }
 
+#[cfg(feature = "napi4")]
+fn run_in_dedicated_pool<T: Task + 'static>(
+  env: sys::napi_env,
+  sender: mpsc::Sender<PoolJob>,
+  work: &'static mut AsyncWork<T>,
+  raw_promise: sys::napi_value,
+  status: Rc<AtomicU8>,
+) -> Result<AsyncWorkPromise> {
+  let deferred = work.deferred;
+  let mut async_resource_name = ptr::null_mut();
+  let s = unsafe { CStr::from_bytes_with_nul_unchecked(b"napi_rs_async_work\0") };
+  check_status!(unsafe {
+    sys::napi_create_string_utf8(env, s.as_ptr(), 18, &mut async_resource_name)
+  })?;
+  let mut tsfn = ptr::null_mut();
+  check_status!(unsafe {
+    sys::napi_create_threadsafe_function(
+      env,
+      ptr::null_mut(),
+      ptr::null_mut(),
+      async_resource_name,
+      0,
+      1,
+      ptr::null_mut(),
+      None,
+      ptr::null_mut(),
+      Some(complete_in_main_thread::<T>),
+      &mut tsfn,
+    )
+  })?;
+  let data = work as *mut AsyncWork<T> as usize;
+  let tsfn_ptr = tsfn as usize;
+  let job: PoolJob = Box::new(move || {
+    let data = data as *mut c_void;
+    let tsfn = tsfn_ptr as sys::napi_threadsafe_function;
+    unsafe {
+      execute::<T>(ptr::null_mut(), data);
+      sys::napi_call_threadsafe_function(tsfn, data, sys::ThreadsafeFunctionCallMode::blocking);
+      sys::napi_release_threadsafe_function(tsfn, sys::ThreadsafeFunctionReleaseMode::release);
+    }
+  });
+  if sender.send(job).is_err() {
+    // All the pool threads have exited, the job is dropped without running
+    unsafe {
+      sys::napi_release_threadsafe_function(tsfn, sys::ThreadsafeFunctionReleaseMode::release);
+      drop(Box::from_raw(data as *mut AsyncWork<T>));
+    }
+    return Err(Error::new(
+      Status::GenericFailure,
+      "Dedicated async work pool is not running".to_owned(),
+    ));
+  }
+  Ok(AsyncWorkPromise {
+    napi_async_work: ptr::null_mut(),
+    raw_promise,
+    deferred,
+    env,
+    status,
+  })
+}
+
+#[cfg(feature = "napi4")]
+unsafe extern "C" fn complete_in_main_thread<T: Task>(
+  env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  _context: *mut c_void,
+  data: *mut c_void,
+) {
+  // env is null when the environment is tearing down
+  if env.is_null() {
+    return;
+  }
+  unsafe { complete::<T>(env, sys::Status::napi_ok, data) };
+}
+
 #[allow(clippy::non_send_fields_in_send_ty)]
 unsafe impl<T: Task> Send for AsyncWork<T> {}
 
@@ -94,6 +330,9 @@ unsafe impl<T: Task> Sync for AsyncWork<T> {}
 /// So it actually could do nothing here, because `execute` function is called in the other thread mostly.
 unsafe extern "C" fn execute<T: Task>(_env: sys::napi_env, data: *mut c_void) {
   let mut work = unsafe { Box::from_raw(data as *mut AsyncWork<T>) };
+  #[cfg(feature = "tracing")]
+  let _span =
+    tracing::debug_span!("napi_task_compute", task = std::any::type_name::<T>()).entered();
   let _ = mem::replace(
     &mut work.value,
     work.inner_task.compute().map(mem::MaybeUninit::new),
@@ -110,6 +349,14 @@ unsafe extern "C" fn complete<T: Task>(
   let value_ptr = mem::replace(&mut work.value, Ok(mem::MaybeUninit::zeroed()));
   let deferred = mem::replace(&mut work.deferred, ptr::null_mut());
   let napi_async_work = mem::replace(&mut work.napi_async_work, ptr::null_mut());
+  #[cfg(feature = "tracing")]
+  let _span = tracing::debug_span!(
+    "napi_task_complete",
+    task = std::any::type_name::<T>(),
+    cancelled = status == sys::Status::napi_cancelled || work.status.load(Ordering::Relaxed) == 2,
+    failed = value_ptr.is_err()
+  )
+  .entered();
   let value = match value_ptr {
     Ok(v) => {
       let output = unsafe { v.assume_init() };
@@ -119,8 +366,10 @@ unsafe extern "C" fn complete<T: Task>(
     }
     Err(e) => work.inner_task.reject(unsafe { Env::from_raw(env) }, e),
   };
+  let finally = work.inner_task.finally(unsafe { Env::from_raw(env) });
   if status != sys::Status::napi_cancelled && work.status.load(Ordering::Relaxed) != 2 {
     match check_status!(status)
+      .and_then(move |_| finally)
       .and_then(move |_| value)
       .and_then(|v| unsafe { ToNapiValue::to_napi_value(env, v) })
     {
@@ -135,13 +384,13 @@ unsafe extern "C" fn complete<T: Task>(
       }
     };
   }
-  if let Err(e) = work.inner_task.finally(unsafe { Env::from_raw(env) }) {
-    debug_assert!(false, "Panic in Task finally fn: {:?}", e);
+  // Tasks run in the dedicated pool don't have a napi_async_work
+  if !napi_async_work.is_null() {
+    let delete_status = unsafe { sys::napi_delete_async_work(env, napi_async_work) };
+    debug_assert!(
+      delete_status == sys::Status::napi_ok,
+      "Delete async work failed"
+    );
   }
-  let delete_status = unsafe { sys::napi_delete_async_work(env, napi_async_work) };
-  debug_assert!(
-    delete_status == sys::Status::napi_ok,
-    "Delete async work failed"
-  );
   work.status.store(1, Ordering::Relaxed);
 }
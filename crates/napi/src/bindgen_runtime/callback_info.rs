@@ -1,17 +1,22 @@
+use std::cell::Cell;
 use std::ffi::c_void;
 use std::ptr;
-use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::{bindgen_prelude::*, check_status, sys, Result};
+use crate::{bindgen_prelude::*, check_status, sys, NapiRaw, Result};
 
-#[doc(hidden)]
-/// Determined is `constructor` called from Class `factory`
-/// Ugly but works
-/// We can even be more ugly without `atomic`
-pub static ___CALL_FROM_FACTORY: AtomicBool = AtomicBool::new(false);
+thread_local! {
+  #[doc(hidden)]
+  /// Determined is `constructor` called from Class `factory`
+  /// Ugly but works
+  /// Thread local, so that classes constructed in different `worker_threads` don't interfere with each other
+  pub static ___CALL_FROM_FACTORY: Cell<bool> = Cell::new(false);
+}
 
 pub struct CallbackInfo<const N: usize> {
   env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
+  /// Number of arguments actually passed from JavaScript, may be greater than `N`
+  argc: usize,
   pub this: sys::napi_value,
   pub args: [sys::napi_value; N],
 }
@@ -52,70 +57,90 @@ impl<const N: usize> CallbackInfo<N> {
       }
     }
 
-    Ok(Self { env, this, args })
+    Ok(Self {
+      env,
+      callback_info,
+      argc,
+      this,
+      args,
+    })
   }
 
-  pub fn get_arg(&self, index: usize) -> sys::napi_value {
-    self.args[index]
+  /// Check the arguments count of `#[napi(strict)]` functions, throw a `TypeError` if there are not enough arguments
+  pub fn check_argc(&self, required_argc: usize, fn_name: &str) -> Result<()> {
+    if self.argc >= required_argc {
+      return Ok(());
+    }
+    unsafe {
+      throw_strict_type_error(
+        self.env,
+        format!(
+          "{} expects at least {} arguments, but received {}",
+          fn_name, required_argc, self.argc
+        ),
+      )
+    }
   }
 
-  pub fn this(&self) -> sys::napi_value {
-    self.this
+  pub fn get_arg(&self, index: usize) -> sys::napi_value {
+    self.args[index]
   }
 
-  pub fn construct<T>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
-    let obj = Box::new(obj);
-    let this = self.this();
+  /// All the arguments passed from JavaScript starting at `start`, used by `#[napi(variadic)]` arguments
+  pub fn get_rest_args(&self, start: usize) -> Result<Vec<sys::napi_value>> {
+    if self.argc <= start {
+      return Ok(vec![]);
+    }
+    if self.argc <= N {
+      return Ok(self.args[start..self.argc].to_vec());
+    }
 
-    unsafe {
-      check_status!(
-        sys::napi_wrap(
+    let mut argc = self.argc;
+    let mut args = vec![ptr::null_mut(); argc];
+    check_status!(
+      unsafe {
+        sys::napi_get_cb_info(
           self.env,
-          this,
-          Box::into_raw(obj) as *mut std::ffi::c_void,
-          Some(raw_finalize_unchecked::<T>),
+          self.callback_info,
+          &mut argc,
+          args.as_mut_ptr(),
           ptr::null_mut(),
-          &mut std::ptr::null_mut()
-        ),
-        "Failed to initialize class `{}`",
-        js_name,
-      )?;
-    };
+          ptr::null_mut(),
+        )
+      },
+      "Failed to get the rest arguments of napi function call."
+    )?;
+    args.drain(..start);
+    Ok(args)
+  }
 
-    Ok(this)
+  pub fn this(&self) -> sys::napi_value {
+    self.this
   }
 
-  pub fn factory<T>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
-    let obj = Box::new(obj);
-    let this = self.this();
-    let mut instance = ptr::null_mut();
-    unsafe {
-      ___CALL_FROM_FACTORY.store(true, Ordering::Relaxed);
-      let status = sys::napi_new_instance(self.env, this, 0, ptr::null_mut(), &mut instance);
-      ___CALL_FROM_FACTORY.store(false, Ordering::Relaxed);
-      // Error thrown in `constructor`
-      if status == sys::Status::napi_pending_exception {
-        let mut exception = ptr::null_mut();
-        sys::napi_get_and_clear_last_exception(self.env, &mut exception);
-        sys::napi_throw(self.env, exception);
-        return Ok(ptr::null_mut());
-      }
+  /// `rust_name` is the name of the Rust struct of the class
+  pub fn construct<T>(&self, rust_name: &'static str, obj: T) -> Result<sys::napi_value> {
+    let mut this = self.this();
+    let mut new_target = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_new_target(self.env, self.callback_info, &mut new_target) },
+      "Failed to get new.target of class `{}`",
+      rust_name
+    )?;
+    // the instance created by a JavaScript parent replaces `this`
+    if let Some(instance) = unsafe {
+      call_js_parent_constructor(self.env, rust_name, new_target, &self.get_rest_args(0)?)?
+    } {
+      this = instance;
+    }
+    unsafe { wrap_class_instance(self.env, this, rust_name, obj)? };
 
-      check_status!(
-        sys::napi_wrap(
-          self.env,
-          instance,
-          Box::into_raw(obj) as *mut std::ffi::c_void,
-          Some(raw_finalize_unchecked::<T>),
-          ptr::null_mut(),
-          &mut std::ptr::null_mut()
-        ),
-        "Failed to initialize class `{}`",
-        js_name,
-      )?;
-    };
+    Ok(this)
+  }
 
-    Ok(instance)
+  /// `rust_name` is the name of the Rust struct of the class
+  pub fn factory<T>(&self, rust_name: &'static str, obj: T) -> Result<sys::napi_value> {
+    unsafe { new_instance_from_constructor(self.env, self.this(), rust_name, obj) }
   }
 
   pub fn unwrap_borrow_mut<T>(&mut self) -> Result<&'static mut T>
@@ -130,6 +155,7 @@ impl<const N: usize> CallbackInfo<N> {
         "Failed to unwrap exclusive reference of `{}` type from napi value",
         T::type_name(),
       )?;
+      check_class_instance::<T>(self.env, self.this, wrapped_val, T::type_name())?;
 
       Ok(&mut *(wrapped_val as *mut T))
     }
@@ -147,8 +173,59 @@ impl<const N: usize> CallbackInfo<N> {
         "Failed to unwrap shared reference of `{}` type from napi value",
         T::type_name(),
       )?;
+      check_class_instance::<T>(self.env, self.this, wrapped_val, T::type_name())?;
 
       Ok(&*(wrapped_val as *const T))
     }
   }
 }
+
+#[doc(hidden)]
+/// Create an instance of the class generated for `rust_name` wrapping `obj`.
+/// Used by async `#[napi(factory)]` methods, which resolve outside of the original `CallbackInfo`.
+///
+/// # Safety
+///
+/// `env` must be a valid `napi_env` of the current thread
+pub unsafe fn new_class_instance<T>(
+  env: sys::napi_env,
+  rust_name: &'static str,
+  obj: T,
+) -> Result<sys::napi_value> {
+  let ctor = get_class_constructors_by_rust_name(env, rust_name)?.remove(0);
+  unsafe { new_instance_from_constructor(env, ctor.raw(), rust_name, obj) }
+}
+
+#[doc(hidden)]
+/// Create an instance of the class constructor `ctor` generated for `rust_name` wrapping `obj`
+///
+/// # Safety
+///
+/// `ctor` must be a constructor of `env` generated for `rust_name`
+pub unsafe fn new_instance_from_constructor<T>(
+  env: sys::napi_env,
+  ctor: sys::napi_value,
+  rust_name: &'static str,
+  obj: T,
+) -> Result<sys::napi_value> {
+  let mut instance = ptr::null_mut();
+  unsafe {
+    ___CALL_FROM_FACTORY.with(|f| f.set(true));
+    let status = sys::napi_new_instance(env, ctor, 0, ptr::null_mut(), &mut instance);
+    ___CALL_FROM_FACTORY.with(|f| f.set(false));
+    // Error thrown in `constructor`
+    if status == sys::Status::napi_pending_exception {
+      let mut exception = ptr::null_mut();
+      sys::napi_get_and_clear_last_exception(env, &mut exception);
+      sys::napi_throw(env, exception);
+      return Ok(ptr::null_mut());
+    }
+  };
+  // factories construct the instance without arguments
+  if let Some(parent_instance) = unsafe { call_js_parent_constructor(env, rust_name, ctor, &[])? } {
+    instance = parent_instance;
+  }
+  unsafe { wrap_class_instance(env, instance, rust_name, obj)? };
+
+  Ok(instance)
+}
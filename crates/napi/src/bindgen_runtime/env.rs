@@ -1,45 +1,34 @@
-use std::cell::RefCell;
 use std::ptr;
 
-use crate::{check_status, sys, JsGlobal, JsNull, JsUndefined, NapiValue, Result};
+use crate::{
+  check_status, sys, Error, JsFunction, JsGlobal, JsNull, JsObject, JsUndefined, JsUnknown,
+  NapiRaw, NapiValue, Result, Status, ValueType,
+};
 
 use super::Array;
+#[cfg(feature = "napi5")]
+use super::ProxyHandler;
 
 pub use crate::Env;
 
-thread_local! {
-  static JS_UNDEFINED: RefCell<Option<JsUndefined>> = RefCell::default();
-  static JS_NULL: RefCell<Option<JsNull>> = RefCell::default();
-}
-
 impl Env {
   pub fn create_array(&self, len: u32) -> Result<Array> {
     Array::new(self.0, len)
   }
 
   /// Get [JsUndefined](./struct.JsUndefined.html) value
+  ///
+  /// `napi_value`s belong to the `Env` and the handle scope they were created in, so they are never cached.
   pub fn get_undefined(&self) -> Result<JsUndefined> {
-    if let Some(js_undefined) = JS_UNDEFINED.with(|x| *x.borrow()) {
-      return Ok(js_undefined);
-    }
     let mut raw_value = ptr::null_mut();
     check_status!(unsafe { sys::napi_get_undefined(self.0, &mut raw_value) })?;
-    let js_undefined = unsafe { JsUndefined::from_raw_unchecked(self.0, raw_value) };
-    JS_UNDEFINED.with(|x| x.borrow_mut().replace(js_undefined));
-    Ok(js_undefined)
+    Ok(unsafe { JsUndefined::from_raw_unchecked(self.0, raw_value) })
   }
 
   pub fn get_null(&self) -> Result<JsNull> {
-    if let Some(js_null) = JS_NULL.with(|cell| *cell.borrow()) {
-      return Ok(js_null);
-    }
     let mut raw_value = ptr::null_mut();
     check_status!(unsafe { sys::napi_get_null(self.0, &mut raw_value) })?;
-    let js_null = unsafe { JsNull::from_raw_unchecked(self.0, raw_value) };
-    JS_NULL.with(|js_null_cell| {
-      js_null_cell.borrow_mut().replace(js_null);
-    });
-    Ok(js_null)
+    Ok(unsafe { JsNull::from_raw_unchecked(self.0, raw_value) })
   }
 
   pub fn get_global(&self) -> Result<JsGlobal> {
@@ -54,4 +43,97 @@ impl Env {
       value_type: crate::ValueType::Object,
     }))
   }
+
+  /// Create a `Proxy` of `target`, with traps implemented in Rust.
+  ///
+  /// The properties can be computed on demand, e.g. for a view of a large native collection.
+  #[cfg(feature = "napi5")]
+  pub fn create_proxy(&self, target: JsObject, handler: ProxyHandler) -> Result<JsObject> {
+    let proxy: JsFunction = self.get_global()?.get_named_property("Proxy")?;
+    proxy.new_instance(&[target, handler.into_object()])
+  }
+
+  /// Get the `process` object of the host runtime.
+  pub fn get_process(&self) -> Result<JsObject> {
+    self.get_global()?.get_named_property("process")
+  }
+
+  /// Read an environment variable through `process.env`.
+  ///
+  /// Unlike `std::env::var`, it reflects changes made by JavaScript, like `process.env.FOO = 'bar'`.
+  pub fn get_env_var<K: AsRef<str>>(&self, key: K) -> Result<Option<String>> {
+    let env: JsObject = self.get_process()?.get_named_property("env")?;
+    let value: JsUnknown = env.get_named_property(key.as_ref())?;
+    match value.get_type()? {
+      ValueType::Undefined | ValueType::Null => Ok(None),
+      _ => value
+        .coerce_to_string()?
+        .into_utf8()?
+        .into_owned()
+        .map(Some),
+    }
+  }
+
+  /// Create a `require` function which resolves modules relative to `filename`,
+  /// see [module.createRequire](https://nodejs.org/api/module.html#modulecreaterequirefilename).
+  ///
+  /// Pass the `__filename` or `import.meta.url` of the JavaScript caller to resolve like it does.
+  ///
+  /// The `module` builtin is loaded with `process.getBuiltinModule` on Node.js >= 22.3,
+  /// older versions fall back to the deprecated `process.mainModule`, which is missing when the
+  /// entry point is an ES module.
+  pub fn create_require<F: AsRef<str>>(&self, filename: F) -> Result<JsFunction> {
+    let module = self.get_builtin_module("module")?;
+    let create_require: JsFunction = module.get_named_property("createRequire")?;
+    let filename = self.create_string(filename.as_ref())?;
+    let require = create_require.call(Some(&module), &[filename])?;
+    if require.get_type()? != ValueType::Function {
+      return Err(Error::new(
+        Status::FunctionExpected,
+        "`createRequire` did not return a function".to_owned(),
+      ));
+    }
+    Ok(unsafe { JsFunction::from_raw_unchecked(self.0, require.raw()) })
+  }
+
+  /// `require` a module relative to the current working directory of the process.
+  ///
+  /// A native addon can't tell which JavaScript module called it, so relative specifiers and
+  /// packages are not resolved relative to the caller, use [`Env::create_require`] with the
+  /// caller's filename for that. ES modules can't be loaded this way either, they need a dynamic
+  /// `import()` from JavaScript.
+  pub fn require<S: AsRef<str>>(&self, specifier: S) -> Result<JsUnknown> {
+    let cwd = std::env::current_dir()?;
+    // `createRequire` only uses the directory of the filename
+    let require = self.create_require(cwd.join("noop.js").to_string_lossy())?;
+    let specifier = self.create_string(specifier.as_ref())?;
+    require.call(None, &[specifier])
+  }
+
+  pub(crate) fn get_builtin_module(&self, name: &str) -> Result<JsObject> {
+    let process = self.get_process()?;
+    let specifier = self.create_string(name)?;
+    // Node.js >= 22.3
+    if process.has_named_property("getBuiltinModule")? {
+      let get_builtin_module: JsFunction = process.get_named_property("getBuiltinModule")?;
+      return get_builtin_module
+        .call(Some(&process), &[specifier])?
+        .coerce_to_object();
+    }
+    let main_module: JsUnknown = process.get_named_property("mainModule")?;
+    if main_module.get_type()? != ValueType::Object {
+      return Err(Error::new(
+        Status::GenericFailure,
+        format!(
+          "Can not load builtin module `{}`, no available `require`",
+          name
+        ),
+      ));
+    }
+    let main_module: JsObject = unsafe { main_module.cast() };
+    let require: JsFunction = main_module.get_named_property("require")?;
+    require
+      .call(Some(&main_module), &[specifier])?
+      .coerce_to_object()
+  }
 }
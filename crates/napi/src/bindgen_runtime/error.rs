@@ -9,3 +9,41 @@ macro_rules! check_status_or_throw {
     }
   };
 }
+
+thread_local! {
+  static PANIC_LOCATION: std::cell::RefCell<Option<String>> = Default::default();
+}
+
+static INSTALL_PANIC_HOOK: std::sync::Once = std::sync::Once::new();
+
+/// Generated for `#[napi(catch_unwind)]`, turn a panic in `f` into an `Error` carrying the panic message and location.
+#[doc(hidden)]
+pub fn catch_unwind<T, F>(f: F) -> crate::Result<T>
+where
+  F: FnOnce() -> crate::Result<T>,
+{
+  INSTALL_PANIC_HOOK.call_once(|| {
+    // The payload of `catch_unwind` has no location, record it in the hook and keep the previous behaviour
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+      PANIC_LOCATION.with(|location| {
+        *location.borrow_mut() = info.location().map(|l| l.to_string());
+      });
+      previous_hook(info);
+    }));
+  });
+  std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+    let message = if let Some(message) = payload.downcast_ref::<String>() {
+      message.clone()
+    } else if let Some(message) = payload.downcast_ref::<&str>() {
+      message.to_string()
+    } else {
+      "Box<dyn Any>".to_owned()
+    };
+    let reason = match PANIC_LOCATION.with(|location| location.borrow_mut().take()) {
+      Some(location) => format!("Rust panicked at {}: {}", location, message),
+      None => format!("Rust panicked: {}", message),
+    };
+    Err(crate::Error::new(crate::Status::GenericFailure, reason))
+  })
+}
@@ -1,29 +1,55 @@
 use std::ptr;
 
-use crate::{check_status, sys, Error, JsUnknown, NapiRaw, NapiValue, Result, Status, ValueType};
+use crate::{
+  check_status, sys, Error, JsError, JsTypeError, JsUnknown, NapiRaw, NapiValue, Result, Status,
+  ValueType,
+};
 
 mod array;
 mod arraybuffer;
 #[cfg(feature = "napi6")]
 mod bigint;
+mod blob;
 mod boolean;
 mod buffer;
+mod buffer_pool;
+#[cfg(feature = "bytes")]
+mod bytes;
+mod checked_number;
+mod class_instance;
 #[cfg(all(feature = "chrono_date", feature = "napi5"))]
 mod date;
+#[cfg(any(feature = "rust_decimal", feature = "bigdecimal"))]
+mod decimal;
 mod either;
+mod event_emitter;
 mod external;
 mod function;
+mod large_int;
 mod map;
+#[cfg(feature = "ndarray")]
+mod ndarray;
 mod nil;
 mod number;
 mod object;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 mod promise;
+mod property_key;
+#[cfg(feature = "napi5")]
+mod proxy;
 #[cfg(feature = "serde-json")]
 mod serde;
 mod string;
 mod symbol;
 mod task;
+mod this;
+#[cfg(all(feature = "time", feature = "napi5"))]
+mod time;
+mod unknown_ref;
+#[cfg(feature = "url")]
+mod url;
+#[cfg(feature = "uuid")]
+mod uuid;
 
 #[cfg(feature = "napi5")]
 pub use crate::JsDate as Date;
@@ -31,18 +57,37 @@ pub use array::*;
 pub use arraybuffer::*;
 #[cfg(feature = "napi6")]
 pub use bigint::*;
+pub use blob::*;
 pub use buffer::*;
+pub use buffer_pool::*;
+pub use checked_number::*;
+pub use class_instance::*;
+#[cfg(any(feature = "rust_decimal", feature = "bigdecimal"))]
+pub use decimal::*;
 pub use either::*;
+pub use event_emitter::*;
 pub use external::*;
-#[cfg(feature = "napi4")]
 pub use function::*;
+pub use large_int::*;
+pub use map::*;
+#[cfg(feature = "ndarray")]
+pub use self::ndarray::*;
 pub use nil::*;
 pub use object::*;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 pub use promise::*;
+pub use property_key::*;
+#[cfg(feature = "napi5")]
+pub use proxy::*;
 pub use string::*;
 pub use symbol::*;
 pub use task::*;
+pub use this::*;
+pub use unknown_ref::*;
+#[cfg(feature = "url")]
+pub use self::url::*;
+#[cfg(feature = "uuid")]
+pub use self::uuid::*;
 
 #[cfg(feature = "latin1")]
 pub use string::latin1_string::*;
@@ -70,6 +115,8 @@ impl TypeName for JsUnknown {
   }
 }
 
+impl ValidateNapiValue for JsUnknown {}
+
 impl<T: NapiRaw> ToNapiValue for T {
   unsafe fn to_napi_value(_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
     Ok(unsafe { NapiRaw::raw(&val) })
@@ -148,6 +195,72 @@ pub trait ValidateNapiValue: FromNapiValue + TypeName {
   }
 }
 
+#[doc(hidden)]
+/// Validate an argument of `#[napi(strict)]` functions, throw a `TypeError` describing the
+/// expected type and the position of the argument if it fails.
+///
+/// # Safety
+///
+/// `napi_val` must be a valid value of `env`
+pub unsafe fn validate_strict_arg<T: ValidateNapiValue>(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  index: usize,
+  fn_name: &str,
+) -> Result<()> {
+  if unsafe { T::validate(env, napi_val) }.is_ok() {
+    return Ok(());
+  }
+  let expected = T::type_of();
+  let expected = if expected.is_empty() {
+    T::type_name().to_owned()
+  } else {
+    expected
+      .iter()
+      .map(|value_type| value_type.to_string().to_lowercase())
+      .collect::<Vec<_>>()
+      .join(" or ")
+  };
+  let mut received = -1;
+  check_status!(
+    unsafe { sys::napi_typeof(env, napi_val, &mut received) },
+    "Failed to detect napi value type",
+  )?;
+  unsafe {
+    throw_strict_type_error(
+      env,
+      format!(
+        "expected {} at argument {} of {}, but received {}",
+        expected,
+        index + 1,
+        fn_name,
+        ValueType::from(received).to_string().to_lowercase()
+      ),
+    )
+  }
+}
+
+#[doc(hidden)]
+/// Throw a `TypeError` with `InvalidArg` code, the returned error tells the caller the exception is pending.
+///
+/// # Safety
+///
+/// `env` must be a valid `napi_env` of the current thread
+pub unsafe fn throw_strict_type_error(env: sys::napi_env, reason: String) -> Result<()> {
+  unsafe { JsTypeError::from(Error::new(Status::InvalidArg, reason.clone())).throw_into(env) };
+  Err(Error::new(Status::PendingException, reason))
+}
+
+#[doc(hidden)]
+/// Convert a field of an `#[napi(object, aggregate_errors)]` struct, the error is recorded
+/// rather than returned so the other fields are still converted.
+pub fn collect_field_error<T, F>(errors: &mut Vec<Error>, convert: F) -> Option<T>
+where
+  F: FnOnce() -> Result<T>,
+{
+  convert().map_err(|e| errors.push(e)).ok()
+}
+
 impl<T: TypeName> TypeName for Option<T> {
   fn type_name() -> &'static str {
     "Option"
@@ -158,6 +271,29 @@ impl<T: TypeName> TypeName for Option<T> {
   }
 }
 
+impl<T: ValidateNapiValue> ValidateNapiValue for Option<T> {
+  fn type_of() -> Vec<ValueType> {
+    let mut types = T::type_of();
+    if !types.is_empty() {
+      types.push(ValueType::Undefined);
+      types.push(ValueType::Null);
+    }
+    types
+  }
+
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+    let mut val_type = 0;
+    check_status!(
+      unsafe { sys::napi_typeof(env, napi_val, &mut val_type) },
+      "Failed to detect napi value type",
+    )?;
+    match val_type {
+      sys::ValueType::napi_undefined | sys::ValueType::napi_null => Ok(()),
+      _ => unsafe { T::validate(env, napi_val) },
+    }
+  }
+}
+
 impl<T> FromNapiValue for Option<T>
 where
   T: FromNapiValue,
@@ -186,8 +322,13 @@ where
       Some(val) => unsafe { T::to_napi_value(env, val) },
       None => {
         let mut ptr = ptr::null_mut();
+        // `None` becomes `undefined` instead of `null` with the `none_as_undefined` feature
+        #[cfg(not(feature = "none_as_undefined"))]
+        let status = unsafe { sys::napi_get_null(env, &mut ptr) };
+        #[cfg(feature = "none_as_undefined")]
+        let status = unsafe { sys::napi_get_undefined(env, &mut ptr) };
         check_status!(
-          unsafe { sys::napi_get_null(env, &mut ptr) },
+          status,
           "Failed to convert rust type `Option<T>` into napi value",
         )?;
         Ok(ptr)
@@ -203,17 +344,7 @@ where
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
     match val {
       Ok(v) => unsafe { T::to_napi_value(env, v) },
-      Err(e) => {
-        let error_code = unsafe { String::to_napi_value(env, format!("{:?}", e.status))? };
-        let reason = unsafe { String::to_napi_value(env, e.reason)? };
-        let mut error = ptr::null_mut();
-        check_status!(
-          unsafe { sys::napi_create_error(env, error_code, reason, &mut error) },
-          "Failed to create napi error"
-        )?;
-
-        Ok(error)
-      }
+      Err(e) => Ok(unsafe { JsError::from(e).into_value(env) }),
     }
   }
 }
@@ -1,6 +1,12 @@
 use std::ptr;
 
-use crate::{bindgen_prelude::*, check_status, sys, JsObject, Value, ValueType};
+use crate::{
+  bindgen_prelude::*, check_status, sys, JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue,
+  Value, ValueType,
+};
+
+/// Numeric arrays of at least this length are created through a `Float64Array`
+const TYPED_ARRAY_FAST_PATH_LEN: usize = 64;
 
 pub struct Array {
   env: sys::napi_env,
@@ -65,6 +71,22 @@ impl Array {
     Ok(())
   }
 
+  /// Set `values` as the elements from `start`, the array grows if it's shorter
+  pub fn set_elements<T: ToNapiValue + Copy>(&mut self, start: u32, values: &[T]) -> Result<()> {
+    let end = start as usize + values.len();
+    if end > u32::MAX as usize {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Array length {} exceeds the maximum of {}", end, u32::MAX),
+      ));
+    }
+    for (index, val) in (start..).zip(values) {
+      unsafe { set_element(self.env, self.inner, index, *val)? };
+    }
+    self.len = self.len.max(end as u32);
+    Ok(())
+  }
+
   #[allow(clippy::len_without_is_empty)]
   pub fn len(&self) -> u32 {
     self.len
@@ -127,28 +149,52 @@ impl FromNapiValue for Array {
   }
 }
 
+impl std::fmt::Debug for Array {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match unsafe { crate::js_values::inspect(self.env, self.inner) } {
+      Ok(inspected) => write!(f, "Array({})", inspected),
+      Err(_) => write!(f, "Array(<uninspectable>)"),
+    }
+  }
+}
+
 impl Array {
   /// Create `Array` from `Vec<T>`
   pub fn from_vec<T>(env: &Env, value: Vec<T>) -> Result<Self>
   where
     T: ToNapiValue,
   {
-    let mut arr = Array::new(env.0, value.len() as u32)?;
-    value.into_iter().enumerate().try_for_each(|(index, val)| {
-      arr.set(index as u32, val)?;
-      Ok::<(), Error>(())
-    })?;
+    Array::from_iter_with_len(env, value.len() as u32, value)
+  }
+
+  /// Create an `Array` of `len` elements and fill it from `iter`.
+  ///
+  /// The array is allocated once up front, elements beyond `len` are ignored
+  /// and the missing ones are left as holes.
+  pub fn from_iter_with_len<T, I>(env: &Env, len: u32, iter: I) -> Result<Self>
+  where
+    T: ToNapiValue,
+    I: IntoIterator<Item = T>,
+  {
+    let arr = Array::new(env.0, len)?;
+    for (index, val) in (0..len).zip(iter) {
+      unsafe { set_element(arr.env, arr.inner, index, val)? };
+    }
     Ok(arr)
   }
 
+  /// Create an `Array` of numbers by copying `values` into a `Float64Array`,
+  /// which is converted by `Array.from` in a single call instead of one call per element.
+  pub fn from_numbers<T>(env: &Env, values: &[T]) -> Result<Self>
+  where
+    T: Into<f64> + Copy,
+  {
+    unsafe { create_numbers_array(env.0, values.iter().map(|v| (*v).into())) }
+  }
+
   /// Create `Array` from `&Vec<String>`
   pub fn from_ref_vec_string(env: &Env, value: &[String]) -> Result<Self> {
-    let mut arr = Array::new(env.0, value.len() as u32)?;
-    value.iter().enumerate().try_for_each(|(index, val)| {
-      arr.set(index as u32, val.as_str())?;
-      Ok::<(), Error>(())
-    })?;
-    Ok(arr)
+    Array::from_iter_with_len(env, value.len() as u32, value.iter().map(|v| v.as_str()))
   }
 
   /// Create `Array` from `&Vec<T: Copy + ToNapiValue>`
@@ -156,12 +202,7 @@ impl Array {
   where
     T: ToNapiValue + Copy,
   {
-    let mut arr = Array::new(env.0, value.len() as u32)?;
-    value.iter().enumerate().try_for_each(|(index, val)| {
-      arr.set(index as u32, *val)?;
-      Ok::<(), Error>(())
-    })?;
-    Ok(arr)
+    Array::from_iter_with_len(env, value.len() as u32, value.iter().copied())
   }
 }
 
@@ -186,11 +227,7 @@ where
   T: ToNapiValue,
 {
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    let mut arr = Array::new(env, val.len() as u32)?;
-
-    for (i, v) in val.into_iter().enumerate() {
-      arr.set(i as u32, v)?;
-    }
+    let arr = Array::from_iter_with_len(&Env::from(env), val.len() as u32, val)?;
 
     unsafe { Array::to_napi_value(env, arr) }
   }
@@ -200,11 +237,21 @@ macro_rules! impl_for_primitive_type {
   ($primitive_type:ident) => {
     impl ToNapiValue for &Vec<$primitive_type> {
       unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-        let mut arr = Array::new(env, val.len() as u32)?;
+        let arr =
+          Array::from_iter_with_len(&Env::from(env), val.len() as u32, val.iter().copied())?;
 
-        for (i, v) in val.iter().enumerate() {
-          arr.set(i as u32, *v)?;
-        }
+        unsafe { Array::to_napi_value(env, arr) }
+      }
+    }
+  };
+  ($primitive_type:ident, numbers) => {
+    impl ToNapiValue for &Vec<$primitive_type> {
+      unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        let arr = if val.len() >= TYPED_ARRAY_FAST_PATH_LEN {
+          Array::from_numbers(&Env::from(env), val)?
+        } else {
+          Array::from_iter_with_len(&Env::from(env), val.len() as u32, val.iter().copied())?
+        };
 
         unsafe { Array::to_napi_value(env, arr) }
       }
@@ -212,24 +259,98 @@ macro_rules! impl_for_primitive_type {
   };
 }
 
-impl_for_primitive_type!(u32);
-impl_for_primitive_type!(i32);
+impl_for_primitive_type!(u32, numbers);
+impl_for_primitive_type!(i32, numbers);
 impl_for_primitive_type!(i64);
-impl_for_primitive_type!(f64);
+impl_for_primitive_type!(f64, numbers);
 impl_for_primitive_type!(bool);
 
 impl ToNapiValue for &Vec<String> {
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    let mut arr = Array::new(env, val.len() as u32)?;
-
-    for (i, v) in val.iter().enumerate() {
-      arr.set(i as u32, v.as_str())?;
-    }
+    let arr = Array::from_iter_with_len(
+      &Env::from(env),
+      val.len() as u32,
+      val.iter().map(|v| v.as_str()),
+    )?;
 
     unsafe { Array::to_napi_value(env, arr) }
   }
 }
 
+/// Set an element of a pre-sized array, without tracking its length in `Array`
+unsafe fn set_element<T: ToNapiValue>(
+  env: sys::napi_env,
+  arr: sys::napi_value,
+  index: u32,
+  val: T,
+) -> Result<()> {
+  let napi_val = unsafe { T::to_napi_value(env, val)? };
+  check_status!(
+    unsafe { sys::napi_set_element(env, arr, index, napi_val) },
+    "Failed to set element with index `{}`",
+    index,
+  )
+}
+
+unsafe fn create_numbers_array<I>(env: sys::napi_env, values: I) -> Result<Array>
+where
+  I: ExactSizeIterator<Item = f64>,
+{
+  let len = values.len();
+  if len == 0 {
+    return Array::new(env, 0);
+  }
+  if len > u32::MAX as usize {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Array length {} exceeds the maximum of {}", len, u32::MAX),
+    ));
+  }
+
+  let mut data = ptr::null_mut();
+  let mut buffer = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_arraybuffer(env, len * 8, &mut data, &mut buffer) },
+    "Failed to create ArrayBuffer of {} numbers",
+    len,
+  )?;
+  // the backing store of an ArrayBuffer is aligned for any typed array
+  let slice = unsafe { std::slice::from_raw_parts_mut(data.cast::<f64>(), len) };
+  for (dst, val) in slice.iter_mut().zip(values) {
+    *dst = val;
+  }
+  let mut typed_array = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_typedarray(
+        env,
+        sys::TypedarrayType::float64_array,
+        len,
+        buffer,
+        0,
+        &mut typed_array,
+      )
+    },
+    "Failed to create Float64Array of {} numbers",
+    len,
+  )?;
+
+  let array_from: JsFunction = Env::from(env)
+    .get_global()?
+    .get_named_property::<JsFunction>("Array")?
+    .coerce_to_object()?
+    .get_named_property("from")?;
+  let arr = array_from.call(
+    None,
+    &[unsafe { JsUnknown::from_raw_unchecked(env, typed_array) }],
+  )?;
+  Ok(Array {
+    env,
+    inner: unsafe { arr.raw() },
+    len: len as u32,
+  })
+}
+
 impl<T> FromNapiValue for Vec<T>
 where
   T: FromNapiValue,
@@ -239,7 +360,7 @@ where
     let mut vec = vec![];
 
     for i in 0..arr.len() {
-      if let Some(val) = arr.get::<T>(i)? {
+      if let Some(val) = arr.get::<T>(i).map_err(|e| e.at_index(i as usize))? {
         vec.push(val);
       } else {
         return Err(Error::new(
@@ -261,3 +382,53 @@ where
     vec![ValueType::Object]
   }
 }
+
+/// Tuples are converted from and into JavaScript arrays of the same length
+macro_rules! impl_tuple_conversion {
+  ($len:expr, $($ident:ident $index:tt),*) => {
+    impl<$($ident),*> TypeName for ($($ident,)*) {
+      fn type_name() -> &'static str {
+        "Tuple"
+      }
+
+      fn value_type() -> ValueType {
+        ValueType::Object
+      }
+    }
+
+    impl<$($ident: ToNapiValue),*> ToNapiValue for ($($ident,)*) {
+      unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        let mut arr = Array::new(env, $len)?;
+        $(arr.set($index, val.$index)?;)*
+        unsafe { Array::to_napi_value(env, arr) }
+      }
+    }
+
+    impl<$($ident: FromNapiValue),*> FromNapiValue for ($($ident,)*) {
+      unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        let arr = unsafe { Array::from_napi_value(env, napi_val)? };
+        if arr.len() != $len {
+          return Err(Error::new(
+            Status::InvalidArg,
+            format!("Expected a tuple of length {}, but received an array of length {}", $len, arr.len()),
+          ));
+        }
+        Ok(($(arr.get::<$ident>($index)?.unwrap(),)*))
+      }
+    }
+
+    impl<$($ident: FromNapiValue),*> ValidateNapiValue for ($($ident,)*) {
+      fn type_of() -> Vec<ValueType> {
+        vec![ValueType::Object]
+      }
+    }
+  };
+}
+
+impl_tuple_conversion!(1, A 0);
+impl_tuple_conversion!(2, A 0, B 1);
+impl_tuple_conversion!(3, A 0, B 1, C 2);
+impl_tuple_conversion!(4, A 0, B 1, C 2, D 3);
+impl_tuple_conversion!(5, A 0, B 1, C 2, D 3, E 4);
+impl_tuple_conversion!(6, A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple_conversion!(7, A 0, B 1, C 2, D 3, E 4, F 5, G 6);
@@ -3,6 +3,7 @@ use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::ptr;
 
+use crate::bindgen_runtime::create_arraybuffer_copy_and_finalize;
 pub use crate::js_values::TypedArrayType;
 use crate::{check_status, sys, Error, Result, Status};
 
@@ -156,19 +157,30 @@ macro_rules! impl_typed_array {
           val.length,
           val.finalizer_notify,
         )));
-        check_status!(
+        let status = unsafe {
+          sys::napi_create_external_arraybuffer(
+            env,
+            val.data as *mut c_void,
+            length,
+            Some(finalizer::<$rust_type>),
+            hint_ptr as *mut c_void,
+            &mut arraybuffer_value,
+          )
+        };
+        if status == sys::Status::napi_no_external_buffers_allowed {
           unsafe {
-            sys::napi_create_external_arraybuffer(
+            create_arraybuffer_copy_and_finalize(
               env,
               val.data as *mut c_void,
               length,
-              Some(finalizer::<$rust_type>),
+              finalizer::<$rust_type>,
               hint_ptr as *mut c_void,
               &mut arraybuffer_value,
-            )
-          },
-          "Create external arraybuffer failed"
-        )?;
+            )?
+          };
+        } else {
+          check_status!(status, "Create external arraybuffer failed")?;
+        }
         let mut napi_val = ptr::null_mut();
         check_status!(
           unsafe {
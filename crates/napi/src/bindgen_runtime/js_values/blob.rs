@@ -0,0 +1,314 @@
+use std::ptr;
+use std::thread::{self, ThreadId};
+
+use crate::{
+  bindgen_prelude::*, check_status, sys, JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue,
+  ValueType,
+};
+
+/// Bytes converted into a JavaScript [`Blob`](https://nodejs.org/api/buffer.html#class-blob) when returned to JavaScript.
+///
+/// The `Blob` could be built from several chunks with [`Blob::push`], every chunk is passed to the `Blob` constructor as a zero copy `Buffer`, so large payloads could be produced piece by piece without concatenating them in Rust first.
+///
+/// Reading a `Blob` is asynchronous in JavaScript, so a `Blob` received from JavaScript only keeps a reference to it.
+/// Its `size` and `type` are available, and it's passed back as a part of the returned `Blob`, pass `await blob.arrayBuffer()` to Rust to consume its bytes.
+#[derive(Default)]
+pub struct Blob {
+  parts: Vec<BlobPart>,
+  mime_type: Option<String>,
+}
+
+enum BlobPart {
+  Bytes(Vec<u8>),
+  Js(JsBlobRef),
+}
+
+impl BlobPart {
+  fn len(&self) -> usize {
+    match self {
+      BlobPart::Bytes(bytes) => bytes.len(),
+      BlobPart::Js(js_blob) => js_blob.size,
+    }
+  }
+}
+
+/// A reference to a JavaScript `Blob`, it's only released on the thread of its `Env`
+struct JsBlobRef {
+  env: sys::napi_env,
+  reference: sys::napi_ref,
+  thread: ThreadId,
+  size: usize,
+}
+
+// The reference is only used on the thread which created it, see `Drop`
+unsafe impl Send for JsBlobRef {}
+unsafe impl Sync for JsBlobRef {}
+
+impl JsBlobRef {
+  fn get(&self, env: sys::napi_env) -> Result<sys::napi_value> {
+    if env != self.env || thread::current().id() != self.thread {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "A Blob received from JavaScript can only be returned to the same Env".to_owned(),
+      ));
+    }
+    let mut value = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env, self.reference, &mut value) },
+      "Failed to get the referenced Blob"
+    )?;
+    Ok(value)
+  }
+}
+
+impl Drop for JsBlobRef {
+  fn drop(&mut self) {
+    // Leak it if dropped on another thread, it's released along with the `Env` anyway
+    if thread::current().id() == self.thread {
+      unsafe { sys::napi_delete_reference(self.env, self.reference) };
+    }
+  }
+}
+
+impl Blob {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Set the MIME type, which is exposed as `blob.type` in JavaScript.
+  pub fn with_type<S: Into<String>>(mut self, mime_type: S) -> Self {
+    self.mime_type = Some(mime_type.into());
+    self
+  }
+
+  /// The MIME type, `blob.type` in JavaScript.
+  pub fn mime_type(&self) -> Option<&str> {
+    self.mime_type.as_deref()
+  }
+
+  /// Append a chunk to the end of the `Blob`.
+  pub fn push<B: Into<Vec<u8>>>(&mut self, chunk: B) {
+    self.parts.push(BlobPart::Bytes(chunk.into()));
+  }
+
+  /// Total size of all chunks in bytes.
+  pub fn len(&self) -> usize {
+    self.parts.iter().map(|p| p.len()).sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.parts.iter().all(|p| p.len() == 0)
+  }
+
+  fn parts_and_options(self, env: &Env) -> Result<(JsObject, JsObject)> {
+    let mut parts = env.create_array(self.parts.len() as u32)?;
+    for (index, part) in self.parts.into_iter().enumerate() {
+      match part {
+        BlobPart::Bytes(bytes) => parts.set(index as u32, Buffer::from(bytes))?,
+        BlobPart::Js(js_blob) => {
+          let value = js_blob.get(env.raw())?;
+          parts.set(index as u32, unsafe {
+            JsObject::from_raw_unchecked(env.raw(), value)
+          })?
+        }
+      }
+    }
+    let mut options = env.create_object()?;
+    if let Some(mime_type) = self.mime_type {
+      options.set_named_property("type", env.create_string_from_std(mime_type)?)?;
+    }
+    Ok((parts.coerce_to_object()?, options))
+  }
+}
+
+impl From<Vec<u8>> for Blob {
+  fn from(data: Vec<u8>) -> Self {
+    let mut blob = Blob::new();
+    blob.push(data);
+    blob
+  }
+}
+
+impl From<&[u8]> for Blob {
+  fn from(data: &[u8]) -> Self {
+    Blob::from(data.to_owned())
+  }
+}
+
+impl<B: Into<Vec<u8>>> FromIterator<B> for Blob {
+  fn from_iter<I: IntoIterator<Item = B>>(iter: I) -> Self {
+    let mut blob = Blob::new();
+    iter.into_iter().for_each(|chunk| blob.push(chunk));
+    blob
+  }
+}
+
+impl<B: Into<Vec<u8>>> Extend<B> for Blob {
+  fn extend<I: IntoIterator<Item = B>>(&mut self, iter: I) {
+    iter.into_iter().for_each(|chunk| self.push(chunk));
+  }
+}
+
+impl TypeName for Blob {
+  fn type_name() -> &'static str {
+    "Blob"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl FromNapiValue for Blob {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let env = unsafe { Env::from_raw(env) };
+    let object = unsafe { expect_web_instance(&env, napi_val, "Blob")? };
+    let size: f64 = object.get_value("size")?;
+    let mime_type: String = object.get_value("type")?;
+    let mut reference = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env.raw(), napi_val, 1, &mut reference) },
+      "Failed to create reference of Blob"
+    )?;
+    Ok(Blob {
+      parts: vec![BlobPart::Js(JsBlobRef {
+        env: env.raw(),
+        reference,
+        thread: thread::current().id(),
+        size: size as usize,
+      })],
+      mime_type: if mime_type.is_empty() {
+        None
+      } else {
+        Some(mime_type)
+      },
+    })
+  }
+}
+
+impl ToNapiValue for Blob {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let env = unsafe { Env::from_raw(env) };
+    let constructor = get_web_constructor(&env, "Blob")?;
+    let (parts, options) = val.parts_and_options(&env)?;
+    let blob = constructor.new_instance(&[parts.into_unknown(), options.into_unknown()])?;
+    Ok(unsafe { blob.raw() })
+  }
+}
+
+/// A [`Blob`] with a file name, converted from and into a JavaScript [`File`](https://nodejs.org/api/buffer.html#class-file).
+///
+/// `File` is available since Node.js 20, converting it on older versions fails with `GenericFailure`.
+pub struct FileBlob {
+  blob: Blob,
+  name: String,
+  last_modified: Option<f64>,
+}
+
+impl FileBlob {
+  pub fn new<S: Into<String>>(name: S, blob: Blob) -> Self {
+    FileBlob {
+      blob,
+      name: name.into(),
+      last_modified: None,
+    }
+  }
+
+  /// Set `file.lastModified`, in milliseconds since the Unix epoch.
+  pub fn with_last_modified(mut self, last_modified: f64) -> Self {
+    self.last_modified = Some(last_modified);
+    self
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// `file.lastModified`, in milliseconds since the Unix epoch.
+  pub fn last_modified(&self) -> Option<f64> {
+    self.last_modified
+  }
+
+  pub fn blob(&self) -> &Blob {
+    &self.blob
+  }
+
+  pub fn blob_mut(&mut self) -> &mut Blob {
+    &mut self.blob
+  }
+
+  pub fn into_blob(self) -> Blob {
+    self.blob
+  }
+}
+
+impl TypeName for FileBlob {
+  fn type_name() -> &'static str {
+    "File"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl FromNapiValue for FileBlob {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let js_env = unsafe { Env::from_raw(env) };
+    let object = unsafe { expect_web_instance(&js_env, napi_val, "File")? };
+    let name: String = object.get_value("name")?;
+    let last_modified: f64 = object.get_value("lastModified")?;
+    // A `File` is a `Blob`, it's referenced the same way
+    let blob = unsafe { Blob::from_napi_value(env, napi_val)? };
+    Ok(FileBlob::new(name, blob).with_last_modified(last_modified))
+  }
+}
+
+impl ToNapiValue for FileBlob {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let env = unsafe { Env::from_raw(env) };
+    let constructor = get_web_constructor(&env, "File")?;
+    let (parts, mut options) = val.blob.parts_and_options(&env)?;
+    if let Some(last_modified) = val.last_modified {
+      options.set_named_property("lastModified", env.create_double(last_modified)?)?;
+    }
+    let file = constructor.new_instance(&[
+      parts.into_unknown(),
+      env.create_string_from_std(val.name)?.into_unknown(),
+      options.into_unknown(),
+    ])?;
+    Ok(unsafe { file.raw() })
+  }
+}
+
+/// `napi_val` as an object, if it's an instance of the `Blob` or `File` constructor `name`
+unsafe fn expect_web_instance(
+  env: &Env,
+  napi_val: sys::napi_value,
+  name: &str,
+) -> Result<JsObject> {
+  let value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), napi_val) };
+  if value.get_type()? != ValueType::Object || !value.instanceof(get_web_constructor(env, name)?)? {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Expected a {}, but received {}", name, value.get_type()?),
+    ));
+  }
+  Ok(unsafe { value.cast() })
+}
+
+fn get_web_constructor(env: &Env, name: &str) -> Result<JsFunction> {
+  let global = env.get_global()?;
+  let mut constructor: JsUnknown = global.get_named_property(name)?;
+  // `Blob` and `File` are only exposed on `globalThis` since Node.js 18 and 20
+  if constructor.get_type()? != ValueType::Function {
+    constructor = env.get_builtin_module("buffer")?.get_named_property(name)?;
+  }
+  if constructor.get_type()? != ValueType::Function {
+    return Err(Error::new(
+      Status::GenericFailure,
+      format!("`{}` is not supported in this Node.js version", name),
+    ));
+  }
+  Ok(unsafe { constructor.cast() })
+}
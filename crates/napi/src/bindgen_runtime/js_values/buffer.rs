@@ -1,6 +1,9 @@
+use std::borrow::Cow;
+use std::ffi::c_void;
 use std::ops::{Deref, DerefMut};
 use std::{mem, ptr};
 
+use crate::bindgen_runtime::create_buffer_copy_and_finalize;
 use crate::{bindgen_prelude::*, check_status, sys, Result, ValueType};
 
 /// zero copy u8 vector shared between rust and napi
@@ -83,25 +86,72 @@ impl FromNapiValue for Buffer {
 impl ToNapiValue for Buffer {
   unsafe fn to_napi_value(env: sys::napi_env, mut val: Self) -> Result<sys::napi_value> {
     let len = val.inner.len();
+    let mut ret = ptr::null_mut();
+    let data = val.inner.as_mut_ptr() as *mut c_void;
+    let hint = Box::into_raw(Box::new((len, val.inner.capacity()))) as *mut c_void;
+    let status = unsafe {
+      sys::napi_create_external_buffer(env, len, data, Some(drop_buffer), hint, &mut ret)
+    };
+    if status == sys::Status::napi_no_external_buffers_allowed {
+      unsafe { create_buffer_copy_and_finalize(env, data, len, drop_buffer, hint, &mut ret)? };
+    } else {
+      check_status!(status, "Failed to create napi buffer")?;
+    }
+
+    Ok(ret)
+  }
+}
+
+impl TypeName for &[u8] {
+  fn type_name() -> &'static str {
+    "&[u8]"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+/// Copies the bytes into a new `Buffer`, since JavaScript can't borrow Rust memory
+impl ToNapiValue for &[u8] {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
     let mut ret = ptr::null_mut();
     check_status!(
       unsafe {
-        sys::napi_create_external_buffer(
+        sys::napi_create_buffer_copy(
           env,
-          len,
-          val.inner.as_mut_ptr() as *mut _,
-          Some(drop_buffer),
-          Box::into_raw(Box::new((len, val.inner.capacity()))) as *mut _,
+          val.len(),
+          val.as_ptr() as *const c_void,
+          ptr::null_mut(),
           &mut ret,
         )
       },
       "Failed to create napi buffer"
     )?;
-
     Ok(ret)
   }
 }
 
+impl TypeName for Cow<'_, [u8]> {
+  fn type_name() -> &'static str {
+    "Cow<[u8]>"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+/// Owned bytes are shared with JavaScript like a [`Buffer`], borrowed ones are copied
+impl ToNapiValue for Cow<'_, [u8]> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    match val {
+      Cow::Borrowed(bytes) => unsafe { <&[u8]>::to_napi_value(env, bytes) },
+      Cow::Owned(bytes) => unsafe { Buffer::to_napi_value(env, bytes.into()) },
+    }
+  }
+}
+
 impl ValidateNapiValue for Buffer {
   fn type_of() -> Vec<ValueType> {
     vec![ValueType::Object]
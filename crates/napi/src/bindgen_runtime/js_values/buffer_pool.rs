@@ -0,0 +1,232 @@
+use std::ffi::c_void;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+use std::{mem, ptr};
+
+use crate::bindgen_runtime::{
+  create_arraybuffer_copy_and_finalize, create_buffer_copy_and_finalize,
+};
+use crate::{bindgen_prelude::*, check_status, sys, Result, ValueType};
+
+/// A pool of the memory backing `Buffer`s and `ArrayBuffer`s.
+///
+/// The memory of a [`PooledBuffer`] returns to the pool once JavaScript garbage collects the
+/// `Buffer` created from it, so the addons producing many short-lived buffers, e.g. the chunks of
+/// a stream, don't have to allocate every one of them.
+///
+/// ```rust
+/// use napi::bindgen_prelude::*;
+///
+/// fn read_chunk(pool: &BufferPool, data: &[u8]) -> PooledBuffer {
+///   let mut chunk = pool.alloc(data.len());
+///   chunk.copy_from_slice(data);
+///   chunk
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BufferPool {
+  inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+  free: Mutex<Vec<Vec<u8>>>,
+  max_buffers: usize,
+  max_buffer_size: usize,
+}
+
+impl PoolInner {
+  fn recycle(&self, mut data: Vec<u8>) {
+    if data.capacity() > self.max_buffer_size {
+      return;
+    }
+    if let Ok(mut free) = self.free.lock() {
+      if free.len() < self.max_buffers {
+        data.clear();
+        free.push(data);
+      }
+    }
+  }
+}
+
+impl BufferPool {
+  /// Keep at most `max_buffers` idle buffers, whose capacities are at most `max_buffer_size` bytes
+  pub fn new(max_buffers: usize, max_buffer_size: usize) -> Self {
+    Self {
+      inner: Arc::new(PoolInner {
+        free: Mutex::new(Vec::with_capacity(max_buffers)),
+        max_buffers,
+        max_buffer_size,
+      }),
+    }
+  }
+
+  /// Get a zero filled buffer of `len` bytes, reusing an idle one when it's large enough
+  pub fn alloc(&self, len: usize) -> PooledBuffer {
+    let recycled = self.inner.free.lock().ok().and_then(|mut free| {
+      free
+        .iter()
+        .position(|data| data.capacity() >= len)
+        .map(|index| free.swap_remove(index))
+    });
+    let mut data = recycled.unwrap_or_else(|| Vec::with_capacity(len));
+    data.resize(len, 0);
+    PooledBuffer {
+      data: mem::ManuallyDrop::new(data),
+      pool: self.inner.clone(),
+    }
+  }
+
+  /// Count of the idle buffers
+  pub fn idle_count(&self) -> usize {
+    self.inner.free.lock().map(|free| free.len()).unwrap_or(0)
+  }
+
+  /// Release the memory of all idle buffers
+  pub fn clear(&self) {
+    if let Ok(mut free) = self.inner.free.lock() {
+      free.clear();
+    }
+  }
+}
+
+/// Bytes allocated from a [`BufferPool`], converted into a JavaScript `Buffer` without copying
+pub struct PooledBuffer {
+  data: mem::ManuallyDrop<Vec<u8>>,
+  pool: Arc<PoolInner>,
+}
+
+impl PooledBuffer {
+  /// Convert into a JavaScript `ArrayBuffer` rather than a `Buffer`
+  pub fn into_array_buffer(self) -> PooledArrayBuffer {
+    PooledArrayBuffer(self)
+  }
+
+  /// Take the parts released in the finalizer, the data is not returned to the pool on drop
+  fn into_raw(self) -> (*mut c_void, usize, *mut c_void) {
+    let mut this = mem::ManuallyDrop::new(self);
+    let len = this.data.len();
+    let data = this.data.as_mut_ptr() as *mut c_void;
+    let pool = unsafe { ptr::read(&this.pool) };
+    let hint = Box::into_raw(Box::new((len, this.data.capacity(), pool))) as *mut c_void;
+    (data, len, hint)
+  }
+}
+
+impl Drop for PooledBuffer {
+  fn drop(&mut self) {
+    let data = unsafe { mem::ManuallyDrop::take(&mut self.data) };
+    self.pool.recycle(data);
+  }
+}
+
+impl Deref for PooledBuffer {
+  type Target = [u8];
+
+  fn deref(&self) -> &Self::Target {
+    self.data.as_slice()
+  }
+}
+
+impl DerefMut for PooledBuffer {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.data.as_mut_slice()
+  }
+}
+
+impl AsRef<[u8]> for PooledBuffer {
+  fn as_ref(&self) -> &[u8] {
+    self.data.as_slice()
+  }
+}
+
+impl AsMut<[u8]> for PooledBuffer {
+  fn as_mut(&mut self) -> &mut [u8] {
+    self.data.as_mut_slice()
+  }
+}
+
+impl TypeName for PooledBuffer {
+  fn type_name() -> &'static str {
+    "Vec<u8>"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ToNapiValue for PooledBuffer {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let (data, len, hint) = val.into_raw();
+    let mut ret = ptr::null_mut();
+    let status = unsafe {
+      sys::napi_create_external_buffer(env, len, data, Some(recycle_buffer), hint, &mut ret)
+    };
+    if status == sys::Status::napi_no_external_buffers_allowed {
+      unsafe { create_buffer_copy_and_finalize(env, data, len, recycle_buffer, hint, &mut ret)? };
+    } else if status != sys::Status::napi_ok {
+      // the finalizer never runs, return the memory to the pool now
+      unsafe { recycle_buffer(env, data, hint) };
+      check_status!(status, "Failed to create napi buffer")?;
+    }
+
+    Ok(ret)
+  }
+}
+
+/// [`PooledBuffer`] converted into a JavaScript `ArrayBuffer`
+pub struct PooledArrayBuffer(PooledBuffer);
+
+impl Deref for PooledArrayBuffer {
+  type Target = PooledBuffer;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl DerefMut for PooledArrayBuffer {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.0
+  }
+}
+
+impl TypeName for PooledArrayBuffer {
+  fn type_name() -> &'static str {
+    "ArrayBuffer"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ToNapiValue for PooledArrayBuffer {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let (data, len, hint) = val.0.into_raw();
+    let mut ret = ptr::null_mut();
+    let status = unsafe {
+      sys::napi_create_external_arraybuffer(env, data, len, Some(recycle_buffer), hint, &mut ret)
+    };
+    if status == sys::Status::napi_no_external_buffers_allowed {
+      unsafe {
+        create_arraybuffer_copy_and_finalize(env, data, len, recycle_buffer, hint, &mut ret)?
+      };
+    } else if status != sys::Status::napi_ok {
+      unsafe { recycle_buffer(env, data, hint) };
+      check_status!(status, "Failed to create napi arraybuffer")?;
+    }
+
+    Ok(ret)
+  }
+}
+
+unsafe extern "C" fn recycle_buffer(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  let (length, cap, pool) =
+    unsafe { *Box::from_raw(finalize_hint as *mut (usize, usize, Arc<PoolInner>)) };
+  pool.recycle(unsafe { Vec::from_raw_parts(finalize_data as *mut u8, length, cap) });
+}
@@ -0,0 +1,76 @@
+use std::ptr;
+
+use bytes::{Bytes, BytesMut};
+
+use crate::{bindgen_prelude::*, check_status, sys, ValueType};
+
+impl TypeName for Bytes {
+  fn type_name() -> &'static str {
+    "Bytes"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for Bytes {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+/// The `Buffer` takes over the memory of the `Bytes` without copying it if it's the only reference to it,
+/// otherwise the content is copied. V8 doesn't allow two `Buffer`s on the same memory, e.g. of cloned `Bytes`.
+impl ToNapiValue for Bytes {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Bytes) -> Result<sys::napi_value> {
+    if val.is_empty() {
+      let mut ret = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_create_buffer(env, 0, ptr::null_mut(), &mut ret) },
+        "Failed to create napi buffer"
+      )?;
+      return Ok(ret);
+    }
+    unsafe { Buffer::to_napi_value(env, Vec::from(val).into()) }
+  }
+}
+
+/// Copies the content of the `Buffer` or `Uint8Array`, which is only accessible on the JavaScript thread
+impl FromNapiValue for Bytes {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let bytes = unsafe { Uint8Array::from_napi_value(env, napi_val)? };
+    Ok(Bytes::copy_from_slice(&bytes))
+  }
+}
+
+impl TypeName for BytesMut {
+  fn type_name() -> &'static str {
+    "BytesMut"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for BytesMut {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+/// Frozen into [`Bytes`] without copying
+impl ToNapiValue for BytesMut {
+  unsafe fn to_napi_value(env: sys::napi_env, val: BytesMut) -> Result<sys::napi_value> {
+    unsafe { Bytes::to_napi_value(env, val.freeze()) }
+  }
+}
+
+/// Copies the content of the `Buffer` or `Uint8Array`, like [`Bytes`]
+impl FromNapiValue for BytesMut {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let bytes = unsafe { Uint8Array::from_napi_value(env, napi_val)? };
+    Ok(BytesMut::from(&bytes[..]))
+  }
+}
@@ -0,0 +1,225 @@
+use crate::{bindgen_prelude::*, sys, ValueType};
+
+/// `number` as JavaScript prints it, `Infinity` rather than Rust's `inf`
+pub(crate) fn js_number_string(value: f64) -> String {
+  if value == f64::INFINITY {
+    "Infinity".to_owned()
+  } else if value == f64::NEG_INFINITY {
+    "-Infinity".to_owned()
+  } else {
+    value.to_string()
+  }
+}
+
+macro_rules! impl_checked_number {
+  ($(($name:ident, $t:ident),)*) => {
+    $(
+      #[doc = concat!(
+        "`", stringify!($t), "` converted from a `number`, a `TypeError` is thrown for `NaN`, `Infinity`, ",
+        "numbers with a fraction and numbers out of the range of `", stringify!($t), "`",
+      )]
+      #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+      pub struct $name(pub $t);
+
+      impl TypeName for $name {
+        fn type_name() -> &'static str {
+          "number"
+        }
+
+        fn value_type() -> ValueType {
+          ValueType::Number
+        }
+      }
+
+      impl ValidateNapiValue for $name {
+        fn type_of() -> Vec<ValueType> {
+          vec![ValueType::Number]
+        }
+      }
+
+      impl ToNapiValue for $name {
+        unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+          unsafe { f64::to_napi_value(env, val.0 as f64) }
+        }
+      }
+
+      impl FromNapiValue for $name {
+        unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+          let value = unsafe { f64::from_napi_value(env, napi_val)? };
+          if !value.is_finite() || value.fract() != 0.0 {
+            return Err(
+              Error::new(
+                Status::InvalidArg,
+                format!(
+                  "Expected an integer for `{}`, got {}",
+                  stringify!($t),
+                  js_number_string(value)
+                ),
+              )
+              .with_class(ErrorClass::TypeError),
+            );
+          }
+          if value < $t::MIN as f64 || value > $t::MAX as f64 {
+            return Err(
+              Error::new(
+                Status::InvalidArg,
+                format!(
+                  "{} is out of the range of `{}`",
+                  js_number_string(value),
+                  stringify!($t)
+                ),
+              )
+              .with_class(ErrorClass::TypeError),
+            );
+          }
+          Ok($name(value as $t))
+        }
+      }
+    )*
+  };
+}
+
+impl_checked_number!(
+  (CheckedI8, i8),
+  (CheckedU8, u8),
+  (CheckedI16, i16),
+  (CheckedU16, u16),
+  (CheckedI32, i32),
+  (CheckedU32, u32),
+);
+
+pub(crate) fn non_finite_error(value: f64, type_name: &str) -> Error {
+  Error::new(
+    Status::InvalidArg,
+    format!(
+      "Cannot convert {} of `{}` into a JavaScript number",
+      js_number_string(value),
+      type_name
+    ),
+  )
+  .with_class(ErrorClass::TypeError)
+}
+
+/// `f32` and `f64`, converted with `#[napi(non_finite)]`
+pub trait JsFloat: Copy {
+  const NAME: &'static str;
+
+  fn to_f64(self) -> f64;
+
+  fn from_f64(value: f64) -> Self;
+}
+
+impl JsFloat for f32 {
+  const NAME: &'static str = "f32";
+
+  fn to_f64(self) -> f64 {
+    self as f64
+  }
+
+  fn from_f64(value: f64) -> Self {
+    value as f32
+  }
+}
+
+impl JsFloat for f64 {
+  const NAME: &'static str = "f64";
+
+  fn to_f64(self) -> f64 {
+    self
+  }
+
+  fn from_f64(value: f64) -> Self {
+    value
+  }
+}
+
+/// A `number` which is neither `NaN` nor `Infinity`, a `TypeError` is thrown for them in both directions
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Finite<T: JsFloat>(pub T);
+
+impl<T: JsFloat> TypeName for Finite<T> {
+  fn type_name() -> &'static str {
+    "number"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Number
+  }
+}
+
+impl<T: JsFloat> ValidateNapiValue for Finite<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Number]
+  }
+}
+
+impl<T: JsFloat> ToNapiValue for Finite<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let value = val.0.to_f64();
+    if !value.is_finite() {
+      return Err(non_finite_error(value, T::NAME));
+    }
+    unsafe { f64::to_napi_value(env, value) }
+  }
+}
+
+impl<T: JsFloat> FromNapiValue for Finite<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let value = unsafe { f64::from_napi_value(env, napi_val)? };
+    if !value.is_finite() {
+      return Err(
+        Error::new(
+          Status::InvalidArg,
+          format!(
+            "Expected a finite number for `{}`, got {}",
+            T::NAME,
+            js_number_string(value)
+          ),
+        )
+        .with_class(ErrorClass::TypeError),
+      );
+    }
+    Ok(Finite(T::from_f64(value)))
+  }
+}
+
+/// `NaN` and `Infinity` are converted into `null` like `JSON.stringify` does, `null` is converted back into `NaN`
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct NonFiniteAsNull<T: JsFloat>(pub T);
+
+impl<T: JsFloat> TypeName for NonFiniteAsNull<T> {
+  fn type_name() -> &'static str {
+    "number | null"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Number
+  }
+}
+
+impl<T: JsFloat> ValidateNapiValue for NonFiniteAsNull<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Number, ValueType::Null]
+  }
+}
+
+impl<T: JsFloat> ToNapiValue for NonFiniteAsNull<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let value = val.0.to_f64();
+    if value.is_finite() {
+      unsafe { f64::to_napi_value(env, value) }
+    } else {
+      unsafe { Null::to_napi_value(env, Null) }
+    }
+  }
+}
+
+impl<T: JsFloat> FromNapiValue for NonFiniteAsNull<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    if type_of!(env, napi_val)? == ValueType::Null {
+      return Ok(NonFiniteAsNull(T::from_f64(f64::NAN)));
+    }
+    let value = unsafe { f64::from_napi_value(env, napi_val)? };
+    Ok(NonFiniteAsNull(T::from_f64(value)))
+  }
+}
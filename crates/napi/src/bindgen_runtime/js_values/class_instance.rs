@@ -0,0 +1,68 @@
+use std::ops::{Deref, DerefMut};
+
+use super::{FromNapiMutRef, FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use crate::{sys, JsObject, NapiRaw, NapiValue, Result, ValueType};
+
+/// An instance of the `#[napi]` class `T` created by this addon, received from JavaScript.
+///
+/// It derefs to `T` and keeps the JavaScript object, e.g. to return the same object.
+/// Instances of other classes or addons fail to convert instead of being read as `T`.
+pub struct ClassInstance<T: 'static> {
+  object: JsObject,
+  inner: &'static mut T,
+}
+
+impl<T: 'static> ClassInstance<T> {
+  pub fn as_object(&self) -> &JsObject {
+    &self.object
+  }
+
+  pub fn into_object(self) -> JsObject {
+    self.object
+  }
+}
+
+impl<T: 'static> Deref for ClassInstance<T> {
+  type Target = T;
+
+  fn deref(&self) -> &Self::Target {
+    self.inner
+  }
+}
+
+impl<T: 'static> DerefMut for ClassInstance<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.inner
+  }
+}
+
+impl<T: TypeName + 'static> TypeName for ClassInstance<T> {
+  fn type_name() -> &'static str {
+    T::type_name()
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: FromNapiMutRef + 'static> FromNapiValue for ClassInstance<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Self {
+      inner: unsafe { T::from_napi_mut_ref(env, napi_val)? },
+      object: unsafe { JsObject::from_raw_unchecked(env, napi_val) },
+    })
+  }
+}
+
+impl<T: FromNapiMutRef + TypeName + 'static> ValidateNapiValue for ClassInstance<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl<T: 'static> ToNapiValue for ClassInstance<T> {
+  unsafe fn to_napi_value(_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    Ok(unsafe { val.object.raw() })
+  }
+}
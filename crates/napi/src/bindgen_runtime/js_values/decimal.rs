@@ -0,0 +1,240 @@
+use crate::{bindgen_prelude::*, sys, type_of, ValueType};
+
+unsafe fn get_decimal_string(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  rust_type: &str,
+) -> Result<String> {
+  let value_type = type_of!(env, napi_val)?;
+  if value_type != ValueType::String {
+    return Err(Error::new(
+      Status::StringExpected,
+      format!(
+        "Expected a decimal string to convert into rust type `{}`, but received {}",
+        rust_type, value_type
+      ),
+    ));
+  }
+  unsafe { String::from_napi_value(env, napi_val) }
+}
+
+fn invalid_decimal<E: std::fmt::Display>(value: &str, rust_type: &str, e: E) -> Error {
+  Error::new(
+    Status::InvalidArg,
+    format!(
+      "Failed to convert `{}` into rust type `{}`, {}",
+      value, rust_type, e
+    ),
+  )
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_impl {
+  #[cfg(feature = "napi6")]
+  use std::ptr;
+
+  use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+  use rust_decimal::Decimal;
+
+  use super::*;
+
+  impl TypeName for Decimal {
+    fn type_name() -> &'static str {
+      "Decimal"
+    }
+
+    fn value_type() -> ValueType {
+      ValueType::String
+    }
+  }
+
+  impl ValidateNapiValue for Decimal {
+    fn type_of() -> Vec<ValueType> {
+      vec![ValueType::String]
+    }
+  }
+
+  /// A string keeps every digit, see [`DecimalNumber`] and [`ScaledDecimal`] for the other representations
+  impl ToNapiValue for Decimal {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Decimal) -> Result<sys::napi_value> {
+      unsafe { String::to_napi_value(env, val.to_string()) }
+    }
+  }
+
+  impl FromNapiValue for Decimal {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+      let value = unsafe { get_decimal_string(env, napi_val, "Decimal")? };
+      value
+        .parse()
+        .map_err(|e| invalid_decimal(&value, "Decimal", e))
+    }
+  }
+
+  /// A [`Decimal`] converted from and into a JavaScript `number`.
+  ///
+  /// A `number` can't represent most decimal fractions exactly, so only use it where the precision doesn't matter.
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+  pub struct DecimalNumber(pub Decimal);
+
+  impl TypeName for DecimalNumber {
+    fn type_name() -> &'static str {
+      "DecimalNumber"
+    }
+
+    fn value_type() -> ValueType {
+      ValueType::Number
+    }
+  }
+
+  impl ValidateNapiValue for DecimalNumber {
+    fn type_of() -> Vec<ValueType> {
+      vec![ValueType::Number]
+    }
+  }
+
+  impl ToNapiValue for DecimalNumber {
+    unsafe fn to_napi_value(env: sys::napi_env, val: DecimalNumber) -> Result<sys::napi_value> {
+      let value = val.0.to_f64().ok_or_else(|| {
+        Error::new(
+          Status::InvalidArg,
+          format!("Failed to convert `{}` into a number", val.0),
+        )
+      })?;
+      unsafe { f64::to_napi_value(env, value) }
+    }
+  }
+
+  impl FromNapiValue for DecimalNumber {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+      let value = unsafe { f64::from_napi_value(env, napi_val)? };
+      // `from_f64` drops the binary representation error, `0.1` rather than `0.1000000000000000055511151231257827`
+      Decimal::from_f64(value)
+        .map(DecimalNumber)
+        .ok_or_else(|| invalid_decimal(&value.to_string(), "Decimal", "out of range"))
+    }
+  }
+
+  /// A [`Decimal`] converted from and into a JavaScript `bigint` of the value times `10^SCALE`,
+  /// e.g. cents as `ScaledDecimal<2>`.
+  ///
+  /// Converting a decimal with more than `SCALE` fraction digits fails rather than rounding it.
+  #[cfg(feature = "napi6")]
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+  pub struct ScaledDecimal<const SCALE: u32>(pub Decimal);
+
+  #[cfg(feature = "napi6")]
+  impl<const SCALE: u32> TypeName for ScaledDecimal<SCALE> {
+    fn type_name() -> &'static str {
+      "ScaledDecimal"
+    }
+
+    fn value_type() -> ValueType {
+      ValueType::BigInt
+    }
+  }
+
+  #[cfg(feature = "napi6")]
+  impl<const SCALE: u32> ValidateNapiValue for ScaledDecimal<SCALE> {
+    fn type_of() -> Vec<ValueType> {
+      vec![ValueType::BigInt]
+    }
+  }
+
+  #[cfg(feature = "napi6")]
+  impl<const SCALE: u32> ToNapiValue for ScaledDecimal<SCALE> {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+      let mut decimal = val.0.normalize();
+      if decimal.scale() > SCALE {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!(
+            "Failed to convert `{}` into a bigint scaled by 10^{}, it has more fraction digits",
+            val.0, SCALE
+          ),
+        ));
+      }
+      decimal.rescale(SCALE);
+      let mantissa = decimal.mantissa();
+      let magnitude = mantissa.unsigned_abs();
+      let words = [magnitude as u64, (magnitude >> 64) as u64];
+      let mut raw_value = ptr::null_mut();
+      check_status!(unsafe {
+        sys::napi_create_bigint_words(
+          env,
+          (mantissa < 0) as i32,
+          words.len(),
+          words.as_ptr(),
+          &mut raw_value,
+        )
+      })?;
+      Ok(raw_value)
+    }
+  }
+
+  #[cfg(feature = "napi6")]
+  impl<const SCALE: u32> FromNapiValue for ScaledDecimal<SCALE> {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+      let bigint = unsafe { BigInt::from_napi_value(env, napi_val)? };
+      let out_of_range = || {
+        Error::new(
+          Status::InvalidArg,
+          format!(
+            "BigInt scaled by 10^{} is out of the range of rust type `Decimal`",
+            SCALE
+          ),
+        )
+      };
+      let magnitude = match bigint.words.as_slice() {
+        [low] => *low as u128,
+        [low, high] => *low as u128 | (*high as u128) << 64,
+        _ => return Err(out_of_range()),
+      };
+      let mantissa = i128::try_from(magnitude).map_err(|_| out_of_range())?;
+      let mantissa = if bigint.sign_bit { -mantissa } else { mantissa };
+      Decimal::try_from_i128_with_scale(mantissa, SCALE)
+        .map(ScaledDecimal)
+        .map_err(|_| out_of_range())
+    }
+  }
+}
+
+#[cfg(feature = "rust_decimal")]
+pub use rust_decimal_impl::*;
+
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal_impl {
+  use bigdecimal::BigDecimal;
+
+  use super::*;
+
+  impl TypeName for BigDecimal {
+    fn type_name() -> &'static str {
+      "BigDecimal"
+    }
+
+    fn value_type() -> ValueType {
+      ValueType::String
+    }
+  }
+
+  impl ValidateNapiValue for BigDecimal {
+    fn type_of() -> Vec<ValueType> {
+      vec![ValueType::String]
+    }
+  }
+
+  impl ToNapiValue for BigDecimal {
+    unsafe fn to_napi_value(env: sys::napi_env, val: BigDecimal) -> Result<sys::napi_value> {
+      unsafe { String::to_napi_value(env, val.to_string()) }
+    }
+  }
+
+  impl FromNapiValue for BigDecimal {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+      let value = unsafe { get_decimal_string(env, napi_val, "BigDecimal")? };
+      value
+        .parse()
+        .map_err(|e| invalid_decimal(&value, "BigDecimal", e))
+    }
+  }
+}
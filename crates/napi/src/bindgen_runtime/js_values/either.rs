@@ -1,7 +1,51 @@
+use std::ptr;
+
 use super::{FromNapiValue, ToNapiValue, TypeName};
-use crate::{type_of, JsNull, JsUndefined, NapiRaw, Status, ValueType};
+use crate::{sys, type_of, JsNull, JsUndefined, JsUnknown, NapiRaw, NapiValue, Status, ValueType};
+
+/// Try the variant if the type of the value matches, the first failed conversion is kept in `error`.
+///
+/// Variants of the same type, e.g. two `#[napi(object)]` structs, are told apart by whether their
+/// conversion succeeds, the first successful one wins.
+unsafe fn try_variant<T: TypeName + FromNapiValue, E>(
+  env: napi_sys::napi_env,
+  napi_val: napi_sys::napi_value,
+  js_type: ValueType,
+  error: &mut Option<crate::Error>,
+  variant: fn(T) -> E,
+) -> Option<E> {
+  if T::value_type() != js_type && T::value_type() != ValueType::Unknown {
+    return None;
+  }
+  match unsafe { T::from_napi_value(env, napi_val) } {
+    Ok(value) => Some(variant(value)),
+    Err(e) => {
+      // e.g. a getter threw, the next variant must not be tried with the exception pending
+      let e = match unsafe { take_exception(env) } {
+        Some(exception) => exception_to_error(exception).unwrap_or(e),
+        None => e,
+      };
+      error.get_or_insert(e);
+      None
+    }
+  }
+}
 
-const ERROR_MSG: &str = "The return value of typeof(T) should not be equal in Either";
+unsafe fn take_exception(env: sys::napi_env) -> Option<JsUnknown> {
+  let mut is_pending = false;
+  unsafe { sys::napi_is_exception_pending(env, &mut is_pending) };
+  if !is_pending {
+    return None;
+  }
+  let mut exception = ptr::null_mut();
+  unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+  Some(unsafe { JsUnknown::from_raw_unchecked(env, exception) })
+}
+
+fn exception_to_error(exception: JsUnknown) -> crate::Result<crate::Error> {
+  let reason = exception.coerce_to_string()?.into_utf8()?.into_owned()?;
+  Ok(crate::Error::new(Status::GenericFailure, reason))
+}
 
 #[derive(Debug, Clone, Copy)]
 pub enum Either<
@@ -65,14 +109,16 @@ impl<A: TypeName + FromNapiValue + ToNapiValue, B: TypeName + FromNapiValue + To
     env: napi_sys::napi_env,
     napi_val: napi_sys::napi_value,
   ) -> crate::Result<Self> {
-    debug_assert!(A::value_type() != B::value_type(), "{}", ERROR_MSG);
     let js_type = type_of!(env, napi_val)?;
-    if js_type == A::value_type() {
-      unsafe { A::from_napi_value(env, napi_val).map(Self::A) }
-    } else if js_type == B::value_type() {
-      unsafe { B::from_napi_value(env, napi_val).map(Self::B) }
-    } else {
-      Err(crate::Error::new(
+    let mut error = None;
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::A) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::B) } {
+      return Ok(value);
+    }
+    Err(error.unwrap_or_else(|| {
+      crate::Error::new(
         Status::InvalidArg,
         format!(
           "Expect type {} or {}, but got {}",
@@ -80,8 +126,8 @@ impl<A: TypeName + FromNapiValue + ToNapiValue, B: TypeName + FromNapiValue + To
           B::value_type(),
           js_type
         ),
-      ))
-    }
+      )
+    }))
   }
 }
 
@@ -135,24 +181,19 @@ impl<
     env: napi_sys::napi_env,
     napi_val: napi_sys::napi_value,
   ) -> crate::Result<Self> {
-    debug_assert!(
-      {
-        let mut types = vec![A::value_type(), B::value_type(), C::value_type()];
-        types.dedup();
-        types.len() == 3
-      },
-      "{}",
-      ERROR_MSG
-    );
     let js_type = type_of!(env, napi_val)?;
-    if js_type == A::value_type() {
-      unsafe { A::from_napi_value(env, napi_val).map(Self::A) }
-    } else if js_type == B::value_type() {
-      unsafe { B::from_napi_value(env, napi_val).map(Self::B) }
-    } else if js_type == C::value_type() {
-      unsafe { C::from_napi_value(env, napi_val).map(Self::C) }
-    } else {
-      Err(crate::Error::new(
+    let mut error = None;
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::A) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::B) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::C) } {
+      return Ok(value);
+    }
+    Err(error.unwrap_or_else(|| {
+      crate::Error::new(
         Status::InvalidArg,
         format!(
           "Expect type {} or {} or {}, but got {}",
@@ -161,8 +202,8 @@ impl<
           C::value_type(),
           js_type
         ),
-      ))
-    }
+      )
+    }))
   }
 }
 
@@ -224,31 +265,22 @@ impl<
     env: napi_sys::napi_env,
     napi_val: napi_sys::napi_value,
   ) -> crate::Result<Self> {
-    debug_assert!(
-      {
-        let mut types = vec![
-          A::value_type(),
-          B::value_type(),
-          C::value_type(),
-          D::value_type(),
-        ];
-        types.dedup();
-        types.len() == 4
-      },
-      "{}",
-      ERROR_MSG
-    );
     let js_type = type_of!(env, napi_val)?;
-    if js_type == A::value_type() {
-      unsafe { A::from_napi_value(env, napi_val).map(Self::A) }
-    } else if js_type == B::value_type() {
-      unsafe { B::from_napi_value(env, napi_val).map(Self::B) }
-    } else if js_type == C::value_type() {
-      unsafe { C::from_napi_value(env, napi_val).map(Self::C) }
-    } else if js_type == D::value_type() {
-      unsafe { D::from_napi_value(env, napi_val).map(Self::D) }
-    } else {
-      Err(crate::Error::new(
+    let mut error = None;
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::A) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::B) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::C) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::D) } {
+      return Ok(value);
+    }
+    Err(error.unwrap_or_else(|| {
+      crate::Error::new(
         Status::InvalidArg,
         format!(
           "Expect type {} or {} or {} or {}, but got {}",
@@ -258,8 +290,8 @@ impl<
           D::value_type(),
           js_type
         ),
-      ))
-    }
+      )
+    }))
   }
 }
 
@@ -327,34 +359,25 @@ impl<
     env: napi_sys::napi_env,
     napi_val: napi_sys::napi_value,
   ) -> crate::Result<Self> {
-    debug_assert!(
-      {
-        let mut types = vec![
-          A::value_type(),
-          B::value_type(),
-          C::value_type(),
-          D::value_type(),
-          E::value_type(),
-        ];
-        types.dedup();
-        types.len() == 5
-      },
-      "{}",
-      ERROR_MSG
-    );
     let js_type = type_of!(env, napi_val)?;
-    if js_type == A::value_type() {
-      unsafe { A::from_napi_value(env, napi_val).map(Self::A) }
-    } else if js_type == B::value_type() {
-      unsafe { B::from_napi_value(env, napi_val).map(Self::B) }
-    } else if js_type == C::value_type() {
-      unsafe { C::from_napi_value(env, napi_val).map(Self::C) }
-    } else if js_type == D::value_type() {
-      unsafe { D::from_napi_value(env, napi_val).map(Self::D) }
-    } else if js_type == E::value_type() {
-      unsafe { E::from_napi_value(env, napi_val).map(Self::E) }
-    } else {
-      Err(crate::Error::new(
+    let mut error = None;
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::A) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::B) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::C) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::D) } {
+      return Ok(value);
+    }
+    if let Some(value) = unsafe { try_variant(env, napi_val, js_type, &mut error, Self::E) } {
+      return Ok(value);
+    }
+    Err(error.unwrap_or_else(|| {
+      crate::Error::new(
         Status::InvalidArg,
         format!(
           "Expect type {} or {} or {} or {} or {}, but got {}",
@@ -365,8 +388,8 @@ impl<
           E::value_type(),
           js_type
         ),
-      ))
-    }
+      )
+    }))
   }
 }
 
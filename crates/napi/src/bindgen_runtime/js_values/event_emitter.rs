@@ -0,0 +1,91 @@
+#[cfg(feature = "napi4")]
+use crate::threadsafe_function::{
+  ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+#[cfg(feature = "napi4")]
+use crate::{Env, JsUnknown, NapiValue, Status};
+use crate::{JsFunction, JsObject, Result};
+
+use super::ToNapiValue;
+
+/// Emit events on a JavaScript `EventEmitter`,
+/// e.g. the `This` of the classes declared with `#[napi(extends = "events:EventEmitter")]`.
+///
+/// ```rust,ignore
+/// #[napi]
+/// impl Ticker {
+///   #[napi]
+///   pub fn tick(&mut self, this: This) -> Result<bool> {
+///     self.count += 1;
+///     this.emit("tick", self.count)
+///   }
+/// }
+/// ```
+pub trait EventEmitter {
+  /// Call `emit(event, payload)`, returns `true` if the event had listeners
+  fn emit<V: ToNapiValue>(&self, event: &str, payload: V) -> Result<bool>;
+
+  /// An emitter for other threads, which keeps this object alive until it's dropped
+  #[cfg(feature = "napi4")]
+  fn threadsafe_emitter<V: ToNapiValue + Send + 'static>(&self) -> Result<ThreadsafeEmitter<V>>;
+}
+
+impl EventEmitter for JsObject {
+  fn emit<V: ToNapiValue>(&self, event: &str, payload: V) -> Result<bool> {
+    let emit: JsFunction = self.get_named_property("emit")?;
+    emit.typed_call(Some(self), (event, payload))
+  }
+
+  #[cfg(feature = "napi4")]
+  fn threadsafe_emitter<V: ToNapiValue + Send + 'static>(&self) -> Result<ThreadsafeEmitter<V>> {
+    let emit: JsFunction = self.get_named_property("emit")?;
+    // threadsafe functions are called with `undefined` as `this`, so `emit` is bound to this object
+    let emit: JsObject = unsafe { emit.into_unknown().cast() };
+    let bind: JsFunction = emit.get_named_property("bind")?;
+    let bound: JsFunction = bind.typed_call(Some(&emit), (self,))?;
+    let tsfn = bound.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(String, V)>| {
+      let (event, payload) = ctx.value;
+      let payload = unsafe { V::to_napi_value(ctx.env.raw(), payload)? };
+      Ok(vec![
+        ctx.env.create_string(&event)?.into_unknown(),
+        unsafe { JsUnknown::from_raw_unchecked(ctx.env.raw(), payload) },
+      ])
+    })?;
+    Ok(ThreadsafeEmitter { tsfn })
+  }
+}
+
+/// Emits events on a JavaScript `EventEmitter` from any thread, created by [`EventEmitter::threadsafe_emitter`].
+///
+/// Like a `ThreadsafeFunction`, it keeps the Node.js event loop alive until it's dropped or [`unref`](Self::unref)ed.
+#[cfg(feature = "napi4")]
+pub struct ThreadsafeEmitter<V: 'static> {
+  tsfn: ThreadsafeFunction<(String, V), ErrorStrategy::Fatal>,
+}
+
+#[cfg(feature = "napi4")]
+impl<V: 'static> Clone for ThreadsafeEmitter<V> {
+  fn clone(&self) -> Self {
+    Self {
+      tsfn: self.tsfn.clone(),
+    }
+  }
+}
+
+#[cfg(feature = "napi4")]
+impl<V: ToNapiValue + Send + 'static> ThreadsafeEmitter<V> {
+  /// Queue `emit(event, payload)` on the JavaScript thread.
+  ///
+  /// Returns [`Status::Closing`] once the environment is tearing down.
+  pub fn emit(&self, event: &str, payload: V) -> Status {
+    self.tsfn.call(
+      (event.to_owned(), payload),
+      ThreadsafeFunctionCallMode::NonBlocking,
+    )
+  }
+
+  /// Let the Node.js event loop exit while this emitter is alive
+  pub fn unref(&mut self, env: &Env) -> Result<()> {
+    self.tsfn.unref(env)
+  }
+}
@@ -3,7 +3,7 @@ use std::{
   ops::{Deref, DerefMut},
 };
 
-use crate::{check_status, Error, Status, TaggedObject};
+use crate::{check_status, check_tagged_object, tag_tagged_object, Error, Status, TaggedObject};
 
 use super::{FromNapiValue, ToNapiValue};
 
@@ -16,7 +16,7 @@ pub struct External<T: 'static> {
 impl<T: 'static> External<T> {
   pub fn new(value: T) -> Self {
     Self {
-      obj: Box::into_raw(Box::new(TaggedObject::new(value))),
+      obj: TaggedObject::into_raw(value),
       size_hint: 0,
       adjusted_size: 0,
     }
@@ -29,7 +29,7 @@ impl<T: 'static> External<T> {
   /// If your `External` object is not effect to GC, you can use `External::new` instead.
   pub fn new_with_size_hint(value: T, size_hint: usize) -> Self {
     Self {
-      obj: Box::into_raw(Box::new(TaggedObject::new(value))),
+      obj: TaggedObject::into_raw(value),
       size_hint,
       adjusted_size: 0,
     }
@@ -46,6 +46,7 @@ impl<T: 'static> FromNapiValue for External<T> {
       unsafe { napi_sys::napi_get_value_external(env, napi_val, &mut unknown_tagged_object) },
       "Failed to get external value"
     )?;
+    unsafe { check_tagged_object(env, napi_val, unknown_tagged_object)? };
 
     let type_id = unknown_tagged_object as *const TypeId;
     if unsafe { *type_id } == TypeId::of::<T>() {
@@ -108,6 +109,7 @@ impl<T: 'static> ToNapiValue for External<T> {
       },
       "Create external value failed"
     )?;
+    unsafe { tag_tagged_object(env, napi_value)? };
 
     let mut adjusted_external_memory_size = std::mem::MaybeUninit::new(0);
 
@@ -1 +1,99 @@
+use std::marker::PhantomData;
+
 pub use crate::JsFunction;
+
+use super::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use crate::{sys, JsObject, JsUnknown, NapiRaw, NapiValue, Result, ValueType};
+
+/// Convert a tuple of Rust values into the arguments list of a JavaScript function call.
+pub trait JsValuesTupleIntoVec {
+  /// # Safety
+  ///
+  /// `env` must be a valid `napi_env` of the current thread
+  unsafe fn into_vec(self, env: sys::napi_env) -> Result<Vec<sys::napi_value>>;
+}
+
+impl JsValuesTupleIntoVec for () {
+  unsafe fn into_vec(self, _env: sys::napi_env) -> Result<Vec<sys::napi_value>> {
+    Ok(Vec::new())
+  }
+}
+
+macro_rules! impl_js_value_tuple_to_vec {
+  ($($ident:ident),*) => {
+    impl<$($ident: ToNapiValue),*> JsValuesTupleIntoVec for ($($ident,)*) {
+      #[allow(non_snake_case)]
+      unsafe fn into_vec(self, env: sys::napi_env) -> Result<Vec<sys::napi_value>> {
+        let ($($ident,)*) = self;
+        Ok(vec![$(unsafe { <$ident as ToNapiValue>::to_napi_value(env, $ident)? }),*])
+      }
+    }
+  };
+}
+
+impl_js_value_tuple_to_vec!(A);
+impl_js_value_tuple_to_vec!(A, B);
+impl_js_value_tuple_to_vec!(A, B, C);
+impl_js_value_tuple_to_vec!(A, B, C, D);
+impl_js_value_tuple_to_vec!(A, B, C, D, E);
+impl_js_value_tuple_to_vec!(A, B, C, D, E, F);
+impl_js_value_tuple_to_vec!(A, B, C, D, E, F, G);
+impl_js_value_tuple_to_vec!(A, B, C, D, E, F, G, H);
+
+/// A JavaScript function with typed arguments and return value.
+///
+/// `Args` is a tuple of the arguments, e.g. `Function<(String, u32), bool>` is typed as
+/// `(arg0: string, arg1: number) => boolean` in the generated TypeScript definitions.
+pub struct Function<Args: JsValuesTupleIntoVec = (), Return: FromNapiValue = JsUnknown> {
+  inner: JsFunction,
+  _marker: PhantomData<fn(Args) -> Return>,
+}
+
+impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> Function<Args, Return> {
+  /// Call the function with `undefined` as `this`
+  pub fn call(&self, args: Args) -> Result<Return> {
+    self.inner.typed_call(None, args)
+  }
+
+  /// Call the function with the given `this`
+  pub fn call_with_this(&self, this: &JsObject, args: Args) -> Result<Return> {
+    self.inner.typed_call(Some(this), args)
+  }
+
+  pub fn into_inner(self) -> JsFunction {
+    self.inner
+  }
+}
+
+impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> TypeName for Function<Args, Return> {
+  fn type_name() -> &'static str {
+    "Function"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Function
+  }
+}
+
+impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> ValidateNapiValue
+  for Function<Args, Return>
+{
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Function]
+  }
+}
+
+impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> FromNapiValue for Function<Args, Return> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Self {
+      inner: unsafe { JsFunction::from_raw(env, napi_val)? },
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> NapiRaw for Function<Args, Return> {
+  unsafe fn raw(&self) -> sys::napi_value {
+    unsafe { self.inner.raw() }
+  }
+}
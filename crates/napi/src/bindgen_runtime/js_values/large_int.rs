@@ -0,0 +1,213 @@
+use super::checked_number::js_number_string;
+use crate::{bindgen_prelude::*, sys, ValueType};
+
+/// `Number.MAX_SAFE_INTEGER`, the largest integer a JavaScript `number` represents exactly
+const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+/// The 64-bit and pointer sized integers converted with `#[napi(large_int)]`
+pub trait LargeInt: Copy {
+  const NAME: &'static str;
+  const MIN: i128;
+  const MAX: i128;
+
+  fn to_i128(self) -> i128;
+
+  /// `value` is within `MIN..=MAX`
+  fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_large_int {
+  ($($t:ident),*) => {
+    $(
+      impl LargeInt for $t {
+        const NAME: &'static str = stringify!($t);
+        const MIN: i128 = $t::MIN as i128;
+        const MAX: i128 = $t::MAX as i128;
+
+        fn to_i128(self) -> i128 {
+          self as i128
+        }
+
+        fn from_i128(value: i128) -> Self {
+          value as $t
+        }
+      }
+    )*
+  };
+}
+
+impl_large_int!(i64, u64, isize, usize);
+
+fn range_error(reason: String) -> Error {
+  Error::new(Status::InvalidArg, reason).with_class(ErrorClass::RangeError)
+}
+
+fn check_range<T: LargeInt>(value: i128) -> Result<T> {
+  if value < T::MIN || value > T::MAX {
+    return Err(range_error(format!(
+      "{} is out of the range of `{}`",
+      value,
+      T::NAME
+    )));
+  }
+  Ok(T::from_i128(value))
+}
+
+/// Converted from and into a `number`, a `RangeError` is thrown for values beyond `Number.MAX_SAFE_INTEGER`,
+/// numbers with a fraction and numbers out of the range of `T`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CheckedInt<T: LargeInt>(pub T);
+
+impl<T: LargeInt> TypeName for CheckedInt<T> {
+  fn type_name() -> &'static str {
+    "number"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Number
+  }
+}
+
+impl<T: LargeInt> ValidateNapiValue for CheckedInt<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Number]
+  }
+}
+
+impl<T: LargeInt> ToNapiValue for CheckedInt<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let value = val.0.to_i128();
+    if value.abs() > MAX_SAFE_INTEGER {
+      return Err(range_error(format!(
+        "{} of `{}` is beyond Number.MAX_SAFE_INTEGER",
+        value,
+        T::NAME
+      )));
+    }
+    unsafe { f64::to_napi_value(env, value as f64) }
+  }
+}
+
+impl<T: LargeInt> FromNapiValue for CheckedInt<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let value = unsafe { f64::from_napi_value(env, napi_val)? };
+    if value.fract() != 0.0 || value.abs() > MAX_SAFE_INTEGER as f64 {
+      return Err(range_error(format!(
+        "Expected a safe integer for `{}`, got {}",
+        T::NAME,
+        js_number_string(value)
+      )));
+    }
+    check_range(value as i128).map(CheckedInt)
+  }
+}
+
+/// Converted from and into a `number`, clamped into `Number.MAX_SAFE_INTEGER` and the range of `T`,
+/// numbers with a fraction are truncated and `NaN` is `0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SaturatingInt<T: LargeInt>(pub T);
+
+impl<T: LargeInt> TypeName for SaturatingInt<T> {
+  fn type_name() -> &'static str {
+    "number"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Number
+  }
+}
+
+impl<T: LargeInt> ValidateNapiValue for SaturatingInt<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Number]
+  }
+}
+
+impl<T: LargeInt> ToNapiValue for SaturatingInt<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let value = val.0.to_i128().clamp(-MAX_SAFE_INTEGER, MAX_SAFE_INTEGER);
+    unsafe { f64::to_napi_value(env, value as f64) }
+  }
+}
+
+impl<T: LargeInt> FromNapiValue for SaturatingInt<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let value = unsafe { f64::from_napi_value(env, napi_val)? };
+    // `as` truncates the fraction, saturates the infinities and converts `NaN` into `0`
+    Ok(SaturatingInt(T::from_i128(
+      (value as i128).clamp(T::MIN, T::MAX),
+    )))
+  }
+}
+
+#[doc(hidden)]
+#[cfg(feature = "napi6")]
+#[macro_export]
+macro_rules! __large_int_bigint {
+  () => {};
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "napi6"))]
+#[macro_export]
+macro_rules! __large_int_bigint {
+  () => {
+    compile_error!("`#[napi(large_int = \"bigint\")]` requires the `napi6` feature of `napi`");
+  };
+}
+
+/// Converted from and into a `bigint`, a `RangeError` is thrown for values out of the range of `T`
+#[cfg(feature = "napi6")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsBigInt<T: LargeInt>(pub T);
+
+#[cfg(feature = "napi6")]
+impl<T: LargeInt> TypeName for AsBigInt<T> {
+  fn type_name() -> &'static str {
+    "bigint"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::BigInt
+  }
+}
+
+#[cfg(feature = "napi6")]
+impl<T: LargeInt> ValidateNapiValue for AsBigInt<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::BigInt]
+  }
+}
+
+#[cfg(feature = "napi6")]
+impl<T: LargeInt> ToNapiValue for AsBigInt<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let value = val.0.to_i128();
+    let bigint = BigInt {
+      sign_bit: value < 0,
+      // the magnitude of a 64-bit integer fits into one word
+      words: vec![value.unsigned_abs() as u64],
+    };
+    unsafe { BigInt::to_napi_value(env, bigint) }
+  }
+}
+
+#[cfg(feature = "napi6")]
+impl<T: LargeInt> FromNapiValue for AsBigInt<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let bigint = unsafe { BigInt::from_napi_value(env, napi_val)? };
+    let magnitude = bigint.words.first().copied().unwrap_or(0) as i128;
+    if bigint.words.iter().skip(1).any(|word| *word != 0) {
+      return Err(range_error(format!(
+        "BigInt is out of the range of `{}`",
+        T::NAME
+      )));
+    }
+    let value = if bigint.sign_bit {
+      -magnitude
+    } else {
+      magnitude
+    };
+    check_range(value).map(AsBigInt)
+  }
+}
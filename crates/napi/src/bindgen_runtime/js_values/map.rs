@@ -1,7 +1,8 @@
-use std::collections::HashMap;
-use std::hash::Hash;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
 
 use crate::bindgen_prelude::{Env, Result, ToNapiValue, *};
+use crate::{JsFunction, JsUnknown, NapiRaw, NapiValue};
 
 impl<K, V, S> TypeName for HashMap<K, V, S> {
   fn type_name() -> &'static str {
@@ -19,13 +20,7 @@ where
   V: ToNapiValue,
 {
   unsafe fn to_napi_value(raw_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    let env = Env::from(raw_env);
-    let mut obj = env.create_object()?;
-    for (k, v) in val.into_iter() {
-      obj.set(k.as_ref(), v)?;
-    }
-
-    unsafe { Object::to_napi_value(raw_env, obj) }
+    unsafe { entries_to_object(raw_env, val) }
   }
 }
 
@@ -35,14 +30,221 @@ where
   V: FromNapiValue,
 {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
-    let obj = unsafe { Object::from_napi_value(env, napi_val)? };
-    let mut map = HashMap::new();
-    for key in Object::keys(&obj)?.into_iter() {
-      if let Some(val) = obj.get(&key)? {
-        map.insert(K::from(key), val);
-      }
+    unsafe { object_to_entries(env, napi_val) }
+  }
+}
+
+impl<K, V> TypeName for BTreeMap<K, V> {
+  fn type_name() -> &'static str {
+    "BTreeMap"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<K, V> ToNapiValue for BTreeMap<K, V>
+where
+  K: AsRef<str>,
+  V: ToNapiValue,
+{
+  unsafe fn to_napi_value(raw_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { entries_to_object(raw_env, val) }
+  }
+}
+
+impl<K, V> FromNapiValue for BTreeMap<K, V>
+where
+  K: From<String> + Ord,
+  V: FromNapiValue,
+{
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { object_to_entries(env, napi_val) }
+  }
+}
+
+unsafe fn entries_to_object<K, V, I>(raw_env: sys::napi_env, entries: I) -> Result<sys::napi_value>
+where
+  K: AsRef<str>,
+  V: ToNapiValue,
+  I: IntoIterator<Item = (K, V)>,
+{
+  let env = Env::from(raw_env);
+  let mut obj = env.create_object()?;
+  for (k, v) in entries.into_iter() {
+    obj.set(k.as_ref(), v)?;
+  }
+
+  unsafe { Object::to_napi_value(raw_env, obj) }
+}
+
+unsafe fn object_to_entries<K, V, M>(env: sys::napi_env, napi_val: sys::napi_value) -> Result<M>
+where
+  K: From<String>,
+  V: FromNapiValue,
+  M: Default + Extend<(K, V)>,
+{
+  let obj = unsafe { Object::from_napi_value(env, napi_val)? };
+  let mut map = M::default();
+  for key in Object::keys(&obj)?.into_iter() {
+    if let Some(val) = obj.get(&key).map_err(|e| e.at_property(&key))? {
+      map.extend(Some((K::from(key), val)));
     }
+  }
+
+  Ok(map)
+}
+
+/// Converts the wrapped `HashMap` or `BTreeMap` from and into a JavaScript `Map` instead of a plain object,
+/// so the keys are not limited to strings.
+///
+/// Arguments, returned values and fields of objects marked with `#[napi(js_map)]` are converted with it.
+pub struct EsMap<M>(pub M);
+
+impl<M> EsMap<M> {
+  pub fn into_inner(self) -> M {
+    self.0
+  }
+}
+
+impl<M> TypeName for EsMap<M> {
+  fn type_name() -> &'static str {
+    "Map"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<K, V, S> ToNapiValue for EsMap<HashMap<K, V, S>>
+where
+  K: ToNapiValue,
+  V: ToNapiValue,
+{
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { entries_to_map(env, val.0) }
+  }
+}
+
+impl<K, V, S> FromNapiValue for EsMap<HashMap<K, V, S>>
+where
+  K: FromNapiValue + Eq + Hash,
+  V: FromNapiValue,
+  S: BuildHasher + Default,
+{
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { map_to_entries(env, napi_val) }.map(EsMap)
+  }
+}
+
+impl<K, V, S> ValidateNapiValue for EsMap<HashMap<K, V, S>>
+where
+  K: FromNapiValue + Eq + Hash,
+  V: FromNapiValue,
+  S: BuildHasher + Default,
+{
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+    unsafe { validate_map(env, napi_val) }
+  }
+}
+
+impl<K, V> ToNapiValue for EsMap<BTreeMap<K, V>>
+where
+  K: ToNapiValue,
+  V: ToNapiValue,
+{
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { entries_to_map(env, val.0) }
+  }
+}
+
+impl<K, V> FromNapiValue for EsMap<BTreeMap<K, V>>
+where
+  K: FromNapiValue + Ord,
+  V: FromNapiValue,
+{
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { map_to_entries(env, napi_val) }.map(EsMap)
+  }
+}
+
+impl<K, V> ValidateNapiValue for EsMap<BTreeMap<K, V>>
+where
+  K: FromNapiValue + Ord,
+  V: FromNapiValue,
+{
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+    unsafe { validate_map(env, napi_val) }
+  }
+}
+
+fn get_map_constructor(env: &Env) -> Result<JsFunction> {
+  env.get_global()?.get_named_property("Map")
+}
 
-    Ok(map)
+unsafe fn validate_map(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+  let env = Env::from(env);
+  let map_constructor = get_map_constructor(&env)?;
+  let value = unsafe { JsUnknown::from_raw_unchecked(env.0, napi_val) };
+  if value.instanceof(map_constructor)? {
+    Ok(())
+  } else {
+    Err(Error::new(
+      Status::InvalidArg,
+      format!("Expected a Map, but received {}", value.get_type()?),
+    ))
   }
 }
+
+unsafe fn entries_to_map<K, V, I>(env: sys::napi_env, entries: I) -> Result<sys::napi_value>
+where
+  K: ToNapiValue,
+  V: ToNapiValue,
+  I: IntoIterator<Item = (K, V)>,
+{
+  let env_wrapper = Env::from(env);
+  let map_constructor = get_map_constructor(&env_wrapper)?;
+  let entries = unsafe { Vec::to_napi_value(env, entries.into_iter().collect::<Vec<_>>())? };
+  let map =
+    map_constructor.new_instance(&[unsafe { JsUnknown::from_raw_unchecked(env, entries) }])?;
+  Ok(unsafe { map.raw() })
+}
+
+unsafe fn map_to_entries<K, V, M>(env: sys::napi_env, napi_val: sys::napi_value) -> Result<M>
+where
+  K: FromNapiValue,
+  V: FromNapiValue,
+  M: FromIterator<(K, V)>,
+{
+  unsafe { validate_map(env, napi_val) }?;
+  let env_wrapper = Env::from(env);
+  // `Array.from(map)` lists the `[key, value]` pairs of the map
+  let array_from: JsFunction = env_wrapper
+    .get_global()?
+    .get_named_property::<JsFunction>("Array")?
+    .coerce_to_object()?
+    .get_named_property("from")?;
+  let entries = array_from.call(
+    None,
+    &[unsafe { JsUnknown::from_raw_unchecked(env, napi_val) }],
+  )?;
+  let entries = unsafe { Vec::<Array>::from_napi_value(env, entries.raw())? };
+  entries
+    .into_iter()
+    .map(|entry| {
+      let key = entry
+        .get::<K>(0)
+        .map_err(|err| {
+          Error::new(
+            Status::InvalidArg,
+            format!("Invalid key of Map, {}", err.reason),
+          )
+        })?
+        .unwrap();
+      let value = entry.get::<V>(1)?.unwrap();
+      Ok((key, value))
+    })
+    .collect()
+}
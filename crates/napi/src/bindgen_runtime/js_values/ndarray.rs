@@ -0,0 +1,204 @@
+use std::ops::DerefMut;
+
+use ndarray::{
+  Array, ArrayView, ArrayViewD, ArrayViewMutD, Dimension, IntoDimension, IxDyn, ShapeError,
+};
+
+use crate::{bindgen_prelude::*, sys, NapiRaw, ValueType};
+
+/// Element types of an ndarray which are stored in a JavaScript `TypedArray`
+pub trait NdArrayElement: Copy + 'static {
+  type TypedArray: FromNapiValue + ToNapiValue + DerefMut<Target = [Self]>;
+
+  fn new_typed_array(data: Vec<Self>) -> Self::TypedArray;
+}
+
+macro_rules! impl_nd_array_element {
+  ($rust_type:ident, $typed_array:ident) => {
+    impl NdArrayElement for $rust_type {
+      type TypedArray = $typed_array;
+
+      fn new_typed_array(data: Vec<Self>) -> Self::TypedArray {
+        $typed_array::new(data)
+      }
+    }
+  };
+}
+
+impl_nd_array_element!(i8, Int8Array);
+impl_nd_array_element!(u8, Uint8Array);
+impl_nd_array_element!(i16, Int16Array);
+impl_nd_array_element!(u16, Uint16Array);
+impl_nd_array_element!(i32, Int32Array);
+impl_nd_array_element!(u32, Uint32Array);
+impl_nd_array_element!(f32, Float32Array);
+impl_nd_array_element!(f64, Float64Array);
+#[cfg(feature = "napi6")]
+impl_nd_array_element!(i64, BigInt64Array);
+#[cfg(feature = "napi6")]
+impl_nd_array_element!(u64, BigUint64Array);
+
+fn shape_error(e: ShapeError) -> Error {
+  Error::new(Status::InvalidArg, format!("Invalid ndarray shape, {}", e))
+}
+
+/// Read the `{ data, shape }` object, checking the length of `data` against the `shape`
+unsafe fn get_nd_array_parts<T: NdArrayElement>(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+) -> Result<(T::TypedArray, Vec<usize>)> {
+  let obj = unsafe { Object::from_napi_value(env, napi_val)? };
+  let data: T::TypedArray = obj.get("data")?.ok_or_else(|| {
+    Error::new(
+      Status::InvalidArg,
+      "Expected the `data` of an ndarray to be a TypedArray".to_owned(),
+    )
+  })?;
+  let shape = obj
+    .get::<_, Vec<u32>>("shape")?
+    .ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        "Expected the `shape` of an ndarray to be an array of numbers".to_owned(),
+      )
+    })?
+    .into_iter()
+    .map(|len| len as usize)
+    .collect::<Vec<_>>();
+  let len = shape
+    .iter()
+    .try_fold(1usize, |len, dim| len.checked_mul(*dim))
+    .ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        format!("ndarray of shape {:?} has too many elements", shape),
+      )
+    })?;
+  if len != data.len() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "ndarray of shape {:?} has {} elements, but its data has {}",
+        shape,
+        len,
+        data.len()
+      ),
+    ));
+  }
+  Ok((data, shape))
+}
+
+unsafe fn create_nd_array_object<T: NdArrayElement>(
+  env: sys::napi_env,
+  data: Vec<T>,
+  shape: &[usize],
+) -> Result<sys::napi_value> {
+  let mut obj = Env::from(env).create_object()?;
+  obj.set("data", T::new_typed_array(data))?;
+  obj.set(
+    "shape",
+    shape.iter().map(|len| *len as u32).collect::<Vec<_>>(),
+  )?;
+  Ok(unsafe { obj.raw() })
+}
+
+/// The elements of `array` in row-major order, reusing its allocation when it's already laid out that way
+fn into_row_major_vec<T: Clone, D: Dimension>(array: Array<T, D>) -> Vec<T> {
+  if !array.is_standard_layout() {
+    return array.iter().cloned().collect();
+  }
+  let len = array.len();
+  let (mut data, offset) = array.into_raw_vec_and_offset();
+  let offset = offset.unwrap_or(0);
+  data.truncate(offset + len);
+  data.drain(..offset);
+  data
+}
+
+impl<T: NdArrayElement, D: Dimension> TypeName for Array<T, D> {
+  fn type_name() -> &'static str {
+    "NdArray"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: NdArrayElement, D: Dimension> ValidateNapiValue for Array<T, D> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+/// Created as `{ data, shape }` with the elements in row-major order in a `TypedArray`,
+/// the allocation of the array is moved into the `TypedArray` when it's already in that order
+impl<T: NdArrayElement, D: Dimension> ToNapiValue for Array<T, D> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let shape = val.shape().to_vec();
+    unsafe { create_nd_array_object(env, into_row_major_vec(val), &shape) }
+  }
+}
+
+/// Copies the `TypedArray` of a `{ data, shape }` object, use [`NdArrayView`] to read it in place
+impl<T: NdArrayElement, D: Dimension> FromNapiValue for Array<T, D> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let (data, shape) = unsafe { get_nd_array_parts::<T>(env, napi_val)? };
+    Array::from_shape_vec(IxDyn(&shape), data.to_vec())
+      .and_then(|array| array.into_dimensionality::<D>())
+      .map_err(shape_error)
+  }
+}
+
+/// Copies the elements of the view into the `TypedArray` of a `{ data, shape }` object
+impl<'a, T: NdArrayElement, D: Dimension> ToNapiValue for ArrayView<'a, T, D> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let data = val.iter().copied().collect::<Vec<_>>();
+    unsafe { create_nd_array_object(env, data, val.shape()) }
+  }
+}
+
+/// A `{ data, shape }` argument read in place through [`NdArrayView::view`] and [`NdArrayView::view_mut`],
+/// writes through `view_mut` are visible to JavaScript.
+pub struct NdArrayView<T: NdArrayElement> {
+  data: T::TypedArray,
+  shape: Vec<usize>,
+}
+
+impl<T: NdArrayElement> NdArrayView<T> {
+  pub fn shape(&self) -> &[usize] {
+    &self.shape
+  }
+
+  pub fn view(&self) -> ArrayViewD<'_, T> {
+    // the length of `data` is checked against the shape in `from_napi_value`
+    ArrayViewD::from_shape(self.shape.as_slice().into_dimension(), &self.data).unwrap()
+  }
+
+  pub fn view_mut(&mut self) -> ArrayViewMutD<'_, T> {
+    ArrayViewMutD::from_shape(self.shape.as_slice().into_dimension(), &mut self.data).unwrap()
+  }
+}
+
+impl<T: NdArrayElement> TypeName for NdArrayView<T> {
+  fn type_name() -> &'static str {
+    "NdArrayView"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: NdArrayElement> ValidateNapiValue for NdArrayView<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl<T: NdArrayElement> FromNapiValue for NdArrayView<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let (data, shape) = unsafe { get_nd_array_parts::<T>(env, napi_val)? };
+    Ok(Self { data, shape })
+  }
+}
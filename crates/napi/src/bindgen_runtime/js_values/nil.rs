@@ -87,3 +87,123 @@ impl ToNapiValue for Undefined {
     Ok(ret)
   }
 }
+
+/// `Option<T>` converted into `null` when it's `None`.
+///
+/// Unlike `Option<T>`, only `null` is accepted as `None` when converted from JavaScript, `undefined` is rejected.
+pub struct Nullable<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for Nullable<T> {
+  fn from(val: Option<T>) -> Self {
+    Nullable(val)
+  }
+}
+
+impl<T> From<Nullable<T>> for Option<T> {
+  fn from(val: Nullable<T>) -> Self {
+    val.0
+  }
+}
+
+impl<T: TypeName> TypeName for Nullable<T> {
+  fn type_name() -> &'static str {
+    "Nullable"
+  }
+
+  fn value_type() -> ValueType {
+    T::value_type()
+  }
+}
+
+impl<T: ValidateNapiValue> ValidateNapiValue for Nullable<T> {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+    match type_of!(env, napi_val)? {
+      ValueType::Null => Ok(()),
+      _ => unsafe { T::validate(env, napi_val) },
+    }
+  }
+}
+
+impl<T: FromNapiValue> FromNapiValue for Nullable<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    match type_of!(env, napi_val)? {
+      ValueType::Null => Ok(Nullable(None)),
+      ValueType::Undefined => Err(Error::new(
+        Status::InvalidArg,
+        "Value is undefined, expected a value or null".to_owned(),
+      )),
+      _ => Ok(Nullable(Some(unsafe {
+        T::from_napi_value(env, napi_val)?
+      }))),
+    }
+  }
+}
+
+impl<T: ToNapiValue> ToNapiValue for Nullable<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    match val.0 {
+      Some(val) => unsafe { T::to_napi_value(env, val) },
+      None => unsafe { Null::to_napi_value(env, Null) },
+    }
+  }
+}
+
+/// `Option<T>` converted into `undefined` when it's `None`.
+///
+/// Unlike `Option<T>`, only `undefined` is accepted as `None` when converted from JavaScript, `null` is rejected.
+pub struct Undefinable<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for Undefinable<T> {
+  fn from(val: Option<T>) -> Self {
+    Undefinable(val)
+  }
+}
+
+impl<T> From<Undefinable<T>> for Option<T> {
+  fn from(val: Undefinable<T>) -> Self {
+    val.0
+  }
+}
+
+impl<T: TypeName> TypeName for Undefinable<T> {
+  fn type_name() -> &'static str {
+    "Undefinable"
+  }
+
+  fn value_type() -> ValueType {
+    T::value_type()
+  }
+}
+
+impl<T: ValidateNapiValue> ValidateNapiValue for Undefinable<T> {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+    match type_of!(env, napi_val)? {
+      ValueType::Undefined => Ok(()),
+      _ => unsafe { T::validate(env, napi_val) },
+    }
+  }
+}
+
+impl<T: FromNapiValue> FromNapiValue for Undefinable<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    match type_of!(env, napi_val)? {
+      ValueType::Undefined => Ok(Undefinable(None)),
+      ValueType::Null => Err(Error::new(
+        Status::InvalidArg,
+        "Value is null, expected a value or undefined".to_owned(),
+      )),
+      _ => Ok(Undefinable(Some(unsafe {
+        T::from_napi_value(env, napi_val)?
+      }))),
+    }
+  }
+}
+
+impl<T: ToNapiValue> ToNapiValue for Undefinable<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    match val.0 {
+      Some(val) => unsafe { T::to_napi_value(env, val) },
+      None => unsafe { Undefined::to_napi_value(env, ()) },
+    }
+  }
+}
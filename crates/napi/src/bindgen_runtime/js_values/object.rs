@@ -21,6 +21,9 @@ impl Object {
     }))
   }
 
+  /// Get the property with the given key and convert it into `V`.
+  ///
+  /// Returns `None` if the property is `undefined` or `null`.
   pub fn get<K: AsRef<str>, V: FromNapiValue>(&self, field: K) -> Result<Option<V>> {
     let c_field = CString::new(field.as_ref())?;
 
@@ -43,6 +46,24 @@ impl Object {
     }
   }
 
+  /// Get the property with the given key and convert it into `V`, even if it's `undefined` or `null`.
+  pub fn get_value<K: AsRef<str>, V: FromNapiValue>(&self, field: K) -> Result<V> {
+    let c_field = CString::new(field.as_ref())?;
+
+    unsafe {
+      let mut ret = ptr::null_mut();
+
+      check_status!(
+        sys::napi_get_named_property(self.0.env, self.0.value, c_field.as_ptr(), &mut ret),
+        "Failed to get property with field `{}`",
+        c_field.to_string_lossy(),
+      )?;
+
+      V::from_napi_value(self.0.env, ret)
+    }
+  }
+
+  /// Convert `val` into a JavaScript value and set it as the property with the given key.
   pub fn set<K: AsRef<str>, V: ToNapiValue>(&mut self, field: K, val: V) -> Result<()> {
     let c_field = CString::new(field.as_ref())?;
 
@@ -59,6 +80,112 @@ impl Object {
     }
   }
 
+  /// Define an enumerable property which can't be written or reconfigured
+  pub fn set_readonly<K: AsRef<str>, V: ToNapiValue>(&mut self, field: K, val: V) -> Result<()> {
+    let c_field = CString::new(field.as_ref())?;
+
+    unsafe {
+      let napi_val = V::to_napi_value(self.0.env, val)?;
+      let property = sys::napi_property_descriptor {
+        utf8name: c_field.as_ptr(),
+        name: ptr::null_mut(),
+        method: None,
+        getter: None,
+        setter: None,
+        value: napi_val,
+        attributes: sys::PropertyAttributes::enumerable,
+        data: ptr::null_mut(),
+      };
+
+      check_status!(
+        sys::napi_define_properties(self.0.env, self.0.value, 1, &property),
+        "Failed to define readonly property with field `{}`",
+        c_field.to_string_lossy(),
+      )?;
+
+      Ok(())
+    }
+  }
+
+  /// Same as [`Object::get`], with a [`PropertyKey`]
+  pub fn get_by_key<V: FromNapiValue>(&self, key: &PropertyKey) -> Result<Option<V>> {
+    let ret = self.get_raw_by_key(key)?;
+
+    unsafe {
+      let ty = type_of!(self.0.env, ret)?;
+
+      Ok(if ty == ValueType::Undefined || ty == ValueType::Null {
+        None
+      } else {
+        Some(V::from_napi_value(self.0.env, ret)?)
+      })
+    }
+  }
+
+  /// Same as [`Object::get_value`], with a [`PropertyKey`]
+  pub fn get_value_by_key<V: FromNapiValue>(&self, key: &PropertyKey) -> Result<V> {
+    let ret = self.get_raw_by_key(key)?;
+
+    unsafe { V::from_napi_value(self.0.env, ret) }
+  }
+
+  fn get_raw_by_key(&self, key: &PropertyKey) -> Result<sys::napi_value> {
+    let key_value = unsafe { key.get(self.0.env)? };
+    let mut ret = ptr::null_mut();
+
+    check_status!(
+      unsafe { sys::napi_get_property(self.0.env, self.0.value, key_value, &mut ret) },
+      "Failed to get property with field `{}`",
+      key.name(),
+    )?;
+
+    Ok(ret)
+  }
+
+  /// Same as [`Object::set`], with a [`PropertyKey`]
+  pub fn set_by_key<V: ToNapiValue>(&mut self, key: &PropertyKey, val: V) -> Result<()> {
+    let key_value = unsafe { key.get(self.0.env)? };
+
+    unsafe {
+      let napi_val = V::to_napi_value(self.0.env, val)?;
+
+      check_status!(
+        sys::napi_set_property(self.0.env, self.0.value, key_value, napi_val),
+        "Failed to set property with field `{}`",
+        key.name(),
+      )?;
+
+      Ok(())
+    }
+  }
+
+  /// Same as [`Object::set_readonly`], with a [`PropertyKey`]
+  pub fn set_readonly_by_key<V: ToNapiValue>(&mut self, key: &PropertyKey, val: V) -> Result<()> {
+    let key_value = unsafe { key.get(self.0.env)? };
+
+    unsafe {
+      let napi_val = V::to_napi_value(self.0.env, val)?;
+      let property = sys::napi_property_descriptor {
+        utf8name: ptr::null(),
+        name: key_value,
+        method: None,
+        getter: None,
+        setter: None,
+        value: napi_val,
+        attributes: sys::PropertyAttributes::enumerable,
+        data: ptr::null_mut(),
+      };
+
+      check_status!(
+        sys::napi_define_properties(self.0.env, self.0.value, 1, &property),
+        "Failed to define readonly property with field `{}`",
+        key.name(),
+      )?;
+
+      Ok(())
+    }
+  }
+
   pub fn keys(obj: &Object) -> Result<Vec<String>> {
     let mut names = ptr::null_mut();
     unsafe {
@@ -77,6 +204,140 @@ impl Object {
 
     Ok(ret)
   }
+
+  /// The `#[napi]` class instance wrapped by this object,
+  /// `None` if it's not an instance of `T` created by this addon.
+  pub fn downcast_ref<T: FromNapiRef + 'static>(&self) -> Option<&T> {
+    unsafe { T::from_napi_ref(self.0.env, self.0.value) }.ok()
+  }
+
+  /// The `#[napi]` class instance wrapped by this object,
+  /// `None` if it's not an instance of `T` created by this addon.
+  pub fn downcast_mut<T: FromNapiMutRef + 'static>(&mut self) -> Option<&mut T> {
+    unsafe { T::from_napi_mut_ref(self.0.env, self.0.value) }.ok()
+  }
+}
+
+/// Collects the properties of a new object and defines them all at once.
+///
+/// One `napi_define_properties` call is much cheaper than setting the properties one by one,
+/// which matters when converting objects with many fields.
+///
+/// ```rust
+/// use napi::bindgen_prelude::*;
+///
+/// const NAME: PropertyKey = PropertyKey::new("name");
+/// const VERSION: PropertyKey = PropertyKey::new("version");
+///
+/// fn create_package(env: &Env) -> Result<Object> {
+///   let mut builder = ObjectBuilder::with_capacity(env, 2);
+///   builder.set(&NAME, "napi")?.set_readonly(&VERSION, 2)?;
+///   builder.build()
+/// }
+/// ```
+pub struct ObjectBuilder {
+  env: sys::napi_env,
+  properties: Vec<sys::napi_property_descriptor>,
+}
+
+impl ObjectBuilder {
+  pub fn new(env: &Env) -> Self {
+    Self::with_capacity(env, 0)
+  }
+
+  pub fn with_capacity(env: &Env, capacity: usize) -> Self {
+    Self {
+      env: env.0,
+      properties: Vec::with_capacity(capacity),
+    }
+  }
+
+  /// Add a property which behaves like an assigned one, see [`Object::set`]
+  pub fn set<V: ToNapiValue>(&mut self, key: &PropertyKey, val: V) -> Result<&mut Self> {
+    self.push(
+      key,
+      val,
+      sys::PropertyAttributes::writable
+        | sys::PropertyAttributes::enumerable
+        | sys::PropertyAttributes::configurable,
+    )
+  }
+
+  /// Add an enumerable property which can't be written or reconfigured, see [`Object::set_readonly`]
+  pub fn set_readonly<V: ToNapiValue>(&mut self, key: &PropertyKey, val: V) -> Result<&mut Self> {
+    self.push(key, val, sys::PropertyAttributes::enumerable)
+  }
+
+  /// Add a property whose name is only known at runtime, it's a regular string rather than a [`PropertyKey`]
+  pub fn set_named<K: AsRef<str>, V: ToNapiValue>(&mut self, name: K, val: V) -> Result<&mut Self> {
+    let name = unsafe { <&str>::to_napi_value(self.env, name.as_ref())? };
+    self.push_raw(
+      name,
+      val,
+      sys::PropertyAttributes::writable
+        | sys::PropertyAttributes::enumerable
+        | sys::PropertyAttributes::configurable,
+    )
+  }
+
+  fn push<V: ToNapiValue>(
+    &mut self,
+    key: &PropertyKey,
+    val: V,
+    attributes: sys::napi_property_attributes,
+  ) -> Result<&mut Self> {
+    let name = unsafe { key.get(self.env)? };
+    self.push_raw(name, val, attributes)
+  }
+
+  fn push_raw<V: ToNapiValue>(
+    &mut self,
+    name: sys::napi_value,
+    val: V,
+    attributes: sys::napi_property_attributes,
+  ) -> Result<&mut Self> {
+    let value = unsafe { V::to_napi_value(self.env, val)? };
+    self.properties.push(sys::napi_property_descriptor {
+      utf8name: ptr::null(),
+      name,
+      method: None,
+      getter: None,
+      setter: None,
+      value,
+      attributes,
+      data: ptr::null_mut(),
+    });
+    Ok(self)
+  }
+
+  /// Create the object with all the collected properties
+  pub fn build(self) -> Result<Object> {
+    let mut obj = ptr::null_mut();
+    unsafe {
+      check_status!(
+        sys::napi_create_object(self.env, &mut obj),
+        "Failed to create napi Object"
+      )?;
+      if !self.properties.is_empty() {
+        check_status!(
+          sys::napi_define_properties(
+            self.env,
+            obj,
+            self.properties.len(),
+            self.properties.as_ptr(),
+          ),
+          "Failed to define {} properties of object",
+          self.properties.len(),
+        )?;
+      }
+    }
+
+    Ok(JsObject(crate::Value {
+      env: self.env,
+      value: obj,
+      value_type: ValueType::Object,
+    }))
+  }
 }
 
 impl TypeName for Object {
@@ -88,3 +349,9 @@ impl TypeName for Object {
     ValueType::Object
   }
 }
+
+impl ValidateNapiValue for Object {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
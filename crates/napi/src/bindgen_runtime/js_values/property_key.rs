@@ -0,0 +1,92 @@
+use std::ptr;
+
+#[cfg(feature = "napi10")]
+use crate::bindgen_runtime::get_napi_version;
+use crate::{bindgen_prelude::*, check_status, sys, ValueType};
+
+/// A property key of a field name known at compile time, e.g. the fields of `#[napi(object)]` structs.
+///
+/// With the `napi10` feature and a Node.js supporting Node-API 10 the key is created with
+/// `node_api_create_property_key_utf8`, the internalized string is compared by identity
+/// by the engine, which makes repeated property accesses with the same name cheaper.
+/// Older versions get a regular string.
+///
+/// ```rust
+/// use napi::bindgen_prelude::*;
+///
+/// const NAME: PropertyKey = PropertyKey::new("name");
+///
+/// fn get_name(obj: &Object) -> Result<Option<String>> {
+///   obj.get_by_key(&NAME)
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PropertyKey(&'static str);
+
+impl PropertyKey {
+  pub const fn new(name: &'static str) -> Self {
+    Self(name)
+  }
+
+  pub fn name(&self) -> &'static str {
+    self.0
+  }
+
+  /// Create the JavaScript string of this key in `env`
+  ///
+  /// # Safety
+  ///
+  /// `env` must be a valid `napi_env` of the current thread
+  pub unsafe fn get(&self, env: sys::napi_env) -> Result<sys::napi_value> {
+    unsafe { create_key(env, self.0) }
+  }
+}
+
+/// Internalized strings are compared by identity when used as property keys,
+/// older Node.js without `node_api_create_property_key_utf8` get a regular string
+#[cfg(feature = "napi10")]
+unsafe fn create_key(env: sys::napi_env, name: &str) -> Result<sys::napi_value> {
+  if get_napi_version(env)? < 10 {
+    return unsafe { create_string_key(env, name) };
+  }
+  let mut value = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::node_api_create_property_key_utf8(env, name.as_ptr().cast(), name.len(), &mut value)
+    },
+    "Failed to create property key `{}`",
+    name
+  )?;
+  Ok(value)
+}
+
+#[cfg(not(feature = "napi10"))]
+unsafe fn create_key(env: sys::napi_env, name: &str) -> Result<sys::napi_value> {
+  unsafe { create_string_key(env, name) }
+}
+
+unsafe fn create_string_key(env: sys::napi_env, name: &str) -> Result<sys::napi_value> {
+  let mut value = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_string_utf8(env, name.as_ptr().cast(), name.len(), &mut value) },
+    "Failed to create property key `{}`",
+    name
+  )?;
+  Ok(value)
+}
+
+impl TypeName for PropertyKey {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ToNapiValue for PropertyKey {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { val.get(env) }
+  }
+}
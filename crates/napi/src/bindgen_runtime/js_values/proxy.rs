@@ -0,0 +1,144 @@
+use crate::{CallContext, Env, JsFunction, JsObject, JsUnknown, NapiValue, Result};
+
+use super::ToNapiValue;
+
+/// The traps of a `Proxy` created by [`Env::create_proxy`].
+///
+/// Operations without a trap are forwarded to the target as usual. Properties which only exist
+/// in the traps must be reported as configurable by `getOwnPropertyDescriptor`,
+/// otherwise the `Proxy` invariants are violated and JavaScript throws a `TypeError`.
+///
+/// ```rust,ignore
+/// let mut handler = ProxyHandler::new(&env)?;
+/// handler
+///   .get(|_, _, key: JsUnknown| Ok(Some(key.coerce_to_string()?.into_utf8()?.into_owned()?.len())))?
+///   .has(|_, _, _| Ok(true))?;
+/// let lengths = env.create_proxy(env.create_object()?, handler)?;
+/// ```
+pub struct ProxyHandler {
+  env: Env,
+  handler: JsObject,
+}
+
+impl ProxyHandler {
+  pub fn new(env: &Env) -> Result<Self> {
+    Ok(Self {
+      env: *env,
+      handler: env.create_object()?,
+    })
+  }
+
+  /// `get(target, key, receiver)`, the key is a string or a symbol.
+  ///
+  /// Returning `None` gets the property from the target with `Reflect.get(target, key, receiver)`,
+  /// so the properties of the target and its prototype chain are still reachable.
+  pub fn get<R, F>(&mut self, trap: F) -> Result<&mut Self>
+  where
+    R: ToNapiValue,
+    F: 'static + Fn(&Env, JsObject, JsUnknown) -> Result<Option<R>>,
+  {
+    self.trap("get", move |ctx| {
+      let env = ctx.env.raw();
+      match trap(ctx.env, ctx.get::<JsObject>(0)?, ctx.get::<JsUnknown>(1)?)? {
+        Some(value) => unsafe { JsUnknown::from_raw(env, R::to_napi_value(env, value)?) },
+        None => {
+          let reflect_get: JsFunction = ctx
+            .env
+            .get_global()?
+            .get_named_property::<JsObject>("Reflect")?
+            .get_named_property("get")?;
+          reflect_get.call(
+            None,
+            &[
+              ctx.get::<JsUnknown>(0)?,
+              ctx.get::<JsUnknown>(1)?,
+              ctx.get::<JsUnknown>(2)?,
+            ],
+          )
+        }
+      }
+    })
+  }
+
+  /// `set(target, key, value, receiver)`, returning `false` throws in strict mode code
+  pub fn set<F>(&mut self, trap: F) -> Result<&mut Self>
+  where
+    F: 'static + Fn(&Env, JsObject, JsUnknown, JsUnknown) -> Result<bool>,
+  {
+    self.trap("set", move |ctx| {
+      trap(
+        ctx.env,
+        ctx.get::<JsObject>(0)?,
+        ctx.get::<JsUnknown>(1)?,
+        ctx.get::<JsUnknown>(2)?,
+      )
+    })
+  }
+
+  /// `has(target, key)`, used by the `in` operator
+  pub fn has<F>(&mut self, trap: F) -> Result<&mut Self>
+  where
+    F: 'static + Fn(&Env, JsObject, JsUnknown) -> Result<bool>,
+  {
+    self.trap("has", move |ctx| {
+      trap(ctx.env, ctx.get::<JsObject>(0)?, ctx.get::<JsUnknown>(1)?)
+    })
+  }
+
+  /// `deleteProperty(target, key)`, used by the `delete` operator
+  pub fn delete_property<F>(&mut self, trap: F) -> Result<&mut Self>
+  where
+    F: 'static + Fn(&Env, JsObject, JsUnknown) -> Result<bool>,
+  {
+    self.trap("deleteProperty", move |ctx| {
+      trap(ctx.env, ctx.get::<JsObject>(0)?, ctx.get::<JsUnknown>(1)?)
+    })
+  }
+
+  /// `ownKeys(target)`, used by `Object.keys` and `for...in` along with `getOwnPropertyDescriptor`
+  pub fn own_keys<R, F>(&mut self, trap: F) -> Result<&mut Self>
+  where
+    R: ToNapiValue,
+    F: 'static + Fn(&Env, JsObject) -> Result<R>,
+  {
+    self.trap("ownKeys", move |ctx| trap(ctx.env, ctx.get::<JsObject>(0)?))
+  }
+
+  /// `getOwnPropertyDescriptor(target, key)`, returns a descriptor object or `None` for `undefined`
+  pub fn get_own_property_descriptor<R, F>(&mut self, trap: F) -> Result<&mut Self>
+  where
+    R: ToNapiValue,
+    F: 'static + Fn(&Env, JsObject, JsUnknown) -> Result<Option<R>>,
+  {
+    self.trap("getOwnPropertyDescriptor", move |ctx| {
+      let env = ctx.env.raw();
+      match trap(ctx.env, ctx.get::<JsObject>(0)?, ctx.get::<JsUnknown>(1)?)? {
+        Some(descriptor) => unsafe { JsUnknown::from_raw(env, R::to_napi_value(env, descriptor)?) },
+        // `null` is not a valid result, it throws a `TypeError`
+        None => ctx
+          .env
+          .get_undefined()
+          .map(|undefined| undefined.into_unknown()),
+      }
+    })
+  }
+
+  /// Any other trap by its JavaScript name, e.g. `defineProperty` or `apply`
+  pub fn trap<R, F>(&mut self, name: &str, trap: F) -> Result<&mut Self>
+  where
+    R: ToNapiValue,
+    F: 'static + Fn(CallContext<'_>) -> Result<R>,
+  {
+    let function = self.env.create_function_from_closure(name, move |ctx| {
+      let env = ctx.env.raw();
+      let value = trap(ctx)?;
+      unsafe { JsUnknown::from_raw(env, R::to_napi_value(env, value)?) }
+    })?;
+    self.handler.set_named_property(name, function)?;
+    Ok(self)
+  }
+
+  pub(crate) fn into_object(self) -> JsObject {
+    self.handler
+  }
+}
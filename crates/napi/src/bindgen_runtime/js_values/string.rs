@@ -1,5 +1,8 @@
+#[cfg(feature = "napi10")]
+use crate::bindgen_runtime::get_napi_version;
 use crate::{bindgen_prelude::*, check_status, sys, Error, Result, Status};
 
+use std::borrow::Cow;
 use std::ffi::{c_void, CStr};
 use std::fmt::Display;
 use std::mem;
@@ -16,19 +19,59 @@ impl TypeName for String {
   }
 }
 
+impl ValidateNapiValue for String {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::String]
+  }
+}
+
 impl ToNapiValue for String {
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    let mut ptr = ptr::null_mut();
+    unsafe { create_string(env, &val) }
+  }
+}
+
+/// Latin1 strings shorter than this are transcoded on the stack
+const LATIN1_STACK_LEN: usize = 64;
+
+/// Create a JavaScript string with the cheapest path for the content of `val`.
+///
+/// V8 stores the strings of latin1 chars with one byte per char, creating them from latin1 saves
+/// decoding the UTF-8, which is most of the cost for short strings.
+unsafe fn create_string(env: sys::napi_env, val: &str) -> Result<sys::napi_value> {
+  let mut ptr = ptr::null_mut();
 
+  if val.is_ascii() {
+    // ASCII is valid latin1 as is
     check_status!(
-      unsafe { sys::napi_create_string_utf8(env, val.as_ptr() as *const _, val.len(), &mut ptr) },
+      unsafe { sys::napi_create_string_latin1(env, val.as_ptr() as *const _, val.len(), &mut ptr) },
       "Failed to convert rust `String` into napi `string`"
     )?;
+    return Ok(ptr);
+  }
 
-    Ok(ptr)
+  // a latin1 char takes at most 2 bytes in UTF-8
+  if val.len() <= LATIN1_STACK_LEN * 2 {
+    let mut latin1 = [0u8; LATIN1_STACK_LEN];
+    if let Some(len) = encode_latin1(val, &mut latin1) {
+      check_status!(
+        unsafe { sys::napi_create_string_latin1(env, latin1.as_ptr() as *const _, len, &mut ptr) },
+        "Failed to convert rust `String` into napi `string`"
+      )?;
+      return Ok(ptr);
+    }
   }
+
+  check_status!(
+    unsafe { sys::napi_create_string_utf8(env, val.as_ptr() as *const _, val.len(), &mut ptr) },
+    "Failed to convert rust `String` into napi `string`"
+  )?;
+
+  Ok(ptr)
 }
 
+/// Lone surrogates of the JavaScript string are replaced with `U+FFFD` silently,
+/// use [`Utf16String`] to reject them or [`Utf16Lossy`] to find out about the replacement.
 impl FromNapiValue for String {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
     let mut len = 0;
@@ -75,6 +118,12 @@ impl TypeName for &str {
   }
 }
 
+impl ValidateNapiValue for &str {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::String]
+  }
+}
+
 impl FromNapiValue for &str {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
     let mut len = 0;
@@ -127,10 +176,109 @@ impl FromNapiValue for &str {
 
 impl ToNapiValue for &str {
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    unsafe { String::to_napi_value(env, val.to_owned()) }
+    unsafe { create_string(env, val) }
   }
 }
 
+impl TypeName for Cow<'_, str> {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ToNapiValue for Cow<'_, str> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { create_string(env, &val) }
+  }
+}
+
+/// Returns `None` if `val` has chars out of latin1 or doesn't fit in `dst`
+fn encode_latin1(val: &str, dst: &mut [u8]) -> Option<usize> {
+  let mut len = 0;
+  for c in val.chars() {
+    *dst.get_mut(len)? = u8::try_from(u32::from(c)).ok()?;
+    len += 1;
+  }
+  Some(len)
+}
+
+/// A string which lives as long as the program, e.g. a literal.
+///
+/// ASCII content is shared with JavaScript as an external string instead of being copied into
+/// the JavaScript heap, with the `napi10` feature on a Node.js supporting Node-API 10.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticStr(pub &'static str);
+
+impl From<&'static str> for StaticStr {
+  fn from(s: &'static str) -> Self {
+    StaticStr(s)
+  }
+}
+
+impl Deref for StaticStr {
+  type Target = str;
+
+  fn deref(&self) -> &Self::Target {
+    self.0
+  }
+}
+
+impl Display for StaticStr {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl TypeName for StaticStr {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ToNapiValue for StaticStr {
+  #[cfg(feature = "napi10")]
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    // older Node.js don't have `node_api_create_external_string_latin1`
+    if !val.0.is_ascii() || get_napi_version(env)? < 10 {
+      return unsafe { create_string(env, val.0) };
+    }
+
+    let mut ptr = ptr::null_mut();
+    let mut copied = false;
+    // the data is never released, so there is nothing to finalize
+    check_status!(
+      unsafe {
+        sys::node_api_create_external_string_latin1(
+          env,
+          val.0.as_ptr() as *mut _,
+          val.0.len(),
+          None,
+          ptr::null_mut(),
+          &mut ptr,
+          &mut copied,
+        )
+      },
+      "Failed to convert rust `&'static str` into napi `string`"
+    )?;
+
+    Ok(ptr)
+  }
+
+  #[cfg(not(feature = "napi10"))]
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { create_string(env, val.0) }
+  }
+}
+
+/// A string read as UTF-16, converting it fails if it contains lone surrogates
 #[derive(Debug)]
 pub struct Utf16String(String);
 
@@ -164,36 +312,17 @@ impl TypeName for Utf16String {
   }
 }
 
+impl ValidateNapiValue for Utf16String {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::String]
+  }
+}
+
 impl FromNapiValue for Utf16String {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
-    let mut len = 0;
-
-    check_status!(
-      unsafe { sys::napi_get_value_string_utf16(env, napi_val, ptr::null_mut(), 0, &mut len) },
-      "Failed to convert napi `utf16 string` into rust type `String`",
-    )?;
-
-    // end char len in C
-    len += 1;
-    let mut ret = vec![0; len];
-    let mut written_char_count = 0;
-
-    check_status!(
-      unsafe {
-        sys::napi_get_value_string_utf16(
-          env,
-          napi_val,
-          ret.as_mut_ptr(),
-          len,
-          &mut written_char_count,
-        )
-      },
-      "Failed to convert napi `utf16 string` into rust type `String`",
-    )?;
-
-    let (_, ret) = ret.split_last().unwrap_or((&0, &[]));
+    let units = unsafe { get_utf16_units(env, napi_val)? };
 
-    match String::from_utf16(ret) {
+    match String::from_utf16(&units) {
       Err(e) => Err(Error::new(
         Status::InvalidArg,
         format!("Failed to read utf16 string, {}", e),
@@ -203,6 +332,38 @@ impl FromNapiValue for Utf16String {
   }
 }
 
+/// The UTF-16 code units of a JavaScript string
+unsafe fn get_utf16_units(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Vec<u16>> {
+  let mut len = 0;
+
+  check_status!(
+    unsafe { sys::napi_get_value_string_utf16(env, napi_val, ptr::null_mut(), 0, &mut len) },
+    "Failed to convert napi `utf16 string` into rust type `String`",
+  )?;
+
+  // end char len in C
+  len += 1;
+  let mut ret = vec![0; len];
+  let mut written_char_count = 0;
+
+  check_status!(
+    unsafe {
+      sys::napi_get_value_string_utf16(
+        env,
+        napi_val,
+        ret.as_mut_ptr(),
+        len,
+        &mut written_char_count,
+      )
+    },
+    "Failed to convert napi `utf16 string` into rust type `String`",
+  )?;
+
+  // drop the end char
+  ret.truncate(written_char_count);
+  Ok(ret)
+}
+
 impl ToNapiValue for Utf16String {
   unsafe fn to_napi_value(env: sys::napi_env, val: Utf16String) -> Result<sys::napi_value> {
     let mut ptr = ptr::null_mut();
@@ -220,6 +381,84 @@ impl ToNapiValue for Utf16String {
   }
 }
 
+/// A string read as UTF-16 with the lone surrogates replaced by `U+FFFD`,
+/// which remembers if there were any, e.g. to flag the data which didn't survive the conversion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Utf16Lossy {
+  string: String,
+  replaced: bool,
+}
+
+impl Utf16Lossy {
+  pub fn as_str(&self) -> &str {
+    &self.string
+  }
+
+  pub fn into_string(self) -> String {
+    self.string
+  }
+
+  /// Whether any lone surrogates were replaced
+  pub fn is_lossy(&self) -> bool {
+    self.replaced
+  }
+}
+
+impl From<String> for Utf16Lossy {
+  fn from(string: String) -> Self {
+    Self {
+      string,
+      replaced: false,
+    }
+  }
+}
+
+impl Display for Utf16Lossy {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.string)
+  }
+}
+
+impl Deref for Utf16Lossy {
+  type Target = str;
+
+  fn deref(&self) -> &Self::Target {
+    &self.string
+  }
+}
+
+impl TypeName for Utf16Lossy {
+  fn type_name() -> &'static str {
+    "String(utf16 lossy)"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl FromNapiValue for Utf16Lossy {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let units = unsafe { get_utf16_units(env, napi_val)? };
+    let mut replaced = false;
+    let string = char::decode_utf16(units)
+      .map(|c| {
+        c.unwrap_or_else(|_| {
+          replaced = true;
+          char::REPLACEMENT_CHARACTER
+        })
+      })
+      .collect();
+    Ok(Self { string, replaced })
+  }
+}
+
+impl ToNapiValue for Utf16Lossy {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Utf16Lossy) -> Result<sys::napi_value> {
+    unsafe { create_string(env, &val.string) }
+  }
+}
+
 #[cfg(feature = "latin1")]
 pub mod latin1_string {
   use super::*;
@@ -257,6 +496,12 @@ pub mod latin1_string {
     }
   }
 
+  impl ValidateNapiValue for Latin1String {
+    fn type_of() -> Vec<ValueType> {
+      vec![ValueType::String]
+    }
+  }
+
   impl FromNapiValue for Latin1String {
     unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
       let mut len = 0;
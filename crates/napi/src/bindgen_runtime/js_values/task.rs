@@ -138,7 +138,7 @@ extern "C" fn on_abort(
   ptr::null_mut()
 }
 
-impl<T: Task> ToNapiValue for AsyncTask<T> {
+impl<T: Task + 'static> ToNapiValue for AsyncTask<T> {
   unsafe fn to_napi_value(
     env: napi_sys::napi_env,
     val: Self,
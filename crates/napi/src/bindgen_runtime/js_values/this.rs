@@ -0,0 +1,48 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{sys, Result};
+
+use super::{FromNapiValue, Object};
+
+/// The JavaScript `this` value of the current call.
+///
+/// Declare it as an argument of a `#[napi]` function to receive the caller context,
+/// it doesn't consume any JavaScript argument.
+/// `T` could be `Object`, a `#[napi(object)]` struct or a reference to a `#[napi]` class.
+pub struct This<T = Object> {
+  pub object: T,
+}
+
+impl<T> This<T> {
+  pub fn into_inner(self) -> T {
+    self.object
+  }
+}
+
+impl<T> From<T> for This<T> {
+  fn from(object: T) -> Self {
+    Self { object }
+  }
+}
+
+impl<T> Deref for This<T> {
+  type Target = T;
+
+  fn deref(&self) -> &Self::Target {
+    &self.object
+  }
+}
+
+impl<T> DerefMut for This<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.object
+  }
+}
+
+impl<T: FromNapiValue> FromNapiValue for This<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Self {
+      object: unsafe { T::from_napi_value(env, napi_val)? },
+    })
+  }
+}
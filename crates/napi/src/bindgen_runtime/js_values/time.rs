@@ -0,0 +1,105 @@
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::{bindgen_prelude::*, check_status, sys, ValueType};
+
+unsafe fn create_date(
+  env: sys::napi_env,
+  val: OffsetDateTime,
+  rust_type: &str,
+) -> Result<sys::napi_value> {
+  let mut ptr = std::ptr::null_mut();
+  let millis_since_epoch_utc = (val.unix_timestamp_nanos() / 1_000_000) as f64;
+
+  check_status!(
+    unsafe { sys::napi_create_date(env, millis_since_epoch_utc, &mut ptr) },
+    "Failed to convert rust type `{}` into napi value",
+    rust_type,
+  )?;
+
+  Ok(ptr)
+}
+
+unsafe fn get_date(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  rust_type: &str,
+) -> Result<OffsetDateTime> {
+  let mut milliseconds_since_epoch_utc = 0.0;
+
+  check_status!(
+    unsafe { sys::napi_get_date_value(env, napi_val, &mut milliseconds_since_epoch_utc) },
+    "Failed to convert napi value into rust type `{}`",
+    rust_type,
+  )?;
+
+  // an invalid date is `NaN`, which is not within the range of `OffsetDateTime` either
+  if !milliseconds_since_epoch_utc.is_finite() {
+    return Err(Error::new(
+      Status::DateExpected,
+      "Found invalid date".to_owned(),
+    ));
+  }
+  OffsetDateTime::from_unix_timestamp_nanos(milliseconds_since_epoch_utc as i128 * 1_000_000)
+    .map_err(|e| Error::new(Status::DateExpected, format!("Found invalid date, {}", e)))
+}
+
+impl TypeName for OffsetDateTime {
+  fn type_name() -> &'static str {
+    "OffsetDateTime"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for OffsetDateTime {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+/// The offset is lost, a JavaScript `Date` is a point in time
+impl ToNapiValue for OffsetDateTime {
+  unsafe fn to_napi_value(env: sys::napi_env, val: OffsetDateTime) -> Result<sys::napi_value> {
+    unsafe { create_date(env, val, "OffsetDateTime") }
+  }
+}
+
+/// Read in UTC
+impl FromNapiValue for OffsetDateTime {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { get_date(env, napi_val, "OffsetDateTime") }
+  }
+}
+
+impl TypeName for PrimitiveDateTime {
+  fn type_name() -> &'static str {
+    "PrimitiveDateTime"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for PrimitiveDateTime {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+/// The date and time are taken as UTC
+impl ToNapiValue for PrimitiveDateTime {
+  unsafe fn to_napi_value(env: sys::napi_env, val: PrimitiveDateTime) -> Result<sys::napi_value> {
+    unsafe { create_date(env, val.assume_utc(), "PrimitiveDateTime") }
+  }
+}
+
+/// The date and time in UTC
+impl FromNapiValue for PrimitiveDateTime {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let date_time = unsafe { get_date(env, napi_val, "PrimitiveDateTime")? };
+    Ok(PrimitiveDateTime::new(date_time.date(), date_time.time()))
+  }
+}
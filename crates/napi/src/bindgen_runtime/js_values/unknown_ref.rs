@@ -0,0 +1,119 @@
+use std::ptr;
+use std::sync::Arc;
+use std::thread::{self, ThreadId};
+
+use crate::{bindgen_prelude::*, check_status, sys, type_of, ValueType};
+
+/// A reference to any JavaScript value, which outlives the call it's received in.
+///
+/// The type parameters of `#[napi]` classes are instantiated with it, so the values stored in the class are kept alive.
+/// Clones share the same reference, it's released on the thread of its `Env` when the last clone is dropped.
+///
+/// Only objects, functions and externals could be referenced on all Node-API versions, other values are kept in a one element array.
+#[derive(Clone)]
+pub struct UnknownRef {
+  inner: Arc<UnknownRefInner>,
+}
+
+struct UnknownRefInner {
+  env: sys::napi_env,
+  reference: sys::napi_ref,
+  thread: ThreadId,
+  /// The value is the first element of the referenced array
+  boxed: bool,
+}
+
+// The reference is only used on the thread which created it, see `Drop`
+unsafe impl Send for UnknownRefInner {}
+unsafe impl Sync for UnknownRefInner {}
+
+impl Drop for UnknownRefInner {
+  fn drop(&mut self) {
+    // Leak it if dropped on another thread, it's released along with the `Env` anyway
+    if thread::current().id() == self.thread {
+      unsafe { sys::napi_delete_reference(self.env, self.reference) };
+    }
+  }
+}
+
+impl UnknownRef {
+  fn get(&self, env: sys::napi_env) -> Result<sys::napi_value> {
+    if env != self.inner.env || thread::current().id() != self.inner.thread {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "A value received from JavaScript can only be returned to the same Env".to_owned(),
+      ));
+    }
+    let mut value = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env, self.inner.reference, &mut value) },
+      "Failed to get the referenced value"
+    )?;
+    if self.inner.boxed {
+      let array = value;
+      check_status!(
+        unsafe { sys::napi_get_element(env, array, 0, &mut value) },
+        "Failed to get the referenced value"
+      )?;
+    }
+    Ok(value)
+  }
+}
+
+impl TypeName for UnknownRef {
+  fn type_name() -> &'static str {
+    "unknown"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Unknown
+  }
+}
+
+impl ValidateNapiValue for UnknownRef {}
+
+impl FromNapiValue for UnknownRef {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let value_type = type_of!(env, napi_val)?;
+    let boxed = !matches!(
+      value_type,
+      ValueType::Object | ValueType::Function | ValueType::External
+    );
+    let mut referenced = napi_val;
+    if boxed {
+      check_status!(
+        unsafe { sys::napi_create_array_with_length(env, 1, &mut referenced) },
+        "Failed to create array"
+      )?;
+      check_status!(
+        unsafe { sys::napi_set_element(env, referenced, 0, napi_val) },
+        "Failed to set array element"
+      )?;
+    }
+    let mut reference = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, referenced, 1, &mut reference) },
+      "Failed to create reference of value"
+    )?;
+    Ok(UnknownRef {
+      inner: Arc::new(UnknownRefInner {
+        env,
+        reference,
+        thread: thread::current().id(),
+        boxed,
+      }),
+    })
+  }
+}
+
+impl ToNapiValue for UnknownRef {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    val.get(env)
+  }
+}
+
+impl ToNapiValue for &UnknownRef {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    val.get(env)
+  }
+}
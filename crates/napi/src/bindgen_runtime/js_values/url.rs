@@ -0,0 +1,202 @@
+use std::ops::{Deref, DerefMut};
+
+use url::{form_urlencoded, Url};
+
+use crate::{
+  bindgen_prelude::*, sys, JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue, ValueType,
+};
+
+fn get_global_constructor(env: &Env, name: &str) -> Result<JsFunction> {
+  env.get_global()?.get_named_property(name)
+}
+
+/// Parse the `href` of a WHATWG `URL` object
+unsafe fn url_from_object(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Url> {
+  let env = Env::from(env);
+  let value = unsafe { JsUnknown::from_raw_unchecked(env.0, napi_val) };
+  if value.get_type()? != ValueType::Object
+    || !value.instanceof(get_global_constructor(&env, "URL")?)?
+  {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Expected a URL, but received {}", value.get_type()?),
+    ));
+  }
+  let href: String = unsafe { value.cast::<JsObject>() }.get_value("href")?;
+  parse_url(&href)
+}
+
+fn parse_url(href: &str) -> Result<Url> {
+  Url::parse(href)
+    .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid URL `{}`, {}", href, e)))
+}
+
+impl TypeName for Url {
+  fn type_name() -> &'static str {
+    "URL"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for Url {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+/// Created as a WHATWG `URL`
+impl ToNapiValue for Url {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Url) -> Result<sys::napi_value> {
+    let env = Env::from(env);
+    let href = env.create_string(val.as_str())?;
+    let url = get_global_constructor(&env, "URL")?.new_instance(&[href])?;
+    Ok(unsafe { url.raw() })
+  }
+}
+
+impl FromNapiValue for Url {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { url_from_object(env, napi_val) }
+  }
+}
+
+/// A `url::Url` argument which is either a `URL` object or a string to parse, `string | URL` in TypeScript
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlOrString(pub Url);
+
+impl UrlOrString {
+  pub fn into_inner(self) -> Url {
+    self.0
+  }
+}
+
+impl Deref for UrlOrString {
+  type Target = Url;
+
+  fn deref(&self) -> &Url {
+    &self.0
+  }
+}
+
+impl DerefMut for UrlOrString {
+  fn deref_mut(&mut self) -> &mut Url {
+    &mut self.0
+  }
+}
+
+impl From<Url> for UrlOrString {
+  fn from(url: Url) -> Self {
+    Self(url)
+  }
+}
+
+impl TypeName for UrlOrString {
+  fn type_name() -> &'static str {
+    "string | URL"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Unknown
+  }
+}
+
+impl ValidateNapiValue for UrlOrString {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::String, ValueType::Object]
+  }
+}
+
+impl FromNapiValue for UrlOrString {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let url = if type_of!(env, napi_val)? == ValueType::String {
+      parse_url(&unsafe { String::from_napi_value(env, napi_val)? })?
+    } else {
+      unsafe { url_from_object(env, napi_val)? }
+    };
+    Ok(Self(url))
+  }
+}
+
+/// Returned as a WHATWG `URL`
+impl ToNapiValue for UrlOrString {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { Url::to_napi_value(env, val.0) }
+  }
+}
+
+/// The decoded name and value pairs of a WHATWG `URLSearchParams`, in order and with duplicates
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchParams(pub Vec<(String, String)>);
+
+impl SearchParams {
+  /// The first value of `name`, like `URLSearchParams.prototype.get`
+  pub fn get(&self, name: &str) -> Option<&str> {
+    self
+      .0
+      .iter()
+      .find(|(key, _)| key == name)
+      .map(|(_, value)| value.as_str())
+  }
+}
+
+impl From<&Url> for SearchParams {
+  fn from(url: &Url) -> Self {
+    Self(url.query_pairs().into_owned().collect())
+  }
+}
+
+impl TypeName for SearchParams {
+  fn type_name() -> &'static str {
+    "URLSearchParams"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for SearchParams {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl ToNapiValue for SearchParams {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let env_wrapper = Env::from(env);
+    let query = form_urlencoded::Serializer::new(String::new())
+      .extend_pairs(val.0)
+      .finish();
+    let query = env_wrapper.create_string(&query)?;
+    let params = get_global_constructor(&env_wrapper, "URLSearchParams")?.new_instance(&[query])?;
+    Ok(unsafe { params.raw() })
+  }
+}
+
+impl FromNapiValue for SearchParams {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let env_wrapper = Env::from(env);
+    let value = unsafe { JsUnknown::from_raw_unchecked(env, napi_val) };
+    if value.get_type()? != ValueType::Object
+      || !value.instanceof(get_global_constructor(&env_wrapper, "URLSearchParams")?)?
+    {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Expected a URLSearchParams, but received {}",
+          value.get_type()?
+        ),
+      ));
+    }
+    // `toString` serializes as `application/x-www-form-urlencoded`
+    let query = value.coerce_to_string()?.into_utf8()?.into_owned()?;
+    Ok(Self(
+      form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect(),
+    ))
+  }
+}
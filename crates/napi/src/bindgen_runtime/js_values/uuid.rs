@@ -0,0 +1,87 @@
+use uuid::Uuid;
+
+use crate::{bindgen_prelude::*, sys, ValueType};
+
+impl TypeName for Uuid {
+  fn type_name() -> &'static str {
+    "Uuid"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ValidateNapiValue for Uuid {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::String]
+  }
+}
+
+/// The lowercase hyphenated form, like `crypto.randomUUID()`
+impl ToNapiValue for Uuid {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Uuid) -> Result<sys::napi_value> {
+    let mut buf = Uuid::encode_buffer();
+    unsafe { <&str>::to_napi_value(env, val.hyphenated().encode_lower(&mut buf)) }
+  }
+}
+
+/// Any of the forms accepted by `Uuid::parse_str`, e.g. hyphenated, simple or braced
+impl FromNapiValue for Uuid {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let value = unsafe { String::from_napi_value(env, napi_val)? };
+    Uuid::parse_str(&value).map_err(|e| {
+      Error::new(
+        Status::InvalidArg,
+        format!("Invalid UUID `{}`, {}", value, e),
+      )
+    })
+  }
+}
+
+/// A [`Uuid`] converted from and into its 16 bytes, a `Buffer` or any other `Uint8Array`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UuidBytes(pub Uuid);
+
+impl From<Uuid> for UuidBytes {
+  fn from(uuid: Uuid) -> Self {
+    Self(uuid)
+  }
+}
+
+impl TypeName for UuidBytes {
+  fn type_name() -> &'static str {
+    "UuidBytes"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for UuidBytes {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl ToNapiValue for UuidBytes {
+  unsafe fn to_napi_value(env: sys::napi_env, val: UuidBytes) -> Result<sys::napi_value> {
+    unsafe { Buffer::to_napi_value(env, val.0.as_bytes().to_vec().into()) }
+  }
+}
+
+impl FromNapiValue for UuidBytes {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let bytes = unsafe { Uint8Array::from_napi_value(env, napi_val)? };
+    Uuid::from_slice(&bytes).map(UuidBytes).map_err(|_| {
+      Error::new(
+        Status::InvalidArg,
+        format!(
+          "Expected 16 bytes of UUID, but received {} bytes",
+          bytes.len()
+        ),
+      )
+    })
+  }
+}
@@ -3,15 +3,19 @@ mod env;
 mod error;
 mod js_values;
 mod module_register;
+mod trace;
 
 pub use callback_info::*;
 pub use ctor::ctor;
 pub use env::*;
+pub use error::catch_unwind;
 pub use js_values::*;
 pub use module_register::*;
+pub use trace::*;
 
 use super::sys;
-use std::{ffi::c_void, mem};
+use crate::{check_status, Result};
+use std::{ffi::c_void, mem, ptr};
 
 /// # Safety
 ///
@@ -21,6 +25,8 @@ pub unsafe extern "C" fn raw_finalize_unchecked<T>(
   finalize_data: *mut c_void,
   finalize_hint: *mut c_void,
 ) {
+  #[cfg(not(feature = "napi8"))]
+  remove_class_instance(finalize_data);
   let obj = finalize_data as *mut T;
   unsafe { Box::from_raw(obj) };
   if !finalize_hint.is_null() {
@@ -50,3 +56,42 @@ pub unsafe extern "C" fn drop_buffer(
     mem::drop(Vec::from_raw_parts(finalize_data as *mut u8, length, cap));
   }
 }
+
+/// Electron forbids external buffers once the V8 memory cage is enabled,
+/// `napi_create_external_buffer` returns `napi_no_external_buffers_allowed` there.
+///
+/// Fallback to copy the data into a VM managed `Buffer`, and release the external data immediately.
+/// Returns the pointer of the copied data.
+pub(crate) unsafe fn create_buffer_copy_and_finalize(
+  env: sys::napi_env,
+  data: *mut c_void,
+  length: usize,
+  finalize: unsafe extern "C" fn(sys::napi_env, *mut c_void, *mut c_void),
+  finalize_hint: *mut c_void,
+  result: *mut sys::napi_value,
+) -> Result<*mut c_void> {
+  let mut copy_data = ptr::null_mut();
+  let status = unsafe { sys::napi_create_buffer_copy(env, length, data, &mut copy_data, result) };
+  unsafe { finalize(env, data, finalize_hint) };
+  check_status!(status, "Failed to copy data into buffer")?;
+  Ok(copy_data)
+}
+
+/// The same with [`create_buffer_copy_and_finalize`], but for `ArrayBuffer`.
+pub(crate) unsafe fn create_arraybuffer_copy_and_finalize(
+  env: sys::napi_env,
+  data: *mut c_void,
+  length: usize,
+  finalize: unsafe extern "C" fn(sys::napi_env, *mut c_void, *mut c_void),
+  finalize_hint: *mut c_void,
+  result: *mut sys::napi_value,
+) -> Result<*mut c_void> {
+  let mut copy_data = ptr::null_mut();
+  let status = unsafe { sys::napi_create_arraybuffer(env, length, &mut copy_data, result) };
+  if status == sys::Status::napi_ok && length > 0 {
+    unsafe { ptr::copy_nonoverlapping(data as *const u8, copy_data as *mut u8, length) };
+  }
+  unsafe { finalize(env, data, finalize_hint) };
+  check_status!(status, "Failed to copy data into arraybuffer")?;
+  Ok(copy_data)
+}
@@ -3,12 +3,17 @@ use std::collections::HashMap;
 use std::ffi::c_void;
 use std::ffi::CStr;
 use std::ptr;
-use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicPtr, AtomicU32, AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 use lazy_static::lazy_static;
 
+#[cfg(feature = "napi8")]
+use crate::{addon_type_tag, check_object_tag, tag_object};
 use crate::{
-  check_status, check_status_or_throw, sys, JsError, JsFunction, Property, Result, Value, ValueType,
+  bindgen_prelude::raw_finalize_unchecked, call_reflect, check_status, check_status_or_throw, sys,
+  Env, Error, JsError, JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue, Property, Result,
+  Status, Value, ValueType,
 };
 
 pub type ExportRegisterCallback = unsafe fn(sys::napi_env) -> Result<sys::napi_value>;
@@ -77,18 +82,41 @@ impl<K, V> Default for PersistedSingleThreadHashMap<K, V> {
   }
 }
 
-type ModuleRegisterCallback =
-  PersistedSingleThreadVec<(Option<&'static str>, (&'static str, ExportRegisterCallback))>;
+type ModuleRegisterCallback = PersistedSingleThreadVec<(
+  Option<&'static str>,
+  (
+    &'static str,
+    ExportRegisterCallback,
+    /* minimum napi version */ u32,
+  ),
+)>;
 
 type ModuleClassProperty = PersistedSingleThreadHashMap<
   &'static str,
   HashMap<Option<&'static str>, (&'static str, Vec<Property>)>,
 >;
 
-type FnRegisterMap = PersistedSingleThreadHashMap<
-  ExportRegisterCallback,
-  (sys::napi_env, sys::napi_callback, &'static str),
->;
+#[doc(hidden)]
+/// Parent of a class declared with `#[napi(extends = ..)]`
+pub enum ClassParent {
+  /// Another `#[napi]` class, by the name of its Rust struct
+  Class(&'static str),
+  /// A JavaScript constructor, a global like `"Error"` or `"module:Export"` for builtin modules
+  Js(&'static str),
+}
+
+type ModuleClassParent =
+  PersistedSingleThreadHashMap</* rust name */ &'static str, ClassParent>;
+
+/// Functions are registered once per `Env`, but the C callbacks are the same for all of them,
+/// so it's keyed by the Rust callback only and shared by all `Env`s.
+type FnRegisterMap = Mutex<HashMap<ExportRegisterCallback, (sys::napi_callback, &'static str)>>;
+
+/// The class instances wrapped by this addon, by the address of their Rust struct.
+/// Shared by all `Env`s, the addresses of live instances are unique in the process.
+/// Only used without the `napi8` feature, the instances are type tagged otherwise.
+#[cfg(not(feature = "napi8"))]
+type WrappedClassInstances = Mutex<HashMap</* address */ usize, /* rust name */ &'static str>>;
 
 unsafe impl<K, V> Send for PersistedSingleThreadHashMap<K, V> {}
 unsafe impl<K, V> Sync for PersistedSingleThreadHashMap<K, V> {}
@@ -97,6 +125,13 @@ lazy_static! {
   static ref MODULE_REGISTER_CALLBACK: ModuleRegisterCallback = Default::default();
   static ref MODULE_CLASS_PROPERTIES: ModuleClassProperty = Default::default();
   static ref FN_REGISTER_MAP: FnRegisterMap = Default::default();
+  static ref MODULE_INIT: PersistedSingleThreadVec<ModuleExportsCallback> = Default::default();
+  static ref MODULE_CLASS_PARENTS: ModuleClassParent = Default::default();
+}
+
+#[cfg(not(feature = "napi8"))]
+lazy_static! {
+  static ref WRAPPED_CLASS_INSTANCES: WrappedClassInstances = Default::default();
 }
 
 #[cfg(feature = "compat-mode")]
@@ -106,20 +141,114 @@ lazy_static! {
 }
 
 thread_local! {
+  /// The `Env` which registered the function last on this thread, for [`get_js_function`]
+  static FN_REGISTER_ENV: RefCell<HashMap<ExportRegisterCallback, sys::napi_env>> = Default::default();
+
+  // An `Env` always runs on one thread, but one thread may hold several `Env`s,
+  // e.g. when an embedder `require` the addon in multiple contexts.
   static REGISTERED_CLASSES: RefCell<HashMap<
-    /* export name */ &'static str,
-    /* constructor */ sys::napi_ref,
+    /* env */ sys::napi_env,
+    HashMap<
+      /* export name */ &'static str,
+      /* constructor */ sys::napi_ref,
+    >,
+  >> = Default::default();
+
+  // JavaScript constructors of the `#[napi(extends = "..")]` classes, called on every new instance
+  static JS_CLASS_PARENTS: RefCell<HashMap<
+    /* env */ sys::napi_env,
+    HashMap<
+      /* rust name */ &'static str,
+      /* parent constructor */ sys::napi_ref,
+    >,
   >> = Default::default();
 }
 
 #[doc(hidden)]
-pub fn get_class_constructor(js_name: &'static str) -> Option<sys::napi_ref> {
+pub fn get_class_constructor(env: sys::napi_env, js_name: &'static str) -> Option<sys::napi_ref> {
   REGISTERED_CLASSES.with(|registered_classes| {
     let classes = registered_classes.borrow();
-    classes.get(js_name).copied()
+    classes
+      .get(&env)
+      .and_then(|classes| classes.get(js_name).copied())
   })
 }
 
+#[doc(hidden)]
+/// Create the instance of a class extending a JavaScript class with `Reflect.construct(parent, args, new_target)`.
+///
+/// `class` constructors can't be called on an existing `this`, so the parent creates the instance itself,
+/// with its internal slots and private `#fields`, and the prototype of `new_target`.
+/// `None` if the class doesn't extend a JavaScript class, the instance is the `this` of the constructor then.
+///
+/// # Safety
+///
+/// `new_target` and `args` must be valid values of `env`
+pub unsafe fn call_js_parent_constructor(
+  env: sys::napi_env,
+  rust_name: &str,
+  new_target: sys::napi_value,
+  args: &[sys::napi_value],
+) -> Result<Option<sys::napi_value>> {
+  let parent = match get_js_class_parent(env, rust_name) {
+    Some(parent) => parent,
+    None => return Ok(None),
+  };
+  let mut parent_ctor = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_reference_value(env, parent, &mut parent_ctor) },
+    "Failed to get parent constructor of class `{}`",
+    rust_name
+  )?;
+  let mut args_array = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_array_with_length(env, args.len(), &mut args_array) },
+    "Failed to create arguments of parent constructor of class `{}`",
+    rust_name
+  )?;
+  for (index, arg) in args.iter().enumerate() {
+    check_status!(
+      unsafe { sys::napi_set_element(env, args_array, index as u32, *arg) },
+      "Failed to create arguments of parent constructor of class `{}`",
+      rust_name
+    )?;
+  }
+  let instance = unsafe { call_reflect(env, "construct", &[parent_ctor, args_array, new_target])? };
+  Ok(Some(instance))
+}
+
+/// The JavaScript constructor `rust_name` or its closest `#[napi]` ancestor extends
+fn get_js_class_parent(env: sys::napi_env, rust_name: &str) -> Option<sys::napi_ref> {
+  let mut current = rust_name;
+  loop {
+    let parent = JS_CLASS_PARENTS.with(|parents| {
+      parents
+        .borrow()
+        .get(&env)
+        .and_then(|parents| parents.get(current).copied())
+    });
+    if parent.is_some() {
+      return parent;
+    }
+    current = match MODULE_CLASS_PARENTS.borrow_mut().get(current) {
+      Some(ClassParent::Class(parent)) => parent,
+      _ => return None,
+    };
+  }
+}
+
+/// Whether `rust_name` or one of its `#[napi]` ancestors extends a JavaScript class
+fn extends_js_class(rust_name: &str) -> bool {
+  let mut current = rust_name;
+  loop {
+    current = match MODULE_CLASS_PARENTS.borrow_mut().get(current) {
+      Some(ClassParent::Class(parent)) => parent,
+      Some(ClassParent::Js(_)) => return true,
+      None => return false,
+    };
+  }
+}
+
 #[doc(hidden)]
 #[cfg(feature = "compat-mode")]
 // compatibility for #[module_exports]
@@ -127,13 +256,30 @@ pub fn register_module_exports(callback: ModuleExportsCallback) {
   MODULE_EXPORTS.push(callback);
 }
 
+#[doc(hidden)]
+/// Generated for `#[napi(module_init)]`, the callback is called after all exports are registered
+pub fn register_module_init(callback: ModuleExportsCallback) {
+  MODULE_INIT.push(callback);
+}
+
 #[doc(hidden)]
 pub fn register_module_export(
   js_mod: Option<&'static str>,
   name: &'static str,
   cb: ExportRegisterCallback,
 ) {
-  MODULE_REGISTER_CALLBACK.push((js_mod, (name, cb)));
+  MODULE_REGISTER_CALLBACK.push((js_mod, (name, cb, 0)));
+}
+
+#[doc(hidden)]
+/// Generated for `#[napi(napi_version = N)]`, the export is skipped if the running Node.js supports a lower Node-API version
+pub fn register_module_export_with_napi_version(
+  js_mod: Option<&'static str>,
+  name: &'static str,
+  cb: ExportRegisterCallback,
+  napi_version: u32,
+) {
+  MODULE_REGISTER_CALLBACK.push((js_mod, (name, cb, napi_version)));
 }
 
 #[doc(hidden)]
@@ -143,7 +289,12 @@ pub fn register_js_function(
   cb: ExportRegisterCallback,
   c_fn: sys::napi_callback,
 ) {
-  FN_REGISTER_MAP.borrow_mut().insert(cb, (env, c_fn, name));
+  if let Ok(mut map) = FN_REGISTER_MAP.lock() {
+    map.insert(cb, (c_fn, name));
+  }
+  FN_REGISTER_ENV.with(|envs| {
+    envs.borrow_mut().insert(cb, env);
+  });
 }
 
 #[doc(hidden)]
@@ -161,6 +312,266 @@ pub fn register_class(
   val.1.extend(props.into_iter());
 }
 
+#[doc(hidden)]
+pub fn register_class_parent(rust_name: &'static str, parent: ClassParent) {
+  MODULE_CLASS_PARENTS.borrow_mut().insert(rust_name, parent);
+}
+
+#[doc(hidden)]
+/// Wrap `obj` into `instance`, a new instance of the class generated for `rust_name` by this addon
+///
+/// # Safety
+///
+/// `instance` must be a valid object of `env`
+pub unsafe fn wrap_class_instance<T>(
+  env: sys::napi_env,
+  instance: sys::napi_value,
+  rust_name: &'static str,
+  obj: T,
+) -> Result<()> {
+  #[cfg(feature = "napi8")]
+  unsafe {
+    tag_object(env, instance, addon_type_tag(rust_name))?
+  };
+  let wrapped = Box::into_raw(Box::new(obj));
+  check_status!(
+    unsafe {
+      sys::napi_wrap(
+        env,
+        instance,
+        wrapped as *mut c_void,
+        Some(raw_finalize_unchecked::<T>),
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    },
+    "Failed to initialize class `{}`",
+    rust_name,
+  )?;
+  // the `Box`es of zero sized structs all share one dangling address, see `is_class_instance`
+  #[cfg(not(feature = "napi8"))]
+  if std::mem::size_of::<T>() != 0 {
+    if let Ok(mut instances) = WRAPPED_CLASS_INSTANCES.lock() {
+      instances.insert(wrapped as usize, rust_name);
+    }
+  }
+  Ok(())
+}
+
+/// Forget the class instance at `wrapped` once it's finalized
+#[cfg(not(feature = "napi8"))]
+pub(crate) fn remove_class_instance(wrapped: *mut c_void) {
+  if let Ok(mut instances) = WRAPPED_CLASS_INSTANCES.lock() {
+    instances.remove(&(wrapped as usize));
+  }
+}
+
+#[doc(hidden)]
+/// Fail unless `value`, whose wrapped Rust struct is `wrapped`, is an instance of the class generated
+/// for `rust_name` by this addon, or of its subclasses.
+///
+/// Otherwise `wrapped` would be read as `T`, while it may be an instance of another class or wrapped by another addon.
+///
+/// # Safety
+///
+/// `value` must be a valid object of `env`
+pub unsafe fn check_class_instance<T>(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  wrapped: *mut c_void,
+  rust_name: &str,
+) -> Result<()> {
+  // any address holds a valid zero sized `T`
+  #[cfg(not(feature = "napi8"))]
+  if std::mem::size_of::<T>() == 0 {
+    return Ok(());
+  }
+  if unsafe { is_class_instance(env, value, wrapped, rust_name)? } {
+    return Ok(());
+  }
+  Err(Error::new(
+    Status::InvalidArg,
+    format!("Object is not an instance of class `{}`", rust_name),
+  ))
+}
+
+#[cfg(feature = "napi8")]
+unsafe fn is_class_instance(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  _wrapped: *mut c_void,
+  rust_name: &str,
+) -> Result<bool> {
+  unsafe { has_class_tag(env, value, rust_name) }
+}
+
+/// Whether `value` is tagged as an instance of the class generated for `rust_name` or of its subclasses
+#[cfg(feature = "napi8")]
+unsafe fn has_class_tag(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  rust_name: &str,
+) -> Result<bool> {
+  if unsafe { check_object_tag(env, value, addon_type_tag(rust_name))? } {
+    return Ok(true);
+  }
+  // instances of `#[napi(extends = Parent)]` classes are unwrapped as `Parent` by the inherited methods
+  let subclasses = MODULE_CLASS_PARENTS
+    .borrow_mut()
+    .iter()
+    .filter(|(_, parent)| matches!(parent, ClassParent::Class(parent) if *parent == rust_name))
+    .map(|(subclass, _)| *subclass)
+    .collect::<Vec<_>>();
+  for subclass in subclasses {
+    if unsafe { has_class_tag(env, value, subclass)? } {
+      return Ok(true);
+    }
+  }
+  Ok(false)
+}
+
+/// Without type tags the wrapped address is looked up in the instances this addon wrapped
+#[cfg(not(feature = "napi8"))]
+unsafe fn is_class_instance(
+  _env: sys::napi_env,
+  _value: sys::napi_value,
+  wrapped: *mut c_void,
+  rust_name: &str,
+) -> Result<bool> {
+  let mut class = WRAPPED_CLASS_INSTANCES
+    .lock()
+    .ok()
+    .and_then(|instances| instances.get(&(wrapped as usize)).copied());
+  while let Some(name) = class {
+    if name == rust_name {
+      return Ok(true);
+    }
+    // instances of `#[napi(extends = Parent)]` classes are unwrapped as `Parent` by the inherited methods
+    class = match MODULE_CLASS_PARENTS.borrow_mut().get(name) {
+      Some(ClassParent::Class(parent)) => Some(*parent),
+      _ => None,
+    };
+  }
+  Ok(false)
+}
+
+/// Constructors of the class generated by the Rust struct `rust_name`, one per `js_mod`
+pub(crate) fn get_class_constructors_by_rust_name(
+  env: sys::napi_env,
+  rust_name: &str,
+) -> Result<Vec<JsObject>> {
+  let js_names = MODULE_CLASS_PROPERTIES
+    .borrow_mut()
+    .get(rust_name)
+    .map(|js_mods| {
+      js_mods
+        .values()
+        .map(|(js_name, _)| *js_name)
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  let ctors = js_names
+    .into_iter()
+    .filter_map(|js_name| get_class_constructor(env, js_name))
+    .map(|ctor_ref| {
+      let mut ctor = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_reference_value(env, ctor_ref, &mut ctor) },
+        "Failed to get constructor of class `{}`",
+        rust_name
+      )?;
+      Ok(unsafe { JsObject::from_raw_unchecked(env, ctor) })
+    })
+    .collect::<Result<Vec<_>>>()?;
+  if ctors.is_empty() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Class `{}` is not registered", rust_name),
+    ));
+  }
+  Ok(ctors)
+}
+
+fn get_js_constructor(env: &Env, name: &str) -> Result<JsObject> {
+  let ctor: JsUnknown = match name.split_once(':') {
+    Some((module, export)) => env.get_builtin_module(module)?.get_named_property(export)?,
+    None => env.get_global()?.get_named_property(name)?,
+  };
+  if ctor.get_type()? != ValueType::Function {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("`{}` is not a constructor", name),
+    ));
+  }
+  Ok(unsafe { ctor.cast() })
+}
+
+/// Instance properties of the `#[napi]` ancestors of `rust_name`.
+///
+/// They are defined again on the class, V8 only allows calling the methods of a class created by
+/// `napi_define_class` on the instances it constructed itself, not on the instances of its subclasses.
+fn inherited_instance_properties(rust_name: &str) -> Vec<Property> {
+  let mut inherited = vec![];
+  let mut current = rust_name;
+  // `extends` can't form a cycle, the parent must be the first field of the struct
+  while let Some(ClassParent::Class(parent)) = MODULE_CLASS_PARENTS.borrow_mut().get(current) {
+    if let Some((_, props)) = MODULE_CLASS_PROPERTIES
+      .borrow_mut()
+      .get(parent)
+      .and_then(|js_mods| js_mods.values().next())
+    {
+      inherited.extend(
+        props
+          .iter()
+          .filter(|prop| !prop.is_ctor && !prop.is_static())
+          .cloned(),
+      );
+    }
+    current = parent;
+  }
+  inherited
+}
+
+/// Wire up the prototype chain of the class generated by `rust_name` with its parent
+fn inherit_class(env: sys::napi_env, rust_name: &'static str, parent: &ClassParent) -> Result<()> {
+  let env_wrapper = unsafe { Env::from_raw(env) };
+  let parent_ctor = match parent {
+    ClassParent::Class(parent_rust_name) => {
+      get_class_constructors_by_rust_name(env, parent_rust_name)?.remove(0)
+    }
+    ClassParent::Js(name) => {
+      let parent_ctor = get_js_constructor(&env_wrapper, name)?;
+      let mut parent_ref = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_create_reference(env, parent_ctor.raw(), 1, &mut parent_ref) },
+        "Failed to create reference of `{}`",
+        name
+      )?;
+      JS_CLASS_PARENTS.with(|parents| {
+        parents
+          .borrow_mut()
+          .entry(env)
+          .or_default()
+          .insert(rust_name, parent_ref);
+      });
+      parent_ctor
+    }
+  };
+  let parent_prototype: JsObject = parent_ctor.get_named_property("prototype")?;
+  let object = env_wrapper
+    .get_global()?
+    .get_named_property::<JsFunction>("Object")?
+    .coerce_to_object()?;
+  let set_prototype_of: JsFunction = object.get_named_property("setPrototypeOf")?;
+  for ctor in get_class_constructors_by_rust_name(env, rust_name)? {
+    let prototype: JsObject = ctor.get_named_property("prototype")?;
+    // instances inherit the methods, the class inherits the static methods
+    set_prototype_of.call(Some(&object), &[&prototype, &parent_prototype])?;
+    set_prototype_of.call(Some(&object), &[&ctor, &parent_ctor])?;
+  }
+  Ok(())
+}
+
 #[inline]
 /// Get `JsFunction` from defined Rust `fn`
 /// ```rust
@@ -179,27 +590,51 @@ pub fn register_class(
 /// returnSomeFn()(); // 1
 /// ```
 ///
+/// The function is created in the `Env` which registered it last on the current thread,
+/// use [`get_js_function_in_env`] if the addon is loaded into several `Env`s of one thread.
 pub fn get_js_function(raw_fn: ExportRegisterCallback) -> Result<JsFunction> {
+  let env = FN_REGISTER_ENV
+    .with(|envs| envs.borrow().get(&raw_fn).copied())
+    .ok_or_else(|| {
+      crate::Error::new(
+        crate::Status::InvalidArg,
+        "JavaScript function does not exist".to_owned(),
+      )
+    })?;
+  get_js_function_in_env(&Env::from(env), raw_fn)
+}
+
+#[inline]
+/// Get `JsFunction` from defined Rust `fn`, created in `env`
+/// ```rust
+/// #[napi]
+/// fn return_some_fn(env: Env) -> Result<JsFunction> {
+///     get_js_function_in_env(&env, some_fn_js_function)
+/// }
+/// ```
+pub fn get_js_function_in_env(env: &Env, raw_fn: ExportRegisterCallback) -> Result<JsFunction> {
+  let env = env.raw();
   FN_REGISTER_MAP
-    .borrow_mut()
-    .get(&raw_fn)
-    .and_then(|(env, cb, name)| {
+    .lock()
+    .ok()
+    .and_then(|map| map.get(&raw_fn).copied())
+    .and_then(|(cb, name)| {
       let mut function = ptr::null_mut();
       let name_len = name.len() - 1;
       let fn_name = unsafe { CStr::from_bytes_with_nul_unchecked(name.as_bytes()) };
       check_status!(unsafe {
         sys::napi_create_function(
-          *env,
+          env,
           fn_name.as_ptr(),
           name_len,
-          *cb,
+          cb,
           ptr::null_mut(),
           &mut function,
         )
       })
       .ok()?;
       Some(JsFunction(Value {
-        env: *env,
+        env,
         value: function,
         value_type: ValueType::Function,
       }))
@@ -233,9 +668,9 @@ pub fn get_js_function(raw_fn: ExportRegisterCallback) -> Result<JsFunction> {
 ///
 pub fn get_c_callback(raw_fn: ExportRegisterCallback) -> Result<crate::Callback> {
   FN_REGISTER_MAP
-    .borrow_mut()
-    .get(&raw_fn)
-    .and_then(|(_env, cb, _name)| *cb)
+    .lock()
+    .ok()
+    .and_then(|map| map.get(&raw_fn).and_then(|(cb, _name)| *cb))
     .ok_or_else(|| {
       crate::Error::new(
         crate::Status::InvalidArg,
@@ -244,22 +679,50 @@ pub fn get_c_callback(raw_fn: ExportRegisterCallback) -> Result<crate::Callback>
     })
 }
 
+/// Node-API version of the running Node.js, `0` until it's read
+static NAPI_VERSION: AtomicU32 = AtomicU32::new(0);
+
+/// The Node-API version supported by the running Node.js, read once for the process.
+///
+/// Functions of newer versions than the enabled feature must check it before being called,
+/// as the addon may be loaded by an older Node.js which doesn't provide them.
+pub(crate) fn get_napi_version(env: sys::napi_env) -> Result<u32> {
+  let version = NAPI_VERSION.load(Ordering::Relaxed);
+  if version != 0 {
+    return Ok(version);
+  }
+  let mut version = 0;
+  check_status!(
+    unsafe { sys::napi_get_version(env, &mut version) },
+    "Failed to get Node-API version"
+  )?;
+  NAPI_VERSION.store(version, Ordering::Relaxed);
+  Ok(version)
+}
+
 #[no_mangle]
 unsafe extern "C" fn napi_register_module_v1(
   env: sys::napi_env,
   exports: sys::napi_value,
 ) -> sys::napi_value {
   let mut exports_objects: HashMap<Option<&'static str>, sys::napi_value> = HashMap::default();
+  let napi_version = get_napi_version(env);
+  debug_assert!(napi_version.is_ok(), "Get Node-API version failed");
+  let napi_version = napi_version.unwrap_or(0);
   MODULE_REGISTER_CALLBACK
     .borrow_mut()
     .iter_mut()
     .fold(
       HashMap::<Option<&'static str>, Vec<(&'static str, ExportRegisterCallback)>>::new(),
-      |mut acc, (js_mod, item)| {
+      |mut acc, (js_mod, (name, cb, min_napi_version))| {
+        if *min_napi_version > napi_version {
+          return acc;
+        }
+        let item = (*name, *cb);
         if let Some(k) = acc.get_mut(js_mod) {
-          k.push(*item);
+          k.push(item);
         } else {
-          acc.insert(*js_mod, vec![*item]);
+          acc.insert(*js_mod, vec![item]);
         }
         acc
       },
@@ -345,14 +808,30 @@ unsafe extern "C" fn napi_register_module_v1(
               exports_objects.insert(*js_mod, exports_js_mod);
             }
           }
-          let (ctor, props): (Vec<_>, Vec<_>) = props.iter().partition(|prop| prop.is_ctor);
+          let inherited = inherited_instance_properties(rust_name);
+          let (ctor, props): (Vec<_>, Vec<_>) = props
+            .iter()
+            .chain(
+              inherited
+                .iter()
+                .filter(|prop| !props.iter().any(|own| own.name == prop.name)),
+            )
+            .partition(|prop| prop.is_ctor);
           // one or more or zero?
           // zero is for `#[napi(task)]`
           if ctor.is_empty() && props.is_empty() {
             continue;
           }
           let ctor = ctor.get(0).map(|c| c.raw().method.unwrap()).unwrap_or(noop);
+          // Instances of classes extending a JavaScript class are created by the parent, see `call_js_parent_constructor`.
+          // `napi_define_class` gives the methods a V8 signature which rejects them, so they're defined on the prototype
+          // and `check_class_instance` checks the receivers instead.
+          let extends_js = extends_js_class(rust_name);
+          let (methods, props): (Vec<_>, Vec<_>) = props
+            .into_iter()
+            .partition(|prop| extends_js && prop.is_instance_method());
           let raw_props: Vec<_> = props.iter().map(|prop| prop.raw()).collect();
+          let raw_methods: Vec<_> = methods.iter().map(|prop| prop.raw()).collect();
 
           let js_class_name = CStr::from_bytes_with_nul_unchecked(js_name.as_bytes());
           let mut class_ptr = ptr::null_mut();
@@ -374,12 +853,37 @@ unsafe extern "C" fn napi_register_module_v1(
             &rust_name
           );
 
+          if !raw_methods.is_empty() {
+            let mut prototype = ptr::null_mut();
+            check_status_or_throw!(
+              env,
+              sys::napi_get_named_property(
+                env,
+                class_ptr,
+                "prototype\0".as_ptr() as *const _,
+                &mut prototype
+              ),
+              "Failed to get prototype of class `{}`",
+              &js_name
+            );
+            check_status_or_throw!(
+              env,
+              sys::napi_define_properties(env, prototype, raw_methods.len(), raw_methods.as_ptr()),
+              "Failed to define methods of class `{}` generate by struct `{}`",
+              &js_name,
+              &rust_name
+            );
+          }
+
           let mut ctor_ref = ptr::null_mut();
           sys::napi_create_reference(env, class_ptr, 1, &mut ctor_ref);
 
           REGISTERED_CLASSES.with(|registered_classes| {
-            let mut registered_class = registered_classes.borrow_mut();
-            registered_class.insert(js_name, ctor_ref);
+            let mut registered_classes = registered_classes.borrow_mut();
+            registered_classes
+              .entry(env)
+              .or_default()
+              .insert(js_name, ctor_ref);
           });
 
           check_status_or_throw!(
@@ -402,6 +906,16 @@ unsafe extern "C" fn napi_register_module_v1(
       }
     });
 
+  // parents are resolved after all classes are defined, they may be registered in any order
+  MODULE_CLASS_PARENTS
+    .borrow_mut()
+    .iter()
+    .for_each(|(rust_name, parent)| {
+      if let Err(e) = inherit_class(env, rust_name, parent) {
+        unsafe { JsError::from(e).throw_into(env) };
+      }
+    });
+
   #[cfg(feature = "compat-mode")]
   MODULE_EXPORTS
     .borrow_mut()
@@ -412,6 +926,22 @@ unsafe extern "C" fn napi_register_module_v1(
       }
     });
 
+  MODULE_INIT.borrow_mut().iter().for_each(|callback| unsafe {
+    if let Err(e) = callback(env, exports) {
+      JsError::from(e).throw_into(env);
+    }
+  });
+
+  #[cfg(feature = "napi3")]
+  {
+    assert_eq!(
+      unsafe {
+        sys::napi_add_env_cleanup_hook(env, Some(remove_registered_classes), env as *mut c_void)
+      },
+      sys::Status::napi_ok
+    );
+  }
+
   #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
   {
     let _ = crate::tokio_runtime::RT.clone();
@@ -427,11 +957,42 @@ unsafe extern "C" fn napi_register_module_v1(
   exports
 }
 
+/// Entry of the addon when it's compiled to WebAssembly and loaded by the emnapi runtime
+///
+/// `ctor` does not run on WebAssembly, so the exports are only collected after the loader called every
+/// exported `__napi_register__*` function, e.g. in the `beforeInit` hook of `instantiateNapiModule`.
+#[cfg(target_family = "wasm")]
+#[no_mangle]
+unsafe extern "C" fn napi_register_wasm_v1(
+  env: sys::napi_env,
+  exports: sys::napi_value,
+) -> sys::napi_value {
+  unsafe { napi_register_module_v1(env, exports) }
+}
+
+#[cfg(feature = "napi3")]
+unsafe extern "C" fn remove_registered_classes(env: *mut c_void) {
+  // References are released along with the `Env`, only the stale entries need to be removed
+  REGISTERED_CLASSES.with(|registered_classes| {
+    registered_classes
+      .borrow_mut()
+      .remove(&(env as sys::napi_env));
+  });
+  FN_REGISTER_ENV.with(|envs| {
+    envs
+      .borrow_mut()
+      .retain(|_, registered_env| *registered_env != env as sys::napi_env);
+  });
+  JS_CLASS_PARENTS.with(|parents| {
+    parents.borrow_mut().remove(&(env as sys::napi_env));
+  });
+}
+
 pub(crate) unsafe extern "C" fn noop(
   env: sys::napi_env,
   _info: sys::napi_callback_info,
 ) -> sys::napi_value {
-  if !crate::bindgen_runtime::___CALL_FROM_FACTORY.load(std::sync::atomic::Ordering::Relaxed) {
+  if !crate::bindgen_runtime::___CALL_FROM_FACTORY.with(|f| f.get()) {
     unsafe {
       sys::napi_throw_error(
         env,
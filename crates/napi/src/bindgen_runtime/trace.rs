@@ -0,0 +1,19 @@
+/// Span of a call from JavaScript into a `#[napi]` function, exited on drop.
+///
+/// Without the `tracing` feature it's a no-op.
+#[doc(hidden)]
+pub struct CallSpan {
+  #[cfg(feature = "tracing")]
+  _span: tracing::span::EnteredSpan,
+}
+
+impl CallSpan {
+  #[inline]
+  #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+  pub fn enter(js_name: &'static str) -> Self {
+    Self {
+      #[cfg(feature = "tracing")]
+      _span: tracing::trace_span!("napi_call", name = js_name).entered(),
+    }
+  }
+}
@@ -6,22 +6,28 @@ use std::os::raw::{c_char, c_void};
 use std::ptr;
 
 use crate::{
+  async_context::AsyncContext,
   async_work::{self, AsyncWorkPromise},
+  bindgen_runtime::{create_arraybuffer_copy_and_finalize, create_buffer_copy_and_finalize},
   check_status,
   js_values::*,
   sys,
-  task::Task,
+  task::{Task, TaskPool},
   Error, ExtendedErrorInfo, NodeVersion, Result, Status, ValueType,
 };
 
 #[cfg(feature = "napi8")]
 use crate::async_cleanup_hook::AsyncCleanupHook;
+#[cfg(feature = "napi4")]
+use crate::bindgen_runtime::ToNapiValue;
 #[cfg(feature = "napi3")]
 use crate::cleanup_env::{CleanupEnvHook, CleanupEnvHookData};
 #[cfg(all(feature = "serde-json"))]
-use crate::js_values::{De, Ser};
+use crate::js_values::{De, Ser, SerdeOptions};
 #[cfg(feature = "napi4")]
 use crate::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction};
+#[cfg(feature = "napi4")]
+use crate::uncaught_exception::UncaughtExceptionReporter;
 #[cfg(feature = "napi3")]
 use crate::JsError;
 #[cfg(all(feature = "serde-json"))]
@@ -255,27 +261,51 @@ impl Env {
   /// This API allocates a node::Buffer object and initializes it with data backed by the passed in buffer.
   ///
   /// While this is still a fully-supported data structure, in most cases using a TypedArray will suffice.
-  pub fn create_buffer_with_data(&self, mut data: Vec<u8>) -> Result<JsBufferValue> {
+  ///
+  /// If external buffers are forbidden, like in Electron with the V8 memory cage enabled, the data will be copied.
+  pub fn create_buffer_with_data(&self, data: Vec<u8>) -> Result<JsBufferValue> {
+    let mut data = mem::ManuallyDrop::new(data);
     let length = data.len();
     let mut raw_value = ptr::null_mut();
-    let data_ptr = data.as_mut_ptr();
-    check_status!(unsafe {
+    let data_ptr = data.as_mut_ptr() as *mut c_void;
+    let hint = Box::into_raw(Box::new((length, data.capacity()))) as *mut c_void;
+    let status = unsafe {
       sys::napi_create_external_buffer(
         self.0,
         length,
-        data_ptr as *mut c_void,
+        data_ptr,
         Some(drop_buffer),
-        Box::into_raw(Box::new((length, data.capacity()))) as *mut c_void,
+        hint,
         &mut raw_value,
       )
-    })?;
+    };
+    if status == sys::Status::napi_no_external_buffers_allowed {
+      let copy_data = unsafe {
+        create_buffer_copy_and_finalize(
+          self.0,
+          data_ptr,
+          length,
+          drop_buffer,
+          hint,
+          &mut raw_value,
+        )?
+      };
+      // The copied data may be null if the buffer is empty
+      data = mem::ManuallyDrop::new(if length == 0 || copy_data.is_null() {
+        Vec::new()
+      } else {
+        unsafe { Vec::from_raw_parts(copy_data as *mut u8, length, length) }
+      });
+    } else {
+      check_status!(status)?;
+    }
     Ok(JsBufferValue::new(
       JsBuffer(Value {
         env: self.0,
         value: raw_value,
         value_type: ValueType::Object,
       }),
-      mem::ManuallyDrop::new(data),
+      data,
     ))
   }
 
@@ -296,23 +326,32 @@ impl Env {
     Finalize: FnOnce(Hint, Env),
   {
     let mut raw_value = ptr::null_mut();
-    check_status!(unsafe {
+    let mut data = data as *mut c_void;
+    let hint = Box::into_raw(Box::new((hint, finalize_callback))) as *mut c_void;
+    let status = unsafe {
       sys::napi_create_external_buffer(
         self.0,
         length,
-        data as *mut c_void,
-        Some(
-          raw_finalize_with_custom_callback::<Hint, Finalize>
-            as unsafe extern "C" fn(
-              env: sys::napi_env,
-              finalize_data: *mut c_void,
-              finalize_hint: *mut c_void,
-            ),
-        ),
-        Box::into_raw(Box::new((hint, finalize_callback))) as *mut c_void,
+        data,
+        Some(raw_finalize_with_custom_callback::<Hint, Finalize>),
+        hint,
         &mut raw_value,
       )
-    })?;
+    };
+    if status == sys::Status::napi_no_external_buffers_allowed {
+      data = unsafe {
+        create_buffer_copy_and_finalize(
+          self.0,
+          data,
+          length,
+          raw_finalize_with_custom_callback::<Hint, Finalize>,
+          hint,
+          &mut raw_value,
+        )?
+      };
+    } else {
+      check_status!(status)?;
+    }
     Ok(JsBufferValue::new(
       JsBuffer(Value {
         env: self.0,
@@ -379,22 +418,38 @@ impl Env {
     ))
   }
 
+  /// If external buffers are forbidden, like in Electron with the V8 memory cage enabled, the data will be copied.
   pub fn create_arraybuffer_with_data(&self, data: Vec<u8>) -> Result<JsArrayBufferValue> {
     let length = data.len();
     let mut raw_value = ptr::null_mut();
-    let data_ptr = data.as_ptr();
-    check_status!(unsafe {
+    let mut data_ptr = data.as_ptr() as *mut c_void;
+    let hint = Box::into_raw(Box::new((length, data.capacity()))) as *mut c_void;
+    mem::forget(data);
+    let status = unsafe {
       sys::napi_create_external_arraybuffer(
         self.0,
-        data_ptr as *mut c_void,
+        data_ptr,
         length,
         Some(drop_buffer),
-        Box::into_raw(Box::new((length, data.capacity()))) as *mut c_void,
+        hint,
         &mut raw_value,
       )
-    })?;
+    };
+    if status == sys::Status::napi_no_external_buffers_allowed {
+      data_ptr = unsafe {
+        create_arraybuffer_copy_and_finalize(
+          self.0,
+          data_ptr,
+          length,
+          drop_buffer,
+          hint,
+          &mut raw_value,
+        )?
+      };
+    } else {
+      check_status!(status)?;
+    }
 
-    mem::forget(data);
     Ok(JsArrayBufferValue::new(
       JsArrayBuffer(Value {
         env: self.0,
@@ -423,23 +478,32 @@ impl Env {
     Finalize: FnOnce(Hint, Env),
   {
     let mut raw_value = ptr::null_mut();
-    check_status!(unsafe {
+    let mut data = data as *mut c_void;
+    let hint = Box::into_raw(Box::new((hint, finalize_callback))) as *mut c_void;
+    let status = unsafe {
       sys::napi_create_external_arraybuffer(
         self.0,
-        data as *mut c_void,
+        data,
         length,
-        Some(
-          raw_finalize_with_custom_callback::<Hint, Finalize>
-            as unsafe extern "C" fn(
-              env: sys::napi_env,
-              finalize_data: *mut c_void,
-              finalize_hint: *mut c_void,
-            ),
-        ),
-        Box::into_raw(Box::new((hint, finalize_callback))) as *mut c_void,
+        Some(raw_finalize_with_custom_callback::<Hint, Finalize>),
+        hint,
         &mut raw_value,
       )
-    })?;
+    };
+    if status == sys::Status::napi_no_external_buffers_allowed {
+      data = unsafe {
+        create_arraybuffer_copy_and_finalize(
+          self.0,
+          data,
+          length,
+          raw_finalize_with_custom_callback::<Hint, Finalize>,
+          hint,
+          &mut raw_value,
+        )?
+      };
+    } else {
+      check_status!(status)?;
+    }
     Ok(JsArrayBufferValue::new(
       JsArrayBuffer(Value {
         env: self.0,
@@ -476,6 +540,11 @@ impl Env {
     Ok(unsafe { JsFunction::from_raw_unchecked(self.0, raw_result) })
   }
 
+  /// Create a JavaScript function backed by a Rust closure.
+  ///
+  /// Unlike [`create_function`](Env::create_function), the closure may capture arbitrary Rust state.
+  ///
+  /// The closure is boxed and attached to the created function, it will be dropped once the function is garbage collected.
   #[cfg(feature = "napi5")]
   pub fn create_function_from_closure<R, F>(&self, name: &str, callback: F) -> Result<JsFunction>
   where
@@ -667,7 +736,7 @@ impl Env {
   }
 
   /// This API throws a JavaScript SyntaxError with the text provided.
-  #[cfg(feature = "experimental")]
+  #[cfg(feature = "napi9")]
   pub fn throw_syntax_error(&self, msg: &str, code: Option<&str>) -> Result<()> {
     let code = code.and_then(|s| CString::new(s).ok());
     let msg = CString::new(msg)?;
@@ -680,26 +749,13 @@ impl Env {
     })
   }
 
-  #[allow(clippy::expect_fun_call)]
   /// In the event of an unrecoverable error in a native module
   ///
   /// A fatal error can be thrown to immediately terminate the process.
+  ///
+  /// It doesn't need an `Env`, use [`fatal_error`](crate::fatal_error()) from other threads.
   pub fn fatal_error(self, location: &str, message: &str) {
-    let location_len = location.len();
-    let message_len = message.len();
-    let location =
-      CString::new(location).expect(format!("Convert [{}] to CString failed", location).as_str());
-    let message =
-      CString::new(message).expect(format!("Convert [{}] to CString failed", message).as_str());
-
-    unsafe {
-      sys::napi_fatal_error(
-        location.as_ptr(),
-        location_len,
-        message.as_ptr(),
-        message_len,
-      )
-    }
+    crate::fatal_error(location, message)
   }
 
   #[cfg(feature = "napi3")]
@@ -713,6 +769,12 @@ impl Env {
     };
   }
 
+  /// Create an [`UncaughtExceptionReporter`], which could be moved to other threads to trigger an `'uncaughtException'` from there.
+  #[cfg(feature = "napi4")]
+  pub fn create_uncaught_exception_reporter(&self) -> Result<UncaughtExceptionReporter> {
+    UncaughtExceptionReporter::new(self.0)
+  }
+
   /// Create JavaScript class
   pub fn define_class(
     &self,
@@ -742,12 +804,15 @@ impl Env {
     Ok(unsafe { JsFunction::from_raw_unchecked(self.0, raw_result) })
   }
 
+  /// With the `napi8` feature the object is type tagged to check it in [`Env::unwrap`], so objects
+  /// which already have a type tag, e.g. instances of `#[napi]` classes, can't be wrapped.
   pub fn wrap<T: 'static>(&self, js_object: &mut JsObject, native_object: T) -> Result<()> {
+    unsafe { tag_tagged_object(self.0, js_object.0.value)? };
     check_status!(unsafe {
       sys::napi_wrap(
         self.0,
         js_object.0.value,
-        Box::into_raw(Box::new(TaggedObject::new(native_object))) as *mut c_void,
+        TaggedObject::into_raw(native_object) as *mut c_void,
         Some(raw_finalize::<T>),
         ptr::null_mut(),
         ptr::null_mut(),
@@ -763,6 +828,7 @@ impl Env {
         js_object.0.value,
         &mut unknown_tagged_object,
       ))?;
+      check_tagged_object(self.0, js_object.0.value, unknown_tagged_object)?;
 
       let type_id = unknown_tagged_object as *const TypeId;
       if *type_id == TypeId::of::<T>() {
@@ -790,6 +856,7 @@ impl Env {
         js_ref.raw_value,
         &mut unknown_tagged_object,
       ))?;
+      check_tagged_object(self.0, js_ref.raw_value, unknown_tagged_object)?;
 
       let type_id = unknown_tagged_object as *const TypeId;
       if *type_id == TypeId::of::<T>() {
@@ -812,14 +879,21 @@ impl Env {
   pub fn drop_wrapped<T: 'static>(&self, js_object: &mut JsObject) -> Result<()> {
     unsafe {
       let mut unknown_tagged_object = ptr::null_mut();
-      check_status!(sys::napi_remove_wrap(
+      // don't remove the wrap of objects wrapped by other addons
+      check_status!(sys::napi_unwrap(
         self.0,
         js_object.0.value,
         &mut unknown_tagged_object,
       ))?;
+      check_tagged_object(self.0, js_object.0.value, unknown_tagged_object)?;
       let type_id = unknown_tagged_object as *const TypeId;
       if *type_id == TypeId::of::<T>() {
-        Box::from_raw(unknown_tagged_object as *mut TaggedObject<T>);
+        check_status!(sys::napi_remove_wrap(
+          self.0,
+          js_object.0.value,
+          &mut unknown_tagged_object,
+        ))?;
+        TaggedObject::drop_raw(unknown_tagged_object as *mut TaggedObject<T>);
         Ok(())
       } else {
         Err(Error::new(
@@ -911,12 +985,13 @@ impl Env {
     check_status!(unsafe {
       sys::napi_create_external(
         self.0,
-        Box::into_raw(Box::new(TaggedObject::new(native_object))) as *mut c_void,
+        TaggedObject::into_raw(native_object) as *mut c_void,
         Some(raw_finalize::<T>),
         Box::into_raw(Box::new(size_hint)) as *mut c_void,
         &mut object_value,
       )
     })?;
+    unsafe { tag_tagged_object(self.0, object_value)? };
     if let Some(changed) = size_hint {
       if changed != 0 {
         let mut adjusted_value = 0i64;
@@ -936,6 +1011,7 @@ impl Env {
         js_external.0.value,
         &mut unknown_tagged_object,
       ))?;
+      check_tagged_object(self.0, js_external.0.value, unknown_tagged_object)?;
 
       let type_id = unknown_tagged_object as *const TypeId;
       if *type_id == TypeId::of::<T>() {
@@ -965,11 +1041,19 @@ impl Env {
     Ok(unsafe { JsObject::from_raw_unchecked(self.0, result) })
   }
 
-  /// Run [Task](./trait.Task.html) in libuv thread pool, return [AsyncWorkPromise](./struct.AsyncWorkPromise.html)
+  /// Run [Task](./trait.Task.html) in the thread pool of its `POOL`, return [AsyncWorkPromise](./struct.AsyncWorkPromise.html)
+  ///
+  /// The default pool could be changed by [set_async_work_pool](./fn.set_async_work_pool.html).
   pub fn spawn<T: 'static + Task>(&self, task: T) -> Result<AsyncWorkPromise> {
     async_work::run(self.0, task, None)
   }
 
+  /// Run [Task](./trait.Task.html) in the dedicated pool regardless of its `POOL`, for CPU heavy
+  /// work which would otherwise block the libuv thread pool shared with Node.js I/O
+  pub fn spawn_blocking<T: 'static + Task>(&self, task: T) -> Result<AsyncWorkPromise> {
+    async_work::run_in_pool(self.0, task, None, TaskPool::Cpu)
+  }
+
   pub fn run_in_scope<T, F>(&self, executor: F) -> Result<T>
   where
     F: FnOnce() -> Result<T>,
@@ -983,6 +1067,52 @@ impl Env {
     result
   }
 
+  /// [napi_async_init](https://nodejs.org/api/n-api.html#napi_async_init)
+  ///
+  /// Create an async resource which captures the current async context, `resource_name` is the `type` reported to `async_hooks`.
+  ///
+  /// If `resource` is `None`, an empty object will be used as the async resource.
+  ///
+  /// Use it to call back into JavaScript from native code, so `AsyncLocalStorage` stores and `async_hooks` are propagated to the callbacks.
+  pub fn create_async_context(
+    &self,
+    resource: Option<JsObject>,
+    resource_name: &str,
+  ) -> Result<AsyncContext> {
+    AsyncContext::new(self.0, resource, resource_name)
+  }
+
+  /// [napi_get_version](https://nodejs.org/api/n-api.html#napi_get_version)
+  ///
+  /// The highest Node-API version supported by the running Node.js, which may be higher than the `napiN` feature this addon was built with.
+  ///
+  /// Use it to register exports conditionally, so one binary could serve multiple Node.js majors:
+  ///
+  /// ```rust,ignore
+  /// #[module_exports]
+  /// fn init(mut exports: JsObject, env: Env) -> Result<()> {
+  ///   if env.node_api_version()? >= 8 {
+  ///     exports.create_named_method("freeze", freeze)?;
+  ///   }
+  ///   Ok(())
+  /// }
+  /// ```
+  ///
+  /// With `#[napi]`, use `#[napi(napi_version = 8)]` on a function instead.
+  pub fn node_api_version(&self) -> Result<u32> {
+    let mut version = 0;
+    check_status!(
+      unsafe { sys::napi_get_version(self.0, &mut version) },
+      "Failed to get Node-API version"
+    )?;
+    Ok(version)
+  }
+
+  /// Version of the running Node.js, same as [`get_node_version`](#method.get_node_version).
+  pub fn node_version(&self) -> Result<NodeVersion> {
+    self.get_node_version()
+  }
+
   pub fn get_napi_version(&self) -> Result<u32> {
     let global = self.get_global()?;
     let process: JsObject = global.get_named_property("process")?;
@@ -995,7 +1125,14 @@ impl Env {
       .map_err(|e| Error::new(Status::InvalidArg, format!("{}", e)))
   }
 
-  #[cfg(feature = "napi2")]
+  /// [napi_get_uv_event_loop](https://nodejs.org/api/n-api.html#napi_get_uv_event_loop)
+  ///
+  /// Get the raw `uv_loop_t*` of current `Env`, so that other libuv based C libraries could attach handles to the Node.js event loop.
+  ///
+  /// The loop is only valid while the `Env` is alive, and must only be used from the JavaScript main thread.
+  ///
+  /// Everything done with the raw loop is unchecked, use it with care.
+  #[cfg(feature = "uv")]
   pub fn get_uv_event_loop(&self) -> Result<*mut sys::uv_loop_s> {
     let mut uv_loop: *mut sys::uv_loop_s = ptr::null_mut();
     check_status!(unsafe { sys::napi_get_uv_event_loop(self.0, &mut uv_loop) })?;
@@ -1051,6 +1188,14 @@ impl Env {
     ThreadsafeFunction::create(self.0, func.0.value, max_queue_size, callback)
   }
 
+  /// Create a `Promise` with a [`JsDeferred`](crate::JsDeferred) to settle it.
+  ///
+  /// The `JsDeferred` could be sent to other threads, so it's suitable for wrapping callback based native libraries.
+  #[cfg(feature = "napi4")]
+  pub fn create_deferred<Data: ToNapiValue>(&self) -> Result<(JsDeferred<Data>, JsObject)> {
+    JsDeferred::new(self.0)
+  }
+
   #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
   pub fn execute_tokio_future<
     T: 'static + Send,
@@ -1223,7 +1368,18 @@ impl Env {
   where
     T: Serialize,
   {
-    let s = Ser(self);
+    self.to_js_value_with_options(node, SerdeOptions::default())
+  }
+
+  /// Serialize data to `JsValue`, choosing how 64-bit integers, `NaN` and the infinities
+  /// and [`serde_date`](crate::serde_date) fields are represented
+  #[cfg(feature = "serde-json")]
+  #[allow(clippy::wrong_self_convention)]
+  pub fn to_js_value_with_options<T>(&self, node: &T, options: SerdeOptions) -> Result<JsUnknown>
+  where
+    T: Serialize,
+  {
+    let s = Ser(self, options);
     node.serialize(s).map(JsUnknown)
   }
 
@@ -1258,6 +1414,62 @@ impl Env {
     T::deserialize(&mut de)
   }
 
+  /// `JSON.parse(text)` with the JSON parser of the engine
+  pub fn json_parse(&self, text: &str) -> Result<JsUnknown> {
+    let json: JsObject = self.get_global()?.get_named_property("JSON")?;
+    let parse: JsFunction = json.get_named_property("parse")?;
+    parse.call(Some(&json), &[self.create_string(text)?])
+  }
+
+  /// `JSON.stringify(value)` with the JSON serializer of the engine,
+  /// `None` for the values without a JSON representation like `undefined` or functions
+  pub fn json_stringify<V>(&self, value: V) -> Result<Option<String>>
+  where
+    V: NapiRaw,
+  {
+    let json: JsObject = self.get_global()?.get_named_property("JSON")?;
+    let stringify: JsFunction = json.get_named_property("stringify")?;
+    let value = unsafe { JsUnknown::from_raw_unchecked(self.0, value.raw()) };
+    let text = stringify.call(Some(&json), &[value])?;
+    if text.get_type()? == ValueType::Undefined {
+      return Ok(None);
+    }
+    unsafe { text.cast::<JsString>() }
+      .into_utf8()?
+      .into_owned()
+      .map(Some)
+  }
+
+  /// Serialize `node` through JSON text, which the engine parses with `JSON.parse`.
+  ///
+  /// It's usually faster than [`to_js_value`](Env::to_js_value) for large data,
+  /// but bytes become arrays and 64-bit integers are always numbers.
+  #[cfg(feature = "serde-json")]
+  #[allow(clippy::wrong_self_convention)]
+  pub fn to_js_value_via_json<T>(&self, node: &T) -> Result<JsUnknown>
+  where
+    T: Serialize,
+  {
+    self.json_parse(&serde_json::to_string(node)?)
+  }
+
+  /// Deserialize from the JSON text of `JSON.stringify(value)`,
+  /// which respects `toJSON` methods unlike [`from_js_value`](Env::from_js_value)
+  #[cfg(feature = "serde-json")]
+  pub fn from_js_value_via_json<T, V>(&self, value: V) -> Result<T>
+  where
+    T: DeserializeOwned,
+    V: NapiRaw,
+  {
+    let text = self.json_stringify(value)?.ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        "Value can not be represented as JSON".to_owned(),
+      )
+    })?;
+    Ok(serde_json::from_str(&text)?)
+  }
+
   /// This API represents the invocation of the Strict Equality algorithm as defined in [Section 7.2.14](https://tc39.es/ecma262/#sec-strict-equality-comparison) of the ECMAScript Language Specification.
   pub fn strict_equals<A: NapiRaw, B: NapiRaw>(&self, a: A, b: B) -> Result<bool> {
     let mut result = false;
@@ -1297,7 +1509,7 @@ pub(crate) unsafe extern "C" fn raw_finalize<T>(
   finalize_hint: *mut c_void,
 ) {
   let tagged_object = finalize_data as *mut TaggedObject<T>;
-  unsafe { Box::from_raw(tagged_object) };
+  unsafe { TaggedObject::drop_raw(tagged_object) };
   if !finalize_hint.is_null() {
     let size_hint = unsafe { *Box::from_raw(finalize_hint as *mut Option<i64>) };
     if let Some(changed) = size_hint {
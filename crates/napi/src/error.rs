@@ -23,12 +23,27 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub struct Error {
   pub status: Status,
   pub reason: String,
+  // boxed so the errors without any of the extras stay small
+  extra: Option<Box<ErrorExtra>>,
   // Convert raw `JsError` into Error
   // Only be used in `async fn(p: Promise<T>)` scenario
   #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
   pub(crate) maybe_raw: sys::napi_ref,
 }
 
+/// What an [`Error`] carries beyond its `status` and `reason`
+#[derive(Debug, Clone, Default)]
+struct ErrorExtra {
+  // `code` of the JavaScript error, the `status` is used if it's not set
+  code: Option<String>,
+  class: Option<ErrorClass>,
+  // property path of the converted value the error happened at, e.g. `retry.maxAttempts`
+  path: Option<String>,
+  // errors reported at once by `Error::aggregate`
+  errors: Vec<Error>,
+  properties: Vec<(String, ErrorProperty)>,
+}
+
 unsafe impl Send for Error {}
 unsafe impl Sync for Error {}
 
@@ -61,6 +76,7 @@ impl From<sys::napi_ref> for Error {
     Self {
       status: Status::InvalidArg,
       reason: "".to_string(),
+      extra: None,
       maybe_raw: value,
     }
   }
@@ -69,7 +85,7 @@ impl From<sys::napi_ref> for Error {
 impl fmt::Display for Error {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     if !self.reason.is_empty() {
-      write!(f, "{:?}, {}", self.status, self.reason)
+      write!(f, "{:?}, {}", self.status, self.js_message())
     } else {
       write!(f, "{:?}", self.status)
     }
@@ -81,6 +97,7 @@ impl Error {
     Error {
       status,
       reason,
+      extra: None,
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
     }
@@ -90,6 +107,7 @@ impl Error {
     Error {
       status,
       reason: "".to_owned(),
+      extra: None,
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
     }
@@ -99,10 +117,318 @@ impl Error {
     Error {
       status: Status::GenericFailure,
       reason,
+      extra: None,
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
     }
   }
+
+  /// Set the `code` of the JavaScript error, instead of the name of the `status`
+  pub fn with_code<C: Into<String>>(mut self, code: C) -> Self {
+    self.extra_mut().code = Some(code.into());
+    self
+  }
+
+  /// Create the JavaScript error with another constructor, e.g. `TypeError`
+  pub fn with_class(mut self, class: ErrorClass) -> Self {
+    self.extra_mut().class = Some(class);
+    self
+  }
+
+  /// Set an extra property on the JavaScript error
+  ///
+  /// ```
+  /// use napi::{Error, Status};
+  ///
+  /// let err = Error::new(Status::GenericFailure, "file not found".to_owned())
+  ///   .with_code("ENOENT")
+  ///   .with_property("path", "/tmp/napi");
+  /// ```
+  pub fn with_property<K: Into<String>, V: Into<ErrorProperty>>(
+    mut self,
+    key: K,
+    value: V,
+  ) -> Self {
+    self.extra_mut().properties.push((key.into(), value.into()));
+    self
+  }
+
+  /// Record that the error happened while converting the property `key` of an object,
+  /// the path grows as the error bubbles up through the nested objects: `retry.maxAttempts: ...`.
+  ///
+  /// The error is thrown as a `TypeError` unless another class was set.
+  pub fn at_property<K: AsRef<str>>(self, key: K) -> Self {
+    self.prefix_path(key.as_ref().to_owned())
+  }
+
+  /// Record that the error happened while converting the element `index` of an array, `items[2].name: ...`
+  pub fn at_index(self, index: usize) -> Self {
+    self.prefix_path(format!("[{}]", index))
+  }
+
+  #[doc(hidden)]
+  /// Prefix the property path with the name of the argument, errors of the argument value itself have no path
+  /// and are left as they are.
+  pub fn at_argument(mut self, name: &str) -> Self {
+    if !self.errors().is_empty() {
+      let extra = self.extra_mut();
+      extra.errors = std::mem::take(&mut extra.errors)
+        .into_iter()
+        .map(|e| e.at_argument(name))
+        .collect();
+      self
+    } else if self.path().is_some() {
+      self.prefix_path(name.to_owned())
+    } else {
+      self
+    }
+  }
+
+  /// Report several errors at once, e.g. of every invalid field of an object.
+  ///
+  /// It's thrown as a `TypeError` listing all the messages, with the JavaScript errors in its `errors` property
+  /// like an `AggregateError`. The errors of other aggregated errors are taken over rather than nested.
+  pub fn aggregate(errors: Vec<Error>) -> Self {
+    let errors = errors
+      .into_iter()
+      .flat_map(|e| match e.extra {
+        Some(extra) if !extra.errors.is_empty() => extra.errors,
+        _ => vec![e],
+      })
+      .collect::<Vec<_>>();
+    let mut error = Error::new(
+      Status::InvalidArg,
+      format!(
+        "{} error{}",
+        errors.len(),
+        if errors.len() == 1 { "" } else { "s" }
+      ),
+    )
+    .with_class(ErrorClass::TypeError);
+    error.extra_mut().errors = errors;
+    error
+  }
+
+  /// The errors reported at once by [`Error::aggregate`]
+  pub fn errors(&self) -> &[Error] {
+    self.extra.as_ref().map_or(&[], |extra| &extra.errors)
+  }
+
+  /// The property path recorded by [`Error::at_property`] and [`Error::at_index`]
+  pub fn path(&self) -> Option<&str> {
+    self.extra.as_ref().and_then(|extra| extra.path.as_deref())
+  }
+
+  fn class(&self) -> Option<ErrorClass> {
+    self.extra.as_ref().and_then(|extra| extra.class)
+  }
+
+  fn properties(&self) -> &[(String, ErrorProperty)] {
+    self.extra.as_ref().map_or(&[], |extra| &extra.properties)
+  }
+
+  fn extra_mut(&mut self) -> &mut ErrorExtra {
+    self.extra.get_or_insert_with(Default::default)
+  }
+
+  fn prefix_path(mut self, segment: String) -> Self {
+    let extra = self.extra_mut();
+    extra.class.get_or_insert(ErrorClass::TypeError);
+    // the path belongs to each of the aggregated errors
+    if !extra.errors.is_empty() {
+      extra.errors = std::mem::take(&mut extra.errors)
+        .into_iter()
+        .map(|e| e.prefix_path(segment.clone()))
+        .collect();
+      return self;
+    }
+    extra.path = Some(match extra.path.take() {
+      Some(path) if path.starts_with('[') => segment + &path,
+      Some(path) => format!("{}.{}", segment, path),
+      None => segment,
+    });
+    self
+  }
+
+  /// `reason` with the property path in front of it, followed by the messages of the aggregated errors
+  fn js_message(&self) -> String {
+    if !self.errors().is_empty() {
+      return format!(
+        "{}: {}",
+        self.reason,
+        self
+          .errors()
+          .iter()
+          .map(|e| e.js_message())
+          .collect::<Vec<_>>()
+          .join("; ")
+      );
+    }
+    match self.path() {
+      Some(path) => format!("{}: {}", path, self.reason),
+      None => self.reason.clone(),
+    }
+  }
+
+  fn js_code(&self) -> String {
+    self
+      .extra
+      .as_ref()
+      .and_then(|extra| extra.code.clone())
+      .unwrap_or_else(|| format!("{:?}", self.status))
+  }
+}
+
+/// Constructor of the JavaScript error created from an [`Error`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+  Error,
+  TypeError,
+  RangeError,
+  #[cfg(feature = "napi9")]
+  SyntaxError,
+}
+
+impl ErrorClass {
+  unsafe fn create(
+    self,
+    env: sys::napi_env,
+    code: sys::napi_value,
+    msg: sys::napi_value,
+    result: *mut sys::napi_value,
+  ) -> sys::napi_status {
+    match self {
+      ErrorClass::Error => unsafe { sys::napi_create_error(env, code, msg, result) },
+      ErrorClass::TypeError => unsafe { sys::napi_create_type_error(env, code, msg, result) },
+      ErrorClass::RangeError => unsafe { sys::napi_create_range_error(env, code, msg, result) },
+      #[cfg(feature = "napi9")]
+      ErrorClass::SyntaxError => unsafe {
+        sys::node_api_create_syntax_error(env, code, msg, result)
+      },
+    }
+  }
+}
+
+#[doc(hidden)]
+#[cfg(feature = "napi9")]
+#[macro_export]
+macro_rules! __syntax_error_class {
+  () => {
+    $crate::bindgen_prelude::ErrorClass::SyntaxError
+  };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "napi9"))]
+#[macro_export]
+macro_rules! __syntax_error_class {
+  () => {
+    compile_error!("`#[napi(class = \"SyntaxError\")]` requires the `napi9` feature of `napi`")
+  };
+}
+
+/// Value of an extra property of the JavaScript error, see [`Error::with_property`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorProperty {
+  String(String),
+  Number(f64),
+  Boolean(bool),
+  Null,
+}
+
+impl ErrorProperty {
+  unsafe fn to_raw(&self, env: sys::napi_env) -> Result<sys::napi_value> {
+    let mut value = ptr::null_mut();
+    match self {
+      ErrorProperty::String(s) => check_status!(unsafe {
+        sys::napi_create_string_utf8(env, s.as_ptr() as *const c_char, s.len(), &mut value)
+      })?,
+      ErrorProperty::Number(n) => {
+        check_status!(unsafe { sys::napi_create_double(env, *n, &mut value) })?
+      }
+      ErrorProperty::Boolean(b) => {
+        check_status!(unsafe { sys::napi_get_boolean(env, *b, &mut value) })?
+      }
+      ErrorProperty::Null => check_status!(unsafe { sys::napi_get_null(env, &mut value) })?,
+    };
+    Ok(value)
+  }
+}
+
+impl From<String> for ErrorProperty {
+  fn from(value: String) -> Self {
+    ErrorProperty::String(value)
+  }
+}
+
+impl From<&str> for ErrorProperty {
+  fn from(value: &str) -> Self {
+    ErrorProperty::String(value.to_owned())
+  }
+}
+
+impl From<bool> for ErrorProperty {
+  fn from(value: bool) -> Self {
+    ErrorProperty::Boolean(value)
+  }
+}
+
+macro_rules! impl_number_error_property {
+  ($($ty:ty),*) => {
+    $(
+      impl From<$ty> for ErrorProperty {
+        fn from(value: $ty) -> Self {
+          ErrorProperty::Number(value as f64)
+        }
+      }
+    )*
+  };
+}
+
+impl_number_error_property!(u8, i8, u16, i16, u32, i32, u64, i64, usize, isize, f32, f64);
+
+impl<T: Into<ErrorProperty>> From<Option<T>> for ErrorProperty {
+  fn from(value: Option<T>) -> Self {
+    value.map_or(ErrorProperty::Null, Into::into)
+  }
+}
+
+/// Set the JavaScript errors of an [`Error::aggregate`] as the `errors` array, like `AggregateError`
+unsafe fn set_aggregated_errors(
+  env: sys::napi_env,
+  js_error: sys::napi_value,
+  errors: &[Error],
+) -> Result<()> {
+  if errors.is_empty() {
+    return Ok(());
+  }
+  let mut js_errors = ptr::null_mut();
+  check_status!(unsafe { sys::napi_create_array_with_length(env, errors.len(), &mut js_errors) })?;
+  for (index, error) in errors.iter().enumerate() {
+    let js_value = unsafe { JsError::from(error.clone()).into_value(env) };
+    check_status!(unsafe { sys::napi_set_element(env, js_errors, index as u32, js_value) })?;
+  }
+  check_status!(unsafe {
+    sys::napi_set_named_property(
+      env,
+      js_error,
+      "errors\0".as_ptr() as *const c_char,
+      js_errors,
+    )
+  })
+}
+
+unsafe fn set_error_properties(
+  env: sys::napi_env,
+  js_error: sys::napi_value,
+  properties: &[(String, ErrorProperty)],
+) -> Result<()> {
+  for (key, value) in properties {
+    let key = CString::new(key.as_str())?;
+    let value = unsafe { value.to_raw(env) }?;
+    check_status!(unsafe { sys::napi_set_named_property(env, js_error, key.as_ptr(), value) })?;
+  }
+  Ok(())
 }
 
 impl From<std::ffi::NulError> for Error {
@@ -110,6 +436,7 @@ impl From<std::ffi::NulError> for Error {
     Error {
       status: Status::GenericFailure,
       reason: format!("{}", error),
+      extra: None,
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
     }
@@ -121,6 +448,7 @@ impl From<std::io::Error> for Error {
     Error {
       status: Status::GenericFailure,
       reason: format!("{}", error),
+      extra: None,
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
     }
@@ -158,7 +486,7 @@ pub struct JsTypeError(Error);
 
 pub struct JsRangeError(Error);
 
-#[cfg(feature = "experimental")]
+#[cfg(feature = "napi9")]
 pub struct JsSyntaxError(Error);
 
 macro_rules! impl_object_methods {
@@ -168,11 +496,12 @@ macro_rules! impl_object_methods {
       ///
       /// This function is safety if env is not null ptr.
       pub unsafe fn into_value(self, env: sys::napi_env) -> sys::napi_value {
-        let error_status = format!("{:?}", self.0.status);
+        let error_status = self.0.js_code();
         let status_len = error_status.len();
         let error_code_string = CString::new(error_status).unwrap();
-        let reason_len = self.0.reason.len();
-        let reason = CString::new(self.0.reason).unwrap();
+        let reason = self.0.js_message();
+        let reason_len = reason.len();
+        let reason = CString::new(reason).unwrap();
         let mut error_code = ptr::null_mut();
         let mut reason_string = ptr::null_mut();
         let mut js_error = ptr::null_mut();
@@ -184,8 +513,16 @@ macro_rules! impl_object_methods {
           sys::napi_create_string_utf8(env, reason.as_ptr(), reason_len, &mut reason_string)
         };
         debug_assert!(create_reason_status == sys::Status::napi_ok);
-        let create_error_status = unsafe { $kind(env, error_code, reason_string, &mut js_error) };
+        let create_error_status = match self.0.class() {
+          Some(class) => unsafe { class.create(env, error_code, reason_string, &mut js_error) },
+          None => unsafe { $kind(env, error_code, reason_string, &mut js_error) },
+        };
         debug_assert!(create_error_status == sys::Status::napi_ok);
+        let set_properties_result =
+          unsafe { set_error_properties(env, js_error, self.0.properties()) };
+        debug_assert!(set_properties_result.is_ok());
+        let set_errors_result = unsafe { set_aggregated_errors(env, js_error, self.0.errors()) };
+        debug_assert!(set_errors_result.is_ok());
         js_error
       }
 
@@ -214,12 +551,13 @@ macro_rules! impl_object_methods {
       }
 
       pub fn throw(&self, env: sys::napi_env) -> Result<()> {
-        let error_status = format!("{:?}\0", self.0.status);
+        let error_status = format!("{}\0", self.0.js_code());
         let status_len = error_status.len();
         let error_code_string =
           unsafe { CStr::from_bytes_with_nul_unchecked(error_status.as_bytes()) };
-        let reason_len = self.0.reason.len();
-        let reason_c_string = format!("{}\0", self.0.reason.clone());
+        let reason = self.0.js_message();
+        let reason_len = reason.len();
+        let reason_c_string = format!("{}\0", reason);
         let reason = unsafe { CStr::from_bytes_with_nul_unchecked(reason_c_string.as_bytes()) };
         let mut error_code = ptr::null_mut();
         let mut reason_string = ptr::null_mut();
@@ -230,7 +568,12 @@ macro_rules! impl_object_methods {
         check_status!(unsafe {
           sys::napi_create_string_utf8(env, reason.as_ptr(), reason_len, &mut reason_string)
         })?;
-        check_status!(unsafe { $kind(env, error_code, reason_string, &mut js_error) })?;
+        check_status!(match self.0.class() {
+          Some(class) => unsafe { class.create(env, error_code, reason_string, &mut js_error) },
+          None => unsafe { $kind(env, error_code, reason_string, &mut js_error) },
+        })?;
+        unsafe { set_error_properties(env, js_error, self.0.properties()) }?;
+        unsafe { set_aggregated_errors(env, js_error, self.0.errors()) }?;
         check_status!(unsafe { sys::napi_throw(env, js_error) })
       }
     }
@@ -246,9 +589,39 @@ macro_rules! impl_object_methods {
 impl_object_methods!(JsError, sys::napi_create_error);
 impl_object_methods!(JsTypeError, sys::napi_create_type_error);
 impl_object_methods!(JsRangeError, sys::napi_create_range_error);
-#[cfg(feature = "experimental")]
+#[cfg(feature = "napi9")]
 impl_object_methods!(JsSyntaxError, sys::node_api_create_syntax_error);
 
+/// [napi_fatal_error](https://nodejs.org/api/n-api.html#napi_fatal_error)
+///
+/// Print `location` and `message` and terminate the process immediately, it could be called from any thread.
+///
+/// Prefer the [`fatal_error!`](crate::fatal_error!) macro, which fills `location` with the caller's file and line.
+pub fn fatal_error(location: &str, message: &str) -> ! {
+  unsafe {
+    sys::napi_fatal_error(
+      location.as_ptr() as *const c_char,
+      location.len(),
+      message.as_ptr() as *const c_char,
+      message.len(),
+    )
+  };
+  // `napi_fatal_error` never returns
+  std::process::abort()
+}
+
+/// Terminate the process with a formatted message, see [`fatal_error`](crate::fatal_error()).
+///
+/// ```no_run
+/// napi::fatal_error!("worker {} lost its connection", 1);
+/// ```
+#[macro_export]
+macro_rules! fatal_error {
+  ($($msg:tt)*) => {
+    $crate::fatal_error(concat!(file!(), ":", line!()), &format!($($msg)*))
+  };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! error {
@@ -244,10 +244,9 @@ impl JsBigInt {
   pub fn get_i128(&mut self) -> Result<(i128, bool)> {
     let (signed, words) = self.get_words()?;
     let len = words.len();
-    let i128_words: [i64; 2] = [words[0] as _, words[1] as _];
-    let mut val = unsafe { ptr::read(i128_words.as_ptr() as *const i128) };
+    let mut val = words_to_u128(&words) as i128;
     if signed {
-      val = -val;
+      val = val.wrapping_neg();
     }
     Ok((val, len > 2))
   }
@@ -255,8 +254,14 @@ impl JsBigInt {
   pub fn get_u128(&mut self) -> Result<(bool, u128, bool)> {
     let (signed, words) = self.get_words()?;
     let len = words.len();
-    let u128_words: [u64; 2] = [words[0], words[1]];
-    let val = unsafe { ptr::read(u128_words.as_ptr() as *const u128) };
+    let val = words_to_u128(&words);
     Ok((signed, val, len > 2))
   }
 }
+
+/// The low 128 bits of the words, `0n` has no words and values below 2^64 only have one
+fn words_to_u128(words: &[u64]) -> u128 {
+  let low = words.first().copied().unwrap_or(0) as u128;
+  let high = words.get(1).copied().unwrap_or(0) as u128;
+  high << 64 | low
+}
@@ -1,11 +1,17 @@
 use std::convert::TryInto;
+use std::ffi::c_void;
+use std::{mem, ptr, slice};
 
 use serde::de::Visitor;
-use serde::de::{DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected, VariantAccess};
+use serde::de::{
+  DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, Unexpected, VariantAccess,
+};
 
+#[cfg(feature = "napi5")]
+use super::serde_date::DATE_NEWTYPE;
 #[cfg(feature = "napi6")]
 use crate::JsBigInt;
-use crate::{type_of, NapiValue, Value, ValueType};
+use crate::{check_status, sys, type_of, NapiValue, Value, ValueType};
 use crate::{
   Error, JsBoolean, JsBufferValue, JsNumber, JsObject, JsString, JsUnknown, Result, Status,
 };
@@ -46,8 +52,12 @@ impl<'x, 'de, 'env> serde::de::Deserializer<'x> for &'de mut De<'env> {
           let mut deserializer =
             JsArrayAccess::new(&js_object, js_object.get_array_length_unchecked()?);
           visitor.visit_seq(&mut deserializer)
-        } else if js_object.is_buffer()? {
-          visitor.visit_bytes(&JsBufferValue::from_raw(self.0.env, self.0.value)?)
+        } else if let Some(data) = unsafe { get_binary_data(self.0)? } {
+          match data {
+            BinaryData::Bytes(bytes) => visitor.visit_borrowed_bytes(bytes),
+            BinaryData::U8(bytes) if js_object.is_buffer()? => visitor.visit_borrowed_bytes(bytes),
+            data => visit_binary_seq(data, visitor),
+          }
         } else {
           let mut deserializer = JsObjectAccess::new(&js_object)?;
           visitor.visit_map(&mut deserializer)
@@ -57,10 +67,18 @@ impl<'x, 'de, 'env> serde::de::Deserializer<'x> for &'de mut De<'env> {
       ValueType::BigInt => {
         let mut js_bigint = unsafe { JsBigInt::from_raw(self.0.env, self.0.value)? };
         let (signed, v, _loss) = js_bigint.get_u128()?;
+        // visit the narrowest integer so `i64` and `u64` fields accept them too
         if signed {
-          visitor.visit_i128(-(v as i128))
+          let v = (v as i128).wrapping_neg();
+          match i64::try_from(v) {
+            Ok(v) => visitor.visit_i64(v),
+            Err(_) => visitor.visit_i128(v),
+          }
         } else {
-          visitor.visit_u128(v)
+          match u64::try_from(v) {
+            Ok(v) => visitor.visit_u64(v),
+            Err(_) => visitor.visit_u128(v),
+          }
         }
       }
       ValueType::External | ValueType::Function | ValueType::Symbol => Err(Error::new(
@@ -71,18 +89,41 @@ impl<'x, 'de, 'env> serde::de::Deserializer<'x> for &'de mut De<'env> {
     }
   }
 
+  /// `Buffer`, any `TypedArray`, `DataView` and `ArrayBuffer` are borrowed from their backing memory
   fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'x>,
   {
-    visitor.visit_bytes(&JsBufferValue::from_raw(self.0.env, self.0.value)?)
+    match unsafe { get_binary_data(self.0)? } {
+      Some(data) => visitor.visit_borrowed_bytes(data.as_bytes()),
+      None => visitor.visit_bytes(&JsBufferValue::from_raw(self.0.env, self.0.value)?),
+    }
   }
 
   fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'x>,
   {
-    visitor.visit_bytes(&JsBufferValue::from_raw(self.0.env, self.0.value)?)
+    self.deserialize_bytes(visitor)
+  }
+
+  /// The elements of a `TypedArray` or `Buffer` are read from its backing memory instead of
+  /// being fetched one by one as JavaScript values
+  fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'x>,
+  {
+    match unsafe { get_binary_data(self.0)? } {
+      Some(BinaryData::Bytes(_)) | None => self.deserialize_any(visitor),
+      Some(data) => visit_binary_seq(data, visitor),
+    }
+  }
+
+  fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'x>,
+  {
+    self.deserialize_seq(visitor)
   }
 
   fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
@@ -143,6 +184,26 @@ impl<'x, 'de, 'env> serde::de::Deserializer<'x> for &'de mut De<'env> {
     }
   }
 
+  /// [`serde_date`](crate::serde_date) fields are deserialized from the milliseconds of a `Date`
+  fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'x>,
+  {
+    #[cfg(feature = "napi5")]
+    if name == DATE_NEWTYPE {
+      let mut is_date = false;
+      check_status!(unsafe { sys::napi_is_date(self.0.env, self.0.value, &mut is_date) })?;
+      if is_date {
+        let mut millis = 0f64;
+        check_status!(unsafe { sys::napi_get_date_value(self.0.env, self.0.value, &mut millis) })?;
+        return visitor.visit_f64(millis);
+      }
+    }
+    #[cfg(not(feature = "napi5"))]
+    let _ = name;
+    visitor.visit_newtype_struct(self)
+  }
+
   fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'x>,
@@ -153,8 +214,7 @@ impl<'x, 'de, 'env> serde::de::Deserializer<'x> for &'de mut De<'env> {
   forward_to_deserialize_any! {
      <V: Visitor<'x>>
       bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-      unit unit_struct seq tuple tuple_struct map struct identifier
-      newtype_struct
+      unit unit_struct tuple_struct map struct identifier
   }
 }
 
@@ -295,11 +355,15 @@ impl<'de, 'env> SeqAccess<'de> for JsArrayAccess<'env> {
     if self.idx >= self.len {
       return Ok(None);
     }
-    let v = self.input.get_element::<JsUnknown>(self.idx)?;
+    let index = self.idx;
+    let v = self.input.get_element::<JsUnknown>(index)?;
     self.idx += 1;
 
     let mut de = De(&v.0);
-    seed.deserialize(&mut de).map(Some)
+    seed
+      .deserialize(&mut de)
+      .map(Some)
+      .map_err(|e| e.at_index(index as usize))
   }
 }
 
@@ -358,7 +422,212 @@ impl<'de, 'env> MapAccess<'de> for JsObjectAccess<'env> {
 
     self.idx += 1;
     let mut de = De(&value.0);
-    let res = seed.deserialize(&mut de)?;
-    Ok(res)
+    // the path is only read from the key once the conversion failed
+    seed.deserialize(&mut de).map_err(|e| {
+      match prop_name.into_utf8().and_then(|key| key.into_owned()) {
+        Ok(key) => e.at_property(key),
+        Err(_) => e,
+      }
+    })
+  }
+}
+
+/// Backing memory of a `TypedArray`, `Buffer`, `DataView` or `ArrayBuffer`
+#[doc(hidden)]
+enum BinaryData<'env> {
+  I8(&'env [i8]),
+  U8(&'env [u8]),
+  I16(&'env [i16]),
+  U16(&'env [u16]),
+  I32(&'env [i32]),
+  U32(&'env [u32]),
+  F32(&'env [f32]),
+  F64(&'env [f64]),
+  #[cfg(feature = "napi6")]
+  I64(&'env [i64]),
+  #[cfg(feature = "napi6")]
+  U64(&'env [u64]),
+  /// `DataView` and `ArrayBuffer` have no element type
+  Bytes(&'env [u8]),
+}
+
+impl<'env> BinaryData<'env> {
+  fn as_bytes(&self) -> &'env [u8] {
+    match *self {
+      BinaryData::I8(data) => as_bytes(data),
+      BinaryData::U8(data) => data,
+      BinaryData::I16(data) => as_bytes(data),
+      BinaryData::U16(data) => as_bytes(data),
+      BinaryData::I32(data) => as_bytes(data),
+      BinaryData::U32(data) => as_bytes(data),
+      BinaryData::F32(data) => as_bytes(data),
+      BinaryData::F64(data) => as_bytes(data),
+      #[cfg(feature = "napi6")]
+      BinaryData::I64(data) => as_bytes(data),
+      #[cfg(feature = "napi6")]
+      BinaryData::U64(data) => as_bytes(data),
+      BinaryData::Bytes(data) => data,
+    }
+  }
+}
+
+fn as_bytes<T: Copy>(data: &[T]) -> &[u8] {
+  unsafe { slice::from_raw_parts(data.as_ptr().cast(), mem::size_of_val(data)) }
+}
+
+/// Detached and empty views have a null data pointer
+unsafe fn raw_slice<'env, T>(data: *mut c_void, len: usize) -> &'env [T] {
+  if data.is_null() || len == 0 {
+    &[]
+  } else {
+    unsafe { slice::from_raw_parts(data as *const T, len) }
+  }
+}
+
+/// Borrow the backing memory of `value` if it's a `TypedArray`, `Buffer`, `DataView` or
+/// `ArrayBuffer`.
+///
+/// The memory lives as long as the handle scope of `value`, which outlives the deserialization:
+/// [`Env::from_js_value`](crate::Env::from_js_value) only creates owned values, nothing borrowed for `'x` escapes it.
+unsafe fn get_binary_data<'x>(value: &Value) -> Result<Option<BinaryData<'x>>> {
+  let env = value.env;
+  let value = value.value;
+  let mut is_typedarray = false;
+  check_status!(unsafe { sys::napi_is_typedarray(env, value, &mut is_typedarray) })?;
+  if is_typedarray {
+    let mut typedarray_type = 0;
+    let mut len = 0;
+    let mut data = ptr::null_mut();
+    let mut arraybuffer = ptr::null_mut();
+    let mut byte_offset = 0;
+    check_status!(
+      unsafe {
+        sys::napi_get_typedarray_info(
+          env,
+          value,
+          &mut typedarray_type,
+          &mut len,
+          &mut data,
+          &mut arraybuffer,
+          &mut byte_offset,
+        )
+      },
+      "Failed to get TypedArray info"
+    )?;
+    let data = unsafe {
+      match typedarray_type {
+        sys::TypedarrayType::int8_array => BinaryData::I8(raw_slice(data, len)),
+        sys::TypedarrayType::uint8_array | sys::TypedarrayType::uint8_clamped_array => {
+          BinaryData::U8(raw_slice(data, len))
+        }
+        sys::TypedarrayType::int16_array => BinaryData::I16(raw_slice(data, len)),
+        sys::TypedarrayType::uint16_array => BinaryData::U16(raw_slice(data, len)),
+        sys::TypedarrayType::int32_array => BinaryData::I32(raw_slice(data, len)),
+        sys::TypedarrayType::uint32_array => BinaryData::U32(raw_slice(data, len)),
+        sys::TypedarrayType::float32_array => BinaryData::F32(raw_slice(data, len)),
+        sys::TypedarrayType::float64_array => BinaryData::F64(raw_slice(data, len)),
+        #[cfg(feature = "napi6")]
+        sys::TypedarrayType::bigint64_array => BinaryData::I64(raw_slice(data, len)),
+        #[cfg(feature = "napi6")]
+        sys::TypedarrayType::biguint64_array => BinaryData::U64(raw_slice(data, len)),
+        _ => return Ok(None),
+      }
+    };
+    return Ok(Some(data));
+  }
+
+  let mut is_dataview = false;
+  check_status!(unsafe { sys::napi_is_dataview(env, value, &mut is_dataview) })?;
+  if is_dataview {
+    let mut len = 0;
+    let mut data = ptr::null_mut();
+    let mut arraybuffer = ptr::null_mut();
+    let mut byte_offset = 0;
+    check_status!(
+      unsafe {
+        sys::napi_get_dataview_info(
+          env,
+          value,
+          &mut len,
+          &mut data,
+          &mut arraybuffer,
+          &mut byte_offset,
+        )
+      },
+      "Failed to get DataView info"
+    )?;
+    return Ok(Some(BinaryData::Bytes(unsafe { raw_slice(data, len) })));
+  }
+
+  let mut is_arraybuffer = false;
+  check_status!(unsafe { sys::napi_is_arraybuffer(env, value, &mut is_arraybuffer) })?;
+  if is_arraybuffer {
+    let mut len = 0;
+    let mut data = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_arraybuffer_info(env, value, &mut data, &mut len) },
+      "Failed to get ArrayBuffer info"
+    )?;
+    return Ok(Some(BinaryData::Bytes(unsafe { raw_slice(data, len) })));
+  }
+
+  Ok(None)
+}
+
+fn visit_binary_seq<'x, V>(data: BinaryData<'_>, visitor: V) -> Result<V::Value>
+where
+  V: Visitor<'x>,
+{
+  match data {
+    BinaryData::I8(data) => visitor.visit_seq(JsTypedArrayAccess::new(data)),
+    BinaryData::U8(data) | BinaryData::Bytes(data) => {
+      visitor.visit_seq(JsTypedArrayAccess::new(data))
+    }
+    BinaryData::I16(data) => visitor.visit_seq(JsTypedArrayAccess::new(data)),
+    BinaryData::U16(data) => visitor.visit_seq(JsTypedArrayAccess::new(data)),
+    BinaryData::I32(data) => visitor.visit_seq(JsTypedArrayAccess::new(data)),
+    BinaryData::U32(data) => visitor.visit_seq(JsTypedArrayAccess::new(data)),
+    BinaryData::F32(data) => visitor.visit_seq(JsTypedArrayAccess::new(data)),
+    BinaryData::F64(data) => visitor.visit_seq(JsTypedArrayAccess::new(data)),
+    #[cfg(feature = "napi6")]
+    BinaryData::I64(data) => visitor.visit_seq(JsTypedArrayAccess::new(data)),
+    #[cfg(feature = "napi6")]
+    BinaryData::U64(data) => visitor.visit_seq(JsTypedArrayAccess::new(data)),
+  }
+}
+
+#[doc(hidden)]
+struct JsTypedArrayAccess<'env, T> {
+  input: slice::Iter<'env, T>,
+}
+
+#[doc(hidden)]
+impl<'env, T> JsTypedArrayAccess<'env, T> {
+  fn new(input: &'env [T]) -> Self {
+    Self {
+      input: input.iter(),
+    }
+  }
+}
+
+#[doc(hidden)]
+impl<'de, 'env, T> SeqAccess<'de> for JsTypedArrayAccess<'env, T>
+where
+  T: Copy + IntoDeserializer<'de, Error>,
+{
+  type Error = Error;
+
+  fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>>
+  where
+    S: DeserializeSeed<'de>,
+  {
+    match self.input.next() {
+      Some(v) => seed.deserialize((*v).into_deserializer()).map(Some),
+      None => Ok(None),
+    }
+  }
+
+  fn size_hint(&self) -> Option<usize> {
+    Some(self.input.len())
   }
 }
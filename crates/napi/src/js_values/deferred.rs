@@ -0,0 +1,137 @@
+use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::os::raw::c_void;
+use std::ptr;
+
+use crate::bindgen_runtime::ToNapiValue;
+use crate::{check_status, sys, Error, JsError, JsObject, NapiValue, Result, Status};
+
+/// A `Promise` whose settlement is driven from Rust.
+///
+/// Created by [`Env::create_deferred`](crate::Env::create_deferred), the returned `JsObject` is the `Promise` to hand back to JavaScript.
+///
+/// `JsDeferred` is `Send`, it could be moved to any thread and resolved or rejected there, the value is converted into a JavaScript value on the main thread.
+///
+/// If it's dropped without being settled, the `Promise` will be rejected.
+pub struct JsDeferred<Data: ToNapiValue> {
+  tsfn: sys::napi_threadsafe_function,
+  _data: PhantomData<Data>,
+}
+
+unsafe impl<Data: ToNapiValue + Send> Send for JsDeferred<Data> {}
+
+impl<Data: ToNapiValue> JsDeferred<Data> {
+  pub(crate) fn new(env: sys::napi_env) -> Result<(Self, JsObject)> {
+    let mut raw_promise = ptr::null_mut();
+    let mut raw_deferred = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_promise(env, &mut raw_deferred, &mut raw_promise) },
+      "Failed to create promise"
+    )?;
+
+    let mut async_resource_name = ptr::null_mut();
+    let s = unsafe { CStr::from_bytes_with_nul_unchecked(b"napi_resolve_deferred\0") };
+    check_status!(unsafe {
+      sys::napi_create_string_utf8(env, s.as_ptr(), 21, &mut async_resource_name)
+    })?;
+
+    let mut tsfn = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_threadsafe_function(
+          env,
+          ptr::null_mut(),
+          ptr::null_mut(),
+          async_resource_name,
+          0,
+          1,
+          ptr::null_mut(),
+          None,
+          raw_deferred as *mut c_void,
+          Some(napi_resolve_deferred::<Data>),
+          &mut tsfn,
+        )
+      },
+      "Failed to create threadsafe function for deferred"
+    )?;
+
+    Ok((
+      JsDeferred {
+        tsfn,
+        _data: PhantomData,
+      },
+      unsafe { JsObject::from_raw_unchecked(env, raw_promise) },
+    ))
+  }
+
+  /// Resolve the `Promise` with `value`, could be called from any thread.
+  pub fn resolve(mut self, value: Data) {
+    self.settle(Ok(value));
+  }
+
+  /// Reject the `Promise` with `error`, could be called from any thread.
+  pub fn reject(mut self, error: Error) {
+    self.settle(Err(error));
+  }
+
+  fn settle(&mut self, result: Result<Data>) {
+    let tsfn = std::mem::replace(&mut self.tsfn, ptr::null_mut());
+    if tsfn.is_null() {
+      return;
+    }
+    let data = Box::into_raw(Box::new(result));
+    let status = unsafe {
+      sys::napi_call_threadsafe_function(
+        tsfn,
+        data as *mut c_void,
+        sys::ThreadsafeFunctionCallMode::nonblocking,
+      )
+    };
+    if status != sys::Status::napi_ok {
+      // the environment is closing, the `Promise` can't be settled anymore,
+      // but the result and the threadsafe function must not leak
+      drop(unsafe { Box::from_raw(data) });
+    }
+    let status = unsafe {
+      sys::napi_release_threadsafe_function(tsfn, sys::ThreadsafeFunctionReleaseMode::release)
+    };
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Release threadsafe function in JsDeferred failed"
+    );
+  }
+}
+
+impl<Data: ToNapiValue> Drop for JsDeferred<Data> {
+  fn drop(&mut self) {
+    self.settle(Err(Error::new(
+      Status::GenericFailure,
+      "JsDeferred was dropped without being resolved or rejected".to_owned(),
+    )));
+  }
+}
+
+unsafe extern "C" fn napi_resolve_deferred<Data: ToNapiValue>(
+  env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  context: *mut c_void,
+  data: *mut c_void,
+) {
+  let deferred = context as sys::napi_deferred;
+  let result = unsafe { *Box::from_raw(data as *mut Result<Data>) };
+  // env is null when the environment is tearing down
+  if env.is_null() {
+    return;
+  }
+  match result.and_then(|v| unsafe { Data::to_napi_value(env, v) }) {
+    Ok(v) => {
+      let status = unsafe { sys::napi_resolve_deferred(env, deferred, v) };
+      debug_assert!(status == sys::Status::napi_ok, "Resolve promise failed");
+    }
+    Err(e) => {
+      let status =
+        unsafe { sys::napi_reject_deferred(env, deferred, JsError::from(e).into_value(env)) };
+      debug_assert!(status == sys::Status::napi_ok, "Reject promise failed");
+    }
+  }
+}
@@ -1,7 +1,7 @@
 use std::ptr;
 
 use super::Value;
-use crate::bindgen_runtime::TypeName;
+use crate::bindgen_runtime::{FromNapiValue, JsValuesTupleIntoVec, TypeName};
 #[cfg(feature = "napi4")]
 use crate::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction};
 use crate::{check_status, ValueType};
@@ -39,38 +39,56 @@ impl JsFunction {
   where
     V: NapiRaw,
   {
-    let raw_this = this
-      .map(|v| unsafe { v.raw() })
-      .or_else(|| {
-        unsafe { Env::from_raw(self.0.env) }
-          .get_undefined()
-          .ok()
-          .map(|u| unsafe { u.raw() })
-      })
-      .ok_or_else(|| Error::new(Status::GenericFailure, "Get raw this failed".to_owned()))?;
     let raw_args = args
       .iter()
       .map(|arg| unsafe { arg.raw() })
       .collect::<Vec<sys::napi_value>>();
-    let mut return_value = ptr::null_mut();
-    check_status!(unsafe {
-      sys::napi_call_function(
-        self.0.env,
-        raw_this,
-        self.0.value,
-        args.len(),
-        raw_args.as_ptr(),
-        &mut return_value,
-      )
-    })?;
-
+    let return_value = self.call_raw(self.raw_this(this)?, &raw_args)?;
     unsafe { JsUnknown::from_raw(self.0.env, return_value) }
   }
 
   /// [napi_call_function](https://nodejs.org/api/n-api.html#n_api_napi_call_function)
   /// The same with `call`, but without arguments
   pub fn call_without_args(&self, this: Option<&JsObject>) -> Result<JsUnknown> {
-    let raw_this = this
+    let return_value = self.call_raw(self.raw_this(this)?, &[])?;
+    unsafe { JsUnknown::from_raw(self.0.env, return_value) }
+  }
+
+  /// [napi_call_function](https://nodejs.org/api/n-api.html#n_api_napi_call_function)
+  ///
+  /// Like `Function.prototype.apply`, `this` could be any JavaScript value rather than only an object.
+  pub fn apply<T, V>(&self, this: &T, args: &[V]) -> Result<JsUnknown>
+  where
+    T: NapiRaw,
+    V: NapiRaw,
+  {
+    let raw_args = args
+      .iter()
+      .map(|arg| unsafe { arg.raw() })
+      .collect::<Vec<sys::napi_value>>();
+    let return_value = self.call_raw(unsafe { this.raw() }, &raw_args)?;
+    unsafe { JsUnknown::from_raw(self.0.env, return_value) }
+  }
+
+  /// [napi_call_function](https://nodejs.org/api/n-api.html#n_api_napi_call_function)
+  ///
+  /// Call the function with a tuple of Rust values, and convert the returned value into `Ret`.
+  ///
+  /// ```ignore
+  /// let sum: u32 = js_func.typed_call(None, (1u32, 2u32))?;
+  /// ```
+  pub fn typed_call<Args, Ret>(&self, this: Option<&JsObject>, args: Args) -> Result<Ret>
+  where
+    Args: JsValuesTupleIntoVec,
+    Ret: FromNapiValue,
+  {
+    let raw_args = unsafe { args.into_vec(self.0.env) }?;
+    let return_value = self.call_raw(self.raw_this(this)?, &raw_args)?;
+    unsafe { Ret::from_napi_value(self.0.env, return_value) }
+  }
+
+  fn raw_this(&self, this: Option<&JsObject>) -> Result<sys::napi_value> {
+    this
       .map(|v| unsafe { v.raw() })
       .or_else(|| {
         unsafe { Env::from_raw(self.0.env) }
@@ -78,20 +96,26 @@ impl JsFunction {
           .ok()
           .map(|u| unsafe { u.raw() })
       })
-      .ok_or_else(|| Error::new(Status::GenericFailure, "Get raw this failed".to_owned()))?;
+      .ok_or_else(|| Error::new(Status::GenericFailure, "Get raw this failed".to_owned()))
+  }
+
+  fn call_raw(
+    &self,
+    raw_this: sys::napi_value,
+    raw_args: &[sys::napi_value],
+  ) -> Result<sys::napi_value> {
     let mut return_value = ptr::null_mut();
     check_status!(unsafe {
       sys::napi_call_function(
         self.0.env,
         raw_this,
         self.0.value,
-        0,
-        ptr::null_mut(),
+        raw_args.len(),
+        raw_args.as_ptr(),
         &mut return_value,
       )
     })?;
-
-    unsafe { JsUnknown::from_raw(self.0.env, return_value) }
+    Ok(return_value)
   }
 
   /// <https://nodejs.org/api/n-api.html#n_api_napi_new_instance>
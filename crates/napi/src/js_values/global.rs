@@ -51,4 +51,29 @@ impl JsGlobal {
       .call(None, &[timer.into_unknown()])
       .and_then(|ret| ret.try_into())
   }
+
+  /// Schedule `callback` to be called as a microtask, see [queueMicrotask](https://developer.mozilla.org/en-US/docs/Web/API/queueMicrotask).
+  pub fn queue_microtask(&self, callback: JsFunction) -> Result<()> {
+    let func = self.get_feature_detected_function("queueMicrotask")?;
+    func.call(None, &[callback]).map(|_| ())
+  }
+
+  /// Deep clone the value, see [structuredClone](https://developer.mozilla.org/en-US/docs/Web/API/structuredClone).
+  ///
+  /// `structuredClone` is available since Node.js 17.0.0, an error with `Status::GenericFailure` is returned in older versions.
+  pub fn structured_clone<V: NapiRaw>(&self, value: V) -> Result<JsUnknown> {
+    let func = self.get_feature_detected_function("structuredClone")?;
+    func.call(None, &[value])
+  }
+
+  fn get_feature_detected_function(&self, name: &str) -> Result<JsFunction> {
+    let func: JsUnknown = self.get_named_property_unchecked(name)?;
+    if func.get_type()? != ValueType::Function {
+      return Err(Error::new(
+        Status::GenericFailure,
+        format!("`{}` is not available in current JavaScript runtime", name),
+      ));
+    }
+    Ok(unsafe { func.cast() })
+  }
 }
@@ -0,0 +1,340 @@
+use std::ffi::CStr;
+use std::fmt::Write;
+use std::ptr;
+
+use crate::{check_status, sys, type_of, JsString, NapiValue, Result, ValueType};
+
+/// Objects nested deeper than this are rendered as `[Object]` or `[Array]`
+const MAX_DEPTH: usize = 2;
+/// Elements and properties after this count are summarized as `... n more items`
+const MAX_ITEMS: u32 = 16;
+/// Strings longer than this are truncated
+const MAX_STRING_LEN: usize = 100;
+
+/// Render `value` like a truncated `util.inspect`, for logging what was received from JavaScript.
+///
+/// Exceptions thrown by getters while rendering are cleared, so inspecting never leaves a pending exception.
+pub(crate) unsafe fn inspect(env: sys::napi_env, value: sys::napi_value) -> Result<String> {
+  let mut scope = ptr::null_mut();
+  check_status!(unsafe { sys::napi_open_handle_scope(env, &mut scope) })?;
+  let mut output = String::new();
+  let result = unsafe { write_value(env, value, 0, &mut output) };
+  check_status!(unsafe { sys::napi_close_handle_scope(env, scope) })?;
+  result.map(|_| output)
+}
+
+unsafe fn write_value(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  depth: usize,
+  output: &mut String,
+) -> Result<()> {
+  match type_of!(env, value)? {
+    ValueType::Undefined => output.push_str("undefined"),
+    ValueType::Null => output.push_str("null"),
+    ValueType::Boolean => {
+      let mut result = false;
+      check_status!(unsafe { sys::napi_get_value_bool(env, value, &mut result) })?;
+      let _ = write!(output, "{}", result);
+    }
+    ValueType::Number => {
+      let mut result = 0f64;
+      check_status!(unsafe { sys::napi_get_value_double(env, value, &mut result) })?;
+      write_number(result, output);
+    }
+    ValueType::String => write_string(&unsafe { get_string(env, value)? }, output),
+    #[cfg(feature = "napi6")]
+    ValueType::BigInt => {
+      let mut string = ptr::null_mut();
+      check_status!(unsafe { sys::napi_coerce_to_string(env, value, &mut string) })?;
+      let _ = write!(output, "{}n", unsafe { get_string(env, string)? });
+    }
+    ValueType::Symbol => {
+      let description = unsafe { get_named_string(env, value, b"description\0") };
+      let _ = write!(output, "Symbol({})", description.unwrap_or_default());
+    }
+    ValueType::Function => match unsafe { get_named_string(env, value, b"name\0") } {
+      Some(name) if !name.is_empty() => {
+        let _ = write!(output, "[Function: {}]", name);
+      }
+      _ => output.push_str("[Function (anonymous)]"),
+    },
+    ValueType::External => output.push_str("[External]"),
+    ValueType::Object => unsafe { write_object(env, value, depth, output)? },
+    ValueType::Unknown => output.push_str("[Unknown]"),
+  }
+  Ok(())
+}
+
+fn write_number(value: f64, output: &mut String) {
+  if value.is_nan() {
+    output.push_str("NaN");
+  } else if value.is_infinite() {
+    output.push_str(if value > 0.0 { "Infinity" } else { "-Infinity" });
+  } else {
+    let _ = write!(output, "{}", value);
+  }
+}
+
+fn write_string(value: &str, output: &mut String) {
+  output.push('\'');
+  let mut chars = value.chars();
+  for c in chars.by_ref().take(MAX_STRING_LEN) {
+    match c {
+      '\'' => output.push_str("\\'"),
+      '\\' => output.push_str("\\\\"),
+      '\n' => output.push_str("\\n"),
+      '\r' => output.push_str("\\r"),
+      '\t' => output.push_str("\\t"),
+      c => output.push(c),
+    }
+  }
+  output.push('\'');
+  let rest = chars.count();
+  if rest > 0 {
+    let _ = write!(output, "... {} more characters", rest);
+  }
+}
+
+unsafe fn write_object(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  depth: usize,
+  output: &mut String,
+) -> Result<()> {
+  let mut is_array = false;
+  check_status!(unsafe { sys::napi_is_array(env, value, &mut is_array) })?;
+  if is_array {
+    if depth > MAX_DEPTH {
+      output.push_str("[Array]");
+      return Ok(());
+    }
+    let mut len = 0;
+    check_status!(unsafe { sys::napi_get_array_length(env, value, &mut len) })?;
+    return unsafe { write_elements(env, value, len, depth, output) };
+  }
+
+  let constructor_name = unsafe { get_constructor_name(env, value) };
+
+  let mut is_typedarray = false;
+  check_status!(unsafe { sys::napi_is_typedarray(env, value, &mut is_typedarray) })?;
+  if is_typedarray {
+    // `napi_is_buffer` is also true for plain `Uint8Array`s, `Buffer`s are told apart by their constructor
+    if constructor_name.as_deref() == Some("Buffer") {
+      return unsafe { write_buffer(env, value, output) };
+    }
+    let mut typedarray_type = 0;
+    let mut len = 0;
+    check_status!(unsafe {
+      sys::napi_get_typedarray_info(
+        env,
+        value,
+        &mut typedarray_type,
+        &mut len,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    })?;
+    let _ = write!(
+      output,
+      "{}({}) ",
+      constructor_name.as_deref().unwrap_or("TypedArray"),
+      len
+    );
+    return unsafe { write_elements(env, value, len as u32, depth, output) };
+  }
+
+  let mut is_arraybuffer = false;
+  check_status!(unsafe { sys::napi_is_arraybuffer(env, value, &mut is_arraybuffer) })?;
+  if is_arraybuffer {
+    let mut len = 0;
+    check_status!(unsafe {
+      sys::napi_get_arraybuffer_info(env, value, ptr::null_mut(), &mut len)
+    })?;
+    let _ = write!(output, "ArrayBuffer {{ byteLength: {} }}", len);
+    return Ok(());
+  }
+
+  let mut is_error = false;
+  check_status!(unsafe { sys::napi_is_error(env, value, &mut is_error) })?;
+  if is_error {
+    let name = unsafe { get_named_string(env, value, b"name\0") };
+    let message = unsafe { get_named_string(env, value, b"message\0") };
+    let _ = write!(
+      output,
+      "[{}: {}]",
+      name.as_deref().unwrap_or("Error"),
+      message.unwrap_or_default()
+    );
+    return Ok(());
+  }
+
+  #[cfg(feature = "napi5")]
+  {
+    let mut is_date = false;
+    check_status!(unsafe { sys::napi_is_date(env, value, &mut is_date) })?;
+    if is_date {
+      let mut time = 0f64;
+      check_status!(unsafe { sys::napi_get_date_value(env, value, &mut time) })?;
+      output.push_str("Date(");
+      write_number(time, output);
+      output.push(')');
+      return Ok(());
+    }
+  }
+
+  let prefix = constructor_name.filter(|name| name != "Object");
+  if depth > MAX_DEPTH {
+    let _ = write!(output, "[{}]", prefix.as_deref().unwrap_or("Object"));
+    return Ok(());
+  }
+  if let Some(prefix) = &prefix {
+    let _ = write!(output, "{} ", prefix);
+  }
+
+  let mut keys = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_property_names(env, value, &mut keys) })?;
+  let mut len = 0;
+  check_status!(unsafe { sys::napi_get_array_length(env, keys, &mut len) })?;
+  if len == 0 {
+    output.push_str("{}");
+    return Ok(());
+  }
+  output.push_str("{ ");
+  for index in 0..len.min(MAX_ITEMS) {
+    if index > 0 {
+      output.push_str(", ");
+    }
+    let mut key = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_element(env, keys, index, &mut key) })?;
+    let key_string = unsafe { get_string(env, key)? };
+    if is_identifier(&key_string) {
+      output.push_str(&key_string);
+    } else {
+      write_string(&key_string, output);
+    }
+    output.push_str(": ");
+    let mut property = ptr::null_mut();
+    if unsafe { sys::napi_get_property(env, value, key, &mut property) } == sys::Status::napi_ok {
+      unsafe { write_value(env, property, depth + 1, output)? };
+    } else {
+      unsafe { clear_exception(env) };
+      output.push_str("[Getter: threw]");
+    }
+  }
+  if len > MAX_ITEMS {
+    let _ = write!(output, ", ... {} more properties", len - MAX_ITEMS);
+  }
+  output.push_str(" }");
+  Ok(())
+}
+
+unsafe fn write_elements(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  len: u32,
+  depth: usize,
+  output: &mut String,
+) -> Result<()> {
+  if len == 0 {
+    output.push_str("[]");
+    return Ok(());
+  }
+  output.push_str("[ ");
+  for index in 0..len.min(MAX_ITEMS) {
+    if index > 0 {
+      output.push_str(", ");
+    }
+    let mut element = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_element(env, value, index, &mut element) })?;
+    unsafe { write_value(env, element, depth + 1, output)? };
+  }
+  if len > MAX_ITEMS {
+    let _ = write!(output, ", ... {} more items", len - MAX_ITEMS);
+  }
+  output.push_str(" ]");
+  Ok(())
+}
+
+fn is_identifier(key: &str) -> bool {
+  let mut chars = key.chars();
+  matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_' || c == '$')
+    && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+unsafe fn get_string(env: sys::napi_env, value: sys::napi_value) -> Result<String> {
+  unsafe { JsString::from_raw_unchecked(env, value) }
+    .into_utf8()?
+    .into_owned()
+}
+
+/// Get a string property, `None` if it's missing, not a string or its getter throws
+unsafe fn get_named_string(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  name: &'static [u8],
+) -> Option<String> {
+  let name = unsafe { CStr::from_bytes_with_nul_unchecked(name) };
+  let mut property = ptr::null_mut();
+  if unsafe { sys::napi_get_named_property(env, value, name.as_ptr(), &mut property) }
+    != sys::Status::napi_ok
+  {
+    unsafe { clear_exception(env) };
+    return None;
+  }
+  match type_of!(env, property) {
+    Ok(ValueType::String) => unsafe { get_string(env, property) }.ok(),
+    _ => None,
+  }
+}
+
+unsafe fn write_buffer(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  output: &mut String,
+) -> Result<()> {
+  let mut data = ptr::null_mut();
+  let mut len = 0;
+  check_status!(unsafe { sys::napi_get_buffer_info(env, value, &mut data, &mut len) })?;
+  let bytes = if len == 0 {
+    &[]
+  } else {
+    unsafe { std::slice::from_raw_parts(data as *const u8, len) }
+  };
+  output.push_str("<Buffer");
+  for byte in bytes.iter().take(MAX_ITEMS as usize) {
+    let _ = write!(output, " {:02x}", byte);
+  }
+  if len > MAX_ITEMS as usize {
+    let _ = write!(output, " ... {} more bytes", len - MAX_ITEMS as usize);
+  }
+  output.push('>');
+  Ok(())
+}
+
+unsafe fn get_constructor_name(env: sys::napi_env, value: sys::napi_value) -> Option<String> {
+  let name = unsafe { CStr::from_bytes_with_nul_unchecked(b"constructor\0") };
+  let mut constructor = ptr::null_mut();
+  if unsafe { sys::napi_get_named_property(env, value, name.as_ptr(), &mut constructor) }
+    != sys::Status::napi_ok
+  {
+    unsafe { clear_exception(env) };
+    return None;
+  }
+  match type_of!(env, constructor) {
+    Ok(ValueType::Function) => {
+      unsafe { get_named_string(env, constructor, b"name\0") }.filter(|name| !name.is_empty())
+    }
+    _ => None,
+  }
+}
+
+unsafe fn clear_exception(env: sys::napi_env) {
+  let mut is_pending = false;
+  unsafe { sys::napi_is_exception_pending(env, &mut is_pending) };
+  if is_pending {
+    let mut exception = ptr::null_mut();
+    unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+  }
+}
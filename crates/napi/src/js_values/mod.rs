@@ -1,5 +1,6 @@
 use std::convert::TryFrom;
 use std::ffi::CString;
+use std::fmt;
 use std::ptr;
 
 use crate::{
@@ -10,6 +11,8 @@ use crate::{
 mod de;
 #[cfg(feature = "serde-json")]
 mod ser;
+#[cfg(feature = "serde-json")]
+pub mod serde_date;
 
 mod arraybuffer;
 #[cfg(feature = "napi6")]
@@ -18,15 +21,20 @@ mod boolean;
 mod buffer;
 #[cfg(feature = "napi5")]
 mod date;
+#[cfg(feature = "napi4")]
+mod deferred;
 mod either;
 mod escapable_handle_scope;
 mod function;
 mod global;
+mod inspect;
 mod number;
 mod object;
 mod object_property;
+mod reflect;
 mod string;
 mod tagged_object;
+mod type_tag;
 mod undefined;
 mod value;
 mod value_ref;
@@ -40,17 +48,27 @@ pub use buffer::*;
 pub use date::*;
 #[cfg(feature = "serde-json")]
 pub(crate) use de::De;
+#[cfg(feature = "napi4")]
+pub use deferred::JsDeferred;
 pub use either::Either;
 pub use escapable_handle_scope::EscapableHandleScope;
 pub use function::JsFunction;
 pub use global::*;
+pub(crate) use inspect::inspect;
 pub use number::JsNumber;
 pub use object::*;
 pub use object_property::*;
+use reflect::call_reflect_bool;
+pub(crate) use reflect::call_reflect;
 #[cfg(feature = "serde-json")]
 pub(crate) use ser::Ser;
+#[cfg(feature = "serde-json")]
+pub use ser::{LargeIntegerMode, NonFiniteMode, SerdeOptions};
 pub use string::*;
-pub(crate) use tagged_object::TaggedObject;
+pub(crate) use tagged_object::{check_tagged_object, tag_tagged_object, TaggedObject};
+pub use type_tag::TypeTag;
+#[cfg(feature = "napi8")]
+pub(crate) use type_tag::{addon_type_tag, check_object_tag, tag_object};
 pub use undefined::JsUndefined;
 pub(crate) use value::Value;
 pub use value_ref::*;
@@ -254,6 +272,20 @@ macro_rules! impl_js_value_methods {
         })?;
         Ok(result)
       }
+
+      /// Render the value like a truncated `util.inspect`, e.g. `{ a: 1, b: [ 'x', null ] }`
+      pub fn inspect(&self) -> Result<std::string::String> {
+        unsafe { inspect(self.0.env, self.0.value) }
+      }
+    }
+
+    impl fmt::Debug for $js_value {
+      fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inspect() {
+          Ok(inspected) => write!(f, "{}({})", stringify!($js_value), inspected),
+          Err(_) => write!(f, "{}(<uninspectable>)", stringify!($js_value)),
+        }
+      }
     }
   };
 }
@@ -357,6 +389,7 @@ macro_rules! impl_object_methods {
         Ok(result)
       }
 
+      /// Like `Reflect.deleteProperty`, returns `false` if the property is not configurable
       pub fn delete_property<S>(&mut self, name: S) -> Result<bool>
       where
         S: NapiRaw,
@@ -479,6 +512,48 @@ macro_rules! impl_object_methods {
         Ok(unsafe { T::from_raw_unchecked(self.0.env, result) })
       }
 
+      /// `Reflect.setPrototypeOf`, returns `false` if the object is not extensible.
+      ///
+      /// The prototype is an object or a `JsNull`.
+      pub fn set_prototype<T>(&mut self, prototype: T) -> Result<bool>
+      where
+        T: NapiRaw,
+      {
+        unsafe {
+          call_reflect_bool(
+            self.0.env,
+            "setPrototypeOf",
+            &[self.0.value, prototype.raw()],
+          )
+        }
+      }
+
+      /// `Reflect.ownKeys`, the `Array` of all own string and symbol keys,
+      /// including the non-enumerable ones
+      #[cfg(feature = "napi6")]
+      pub fn own_keys(&self) -> Result<JsObject> {
+        self.get_all_property_names(
+          KeyCollectionMode::OwnOnly,
+          KeyFilter::AllProperties,
+          KeyConversion::NumbersToStrings,
+        )
+      }
+
+      /// `Reflect.defineProperty` with a descriptor object like `{ value, writable, get, set }`,
+      /// returns `false` instead of throwing if the property can't be defined
+      pub fn define_property<K>(&mut self, key: K, descriptor: &JsObject) -> Result<bool>
+      where
+        K: NapiRaw,
+      {
+        unsafe {
+          call_reflect_bool(
+            self.0.env,
+            "defineProperty",
+            &[self.0.value, key.raw(), descriptor.raw()],
+          )
+        }
+      }
+
       pub fn set_element<T>(&mut self, index: u32, value: T) -> Result<()>
       where
         T: NapiRaw,
@@ -572,6 +647,26 @@ macro_rules! impl_object_methods {
       pub fn seal(&mut self) -> Result<()> {
         check_status!(unsafe { sys::napi_object_seal(self.0.env, self.0.value) })
       }
+
+      /// See [napi_type_tag_object](https://nodejs.org/api/n-api.html#napi_type_tag_object),
+      /// an object can only be tagged once.
+      ///
+      /// Instances of `#[napi]` classes and objects passed to [`Env::wrap`](crate::Env::wrap) are
+      /// tagged already, so this fails on them.
+      #[cfg(feature = "napi8")]
+      pub fn type_tag(&self, tag: &TypeTag) -> Result<()> {
+        let tag = sys::napi_type_tag::from(*tag);
+        check_status!(
+          unsafe { sys::napi_type_tag_object(self.0.env, self.0.value, &tag) },
+          "Failed to tag object, Node-API allows only one type tag per object and it's tagged already"
+        )
+      }
+
+      /// Whether the object is tagged with `tag`
+      #[cfg(feature = "napi8")]
+      pub fn check_type_tag(&self, tag: &TypeTag) -> Result<bool> {
+        unsafe { check_object_tag(self.0.env, self.0.value, *tag) }
+      }
     }
   };
 }
@@ -667,9 +762,29 @@ impl<'env> NapiRaw for &'env JsUnknown {
   }
 }
 
+#[cfg(feature = "napi8")]
+impl JsExternal {
+  /// See [napi_type_tag_object](https://nodejs.org/api/n-api.html#napi_type_tag_object),
+  /// an external can only be tagged once.
+  ///
+  /// The externals created by [`Env::create_external`](crate::Env::create_external) are tagged already.
+  pub fn type_tag(&self, tag: &TypeTag) -> Result<()> {
+    let tag = sys::napi_type_tag::from(*tag);
+    check_status!(
+      unsafe { sys::napi_type_tag_object(self.0.env, self.0.value, &tag) },
+      "Failed to tag external, Node-API allows only one type tag per object and it's tagged already"
+    )
+  }
+
+  /// Whether the external is tagged with `tag`
+  pub fn check_type_tag(&self, tag: &TypeTag) -> Result<bool> {
+    unsafe { check_object_tag(self.0.env, self.0.value, *tag) }
+  }
+}
+
 impl JsUnknown {
   pub fn get_type(&self) -> Result<ValueType> {
-    unsafe { type_of!(self.0.env, self.0.value) }
+    type_of!(self.0.env, self.0.value)
   }
 
   /// # Safety
@@ -108,6 +108,14 @@ impl Property {
     }
   }
 
+  pub(crate) fn is_static(&self) -> bool {
+    self.attrs == PropertyAttributes::Static
+  }
+
+  pub(crate) fn is_instance_method(&self) -> bool {
+    self.method.is_some() && !self.is_ctor && !self.is_static()
+  }
+
   pub fn with_ctor(mut self, callback: Callback) -> Self {
     self.method = Some(callback);
     self.is_ctor = true;
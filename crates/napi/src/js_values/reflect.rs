@@ -0,0 +1,57 @@
+use std::ffi::CString;
+use std::ptr;
+
+use crate::{check_status, sys, Result};
+
+/// Call `Reflect[method](...args)`, for the operations Node-API has no function of its own for
+pub(crate) unsafe fn call_reflect(
+  env: sys::napi_env,
+  method: &str,
+  args: &[sys::napi_value],
+) -> Result<sys::napi_value> {
+  let mut global = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+  let mut reflect = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, global, "Reflect\0".as_ptr().cast(), &mut reflect) },
+    "Failed to get `Reflect`"
+  )?;
+  let name = CString::new(method)?;
+  let mut function = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, reflect, name.as_ptr(), &mut function) },
+    "Failed to get `Reflect.{}`",
+    method
+  )?;
+  let mut result = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_call_function(
+        env,
+        reflect,
+        function,
+        args.len(),
+        args.as_ptr(),
+        &mut result,
+      )
+    },
+    "Failed to call `Reflect.{}`",
+    method
+  )?;
+  Ok(result)
+}
+
+pub(crate) unsafe fn call_reflect_bool(
+  env: sys::napi_env,
+  method: &str,
+  args: &[sys::napi_value],
+) -> Result<bool> {
+  let value = unsafe { call_reflect(env, method, args)? };
+  let mut result = false;
+  check_status!(
+    unsafe { sys::napi_get_value_bool(env, value, &mut result) },
+    "`Reflect.{}` did not return a boolean",
+    method
+  )?;
+  Ok(result)
+}
@@ -4,14 +4,74 @@ use std::slice;
 
 use serde::{ser, Serialize, Serializer};
 
+#[cfg(feature = "napi5")]
+use super::serde_date::DATE_NEWTYPE;
 use super::*;
 use crate::{Env, Error, Result};
 
-pub(crate) struct Ser<'env>(pub(crate) &'env Env);
+/// How [`Env::to_js_value_with_options`](crate::Env::to_js_value_with_options) converts 64-bit integers.
+///
+/// `BigInt`s need the `napi6` feature, otherwise they are all `number`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LargeIntegerMode {
+  /// `i64` as `number`, `u64` as `BigInt`
+  Mixed,
+  /// `number` within `Number.MAX_SAFE_INTEGER`, `BigInt` beyond it
+  Lossless,
+  /// Always `BigInt`
+  BigInt,
+}
+
+/// How [`Env::to_js_value_with_options`](crate::Env::to_js_value_with_options) converts `NaN` and the infinities
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonFiniteMode {
+  /// `NaN`, `Infinity` and `-Infinity`
+  PassThrough,
+  /// `null`, as `JSON.stringify` does
+  Null,
+  /// Fail with a `TypeError`
+  Error,
+}
+
+/// Options of [`Env::to_js_value_with_options`](crate::Env::to_js_value_with_options)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerdeOptions {
+  pub large_integers: LargeIntegerMode,
+  /// Create `Date`s of the [`serde_date`](crate::serde_date) fields, rather than `number`s
+  pub dates: bool,
+  pub non_finite: NonFiniteMode,
+}
+
+impl Default for SerdeOptions {
+  fn default() -> Self {
+    Self {
+      large_integers: LargeIntegerMode::Mixed,
+      dates: true,
+      non_finite: NonFiniteMode::PassThrough,
+    }
+  }
+}
+
+/// `Number.MAX_SAFE_INTEGER`
+#[cfg(feature = "napi6")]
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+pub(crate) struct Ser<'env>(pub(crate) &'env Env, pub(crate) SerdeOptions);
 
 impl<'env> Ser<'env> {
-  fn new(env: &'env Env) -> Self {
-    Self(env)
+  fn new(env: &'env Env, options: SerdeOptions) -> Self {
+    Self(env, options)
+  }
+
+  fn serialize_float(self, v: f64, type_name: &str) -> Result<Value> {
+    if !v.is_finite() {
+      match self.1.non_finite {
+        NonFiniteMode::PassThrough => {}
+        NonFiniteMode::Null => return self.0.get_null().map(|null| null.0),
+        NonFiniteMode::Error => return Err(crate::bindgen_runtime::non_finite_error(v, type_name)),
+      }
+    }
+    self.0.create_double(v).map(|js_number| js_number.0)
   }
 }
 
@@ -45,11 +105,11 @@ impl<'env> Serializer for Ser<'env> {
   }
 
   fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
-    self.0.create_double(v as _).map(|js_number| js_number.0)
+    self.serialize_float(v as _, "f32")
   }
 
   fn serialize_f64(self, v: f64) -> Result<Self::Ok> {
-    self.0.create_double(v).map(|js_number| js_number.0)
+    self.serialize_float(v, "f64")
   }
 
   fn serialize_i16(self, v: i16) -> Result<Self::Ok> {
@@ -61,6 +121,20 @@ impl<'env> Serializer for Ser<'env> {
   }
 
   fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
+    #[cfg(feature = "napi6")]
+    {
+      let as_bigint = match self.1.large_integers {
+        LargeIntegerMode::Mixed => false,
+        LargeIntegerMode::Lossless => !(-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&v),
+        LargeIntegerMode::BigInt => true,
+      };
+      if as_bigint {
+        return self
+          .0
+          .create_bigint_from_i64(v)
+          .map(|js_bigint| js_bigint.raw);
+      }
+    }
     self.0.create_int64(v).map(|js_number| js_number.0)
   }
 
@@ -95,6 +169,9 @@ impl<'env> Serializer for Ser<'env> {
 
   #[cfg(feature = "napi6")]
   fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
+    if self.1.large_integers == LargeIntegerMode::Lossless && v <= MAX_SAFE_INTEGER as u64 {
+      return self.0.create_int64(v as _).map(|js_number| js_number.0);
+    }
     self
       .0
       .create_bigint_from_u64(v)
@@ -170,7 +247,11 @@ impl<'env> Serializer for Ser<'env> {
     let env = self.0;
     let key = env.create_string("")?;
     let obj = env.create_object()?;
-    Ok(MapSerializer { key, obj })
+    Ok(MapSerializer {
+      key,
+      obj,
+      options: self.1,
+    })
   }
 
   fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
@@ -178,6 +259,7 @@ impl<'env> Serializer for Ser<'env> {
     Ok(SeqSerializer {
       current_index: 0,
       array,
+      options: self.1,
     })
   }
 
@@ -202,6 +284,7 @@ impl<'env> Serializer for Ser<'env> {
     Ok(SeqSerializer {
       current_index: 0,
       array,
+      options: self.1,
     })
   }
 
@@ -218,10 +301,18 @@ impl<'env> Serializer for Ser<'env> {
     self.0.create_string(variant).map(|string| string.0)
   }
 
-  fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok>
+  fn serialize_newtype_struct<T: ?Sized>(self, name: &'static str, value: &T) -> Result<Self::Ok>
   where
     T: Serialize,
   {
+    #[cfg(feature = "napi5")]
+    if name == DATE_NEWTYPE && self.1.dates {
+      let env = self.0;
+      let millis = unsafe { JsNumber::from_raw_unchecked(env.0, value.serialize(self)?.value) };
+      return env.create_date(millis.get_double()?).map(|date| date.0);
+    }
+    #[cfg(not(feature = "napi5"))]
+    let _ = name;
     value.serialize(self)
   }
 
@@ -244,6 +335,7 @@ impl<'env> Serializer for Ser<'env> {
     Ok(SeqSerializer {
       array: self.0.create_array_with_length(len)?,
       current_index: 0,
+      options: self.1,
     })
   }
 
@@ -255,12 +347,14 @@ impl<'env> Serializer for Ser<'env> {
     Ok(SeqSerializer {
       array: self.0.create_array_with_length(len)?,
       current_index: 0,
+      options: self.1,
     })
   }
 
   fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
     Ok(StructSerializer {
       obj: self.0.create_object()?,
+      options: self.1,
     })
   }
 
@@ -283,6 +377,7 @@ impl<'env> Serializer for Ser<'env> {
     )?;
     Ok(StructSerializer {
       obj: self.0.create_object()?,
+      options: self.1,
     })
   }
 }
@@ -290,6 +385,7 @@ impl<'env> Serializer for Ser<'env> {
 pub struct SeqSerializer {
   array: JsObject,
   current_index: usize,
+  options: SerdeOptions,
 }
 
 impl ser::SerializeSeq for SeqSerializer {
@@ -303,7 +399,7 @@ impl ser::SerializeSeq for SeqSerializer {
     let env = unsafe { Env::from_raw(self.array.0.env) };
     self.array.set_element(
       self.current_index as _,
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(value.serialize(Ser::new(&env, self.options))?),
     )?;
     self.current_index += 1;
     Ok(())
@@ -326,7 +422,7 @@ impl ser::SerializeTuple for SeqSerializer {
     let env = unsafe { Env::from_raw(self.array.0.env) };
     self.array.set_element(
       self.current_index as _,
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(value.serialize(Ser::new(&env, self.options))?),
     )?;
     self.current_index += 1;
     Ok(())
@@ -349,7 +445,7 @@ impl ser::SerializeTupleStruct for SeqSerializer {
     let env = unsafe { Env::from_raw(self.array.0.env) };
     self.array.set_element(
       self.current_index as _,
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(value.serialize(Ser::new(&env, self.options))?),
     )?;
     self.current_index += 1;
     Ok(())
@@ -372,7 +468,7 @@ impl ser::SerializeTupleVariant for SeqSerializer {
     let env = unsafe { Env::from_raw(self.array.0.env) };
     self.array.set_element(
       self.current_index as _,
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(value.serialize(Ser::new(&env, self.options))?),
     )?;
     self.current_index += 1;
     Ok(())
@@ -386,6 +482,7 @@ impl ser::SerializeTupleVariant for SeqSerializer {
 pub struct MapSerializer {
   key: JsString,
   obj: JsObject,
+  options: SerdeOptions,
 }
 
 #[doc(hidden)]
@@ -398,7 +495,7 @@ impl ser::SerializeMap for MapSerializer {
     T: Serialize,
   {
     let env = unsafe { Env::from_raw(self.obj.0.env) };
-    self.key = JsString(key.serialize(Ser::new(&env))?);
+    self.key = JsString(key.serialize(Ser::new(&env, self.options))?);
     Ok(())
   }
 
@@ -413,7 +510,7 @@ impl ser::SerializeMap for MapSerializer {
         value: self.key.0.value,
         value_type: ValueType::String,
       }),
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(value.serialize(Ser::new(&env, self.options))?),
     )?;
     Ok(())
   }
@@ -429,8 +526,8 @@ impl ser::SerializeMap for MapSerializer {
   {
     let env = unsafe { Env::from_raw(self.obj.0.env) };
     self.obj.set_property(
-      JsString(key.serialize(Ser::new(&env))?),
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsString(key.serialize(Ser::new(&env, self.options))?),
+      JsUnknown(value.serialize(Ser::new(&env, self.options))?),
     )?;
     Ok(())
   }
@@ -442,6 +539,7 @@ impl ser::SerializeMap for MapSerializer {
 
 pub struct StructSerializer {
   obj: JsObject,
+  options: SerdeOptions,
 }
 
 #[doc(hidden)]
@@ -454,9 +552,10 @@ impl ser::SerializeStruct for StructSerializer {
     T: Serialize,
   {
     let env = unsafe { Env::from_raw(self.obj.0.env) };
-    self
-      .obj
-      .set_named_property(key, JsUnknown(value.serialize(Ser::new(&env))?))?;
+    self.obj.set_named_property(
+      key,
+      JsUnknown(value.serialize(Ser::new(&env, self.options))?),
+    )?;
     Ok(())
   }
 
@@ -475,9 +574,10 @@ impl ser::SerializeStructVariant for StructSerializer {
     T: Serialize,
   {
     let env = unsafe { Env::from_raw(self.obj.0.env) };
-    self
-      .obj
-      .set_named_property(key, JsUnknown(value.serialize(Ser::new(&env))?))?;
+    self.obj.set_named_property(
+      key,
+      JsUnknown(value.serialize(Ser::new(&env, self.options))?),
+    )?;
     Ok(())
   }
 
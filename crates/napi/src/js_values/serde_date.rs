@@ -0,0 +1,112 @@
+//! Convert `SystemTime` and `chrono::DateTime<Utc>` fields from and into JavaScript `Date`s.
+//!
+//! ```rust
+//! use std::time::SystemTime;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!   #[serde(with = "napi::serde_date")]
+//!   at: SystemTime,
+//! }
+//! ```
+//!
+//! Dates are represented as the milliseconds since the Unix epoch, so other serializers like
+//! `serde_json` see a number.
+//! `Date`s need the `napi5` feature, otherwise the number is used in JavaScript too.
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+
+/// Name of the newtype struct recognized by the napi serializer and deserializer
+pub(crate) const DATE_NEWTYPE: &str = "$napi::Date";
+
+/// The range of JavaScript `Date`s, 100,000,000 days around the Unix epoch
+const MAX_DATE_MILLIS: f64 = 8.64e15;
+
+/// Values converted by [`serialize`] and [`deserialize`]
+pub trait DateValue: Sized {
+  fn to_millis(&self) -> f64;
+
+  fn from_millis(millis: f64) -> Option<Self>;
+}
+
+impl DateValue for SystemTime {
+  fn to_millis(&self) -> f64 {
+    match self.duration_since(UNIX_EPOCH) {
+      Ok(since) => since.as_secs_f64() * 1000.0,
+      Err(e) => -e.duration().as_secs_f64() * 1000.0,
+    }
+  }
+
+  fn from_millis(millis: f64) -> Option<Self> {
+    if !millis.is_finite() || millis.abs() > MAX_DATE_MILLIS {
+      return None;
+    }
+    let duration = Duration::from_secs_f64(millis.abs() / 1000.0);
+    if millis >= 0.0 {
+      UNIX_EPOCH.checked_add(duration)
+    } else {
+      UNIX_EPOCH.checked_sub(duration)
+    }
+  }
+}
+
+#[cfg(feature = "chrono_date")]
+impl DateValue for chrono::DateTime<chrono::Utc> {
+  fn to_millis(&self) -> f64 {
+    self.timestamp_millis() as f64
+  }
+
+  fn from_millis(millis: f64) -> Option<Self> {
+    if !millis.is_finite() || millis.abs() > MAX_DATE_MILLIS {
+      return None;
+    }
+    chrono::DateTime::from_timestamp_millis(millis as i64)
+  }
+}
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+  T: DateValue,
+  S: Serializer,
+{
+  serializer.serialize_newtype_struct(DATE_NEWTYPE, &value.to_millis())
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+  T: DateValue,
+  D: Deserializer<'de>,
+{
+  let millis = deserializer.deserialize_newtype_struct(DATE_NEWTYPE, MillisVisitor)?;
+  T::from_millis(millis).ok_or_else(|| de::Error::custom("Found invalid date"))
+}
+
+struct MillisVisitor;
+
+impl<'de> Visitor<'de> for MillisVisitor {
+  type Value = f64;
+
+  fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("a Date or the milliseconds since the Unix epoch")
+  }
+
+  fn visit_f64<E: de::Error>(self, v: f64) -> Result<f64, E> {
+    Ok(v)
+  }
+
+  fn visit_i64<E: de::Error>(self, v: i64) -> Result<f64, E> {
+    Ok(v as f64)
+  }
+
+  fn visit_u64<E: de::Error>(self, v: u64) -> Result<f64, E> {
+    Ok(v as f64)
+  }
+
+  fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<f64, D::Error> {
+    deserializer.deserialize_f64(self)
+  }
+}
@@ -1,4 +1,24 @@
 use std::any::TypeId;
+#[cfg(not(feature = "napi8"))]
+use std::collections::HashSet;
+use std::ffi::c_void;
+#[cfg(not(feature = "napi8"))]
+use std::sync::Mutex;
+
+#[cfg(not(feature = "napi8"))]
+use lazy_static::lazy_static;
+
+#[cfg(feature = "napi8")]
+use crate::{addon_type_tag, check_object_tag, tag_object, TypeTag};
+use crate::{sys, Error, Result, Status};
+
+#[cfg(not(feature = "napi8"))]
+lazy_static! {
+  /// The `TaggedObject`s wrapped or created by this addon, by their address.
+  /// Objects wrapped by other addons may hold anything, their `TypeId` can't be read.
+  /// Only used without the `napi8` feature, the objects are type tagged otherwise.
+  static ref TAGGED_OBJECTS: Mutex<HashSet<usize>> = Default::default();
+}
 
 #[repr(C)]
 pub struct TaggedObject<T> {
@@ -13,4 +33,82 @@ impl<T: 'static> TaggedObject<T> {
       object: Some(object),
     }
   }
+
+  /// Box `object` to wrap it or to create an external with it
+  pub(crate) fn into_raw(object: T) -> *mut Self {
+    let tagged_object = Box::into_raw(Box::new(TaggedObject::new(object)));
+    #[cfg(not(feature = "napi8"))]
+    if let Ok(mut tagged_objects) = TAGGED_OBJECTS.lock() {
+      tagged_objects.insert(tagged_object as usize);
+    }
+    tagged_object
+  }
+}
+
+impl<T> TaggedObject<T> {
+  /// Drop a `TaggedObject` boxed by [`TaggedObject::into_raw`]
+  pub(crate) unsafe fn drop_raw(tagged_object: *mut Self) {
+    #[cfg(not(feature = "napi8"))]
+    if let Ok(mut tagged_objects) = TAGGED_OBJECTS.lock() {
+      tagged_objects.remove(&(tagged_object as usize));
+    }
+    drop(unsafe { Box::from_raw(tagged_object) });
+  }
+}
+
+/// The tag of the objects and externals holding a `TaggedObject` of this addon
+#[cfg(feature = "napi8")]
+fn tagged_object_type_tag() -> TypeTag {
+  addon_type_tag("napi::TaggedObject")
+}
+
+/// Tag `value`, which holds a `TaggedObject` boxed by [`TaggedObject::into_raw`],
+/// it's a no-op without the `napi8` feature
+#[cfg_attr(not(feature = "napi8"), allow(unused_variables))]
+pub(crate) unsafe fn tag_tagged_object(env: sys::napi_env, value: sys::napi_value) -> Result<()> {
+  #[cfg(feature = "napi8")]
+  unsafe {
+    tag_object(env, value, tagged_object_type_tag())?
+  };
+  Ok(())
+}
+
+/// Fail unless `unknown_tagged_object`, unwrapped from the object or external `value`,
+/// is a `TaggedObject` wrapped or created by this addon
+pub(crate) unsafe fn check_tagged_object(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  unknown_tagged_object: *mut c_void,
+) -> Result<()> {
+  if unsafe { is_tagged_object(env, value, unknown_tagged_object)? } {
+    Ok(())
+  } else {
+    Err(Error::new(
+      Status::InvalidArg,
+      "Object is not wrapped by this addon".to_owned(),
+    ))
+  }
+}
+
+#[cfg(feature = "napi8")]
+unsafe fn is_tagged_object(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  _unknown_tagged_object: *mut c_void,
+) -> Result<bool> {
+  unsafe { check_object_tag(env, value, tagged_object_type_tag()) }
+}
+
+#[cfg(not(feature = "napi8"))]
+unsafe fn is_tagged_object(
+  _env: sys::napi_env,
+  _value: sys::napi_value,
+  unknown_tagged_object: *mut c_void,
+) -> Result<bool> {
+  Ok(
+    TAGGED_OBJECTS
+      .lock()
+      .map(|tagged_objects| tagged_objects.contains(&(unknown_tagged_object as usize)))
+      .unwrap_or(false),
+  )
 }
@@ -0,0 +1,77 @@
+#[cfg(feature = "napi8")]
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "napi8")]
+use std::hash::{Hash, Hasher};
+
+#[cfg(feature = "napi8")]
+use crate::{check_status, sys, Result};
+
+/// A 128-bit tag associated with an object, to tell whether it's of the expected type before unwrapping it.
+///
+/// See [napi_type_tag_object](https://nodejs.org/api/n-api.html#napi_type_tag_object).
+/// With the `napi8` feature class instances, objects passed to [`Env::wrap`](crate::Env::wrap) and
+/// externals created by this crate are tagged already. Node-API allows only one tag per object,
+/// so they can't be tagged again, and objects tagged with a `TypeTag` can't be wrapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypeTag {
+  pub lower: u64,
+  pub upper: u64,
+}
+
+impl TypeTag {
+  pub const fn new(lower: u64, upper: u64) -> Self {
+    TypeTag { lower, upper }
+  }
+}
+
+#[cfg(feature = "napi8")]
+impl From<TypeTag> for sys::napi_type_tag {
+  fn from(tag: TypeTag) -> Self {
+    sys::napi_type_tag {
+      lower: tag.lower,
+      upper: tag.upper,
+    }
+  }
+}
+
+/// Every addon links its own copy of this crate, so the address of this static tells the addons apart
+#[cfg(feature = "napi8")]
+static ADDON_MARKER: u8 = 0;
+
+/// The tag of the objects named `name` by this addon, e.g. the instances of a class
+#[cfg(feature = "napi8")]
+pub(crate) fn addon_type_tag(name: &str) -> TypeTag {
+  let mut hasher = DefaultHasher::new();
+  name.hash(&mut hasher);
+  TypeTag::new(hasher.finish(), &ADDON_MARKER as *const u8 as u64)
+}
+
+/// Tag `value` unless it's tagged with `tag` already
+#[cfg(feature = "napi8")]
+pub(crate) unsafe fn tag_object(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  tag: TypeTag,
+) -> Result<()> {
+  if unsafe { check_object_tag(env, value, tag)? } {
+    return Ok(());
+  }
+  let tag = sys::napi_type_tag::from(tag);
+  check_status!(
+    unsafe { sys::napi_type_tag_object(env, value, &tag) },
+    "Failed to tag object, Node-API allows only one type tag per object and it's tagged already"
+  )
+}
+
+/// Whether `value` is tagged with `tag`
+#[cfg(feature = "napi8")]
+pub(crate) unsafe fn check_object_tag(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  tag: TypeTag,
+) -> Result<bool> {
+  let tag = sys::napi_type_tag::from(tag);
+  let mut result = false;
+  check_status!(unsafe { sys::napi_check_object_type_tag(env, value, &tag, &mut result) })?;
+  Ok(result)
+}
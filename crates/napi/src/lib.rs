@@ -7,13 +7,25 @@
 //!
 //! ## Feature flags
 //!
-//! ### napi1 ~ napi8
+//! ### napi1 ~ napi10
 //!
 //! Because `Node.js` N-API has versions. So there are feature flags to choose what version of `N-API` you want to build for.
 //! For example, if you want build a library which can be used by `node@10.17.0`, you should choose the `napi5` or lower.
 //!
 //! The details of N-API versions and support matrix: [n_api_version_matrix](https://nodejs.org/api/n-api.html#n_api_n_api_version_matrix)
 //!
+//! ### experimental
+//!
+//! Node-API functions which are still experimental in Node.js, enabling it implies `napi10`.
+//!
+//! ### WebAssembly
+//!
+//! Addons could be compiled to `wasm32-wasip1-threads` and loaded by the [emnapi](https://github.com/toyobayashi/emnapi) runtime as a portable fallback,
+//! `napi-build` configures the linker for it. The module is registered through the exported `napi_register_wasm_v1`,
+//! after the loader called the exported `__napi_register__*` functions which collect the exports.
+//!
+//! The `tokio_rt` feature needs the multi-threaded tokio runtime, which is not available in WebAssembly.
+//!
 //! ### tokio_rt
 //! With `tokio_rt` feature, `napi-rs` provides a ***tokio runtime*** in an additional thread.
 //! And you can easily run tokio `future` in it and return `promise`.
@@ -47,6 +59,12 @@
 //!
 //! With this feature, you can use `JsString.as_latin1_string` function
 //!
+//! ### uv
+//!
+//! Expose the raw `uv_loop_t*` of Node.js via `Env::get_uv_event_loop`, for addons integrating other libuv based C libraries.
+//!
+//! Everything done with the raw loop is unchecked, use it with care.
+//!
 //! ### serde-json
 //!
 //! Enable Serialize/Deserialize data cross `JavaScript Object` and `Rust struct`.
@@ -72,12 +90,21 @@
 //! }
 //! ```
 //!
+//! ### tracing
+//!
+//! Emit [tracing](https://docs.rs/tracing) spans and events at the FFI boundary, so addons could be profiled with any `tracing` subscriber:
+//!
+//! - `napi_call` spans of the calls from JavaScript into `#[napi]` functions, at `TRACE` level.
+//! - `napi_task_compute` and `napi_task_complete` spans and the queued events of the async tasks, at `DEBUG` level.
+//! - The `queue_depth` of the `ThreadsafeFunction`s on every call and callback, at `TRACE` level.
+//!
 
 #[cfg(feature = "napi8")]
 mod async_cleanup_hook;
 
 #[cfg(feature = "napi8")]
 pub use async_cleanup_hook::AsyncCleanupHook;
+mod async_context;
 mod async_work;
 mod bindgen_runtime;
 mod call_context;
@@ -98,23 +125,30 @@ mod value_type;
 pub use cleanup_env::CleanupEnvHook;
 #[cfg(feature = "napi4")]
 pub mod threadsafe_function;
+#[cfg(feature = "napi4")]
+mod uncaught_exception;
 
 mod version;
-#[cfg(target_os = "windows")]
-mod win_delay_load_hook;
 
 pub use napi_sys as sys;
 
+pub use async_context::AsyncContext;
+#[cfg(feature = "napi3")]
+pub use async_context::CallbackScope;
 pub use async_work::AsyncWorkPromise;
+#[cfg(feature = "napi4")]
+pub use async_work::{set_async_work_pool, AsyncWorkPool};
 pub use call_context::CallContext;
 
 pub use env::*;
 pub use error::*;
 pub use js_values::*;
 pub use status::Status;
-pub use task::Task;
+pub use task::{Task, TaskPool};
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 pub use tokio_runtime::shutdown_tokio_rt;
+#[cfg(feature = "napi4")]
+pub use uncaught_exception::UncaughtExceptionReporter;
 pub use value_type::*;
 pub use version::NodeVersion;
 #[cfg(feature = "serde-json")]
@@ -195,7 +229,7 @@ pub mod bindgen_prelude {
   pub use crate::tokio_runtime::*;
   pub use crate::{
     assert_type_of, bindgen_runtime::*, check_status, check_status_or_throw, error, error::*, sys,
-    type_of, JsError, Property, PropertyAttributes, Result, Status, Task, ValueType,
+    type_of, JsError, Property, PropertyAttributes, Result, Status, Task, TaskPool, ValueType,
   };
 }
 
@@ -3,11 +3,27 @@ use crate::{
   Env, Error, Result,
 };
 
+/// The thread pool which runs [`Task::compute`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskPool {
+  /// The pool chosen by [`set_async_work_pool`](crate::set_async_work_pool)
+  Default,
+  /// The libuv thread pool, for the short blocking I/O like reading a file
+  Io,
+  /// The dedicated pool, for CPU heavy work which would otherwise block `fs` and `dns` of Node.js.
+  ///
+  /// Falls back to the libuv thread pool without the `napi4` feature.
+  Cpu,
+}
+
 pub trait Task: Send + Sized {
   type Output: Send + Sized + 'static;
   type JsValue: ToNapiValue + TypeName;
 
-  /// Compute logic in libuv thread
+  /// The thread pool which runs `compute`
+  const POOL: TaskPool = TaskPool::Default;
+
+  /// Compute logic in the thread pool of `POOL`
   fn compute(&mut self) -> Result<Self::Output>;
 
   /// Into this method if `compute` return `Ok`
@@ -18,7 +34,10 @@ pub trait Task: Send + Sized {
     Err(err)
   }
 
-  // after resolve or reject
+  /// Called after `resolve` or `reject` on the JavaScript thread, before the promise settles.
+  ///
+  /// For the cleanup which needs `Env`, like releasing references to JavaScript values.
+  /// An `Err` rejects the promise, like a throwing `finally` block in JavaScript.
   fn finally(&mut self, _env: Env) -> Result<()> {
     Ok(())
   }
@@ -4,11 +4,15 @@ use std::convert::Into;
 use std::ffi::CString;
 use std::marker::PhantomData;
 use std::os::raw::c_void;
-use std::ptr;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::{mem, ptr};
 
-use crate::{check_status, sys, Env, Error, JsError, NapiRaw, Result, Status};
+use crate::bindgen_runtime::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use crate::{
+  check_status, sys, Env, Error, JsError, JsUnknown, NapiRaw, NapiValue, Result, Status, ValueType,
+};
 
 /// ThreadSafeFunction Context object
 /// the `value` is the value passed to `call` method
@@ -17,9 +21,17 @@ pub struct ThreadSafeCallContext<T: 'static> {
   pub value: T,
 }
 
+/// Behavior of [`ThreadsafeFunction::call`] when the queue is full.
+///
+/// The queue is only bounded when the `ThreadsafeFunction` is created with a non-zero `max_queue_size`.
 #[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThreadsafeFunctionCallMode {
+  /// Return [`Status::QueueFull`] immediately instead of waiting for the queue.
   NonBlocking,
+  /// Block the calling thread until there is room in the queue.
+  ///
+  /// Never call in this mode on the JavaScript main thread with a bounded queue, it will deadlock.
   Blocking,
 }
 
@@ -147,9 +159,19 @@ pub struct ThreadsafeFunction<T: 'static, ES: ErrorStrategy::T = ErrorStrategy::
   raw_tsfn: sys::napi_threadsafe_function,
   aborted: Arc<AtomicBool>,
   ref_count: Arc<AtomicUsize>,
+  #[cfg(feature = "tracing")]
+  queue_depth: Arc<AtomicUsize>,
   _phantom: PhantomData<(T, ES)>,
 }
 
+/// The `context` of the raw threadsafe function, released in `thread_finalize_cb`
+struct TsfnContext<R> {
+  callback: R,
+  /// Count of the calls waiting in the queue
+  #[cfg(feature = "tracing")]
+  queue_depth: Arc<AtomicUsize>,
+}
+
 impl<T: 'static, ES: ErrorStrategy::T> Clone for ThreadsafeFunction<T, ES> {
   fn clone(&self) -> Self {
     if !self.aborted.load(Ordering::Acquire) {
@@ -164,6 +186,8 @@ impl<T: 'static, ES: ErrorStrategy::T> Clone for ThreadsafeFunction<T, ES> {
       raw_tsfn: self.raw_tsfn,
       aborted: Arc::clone(&self.aborted),
       ref_count: Arc::clone(&self.ref_count),
+      #[cfg(feature = "tracing")]
+      queue_depth: Arc::clone(&self.queue_depth),
       _phantom: PhantomData,
     }
   }
@@ -175,6 +199,10 @@ unsafe impl<T, ES: ErrorStrategy::T> Sync for ThreadsafeFunction<T, ES> {}
 impl<T: 'static, ES: ErrorStrategy::T> ThreadsafeFunction<T, ES> {
   /// See [napi_create_threadsafe_function](https://nodejs.org/api/n-api.html#n_api_napi_create_threadsafe_function)
   /// for more information.
+  ///
+  /// `max_queue_size` bounds the number of pending calls, `0` means unlimited.
+  /// Once the queue is full, [`ThreadsafeFunctionCallMode::NonBlocking`] calls return [`Status::QueueFull`]
+  /// and [`ThreadsafeFunctionCallMode::Blocking`] calls wait, so that producers could apply backpressure.
   pub fn create<
     V: NapiRaw,
     R: 'static + Send + FnMut(ThreadSafeCallContext<T>) -> Result<Vec<V>>,
@@ -194,7 +222,13 @@ impl<T: 'static, ES: ErrorStrategy::T> ThreadsafeFunction<T, ES> {
 
     let initial_thread_count = 1usize;
     let mut raw_tsfn = ptr::null_mut();
-    let ptr = Box::into_raw(Box::new(callback)) as *mut c_void;
+    #[cfg(feature = "tracing")]
+    let queue_depth = Arc::new(AtomicUsize::new(0));
+    let ptr = Box::into_raw(Box::new(TsfnContext {
+      callback,
+      #[cfg(feature = "tracing")]
+      queue_depth: queue_depth.clone(),
+    })) as *mut c_void;
     check_status!(unsafe {
       sys::napi_create_threadsafe_function(
         env,
@@ -219,6 +253,8 @@ impl<T: 'static, ES: ErrorStrategy::T> ThreadsafeFunction<T, ES> {
       raw_tsfn,
       aborted,
       ref_count: Arc::new(AtomicUsize::new(initial_thread_count)),
+      #[cfg(feature = "tracing")]
+      queue_depth,
       _phantom: PhantomData,
     })
   }
@@ -270,41 +306,158 @@ impl<T: 'static, ES: ErrorStrategy::T> ThreadsafeFunction<T, ES> {
   pub fn raw(&self) -> sys::napi_threadsafe_function {
     self.raw_tsfn
   }
+
+  fn call_with_payload<P>(&self, payload: P, mode: ThreadsafeFunctionCallMode) -> Status {
+    if self.aborted.load(Ordering::Acquire) {
+      return Status::Closing;
+    }
+    let payload = into_raw_payload(payload);
+    #[cfg(feature = "tracing")]
+    let queue_depth = self.queue_depth.fetch_add(1, Ordering::AcqRel) + 1;
+    let status: Status =
+      unsafe { sys::napi_call_threadsafe_function(self.raw_tsfn, payload, mode.into()) }.into();
+    if status != Status::Ok {
+      // The payload is not enqueued, so `call_js_cb` will never take it back
+      drop(unsafe { from_raw_payload::<P>(payload) });
+      #[cfg(feature = "tracing")]
+      self.queue_depth.fetch_sub(1, Ordering::AcqRel);
+    }
+    #[cfg(feature = "tracing")]
+    tracing::trace!(queue_depth, status = ?status, "napi threadsafe function called");
+    status
+  }
 }
 
 impl<T: 'static> ThreadsafeFunction<T, ErrorStrategy::CalleeHandled> {
   /// See [napi_call_threadsafe_function](https://nodejs.org/api/n-api.html#n_api_napi_call_threadsafe_function)
   /// for more information.
+  ///
+  /// Returns [`Status::QueueFull`] if the queue is full in [`ThreadsafeFunctionCallMode::NonBlocking`] mode,
+  /// and [`Status::Closing`] if the `ThreadsafeFunction` is aborted.
   pub fn call(&self, value: Result<T>, mode: ThreadsafeFunctionCallMode) -> Status {
-    if self.aborted.load(Ordering::Acquire) {
-      return Status::Closing;
-    }
-    unsafe {
-      sys::napi_call_threadsafe_function(
-        self.raw_tsfn,
-        Box::into_raw(Box::new(value)) as *mut _,
-        mode.into(),
-      )
-    }
-    .into()
+    self.call_with_payload(value, mode)
   }
 }
 
 impl<T: 'static> ThreadsafeFunction<T, ErrorStrategy::Fatal> {
   /// See [napi_call_threadsafe_function](https://nodejs.org/api/n-api.html#n_api_napi_call_threadsafe_function)
   /// for more information.
+  ///
+  /// Returns [`Status::QueueFull`] if the queue is full in [`ThreadsafeFunctionCallMode::NonBlocking`] mode,
+  /// and [`Status::Closing`] if the `ThreadsafeFunction` is aborted.
   pub fn call(&self, value: T, mode: ThreadsafeFunctionCallMode) -> Status {
-    if self.aborted.load(Ordering::Acquire) {
-      return Status::Closing;
-    }
-    unsafe {
-      sys::napi_call_threadsafe_function(
-        self.raw_tsfn,
-        Box::into_raw(Box::new(value)) as *mut _,
-        mode.into(),
-      )
-    }
-    .into()
+    self.call_with_payload(value, mode)
+  }
+}
+
+/// What [`ThreadsafeFunction::forward`] does with a received value when the queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backpressure {
+  /// Stop receiving until there is room in the queue, so that bounded channels push back on their senders.
+  Block,
+  /// Drop the value and keep receiving, for the events where only the latest ones matter.
+  Drop,
+}
+
+/// The receiving half of a channel, drained by [`ThreadsafeFunction::forward`].
+pub trait ChannelReceiver<T>: Send + 'static {
+  /// Wait for the next value, `None` once all the senders are dropped.
+  fn recv(&mut self) -> Option<T>;
+}
+
+impl<T: Send + 'static> ChannelReceiver<T> for mpsc::Receiver<T> {
+  fn recv(&mut self) -> Option<T> {
+    mpsc::Receiver::recv(self).ok()
+  }
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl<T: Send + 'static> ChannelReceiver<T> for crossbeam_channel::Receiver<T> {
+  fn recv(&mut self) -> Option<T> {
+    crossbeam_channel::Receiver::recv(self).ok()
+  }
+}
+
+#[cfg(feature = "tokio_rt")]
+impl<T: Send + 'static> ChannelReceiver<T> for tokio::sync::mpsc::Receiver<T> {
+  fn recv(&mut self) -> Option<T> {
+    self.blocking_recv()
+  }
+}
+
+#[cfg(feature = "tokio_rt")]
+impl<T: Send + 'static> ChannelReceiver<T> for tokio::sync::mpsc::UnboundedReceiver<T> {
+  fn recv(&mut self) -> Option<T> {
+    self.blocking_recv()
+  }
+}
+
+impl<T: Send + 'static, ES: ErrorStrategy::T> ThreadsafeFunction<T, ES> {
+  /// Call the JavaScript function with every value received from `receiver`, on a new thread.
+  ///
+  /// ```rust,ignore
+  /// let (sender, receiver) = std::sync::mpsc::sync_channel(16);
+  /// tsfn.forward(receiver, Backpressure::Block);
+  /// ```
+  ///
+  /// [`Backpressure::Drop`] only drops values if the `ThreadsafeFunction` is created with a non-zero `max_queue_size`.
+  /// The thread exits once all the senders are dropped or the `ThreadsafeFunction` is aborted.
+  pub fn forward<C: ChannelReceiver<T>>(
+    self,
+    mut receiver: C,
+    backpressure: Backpressure,
+  ) -> JoinHandle<()> {
+    let mode = match backpressure {
+      Backpressure::Block => ThreadsafeFunctionCallMode::Blocking,
+      Backpressure::Drop => ThreadsafeFunctionCallMode::NonBlocking,
+    };
+    thread::spawn(move || {
+      while let Some(value) = receiver.recv() {
+        let status = match ES::VALUE {
+          ErrorStrategy::CalleeHandled::VALUE => {
+            self.call_with_payload(Ok::<T, Error>(value), mode)
+          }
+          ErrorStrategy::Fatal::VALUE => self.call_with_payload(value, mode),
+        };
+        if status == Status::Closing {
+          break;
+        }
+      }
+    })
+  }
+}
+
+impl<T: 'static, ES: ErrorStrategy::T> TypeName for ThreadsafeFunction<T, ES> {
+  fn type_name() -> &'static str {
+    "ThreadsafeFunction"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Function
+  }
+}
+
+/// Allow `#[napi]` functions to receive a `ThreadsafeFunction` directly.
+///
+/// The value passed to `call` is converted with [`ToNapiValue`] as the only argument,
+/// preceded by the `error` argument in [`ErrorStrategy::CalleeHandled`] mode.
+impl<T: ToNapiValue + 'static, ES: ErrorStrategy::T> FromNapiValue for ThreadsafeFunction<T, ES> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Self::create(env, napi_val, 0, |ctx: ThreadSafeCallContext<T>| {
+      let raw_env = ctx.env.raw();
+      let value = unsafe { T::to_napi_value(raw_env, ctx.value)? };
+      Ok(vec![unsafe {
+        JsUnknown::from_raw_unchecked(raw_env, value)
+      }])
+    })
+  }
+}
+
+impl<T: ToNapiValue + 'static, ES: ErrorStrategy::T> ValidateNapiValue
+  for ThreadsafeFunction<T, ES>
+{
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Function]
   }
 }
 
@@ -325,6 +478,36 @@ impl<T: 'static, ES: ErrorStrategy::T> Drop for ThreadsafeFunction<T, ES> {
   }
 }
 
+/// Whether the payload fits in the `data` pointer of `napi_call_threadsafe_function`
+const fn is_inline_payload<P>() -> bool {
+  mem::size_of::<P>() <= mem::size_of::<*mut c_void>()
+    && mem::align_of::<P>() <= mem::align_of::<*mut c_void>()
+}
+
+/// Small payloads like numbers, `Arc`s and `Box`es are passed by value in the data pointer,
+/// only the larger ones are boxed
+fn into_raw_payload<P>(payload: P) -> *mut c_void {
+  if is_inline_payload::<P>() {
+    let mut data = ptr::null_mut::<c_void>();
+    unsafe { ptr::write((&mut data as *mut *mut c_void).cast::<P>(), payload) };
+    data
+  } else {
+    Box::into_raw(Box::new(payload)).cast()
+  }
+}
+
+/// Take back the payload of [`into_raw_payload`], it must be called exactly once per payload
+unsafe fn from_raw_payload<P>(data: *mut c_void) -> P {
+  if is_inline_payload::<P>() {
+    unsafe { ptr::read((&data as *const *mut c_void).cast::<P>()) }
+  } else {
+    *unsafe { Box::from_raw(data.cast::<P>()) }
+  }
+}
+
+/// Arguments up to this count are passed to the callback without allocating
+const INLINE_ARGS_LEN: usize = 8;
+
 unsafe extern "C" fn cleanup_cb(cleanup_data: *mut c_void) {
   let aborted = unsafe { Arc::<AtomicBool>::from_raw(cleanup_data.cast()) };
   aborted.store(true, Ordering::SeqCst);
@@ -338,7 +521,7 @@ unsafe extern "C" fn thread_finalize_cb<T: 'static, V: NapiRaw, R>(
   R: 'static + Send + FnMut(ThreadSafeCallContext<T>) -> Result<Vec<V>>,
 {
   // cleanup
-  drop(unsafe { Box::<R>::from_raw(finalize_data.cast()) });
+  drop(unsafe { Box::<TsfnContext<R>>::from_raw(finalize_data.cast()) });
 }
 
 unsafe extern "C" fn call_js_cb<T: 'static, V: NapiRaw, R, ES>(
@@ -350,16 +533,23 @@ unsafe extern "C" fn call_js_cb<T: 'static, V: NapiRaw, R, ES>(
   R: 'static + Send + FnMut(ThreadSafeCallContext<T>) -> Result<Vec<V>>,
   ES: ErrorStrategy::T,
 {
+  let ctx: &mut TsfnContext<R> = unsafe { &mut *context.cast::<TsfnContext<R>>() };
+  #[cfg(feature = "tracing")]
+  let _span = tracing::trace_span!(
+    "napi_threadsafe_function_callback",
+    queue_depth = ctx.queue_depth.fetch_sub(1, Ordering::AcqRel) - 1
+  )
+  .entered();
+
   // env and/or callback can be null when shutting down
   if raw_env.is_null() || js_callback.is_null() {
     return;
   }
 
-  let ctx: &mut R = unsafe { &mut *context.cast::<R>() };
   let val: Result<T> = unsafe {
     match ES::VALUE {
-      ErrorStrategy::CalleeHandled::VALUE => *Box::<Result<T>>::from_raw(data.cast()),
-      ErrorStrategy::Fatal::VALUE => Ok(*Box::<T>::from_raw(data.cast())),
+      ErrorStrategy::CalleeHandled::VALUE => from_raw_payload::<Result<T>>(data),
+      ErrorStrategy::Fatal::VALUE => Ok(from_raw_payload::<T>(data)),
     }
   };
 
@@ -367,7 +557,7 @@ unsafe extern "C" fn call_js_cb<T: 'static, V: NapiRaw, R, ES>(
   unsafe { sys::napi_get_undefined(raw_env, &mut recv) };
 
   let ret = val.and_then(|v| {
-    (ctx)(ThreadSafeCallContext {
+    (ctx.callback)(ThreadSafeCallContext {
       env: unsafe { Env::from_raw(raw_env) },
       value: v,
     })
@@ -379,12 +569,26 @@ unsafe extern "C" fn call_js_cb<T: 'static, V: NapiRaw, R, ES>(
   let status = match ret {
     Ok(values) => {
       let values = values.iter().map(|v| unsafe { v.raw() });
-      let args: Vec<sys::napi_value> = if ES::VALUE == ErrorStrategy::CalleeHandled::VALUE {
-        let mut js_null = ptr::null_mut();
+      let mut js_null = ptr::null_mut();
+      let error_arg = if ES::VALUE == ErrorStrategy::CalleeHandled::VALUE {
         unsafe { sys::napi_get_null(raw_env, &mut js_null) };
-        ::core::iter::once(js_null).chain(values).collect()
+        Some(js_null)
+      } else {
+        None
+      };
+      let args = error_arg.into_iter().chain(values);
+      let mut inline_args = [ptr::null_mut(); INLINE_ARGS_LEN];
+      let heap_args: Vec<sys::napi_value>;
+      let args: &[sys::napi_value] = if args.size_hint().0 <= INLINE_ARGS_LEN {
+        let mut len = 0;
+        for (slot, arg) in inline_args.iter_mut().zip(args) {
+          *slot = arg;
+          len += 1;
+        }
+        &inline_args[..len]
       } else {
-        values.collect()
+        heap_args = args.collect();
+        &heap_args
       };
       unsafe {
         sys::napi_call_function(
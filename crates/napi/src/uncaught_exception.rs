@@ -0,0 +1,110 @@
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::ptr;
+
+use crate::{check_status, sys, Error, JsError, Result};
+
+/// Surface errors of background threads to the `'uncaughtException'` handling of Node.js.
+///
+/// Created by [`Env::create_uncaught_exception_reporter`](crate::Env::create_uncaught_exception_reporter), it's `Send` and `Clone`,
+/// the error is thrown with `napi_fatal_exception` on the JavaScript main thread.
+///
+/// It doesn't keep the event loop alive.
+pub struct UncaughtExceptionReporter {
+  tsfn: sys::napi_threadsafe_function,
+}
+
+unsafe impl Send for UncaughtExceptionReporter {}
+unsafe impl Sync for UncaughtExceptionReporter {}
+
+impl UncaughtExceptionReporter {
+  pub(crate) fn new(env: sys::napi_env) -> Result<Self> {
+    let mut async_resource_name = ptr::null_mut();
+    let s = unsafe { CStr::from_bytes_with_nul_unchecked(b"napi_fatal_exception\0") };
+    check_status!(unsafe {
+      sys::napi_create_string_utf8(env, s.as_ptr(), 20, &mut async_resource_name)
+    })?;
+
+    let mut tsfn = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_threadsafe_function(
+          env,
+          ptr::null_mut(),
+          ptr::null_mut(),
+          async_resource_name,
+          0,
+          1,
+          ptr::null_mut(),
+          None,
+          ptr::null_mut(),
+          Some(call_fatal_exception),
+          &mut tsfn,
+        )
+      },
+      "Failed to create threadsafe function for uncaught exception reporter"
+    )?;
+    check_status!(unsafe { sys::napi_unref_threadsafe_function(env, tsfn) })?;
+    Ok(UncaughtExceptionReporter { tsfn })
+  }
+
+  /// Trigger an `'uncaughtException'` with `error` on the JavaScript main thread, could be called from any thread.
+  ///
+  /// By default Node.js prints the error and exits unless an `'uncaughtException'` listener is registered.
+  pub fn report(&self, error: Error) -> Result<()> {
+    let error = Box::into_raw(Box::new(error));
+    let status = unsafe {
+      sys::napi_call_threadsafe_function(
+        self.tsfn,
+        error as *mut c_void,
+        sys::ThreadsafeFunctionCallMode::nonblocking,
+      )
+    };
+    if status != sys::Status::napi_ok {
+      // not enqueued, so it will never be freed by `call_fatal_exception`
+      drop(unsafe { Box::from_raw(error) });
+    }
+    check_status!(status, "Failed to report uncaught exception")
+  }
+}
+
+impl Clone for UncaughtExceptionReporter {
+  fn clone(&self) -> Self {
+    let status = unsafe { sys::napi_acquire_threadsafe_function(self.tsfn) };
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Acquire threadsafe function in UncaughtExceptionReporter failed"
+    );
+    UncaughtExceptionReporter { tsfn: self.tsfn }
+  }
+}
+
+impl Drop for UncaughtExceptionReporter {
+  fn drop(&mut self) {
+    let status = unsafe {
+      sys::napi_release_threadsafe_function(self.tsfn, sys::ThreadsafeFunctionReleaseMode::release)
+    };
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Release threadsafe function in UncaughtExceptionReporter failed"
+    );
+  }
+}
+
+unsafe extern "C" fn call_fatal_exception(
+  env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  _context: *mut c_void,
+  data: *mut c_void,
+) {
+  let error = unsafe { *Box::from_raw(data as *mut Error) };
+  // env is null when the environment is tearing down
+  if env.is_null() {
+    return;
+  }
+  let status = unsafe { sys::napi_fatal_exception(env, JsError::from(error).into_value(env)) };
+  debug_assert!(
+    status == sys::Status::napi_ok,
+    "Trigger uncaught exception failed"
+  );
+}
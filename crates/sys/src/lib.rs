@@ -5,6 +5,44 @@
 
 use std::os::raw::{c_char, c_int, c_uint, c_void};
 
+#[cfg(windows)]
+mod windows;
+
+/// Declare Node-API functions.
+///
+/// They are linked as regular `extern "C"` functions, except on Windows, where they are resolved lazily
+/// from the host process, so the same addon could be loaded by any executable embedding Node.js.
+///
+/// On WebAssembly they are imported from the `napi` module, which is provided by the [emnapi](https://github.com/toyobayashi/emnapi) runtime.
+macro_rules! generate {
+  ($(pub fn $name:ident($($param:ident: $ptype:ty),* $(,)?) $(-> $rtype:ty)?;)*) => {
+    #[cfg(not(windows))]
+    #[cfg_attr(target_family = "wasm", link(wasm_import_module = "napi"))]
+    extern "C" {
+      $(pub fn $name($($param: $ptype),*) $(-> $rtype)?;)*
+    }
+
+    $(
+      #[cfg(windows)]
+      #[allow(clippy::missing_safety_doc)]
+      pub unsafe fn $name($($param: $ptype),*) $(-> $rtype)? {
+        use std::sync::atomic::{AtomicPtr, Ordering};
+
+        static ADDRESS: AtomicPtr<c_void> = AtomicPtr::new(std::ptr::null_mut());
+
+        let mut address = ADDRESS.load(Ordering::Relaxed);
+        if address.is_null() {
+          address = windows::resolve(concat!(stringify!($name), "\0"));
+          ADDRESS.store(address, Ordering::Relaxed);
+        }
+        let function: unsafe extern "C" fn($($ptype),*) $(-> $rtype)? =
+          std::mem::transmute(address);
+        function($($param),*)
+      }
+    )*
+  };
+}
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct napi_env__ {
@@ -55,6 +93,7 @@ pub struct napi_deferred__ {
 pub struct uv_loop_s {
   _unused: [u8; 0],
 }
+pub type uv_loop_t = uv_loop_s;
 pub type napi_deferred = *mut napi_deferred__;
 
 pub type napi_property_attributes = i32;
@@ -131,6 +170,8 @@ pub mod Status {
   pub const napi_arraybuffer_expected: i32 = 19;
   pub const napi_detachable_arraybuffer_expected: i32 = 20;
   pub const napi_would_deadlock: i32 = 21; // unused
+  pub const napi_no_external_buffers_allowed: i32 = 22;
+  pub const napi_cannot_run_js: i32 = 23;
 }
 
 pub type napi_callback =
@@ -208,7 +249,15 @@ pub type napi_async_cleanup_hook_handle = *mut napi_async_cleanup_hook_handle__;
 pub type napi_async_cleanup_hook =
   Option<unsafe extern "C" fn(handle: napi_async_cleanup_hook_handle, data: *mut c_void)>;
 
-extern "C" {
+#[cfg(feature = "napi8")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct napi_type_tag {
+  pub lower: u64,
+  pub upper: u64,
+}
+
+generate! {
   pub fn napi_get_last_error_info(
     env: napi_env,
     result: *mut *const napi_extended_error_info,
@@ -686,12 +735,12 @@ extern "C" {
 }
 
 #[cfg(feature = "napi2")]
-extern "C" {
+generate! {
   pub fn napi_get_uv_event_loop(env: napi_env, loop_: *mut *mut uv_loop_s) -> napi_status;
 }
 
 #[cfg(feature = "napi3")]
-extern "C" {
+generate! {
   pub fn napi_fatal_exception(env: napi_env, err: napi_value) -> napi_status;
   pub fn napi_add_env_cleanup_hook(
     env: napi_env,
@@ -713,7 +762,7 @@ extern "C" {
 }
 
 #[cfg(feature = "napi4")]
-extern "C" {
+generate! {
   pub fn napi_create_threadsafe_function(
     env: napi_env,
     func: napi_value,
@@ -750,7 +799,7 @@ extern "C" {
 }
 
 #[cfg(feature = "napi5")]
-extern "C" {
+generate! {
   pub fn napi_create_date(env: napi_env, time: f64, result: *mut napi_value) -> napi_status;
   pub fn napi_is_date(env: napi_env, value: napi_value, is_date: *mut bool) -> napi_status;
   pub fn napi_get_date_value(env: napi_env, value: napi_value, result: *mut f64) -> napi_status;
@@ -765,7 +814,7 @@ extern "C" {
 }
 
 #[cfg(feature = "napi6")]
-extern "C" {
+generate! {
   pub fn napi_create_bigint_int64(
     env: napi_env,
     value: i64,
@@ -820,7 +869,7 @@ extern "C" {
 }
 
 #[cfg(feature = "napi7")]
-extern "C" {
+generate! {
   pub fn napi_detach_arraybuffer(env: napi_env, arraybuffer: napi_value) -> napi_status;
   pub fn napi_is_detached_arraybuffer(
     env: napi_env,
@@ -830,7 +879,7 @@ extern "C" {
 }
 
 #[cfg(feature = "napi8")]
-extern "C" {
+generate! {
   pub fn napi_add_async_cleanup_hook(
     env: napi_env,
     hook: napi_async_cleanup_hook,
@@ -845,10 +894,29 @@ extern "C" {
   pub fn napi_object_freeze(env: napi_env, object: napi_value) -> napi_status;
 
   pub fn napi_object_seal(env: napi_env, object: napi_value) -> napi_status;
+
+  pub fn napi_type_tag_object(
+    env: napi_env,
+    value: napi_value,
+    type_tag: *const napi_type_tag,
+  ) -> napi_status;
+
+  pub fn napi_check_object_type_tag(
+    env: napi_env,
+    value: napi_value,
+    type_tag: *const napi_type_tag,
+    result: *mut bool,
+  ) -> napi_status;
 }
 
-#[cfg(feature = "experimental")]
-extern "C" {
+#[cfg(feature = "napi9")]
+generate! {
+  pub fn node_api_symbol_for(
+    env: napi_env,
+    utf8description: *const c_char,
+    length: usize,
+    result: *mut napi_value,
+  ) -> napi_status;
   pub fn node_api_get_module_file_name(env: napi_env, result: *mut *const c_char) -> napi_status;
   pub fn node_api_create_syntax_error(
     env: napi_env,
@@ -863,6 +931,67 @@ extern "C" {
   ) -> napi_status;
 }
 
+#[cfg(feature = "napi10")]
+generate! {
+  pub fn node_api_create_external_string_latin1(
+    env: napi_env,
+    str_: *mut c_char,
+    length: usize,
+    finalize_callback: napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut napi_value,
+    copied: *mut bool,
+  ) -> napi_status;
+  pub fn node_api_create_external_string_utf16(
+    env: napi_env,
+    str_: *mut u16,
+    length: usize,
+    finalize_callback: napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut napi_value,
+    copied: *mut bool,
+  ) -> napi_status;
+  pub fn node_api_create_property_key_latin1(
+    env: napi_env,
+    str_: *const c_char,
+    length: usize,
+    result: *mut napi_value,
+  ) -> napi_status;
+  pub fn node_api_create_property_key_utf8(
+    env: napi_env,
+    str_: *const c_char,
+    length: usize,
+    result: *mut napi_value,
+  ) -> napi_status;
+  pub fn node_api_create_property_key_utf16(
+    env: napi_env,
+    str_: *const u16,
+    length: usize,
+    result: *mut napi_value,
+  ) -> napi_status;
+}
+
+/// Returned by `node_api_module_get_api_version_v1` to opt into the experimental Node-API behaviours
+#[cfg(feature = "experimental")]
+pub const NAPI_VERSION_EXPERIMENTAL: i32 = 2147483647;
+
+#[cfg(feature = "experimental")]
+generate! {
+  pub fn node_api_post_finalizer(
+    env: napi_env,
+    finalize_cb: napi_finalize,
+    finalize_data: *mut c_void,
+    finalize_hint: *mut c_void,
+  ) -> napi_status;
+  pub fn node_api_create_buffer_from_arraybuffer(
+    env: napi_env,
+    arraybuffer: napi_value,
+    byte_offset: usize,
+    byte_length: usize,
+    result: *mut napi_value,
+  ) -> napi_status;
+}
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct napi_callback_scope__ {
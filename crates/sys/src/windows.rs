@@ -0,0 +1,50 @@
+//! On Windows the Node-API symbols live in the host executable, which may be `node.exe`, `electron.exe`,
+//! or any other binary embedding Node.js. Instead of linking against an import library of a specific host,
+//! the symbols are resolved at runtime from the process which loaded the addon.
+
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+
+// Candidates for embedders which ship Node.js as a shared library instead of a static executable
+const HOST_LIBRARIES: &[&[u16]] = &[
+  &[
+    'l' as u16, 'i' as u16, 'b' as u16, 'n' as u16, 'o' as u16, 'd' as u16, 'e' as u16, '.' as u16,
+    'd' as u16, 'l' as u16, 'l' as u16, 0,
+  ],
+  &[
+    'n' as u16, 'o' as u16, 'd' as u16, 'e' as u16, '.' as u16, 'd' as u16, 'l' as u16, 'l' as u16,
+    0,
+  ],
+];
+
+extern "system" {
+  fn GetModuleHandleW(module_name: *const u16) -> *mut c_void;
+  fn GetProcAddress(module: *mut c_void, proc_name: *const c_char) -> *mut c_void;
+}
+
+/// Find `name` (nul terminated) in the host process.
+///
+/// Panics if no loaded module exports it, e.g. calling a `napi8` function in a Node.js which only supports `napi6`.
+pub(crate) fn resolve(name: &'static str) -> *mut c_void {
+  let symbol = name.as_ptr() as *const c_char;
+  let host = unsafe { GetModuleHandleW(ptr::null()) };
+  let mut address = unsafe { GetProcAddress(host, symbol) };
+  if address.is_null() {
+    for library in HOST_LIBRARIES {
+      let module = unsafe { GetModuleHandleW(library.as_ptr()) };
+      if !module.is_null() {
+        address = unsafe { GetProcAddress(module, symbol) };
+        if !address.is_null() {
+          break;
+        }
+      }
+    }
+  }
+  if address.is_null() {
+    panic!(
+      "Node-API `{}` could not be found in the host process",
+      name.trim_end_matches('\0')
+    );
+  }
+  address
+}
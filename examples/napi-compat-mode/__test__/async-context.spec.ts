@@ -0,0 +1,33 @@
+import { AsyncLocalStorage, executionAsyncId } from 'async_hooks'
+
+import test from 'ava'
+
+const bindings = require('../index.node')
+
+test('should propagate AsyncLocalStorage store with make_callback', (t) => {
+  const storage = new AsyncLocalStorage<{ id: number }>()
+  const store = { id: 1 }
+  const context = storage.run(store, () => bindings.createAsyncContext())
+  t.is(storage.getStore(), undefined)
+  t.is(
+    bindings.callInAsyncContext(context, () => storage.getStore()),
+    store,
+  )
+})
+
+test('should propagate AsyncLocalStorage store in callback scope', (t) => {
+  const storage = new AsyncLocalStorage<{ id: number }>()
+  const store = { id: 2 }
+  const context = storage.run(store, () => bindings.createAsyncContext())
+  t.is(
+    bindings.callInCallbackScope(context, () => storage.getStore()),
+    store,
+  )
+})
+
+test('should run callback in a new async id', (t) => {
+  const context = bindings.createAsyncContext()
+  const outerId = executionAsyncId()
+  const innerId = bindings.callInAsyncContext(context, () => executionAsyncId())
+  t.not(innerId, outerId)
+})
@@ -27,3 +27,7 @@ test('should be able to coerceToBool', (t) => {
   t.false(bindings.coerceToBool(null))
   t.false(bindings.coerceToBool(NaN))
 })
+
+test('should be able to get uv event loop', (t) => {
+  t.true(bindings.hasUvEventLoop())
+})
@@ -20,3 +20,22 @@ test('should set "this" properly', (t) => {
     t.is(this, obj)
   })
 })
+
+test('should apply function with any "this" value', (t) => {
+  bindings.testApplyFunctionWithThis(function (this: number, arg: string) {
+    t.is(this.valueOf(), 42)
+    t.is(arg, 'hello')
+  }, 42)
+})
+
+test('should convert arguments and return value with typed call', (t) => {
+  const ret = bindings.testTypedCallFunction(
+    (arg1: string, arg2: number, arg3: boolean) => {
+      t.is(arg1, 'hello')
+      t.is(arg2, 42)
+      t.true(arg3)
+      return `${arg1} world`
+    },
+  )
+  t.is(ret, 'hello world')
+})
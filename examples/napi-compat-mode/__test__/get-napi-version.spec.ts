@@ -7,3 +7,7 @@ test('should get napi version', (t) => {
   t.true(typeof napiVersion === 'number')
   t.is(`${napiVersion}`, process.versions.napi!)
 })
+
+test('should get Node-API version from napi_get_version', (t) => {
+  t.is(bindings.getNodeApiVersion(), bindings.getNapiVersion())
+})
@@ -0,0 +1,39 @@
+import { spawnSync } from 'child_process'
+import { join } from 'path'
+
+import test from 'ava'
+
+import { napiVersion } from '../napi-version'
+
+const bindingsPath = JSON.stringify(join(__dirname, '..', '..', 'index.node'))
+
+test('should terminate the process with fatal_error!', (t) => {
+  if (napiVersion < 4) {
+    t.pass()
+    return
+  }
+  const { status, stderr } = spawnSync(process.execPath, [
+    '-e',
+    `require(${bindingsPath}).testFatalError('unrecoverable')`,
+  ])
+  t.not(status, 0)
+  t.regex(stderr.toString('utf8'), /FATAL ERROR: .*fatal\.rs:\d+ unrecoverable/)
+})
+
+test('should trigger uncaughtException from a thread', (t) => {
+  if (napiVersion < 4) {
+    t.pass()
+    return
+  }
+  const { status, stdout } = spawnSync(process.execPath, [
+    '-e',
+    `process.on('uncaughtException', (e) => {
+      console.log(e.message)
+      process.exit(0)
+    })
+    setTimeout(() => process.exit(1), 5000)
+    require(${bindingsPath}).testReportUncaughtExceptionFromThread('error from thread')`,
+  ])
+  t.is(status, 0)
+  t.is(stdout.toString('utf8').trim(), 'error from thread')
+})
@@ -0,0 +1,20 @@
+import test from 'ava'
+
+import { napiVersion } from '../napi-version'
+
+const bindings = require('../../index.node')
+
+test('should create function from closure with captured state', (t) => {
+  if (napiVersion >= 5) {
+    const counter = bindings.testCreateCounterFromClosure(10)
+    t.is(typeof counter, 'function')
+    t.is(counter.name, 'counter')
+    t.is(counter(), 11)
+    t.is(counter(), 12)
+    const another = bindings.testCreateCounterFromClosure(0)
+    t.is(another(), 1)
+    t.is(counter(), 13)
+  } else {
+    t.is(bindings.testCreateCounterFromClosure, undefined)
+  }
+})
@@ -1,5 +1,10 @@
+import { execFileSync } from 'child_process'
+import { join } from 'path'
+
 import test from 'ava'
 
+import { napiVersion } from './napi-version'
+
 const bindings = require('../index.node')
 
 test('should be able to spawn thread and return promise', async (t) => {
@@ -23,3 +28,21 @@ test('should be able to spawn with error', async (t) => {
     t.not(e, err)
   }
 })
+
+test('should be able to spawn thread in dedicated pool', (t) => {
+  if (napiVersion >= 4) {
+    const output = execFileSync(
+      process.execPath,
+      [
+        '-e',
+        `require(${JSON.stringify(
+          join(__dirname, '..', 'index.node'),
+        )}).testSpawnThread(20).then(console.log)`,
+      ],
+      { env: { ...process.env, NAPI_RS_ASYNC_WORK_THREADS: '2' } },
+    )
+    t.is(output.toString('utf8').trim(), '6765')
+  } else {
+    t.pass()
+  }
+})
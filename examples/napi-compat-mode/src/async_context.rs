@@ -0,0 +1,38 @@
+use napi::{
+  AsyncContext, CallContext, ContextlessResult, Env, JsExternal, JsFunction, JsObject, JsUnknown,
+  Result,
+};
+
+#[contextless_function]
+fn create_async_context(env: Env) -> ContextlessResult<JsExternal> {
+  let context = env.create_async_context(None, "napi_rs_test_async_context")?;
+  env.create_external(context, None).map(Some)
+}
+
+#[js_function(2)]
+fn call_in_async_context(ctx: CallContext) -> Result<JsUnknown> {
+  let context_external = ctx.get::<JsExternal>(0)?;
+  let callback = ctx.get::<JsFunction>(1)?;
+  let context = ctx
+    .env
+    .get_value_external::<AsyncContext>(&context_external)?;
+  context.make_callback::<JsUnknown>(None, &callback, &[])
+}
+
+#[js_function(2)]
+fn call_in_callback_scope(ctx: CallContext) -> Result<JsUnknown> {
+  let context_external = ctx.get::<JsExternal>(0)?;
+  let callback = ctx.get::<JsFunction>(1)?;
+  let context = ctx
+    .env
+    .get_value_external::<AsyncContext>(&context_external)?;
+  let _scope = context.enter()?;
+  callback.call_without_args(None)
+}
+
+pub fn register_js(exports: &mut JsObject) -> Result<()> {
+  exports.create_named_method("createAsyncContext", create_async_context)?;
+  exports.create_named_method("callInAsyncContext", call_in_async_context)?;
+  exports.create_named_method("callInCallbackScope", call_in_callback_scope)?;
+  Ok(())
+}
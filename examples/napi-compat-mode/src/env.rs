@@ -59,6 +59,11 @@ fn coerce_to_bool(ctx: CallContext) -> Result<JsBoolean> {
   arg.coerce_to_bool()
 }
 
+#[contextless_function]
+fn has_uv_event_loop(env: Env) -> ContextlessResult<JsBoolean> {
+  env.get_boolean(!env.get_uv_event_loop()?.is_null()).map(Some)
+}
+
 pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("instanceof", instanceof)?;
   exports.create_named_method("isTypedarray", is_typedarray)?;
@@ -68,5 +73,6 @@ pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("getEnvVariable", get_env_variable)?;
   exports.create_named_method("throwSyntaxError", throw_syntax_error)?;
   exports.create_named_method("coerceToBool", coerce_to_bool)?;
+  exports.create_named_method("hasUvEventLoop", has_uv_event_loop)?;
   Ok(())
 }
@@ -1,4 +1,4 @@
-use napi::{CallContext, JsFunction, JsNull, JsObject, Result};
+use napi::{CallContext, JsFunction, JsNull, JsObject, JsString, JsUnknown, Result};
 
 #[js_function(1)]
 pub fn call_function(ctx: CallContext) -> Result<JsNull> {
@@ -32,6 +32,25 @@ pub fn call_function_with_this(ctx: CallContext) -> Result<JsNull> {
   ctx.env.get_null()
 }
 
+#[js_function(2)]
+pub fn apply_function_with_this(ctx: CallContext) -> Result<JsNull> {
+  let js_func = ctx.get::<JsFunction>(0)?;
+  let js_this = ctx.get::<JsUnknown>(1)?;
+
+  js_func.apply(&js_this, &[ctx.env.create_string("hello")?])?;
+
+  ctx.env.get_null()
+}
+
+#[js_function(1)]
+pub fn typed_call_function(ctx: CallContext) -> Result<JsString> {
+  let js_func = ctx.get::<JsFunction>(0)?;
+
+  let ret: String = js_func.typed_call(None, ("hello".to_owned(), 42u32, true))?;
+
+  ctx.env.create_string_from_std(ret)
+}
+
 pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("testCallFunction", call_function)?;
   exports.create_named_method(
@@ -39,5 +58,7 @@ pub fn register_js(exports: &mut JsObject) -> Result<()> {
     call_function_with_ref_arguments,
   )?;
   exports.create_named_method("testCallFunctionWithThis", call_function_with_this)?;
+  exports.create_named_method("testApplyFunctionWithThis", apply_function_with_this)?;
+  exports.create_named_method("testTypedCallFunction", typed_call_function)?;
   Ok(())
 }
@@ -7,6 +7,7 @@ extern crate serde_derive;
 
 use napi::{Env, JsObject, Result};
 
+mod async_context;
 mod cleanup_env;
 #[cfg(feature = "latest")]
 mod napi4;
@@ -38,11 +39,12 @@ mod string;
 mod symbol;
 mod task;
 
-use napi_version::get_napi_version;
+use napi_version::{get_napi_version, get_node_api_version};
 
 #[module_exports]
 fn init(mut exports: JsObject, env: Env) -> Result<()> {
   exports.create_named_method("getNapiVersion", get_napi_version)?;
+  exports.create_named_method("getNodeApiVersion", get_node_api_version)?;
   array::register_js(&mut exports)?;
   error::register_js(&mut exports)?;
   string::register_js(&mut exports)?;
@@ -59,6 +61,7 @@ fn init(mut exports: JsObject, env: Env) -> Result<()> {
   object::register_js(&mut exports)?;
   global::register_js(&mut exports)?;
   cleanup_env::register_js(&mut exports)?;
+  async_context::register_js(&mut exports)?;
   #[cfg(feature = "latest")]
   napi4::register_js(&mut exports, &env)?;
   #[cfg(feature = "latest")]
@@ -0,0 +1,21 @@
+use std::thread;
+
+use napi::{CallContext, Error, JsString, JsUndefined, Result};
+
+#[js_function(1)]
+pub fn test_fatal_error(ctx: CallContext) -> Result<JsUndefined> {
+  let message = ctx.get::<JsString>(0)?.into_utf8()?;
+  napi::fatal_error!("{}", message.as_str()?);
+}
+
+#[js_function(1)]
+pub fn test_report_uncaught_exception_from_thread(ctx: CallContext) -> Result<JsUndefined> {
+  let message = ctx.get::<JsString>(0)?.into_utf8()?.into_owned()?;
+  let reporter = ctx.env.create_uncaught_exception_reporter()?;
+  thread::spawn(move || {
+    reporter
+      .report(Error::from_reason(message))
+      .expect("Failed to report uncaught exception");
+  });
+  ctx.env.get_undefined()
+}
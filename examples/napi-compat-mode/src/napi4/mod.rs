@@ -1,8 +1,10 @@
 use napi::{Env, JsObject, Result};
 
+mod fatal;
 mod tsfn;
 mod tsfn_dua_instance;
 
+use fatal::*;
 use tsfn::*;
 use tsfn_dua_instance::constructor;
 
@@ -23,6 +25,11 @@ pub fn register_js(exports: &mut JsObject, env: &Env) -> Result<()> {
     test_call_aborted_threadsafe_function,
   )?;
   exports.create_named_method("testTsfnWithRef", test_tsfn_with_ref)?;
+  exports.create_named_method("testFatalError", test_fatal_error)?;
+  exports.create_named_method(
+    "testReportUncaughtExceptionFromThread",
+    test_report_uncaught_exception_from_thread,
+  )?;
 
   let obj = env.define_class("A", constructor, &[])?;
 
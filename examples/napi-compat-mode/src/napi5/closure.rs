@@ -0,0 +1,16 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use napi::{CallContext, JsFunction, JsNumber, Result};
+
+#[js_function(1)]
+pub fn test_create_counter_from_closure(ctx: CallContext) -> Result<JsFunction> {
+  let start: u32 = ctx.get::<JsNumber>(0)?.get_uint32()?;
+  let count = Rc::new(Cell::new(start));
+  ctx
+    .env
+    .create_function_from_closure("counter", move |ctx| {
+      count.set(count.get() + 1);
+      ctx.env.create_uint32(count.get())
+    })
+}
@@ -1,10 +1,15 @@
 use napi::{JsObject, Result};
 
+mod closure;
 mod date;
 
 pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("testObjectIsDate", date::test_object_is_date)?;
   exports.create_named_method("testCreateDate", date::test_create_date)?;
   exports.create_named_method("testGetDateValue", date::test_get_date_value)?;
+  exports.create_named_method(
+    "testCreateCounterFromClosure",
+    closure::test_create_counter_from_closure,
+  )?;
   Ok(())
 }
@@ -4,3 +4,8 @@ use napi::{CallContext, JsNumber, Result};
 pub fn get_napi_version(ctx: CallContext) -> Result<JsNumber> {
   ctx.env.create_uint32(ctx.env.get_napi_version()?)
 }
+
+#[js_function]
+pub fn get_node_api_version(ctx: CallContext) -> Result<JsNumber> {
+  ctx.env.create_uint32(ctx.env.node_api_version()?)
+}
@@ -0,0 +1,53 @@
+import { existsSync, readFileSync } from 'fs'
+import { join } from 'path'
+
+import ava from 'ava'
+
+// built for `wasm32-wasip1-threads` by the WASI workflow
+const wasmPath = join(__dirname, '..', 'index.wasm')
+
+const test = existsSync(wasmPath) ? ava : ava.skip
+
+function load() {
+  const { WASI } = require('wasi')
+  const { instantiateNapiModuleSync } = require('@emnapi/core')
+  const { getDefaultContext } = require('@emnapi/runtime')
+
+  const memory = new WebAssembly.Memory({
+    initial: 4000,
+    maximum: 65536,
+    shared: true,
+  })
+  const { napiModule } = instantiateNapiModuleSync(readFileSync(wasmPath), {
+    context: getDefaultContext(),
+    wasi: new WASI({ version: 'preview1', env: process.env, preopens: {} }),
+    overwriteImports(importObject: any) {
+      importObject.env = {
+        ...importObject.env,
+        ...importObject.napi,
+        ...importObject.emnapi,
+        memory,
+      }
+      return importObject
+    },
+    // `ctor` does not run on WebAssembly, the exports are collected by the exported register functions
+    beforeInit({ instance }: { instance: WebAssembly.Instance }) {
+      for (const name of Object.keys(instance.exports)) {
+        if (name.startsWith('__napi_register__')) {
+          ;(instance.exports[name] as () => void)()
+        }
+      }
+    },
+  })
+  return napiModule.exports
+}
+
+test('should register exports of the wasm build', (t) => {
+  const { ANSWER, Direction, sum, Counter } = load()
+  t.is(ANSWER, 42)
+  t.deepEqual(Direction, { Up: 0, Down: 1 })
+  t.is(sum(1, 2), 3)
+  const counter = new Counter(1)
+  t.is(counter.increment(), 2)
+  t.is(counter.count, 2)
+})
@@ -0,0 +1,32 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate napi_derive;
+
+#[napi]
+pub const ANSWER: u32 = 42;
+
+#[napi]
+pub enum Direction {
+  Up,
+  Down,
+}
+
+#[napi]
+pub fn sum(a: u32, b: u32) -> u32 {
+  a + b
+}
+
+#[napi(constructor)]
+pub struct Counter {
+  pub count: u32,
+}
+
+#[napi]
+impl Counter {
+  #[napi]
+  pub fn increment(&mut self) -> u32 {
+    self.count += 1;
+    self.count
+  }
+}
@@ -1,22 +1,76 @@
 import { exec } from 'child_process'
+import { EventEmitter } from 'events'
+import type { LoadAverage } from 'os'
 import { join } from 'path'
 
 import test from 'ava'
 
 import {
   DEFAULT_COST,
+  PACKAGE_VERSION,
+  DEFAULT_LIMITS,
+  exceedsLimits,
+  getThisProperty,
+  getAnimalNameFromThis,
+  renameAnimal,
+  describePet,
+  parseLoadAverage,
+  inspectValue,
   add,
+  addTax,
+  toCents,
+  fromCents,
+  fileRangeEnd,
+  elapsedNanos,
+  totalBytes,
+  rgbToHex,
+  viewportArea,
+  divide,
+  dropDeferredFromThread,
+  sampleRange,
   fibonacci,
   contains,
   concatLatin1,
   concatStr,
   concatUtf16,
+  sanitizeText,
+  uuidVersion,
+  uuidToBytes,
+  uuidFromBytes,
+  splitBytes,
+  cloneBytes,
+  repeatBytes,
+  transposeMatrix,
+  scaleInPlace,
+  totalTimeout,
+  countRouteMethods,
+  describeServer,
   roundtripStr,
+  getStaticLabels,
+  getUnitLabel,
+  Snippet,
+  joinWith,
+  padStart,
   getNums,
   getWords,
+  getSquares,
+  numberedLabels,
+  concatNums,
   sumNums,
+  firstElement,
+  reverseItems,
+  minMax,
+  swapPair,
+  segmentLength,
   getMapping,
   sumMapping,
+  getSortedMapping,
+  getRanks,
+  sumMap,
+  restock,
+  normalizeHeaders,
+  formatMeasurements,
+  isModuleInitialized,
   getCwd,
   Animal,
   Kind,
@@ -24,21 +78,38 @@ import {
   CustomNumEnum,
   Context,
   enumToI32,
+  advanceTaskStatus,
+  shapeArea,
+  scaleShape,
   listObjKeys,
   createObj,
+  createSquares,
+  hideAndReparent,
   mapOption,
+  repeatWithDefault,
   readFile,
   throwError,
+  panicWithMessage,
+  checkConfigEntry,
   readPackageJson,
   getPackageJsonName,
+  stringifyWithEngine,
+  resolveUrl,
+  getSearchParams,
+  setSearchParams,
+  bumpPackageVersion,
   getBuffer,
   readFileAsync,
   eitherStringOrNumber,
   returnEither,
   either3,
   either4,
+  describeDrawing,
+  scaleDrawing,
   withoutAbortController,
   withAbortController,
+  countPrimesBelow,
+  trackedSum,
   asyncMultiTwo,
   bigintAdd,
   createBigInt,
@@ -47,12 +118,23 @@ import {
   callThreadsafeFunction,
   threadsafeFunctionThrowError,
   asyncPlus100,
+  resolveDeferredFromThread,
   getGlobal,
+  getEnvVarFromJs,
+  getNodeApiVersion,
+  requireFromNative,
+  queueMicrotaskFromNative,
+  structuredCloneFromNative,
   getUndefined,
   getNull,
   setSymbolInObj,
   createSymbol,
   threadsafeFunctionFatalMode,
+  threadsafeFunctionQueueFull,
+  tsfnCalleeHandledFromArg,
+  tsfnFatalFromArg,
+  tsfnForwardChannel,
+  tsfnSpreadArgs,
   createExternal,
   getExternal,
   mutateExternal,
@@ -61,35 +143,148 @@ import {
   xxh3,
   xxh64Alias,
   tsRename,
+  overrideIndividualArgOnFunction,
+  parseFlag,
+  makeTagged,
+  runJob,
+  Job,
   convertU32Array,
   createExternalTypedArray,
   mutateTypedArray,
+  createBlobFromChunks,
+  appendToBlob,
+  createFile,
+  renameFile,
   receiveAllOptionalObject,
   fnReceivedAliased,
   ALIAS,
   AliasedStruct,
   appendBuffer,
+  ChunkPool,
   returnNull,
   returnUndefined,
+  findIndex,
+  describeOwner,
+  nullableDouble,
+  normalizeContact,
   Dog,
   Bird,
+  Vehicle,
+  Bicycle,
+  Ticker,
+  ValidationError,
+  Stack,
   Assets,
   receiveStrictObject,
   receiveClassOrNumber,
   JsClassForEither,
   receiveMutClassOrNumber,
   getStrFromObject,
+  sumTypedObjectFields,
+  sumTypedFieldsOfObjects,
+  createLabelledObj,
+  doubleRenamedFields,
+  getPackageInfo,
   returnJsFunction,
+  mapWithCallback,
+  callWithNoArgs,
   testSerdeRoundtrip,
+  sumBinaryPayload,
+  createSnapshot,
+  getSnapshotMillis,
+  serializeReadings,
   createObjWithProperty,
   dateToNumber,
   chronoDateToMillis,
   derefUint8Array,
   chronoDateAdd1Minute,
+  timeDateToMillis,
+  timeDateAdd1Hour,
 } from '../'
 
 test('export const', (t) => {
   t.is(DEFAULT_COST, 12)
+  t.is(PACKAGE_VERSION, '1.0.0')
+  t.deepEqual(DEFAULT_LIMITS, { maxDepth: 8, maxLength: 1024 })
+})
+
+test('receive this', (t) => {
+  const limits = { ...DEFAULT_LIMITS, exceedsLimits }
+  t.false(limits.exceedsLimits(1, 10))
+  t.true(limits.exceedsLimits(9, 10))
+  t.true(limits.exceedsLimits(1, 2048))
+
+  const obj = { name: 'napi', getThisProperty }
+  t.is(obj.getThisProperty('name'), 'napi')
+  t.is(obj.getThisProperty('unknown'), null)
+
+  const animal = new Animal(Kind.Cat, '小喵')
+  t.is(getAnimalNameFromThis.call(animal), '小喵')
+  t.throws(() => getAnimalNameFromThis.call({}))
+})
+
+test('unwrap instances of other classes', (t) => {
+  t.throws(() => getAnimalNameFromThis.call(new Dog('旺财') as any), {
+    message: 'Object is not an instance of class `Animal`',
+  })
+  t.throws(() => getAnimalNameFromThis.call(new Bicycle('brompton') as any), {
+    message: 'Object is not an instance of class `Animal`',
+  })
+  // methods inherited from `Vehicle` unwrap `Bicycle` instances
+  t.is(new Bicycle('brompton').getWheels(), 2)
+})
+
+test('downcast objects to class instances', (t) => {
+  const animal = new Animal(Kind.Cat, '小喵')
+  t.is(renameAnimal(animal, '大喵'), animal)
+  t.is(animal.name, '大喵')
+  t.throws(() => renameAnimal(new Dog('旺财') as any, '大喵'), {
+    message: 'Object is not an instance of class `Animal`',
+  })
+  t.is(describePet(animal), 'animal 大喵')
+  t.is(describePet(new Dog('旺财')), 'dog 旺财')
+  t.is(describePet({ name: 'plain' }), 'unknown')
+})
+
+test('interface declared in module augmentation', (t) => {
+  const loadAverage: LoadAverage | undefined | null = parseLoadAverage(
+    '0.52 0.58 0.59 1/473 12345',
+  )
+  t.deepEqual(loadAverage, { one: 0.52, five: 0.58, fifteen: 0.59 })
+  t.is(parseLoadAverage('0.52 n/a'), null)
+})
+
+test('inspect js values', (t) => {
+  class Point {
+    x = 1
+  }
+  function named() {}
+  const nested = {
+    a: 1,
+    b: "it's",
+    c: [true, null, undefined],
+    d: { e: { f: {} } },
+  }
+  t.is(
+    inspectValue(nested),
+    "{ a: 1, b: 'it\\'s', c: [ true, null, undefined ], d: { e: { f: [Object] } } }",
+  )
+  t.is(inspectValue({ 'a-b': new Point() }), "{ 'a-b': Point { x: 1 } }")
+  t.is(inspectValue(Buffer.from([1, 255])), '<Buffer 01 ff>')
+  t.is(inspectValue(new Uint8Array([1, 2])), 'Uint8Array(2) [ 1, 2 ]')
+  t.is(inspectValue(new TypeError('bad')), '[TypeError: bad]')
+  t.is(inspectValue(named), '[Function: named]')
+  t.is(inspectValue(Symbol('s')), 'Symbol(s)')
+  t.is(inspectValue(BigInt(10)), '10n')
+  t.is(inspectValue(NaN), 'NaN')
+  t.is(
+    inspectValue(Array.from({ length: 20 }, (_, i) => i)),
+    '[ 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, ... 4 more items ]',
+  )
+  t.is(
+    inspectValue('x'.repeat(102)),
+    `'${'x'.repeat(100)}'... 2 more characters`,
+  )
 })
 
 test('number', (t) => {
@@ -104,6 +299,172 @@ test('number', (t) => {
   )
 })
 
+test('decimal', (t) => {
+  t.is(addTax('19.99', 0.2), '23.99')
+  t.is(addTax('0.1', 0), '0.1')
+  t.throws(() => addTax(19.99 as any, 0.2), {
+    code: 'StringExpected',
+    message:
+      'Expected a decimal string to convert into rust type `Decimal`, but received Number',
+  })
+  t.throws(() => addTax('19,99', 0.2), {
+    code: 'InvalidArg',
+    message: /^Failed to convert `19,99` into rust type `Decimal`/,
+  })
+
+  t.is(toCents('12.3'), BigInt(1230))
+  t.is(toCents('-0.01'), BigInt(-1))
+  t.throws(() => toCents('0.001'), {
+    code: 'InvalidArg',
+    message:
+      'Failed to convert `0.001` into a bigint scaled by 10^2, it has more fraction digits',
+  })
+  t.is(fromCents(BigInt(1230)), '12.30')
+  t.is(fromCents(BigInt('-99999999999')), '-999999999.99')
+})
+
+test('large integer policies', (t) => {
+  t.is(fileRangeEnd({ offset: 4096, len: 512 }), 4608)
+  t.is(fileRangeEnd({ offset: 4096 }), 4096)
+  t.throws(() => fileRangeEnd({ offset: Number.MAX_SAFE_INTEGER + 1 }), {
+    instanceOf: RangeError,
+    code: 'InvalidArg',
+    message:
+      'range.offset: Expected a safe integer for `u64`, got 9007199254740992',
+  })
+  t.throws(() => fileRangeEnd({ offset: 0, len: -1 }), {
+    instanceOf: RangeError,
+    message: 'range.len: -1 is out of the range of `u64`',
+  })
+  t.throws(() => fileRangeEnd({ offset: 1.5 }), {
+    instanceOf: RangeError,
+    message: 'range.offset: Expected a safe integer for `u64`, got 1.5',
+  })
+  t.throws(() => fileRangeEnd({ offset: Number.MAX_SAFE_INTEGER, len: 1 }), {
+    instanceOf: RangeError,
+    message: '9007199254740992 of `u64` is beyond Number.MAX_SAFE_INTEGER',
+  })
+
+  t.is(elapsedNanos(1, 5.9), 4)
+  t.is(elapsedNanos(NaN, 3), 3)
+  t.is(elapsedNanos(0, Number.MAX_SAFE_INTEGER * 4), Number.MAX_SAFE_INTEGER)
+  t.is(elapsedNanos(Infinity, -Infinity), -Number.MAX_SAFE_INTEGER)
+
+  t.is(totalBytes(BigInt(4096), BigInt(4096)), BigInt(16777216))
+  t.is(
+    totalBytes(BigInt('18446744073709551615'), BigInt(2)),
+    BigInt('18446744073709551615'),
+  )
+  t.throws(() => totalBytes(BigInt(-1), BigInt(1)), {
+    instanceOf: RangeError,
+    message: '-1 is out of the range of `u64`',
+  })
+  t.throws(() => totalBytes(BigInt(2) ** BigInt(64), BigInt(1)), {
+    instanceOf: RangeError,
+    message: 'BigInt is out of the range of `u64`',
+  })
+})
+
+test('strict numbers', (t) => {
+  t.is(rgbToHex(255, 128, 0), '#ff8000')
+  t.throws(() => rgbToHex(256, 0, 0), {
+    instanceOf: TypeError,
+    code: 'InvalidArg',
+    message: '256 is out of the range of `u8`',
+  })
+  t.throws(() => rgbToHex(0, NaN, 0), {
+    instanceOf: TypeError,
+    message: 'Expected an integer for `u8`, got NaN',
+  })
+  t.throws(() => rgbToHex(0, 0, 1.5), {
+    instanceOf: TypeError,
+    message: 'Expected an integer for `u8`, got 1.5',
+  })
+  t.throws(() => rgbToHex(-Infinity, 0, 0), {
+    instanceOf: TypeError,
+    message: 'Expected an integer for `u8`, got -Infinity',
+  })
+
+  t.is(viewportArea({ width: 1920, height: 1080 }), 2073600)
+  t.is(viewportArea({ width: 1920, height: 1080, zoom: -2 }), 2073600)
+  t.throws(() => viewportArea({ width: -1, height: 1080 }), {
+    instanceOf: TypeError,
+    message: 'viewport.width: -1 is out of the range of `u16`',
+  })
+  t.throws(() => viewportArea({ width: 1920, height: 1080, zoom: 128 }), {
+    instanceOf: TypeError,
+    message: 'viewport.zoom: 128 is out of the range of `i8`',
+  })
+})
+
+test('non-finite numbers', (t) => {
+  t.is(divide(1, 4), 0.25)
+  t.throws(() => divide(1, 0), {
+    instanceOf: TypeError,
+    code: 'InvalidArg',
+    message: 'Cannot convert Infinity of `f64` into a JavaScript number',
+  })
+  t.throws(() => divide(NaN, 1), {
+    instanceOf: TypeError,
+    message: 'Expected a finite number for `f64`, got NaN',
+  })
+
+  t.deepEqual(sampleRange([3, -1.5, 8]), { min: -1.5, max: 8 })
+  t.deepEqual(sampleRange([]), { min: null, max: null })
+
+  t.deepEqual(serializeReadings([1.5, NaN, -Infinity], false), [
+    1.5,
+    null,
+    null,
+  ])
+  t.throws(() => serializeReadings([1.5, NaN], true), {
+    instanceOf: TypeError,
+    message: 'Cannot convert NaN of `f64` into a JavaScript number',
+  })
+})
+
+test('strict arguments validation', (t) => {
+  t.is(padStart('1', 3, '0'), '001')
+  t.is(padStart('1', 3), '  1')
+
+  t.throws(
+    // @ts-expect-error
+    () => padStart('1'),
+    {
+      instanceOf: TypeError,
+      code: 'InvalidArg',
+      message: 'padStart expects at least 2 arguments, but received 1',
+    },
+  )
+  t.throws(
+    // @ts-expect-error
+    () => padStart('1', '3'),
+    {
+      instanceOf: TypeError,
+      code: 'InvalidArg',
+      message: 'expected number at argument 2 of padStart, but received string',
+    },
+  )
+  t.throws(
+    // @ts-expect-error
+    () => padStart('1', 3, 0),
+    {
+      instanceOf: TypeError,
+      message:
+        'expected string or undefined or null at argument 3 of padStart, but received number',
+    },
+  )
+  t.throws(
+    // @ts-expect-error
+    () => fibonacci(''),
+    {
+      instanceOf: TypeError,
+      message:
+        'expected number at argument 1 of fibonacci, but received string',
+    },
+  )
+})
+
 test('string', (t) => {
   t.true(contains('hello', 'ell'))
   t.false(contains('John', 'jn'))
@@ -114,10 +475,41 @@ test('string', (t) => {
     concatUtf16('JavaScript 🌳 你好 napi'),
     'JavaScript 🌳 你好 napi + Rust 🦀 string!',
   )
+  // lone surrogates are rejected, replaced silently or replaced and reported
+  t.throws(() => concatUtf16('broken \ud83e'), {
+    code: 'InvalidArg',
+    message: /^Failed to read utf16 string/,
+  })
+  t.is(concatStr('broken \ud83e'), 'broken \ufffd + Rust 🦀 string!')
+  t.deepEqual(sanitizeText('broken \ud83e text'), ['broken \ufffd text', true])
+  t.deepEqual(sanitizeText('whole 🦀'), ['whole 🦀', false])
   t.is(
     roundtripStr('what up?!\u0000after the NULL'),
     'what up?!\u0000after the NULL',
   )
+  // ASCII, latin1 and the rest of UTF-8 are created through different paths
+  t.is(roundtripStr('plain ascii'), 'plain ascii')
+  t.is(roundtripStr('ÿ latin1 ©'), 'ÿ latin1 ©')
+  t.is(roundtripStr('é'.repeat(100)), 'é'.repeat(100))
+  t.is(roundtripStr('mixed ÿ 🦀'), 'mixed ÿ 🦀')
+  t.deepEqual(getStaticLabels(), ['pending', 'café', '🦀 done'])
+})
+
+test('borrowed return values', (t) => {
+  t.is(getUnitLabel(1), 'item')
+  t.is(getUnitLabel(2), 'items')
+  const snippet = new Snippet('first\tline\nsecond')
+  t.is(snippet.firstLine(), 'first\tline')
+  t.is(snippet.expandTabs(), 'first  line\nsecond')
+  t.is(new Snippet('no tabs').expandTabs(), 'no tabs')
+  t.deepEqual(snippet.asBytes(), Buffer.from('first\tline\nsecond'))
+})
+
+test('variadic arguments', (t) => {
+  t.is(joinWith(', '), '')
+  t.is(joinWith(', ', 'a'), 'a')
+  t.is(joinWith('-', 'a', 1, 'b', 2, 'c'), 'a-1-b-2-c')
+  t.throws(() => joinWith('-', 'a', {} as any))
 })
 
 test('array', (t) => {
@@ -127,16 +519,136 @@ test('array', (t) => {
   t.is(sumNums([1, 2, 3, 4, 5]), 15)
 })
 
+test('bulk array construction', (t) => {
+  t.deepEqual(getSquares(0), [])
+  t.deepEqual(getSquares(4), [0, 1, 4, 9])
+  const squares = getSquares(1000)
+  t.true(Array.isArray(squares))
+  t.is(squares.length, 1000)
+  t.is(squares[999], 998001)
+  t.deepEqual(numberedLabels('item', 3), ['item1', 'item2', 'item3'])
+  t.deepEqual(concatNums([1, 2], [3]), [1, 2, 3])
+  t.deepEqual(concatNums([], []), [])
+})
+
+test('generic functions', (t) => {
+  const obj = { three: 3 }
+  t.is(firstElement([1, 'two']), 1)
+  t.is(firstElement(['one']), 'one')
+  t.is(firstElement([]), null)
+  const reversed: Array<string | number | { three: number }> = reverseItems([
+    1,
+    'two',
+    obj,
+  ])
+  t.deepEqual(reversed, [obj, 'two', 1])
+  t.is(reversed[0], obj)
+})
+
+test('tuple', (t) => {
+  t.deepEqual(minMax([3, -1, 7, 2]), [-1, 7])
+  t.is(minMax([]), null)
+  t.deepEqual(swapPair(['answer', 42]), [42, 'answer'])
+  t.is(segmentLength({ start: [1, 1], end: [4, 5] }), 5)
+  t.throws(
+    // @ts-expect-error
+    () => swapPair(['answer']),
+    {
+      code: 'InvalidArg',
+      message:
+        'Expected a tuple of length 2, but received an array of length 1',
+    },
+  )
+})
+
 test('map', (t) => {
   t.deepEqual(getMapping(), { a: 101, b: 102 })
   t.is(sumMapping({ a: 101, b: 102 }), 203)
 })
 
+test('map as es Map', (t) => {
+  t.deepEqual(getSortedMapping(), { a: 101, b: 102 })
+  const ranks = getRanks()
+  t.true(ranks instanceof Map)
+  t.deepEqual(
+    [...ranks],
+    [
+      [1, 'gold'],
+      [2, 'silver'],
+    ],
+  )
+  t.is(sumMap(new Map(Object.entries({ a: 101, b: 102 }))), 203)
+  t.throws(() => sumMap({ a: 101 } as any), {
+    code: 'InvalidArg',
+    message: 'Expected a Map, but received Object',
+  })
+  const inventory = restock(
+    { name: 'store', stock: new Map([[1, 2]]) },
+    1,
+    3,
+  )
+  t.is(inventory.name, 'store')
+  t.deepEqual([...inventory.stock], [[1, 5]])
+  t.throws(
+    () => restock({ name: 'store', stock: new Map([['1', 2]]) as any }, 1, 3),
+    {
+      code: 'InvalidArg',
+      message:
+        'inventory.stock: Invalid key of Map, Failed to convert napi value into rust type `u32`',
+    },
+  )
+})
+
+test('object with index signature', (t) => {
+  t.deepEqual(
+    normalizeHeaders({ host: 'example.com', Accept: ' text/html ' }),
+    { host: 'example.com', accept: 'text/html' },
+  )
+  t.deepEqual(normalizeHeaders({ host: 'example.com' }), {
+    host: 'example.com',
+  })
+  t.throws(() => normalizeHeaders({ host: 'example.com', port: 80 } as any), {
+    code: 'StringExpected',
+  })
+  t.deepEqual(
+    formatMeasurements({ unit: 'ms', precision: 1, p50: 12.34, p99: 80 }),
+    ['p50: 12.3 ms', 'p99: 80.0 ms'],
+  )
+  t.deepEqual(formatMeasurements({ unit: 'ms', p50: 12.34 }), ['p50: 12 ms'])
+})
+
+test('module init', (t) => {
+  t.true(isModuleInitialized())
+})
+
 test('enum', (t) => {
   t.deepEqual([Kind.Dog, Kind.Cat, Kind.Duck], [0, 1, 2])
   t.is(enumToI32(CustomNumEnum.Eight), 8)
 })
 
+test('string enum', (t) => {
+  t.is(advanceTaskStatus('pending'), 'in-progress')
+  t.is(advanceTaskStatus('in-progress'), 'done')
+  t.throws(() => advanceTaskStatus('unknown' as any), {
+    code: 'InvalidArg',
+    message: 'value `unknown` does not match any variant of enum `TaskStatus`',
+  })
+})
+
+test('tagged union enum', (t) => {
+  t.is(shapeArea({ type: 'Rectangle', width: 2, height: 3 }), 6)
+  t.is(shapeArea({ type: 'Empty' }), 0)
+  t.deepEqual(scaleShape({ type: 'Circle', radius: 1 }, 2), {
+    type: 'Circle',
+    radius: 2,
+  })
+  t.deepEqual(scaleShape({ type: 'Empty' }, 2), { type: 'Empty' })
+  t.throws(() => shapeArea({ type: 'Triangle' } as any), {
+    code: 'InvalidArg',
+    message: 'value `Triangle` does not match any variant of enum `Shape`',
+  })
+})
+
 test('class', (t) => {
   const dog = new Animal(Kind.Dog, '旺财')
 
@@ -188,6 +700,82 @@ test('class Factory return Result', (t) => {
   t.is(c.method(), 'not empty')
 })
 
+test('class async Factory', async (t) => {
+  const c = await Context.withDataAsync('from async factory')
+  t.true(c instanceof Context)
+  t.is(c.method(), 'from async factory')
+  t.true(c.maybeNeed)
+  await t.throwsAsync(() => Context.withDataAsync(''), {
+    code: 'InvalidArg',
+    message: 'Context data must not be empty',
+  })
+})
+
+test('class extends class', (t) => {
+  const bicycle = new Bicycle('Brompton')
+  t.true(bicycle instanceof Vehicle)
+  t.is(bicycle.getWheels(), 2)
+  t.is(bicycle.brand, 'Brompton')
+  t.is(bicycle.ring(), 'Brompton on 2 wheels: ring ring')
+  t.is(new Vehicle(4).getWheels(), 4)
+  t.is(Vehicle.maxWheels, 18)
+})
+
+test('class constants', (t) => {
+  t.is(Vehicle.CAR_WHEELS, 4)
+  t.is(Vehicle.DEFAULT_KIND, 'vehicle')
+  t.is(new Vehicle(Vehicle.CAR_WHEELS).getWheels(), 4)
+  // static members are inherited by subclasses
+  t.is(Bicycle.CAR_WHEELS, 4)
+})
+
+test('class extends Error', (t) => {
+  const err = new ValidationError('name is required', 'name')
+  t.true(err instanceof Error)
+  t.is(err.message, 'name is required')
+  t.is(err.field, 'name')
+})
+
+test('class extends JavaScript class', (t) => {
+  const ticker = new Ticker()
+  t.true(ticker instanceof EventEmitter)
+  let received = 0
+  ticker.on('tick', (count: number) => {
+    received = count
+  })
+  ticker.emit('tick', ticker.tick())
+  t.is(received, 1)
+})
+
+test('emit events from Rust', async (t) => {
+  const ticker = new Ticker()
+  const received: number[] = []
+  ticker.on('tick', (count: number) => received.push(count))
+  t.true(ticker.tickAndEmit())
+  t.deepEqual(received, [1])
+  t.false(new Ticker().tickAndEmit())
+  await new Promise<void>((resolve) => {
+    ticker.on('tick', (count: number) => count === 3 && resolve())
+    ticker.tickInBackground(3)
+  })
+  t.deepEqual(received, [1, 1, 2, 3])
+})
+
+test('generic class', (t) => {
+  const stack = new Stack<number | string | { three: number }>()
+  const obj = { three: 3 }
+  t.is(stack.push(1), 1)
+  t.is(stack.push('two'), 2)
+  t.is(stack.push(obj), 3)
+  t.is(stack.size, 3)
+  t.is(stack.peek(), obj)
+  t.is(stack.pop(), obj)
+  t.is(stack.pop(), 'two')
+  t.is(stack.pop(), 1)
+  t.is(stack.pop(), null)
+  t.is(stack.size, 0)
+})
+
 test('callback', (t) => {
   getCwd((cwd) => {
     t.is(cwd, process.cwd())
@@ -206,6 +794,21 @@ test('callback', (t) => {
   })
 })
 
+test('typed function', (t) => {
+  t.deepEqual(
+    mapWithCallback([3, 2, 1], (value, index) => `${index}: ${value}`),
+    ['0: 3', '1: 2', '2: 1'],
+  )
+  // the returned value must be a string
+  t.throws(() => mapWithCallback([1], (() => 1) as any))
+
+  let called = false
+  callWithNoArgs(() => {
+    called = true
+  })
+  t.true(called)
+})
+
 test('return function', (t) => {
   return new Promise<void>((resolve) => {
     returnJsFunction()((err: Error | undefined, content: string) => {
@@ -221,10 +824,87 @@ test('object', (t) => {
   t.deepEqual(createObj(), { test: 1 })
 })
 
+test('reflect and prototype helpers', (t) => {
+  const tag = Symbol('tag')
+  const proto = { greet: () => 'hi' }
+  const obj: any = { visible: 1, [tag]: true }
+  t.deepEqual(hideAndReparent(obj, 'hidden', 2, proto), [
+    'visible',
+    'hidden',
+    tag,
+  ])
+  t.is(obj.hidden, 2)
+  t.deepEqual(Object.keys(obj), ['visible'])
+  t.is(Object.getPrototypeOf(obj), proto)
+  t.is(obj.greet(), 'hi')
+
+  const bare = {}
+  hideAndReparent(bare, 'hidden', 1)
+  t.is(Object.getPrototypeOf(bare), null)
+  t.throws(() => hideAndReparent(Object.freeze({}), 'hidden', 1), {
+    message: 'Can not define `hidden`',
+  })
+})
+
+test('proxy with traps in Rust', (t) => {
+  const squares = createSquares(4)
+  t.is(squares[3], 9)
+  t.is(squares[4], undefined)
+  // properties without a value from the trap are read from the target
+  t.is(String(squares), '[object Object]')
+  t.is(Object.getOwnPropertyDescriptor(squares, 'length'), undefined)
+  t.true(2 in squares)
+  t.false('length' in squares)
+  t.deepEqual(Object.keys(squares), ['0', '1', '2', '3'])
+  t.deepEqual({ ...squares }, { 0: 0, 1: 1, 2: 4, 3: 9 })
+})
+
 test('get str from object', (t) => {
   t.notThrows(() => getStrFromObject())
 })
 
+test('get typed fields from object', (t) => {
+  t.is(sumTypedObjectFields({ a: 1, b: 2 }), 3)
+  t.is(sumTypedObjectFields({ a: 1 }), 1)
+  t.is(sumTypedObjectFields({ a: 1, b: null }), 1)
+  t.is(sumTypedObjectFields({}), 0)
+})
+
+test('get typed fields with cached keys', (t) => {
+  t.is(sumTypedFieldsOfObjects([{ a: 1, b: 2 }, { a: 3 }, { b: null }, {}]), 4)
+  t.is(sumTypedFieldsOfObjects([]), 0)
+})
+
+test('rename all object fields', (t) => {
+  t.deepEqual(doubleRenamedFields({ MAX_RETRIES: 3, timeoutMs: 100 }), {
+    MAX_RETRIES: 6,
+    timeoutMs: 200,
+  })
+})
+
+test('readonly object fields', (t) => {
+  const info = getPackageInfo()
+  t.deepEqual(info, { name: 'napi-examples', version: '1.0.0' })
+  t.false(Object.getOwnPropertyDescriptor(info, 'name')!.writable)
+  t.true(Object.getOwnPropertyDescriptor(info, 'version')!.writable)
+  t.throws(
+    () => {
+      // @ts-expect-error
+      info.name = 'renamed'
+    },
+    { instanceOf: TypeError },
+  )
+  t.is(info.name, 'napi-examples')
+})
+
+test('object builder', (t) => {
+  const obj = createLabelledObj(1, 'first')
+  t.deepEqual(obj, { id: 1, label: 'first' })
+  t.deepEqual(Object.keys(obj), ['id', 'label'])
+  t.false(Object.getOwnPropertyDescriptor(obj, 'id')!.writable)
+  t.true(Object.getOwnPropertyDescriptor(obj, 'label')!.writable)
+})
+
 test('create object from Property', (t) => {
   const obj = createObjWithProperty()
   t.true(obj.value instanceof ArrayBuffer)
@@ -235,6 +915,45 @@ test('global', (t) => {
   t.is(getGlobal(), global)
 })
 
+test('get env var through process.env', (t) => {
+  process.env.NAPI_RS_ENV_VAR_FROM_JS = 'from js'
+  t.is(getEnvVarFromJs('NAPI_RS_ENV_VAR_FROM_JS'), 'from js')
+  delete process.env.NAPI_RS_ENV_VAR_FROM_JS
+  t.is(getEnvVarFromJs('NAPI_RS_ENV_VAR_FROM_JS'), null)
+})
+
+test('require from native', (t) => {
+  t.is(requireFromNative('path'), require('path'))
+})
+
+test('conditional exports by Node-API version', (t) => {
+  t.is(getNodeApiVersion(), Number(process.versions.napi))
+  t.false('unsupportedNapiVersionFn' in require('../'))
+})
+
+test('queue microtask from native', async (t) => {
+  const order: string[] = []
+  const microtask = new Promise<void>((resolve) => {
+    queueMicrotaskFromNative(() => {
+      order.push('microtask')
+      resolve()
+    })
+  })
+  order.push('sync')
+  await microtask
+  t.deepEqual(order, ['sync', 'microtask'])
+})
+
+const StructuredCloneTest =
+  typeof (global as any).structuredClone === 'function' ? test : test.skip
+
+StructuredCloneTest('structured clone from native', (t) => {
+  const fixture = { a: [1, 2, { b: new Date(0) }], c: new Map([['d', 1]]) }
+  const cloned = structuredCloneFromNative(fixture)
+  t.not(cloned, fixture)
+  t.deepEqual(cloned, fixture)
+})
+
 test('get undefined', (t) => {
   for (const _ of Array.from({ length: 100 })) {
     t.is(getUndefined(), undefined)
@@ -268,16 +987,159 @@ test('create symbol', (t) => {
 test('Option', (t) => {
   t.is(mapOption(null), null)
   t.is(mapOption(3), 4)
+  t.is(mapOption(), null)
+})
+
+test('null or undefined as None', (t) => {
+  t.is(findIndex(['a', 'b'], 'b'), 1)
+  t.is(findIndex(['a', 'b'], 'c'), undefined)
+  t.is(describeOwner('Ann'), 'Ann')
+  t.is(describeOwner(null), 'nobody')
+  // `null` is required, omitting the argument is rejected
+  t.throws(() => (describeOwner as any)(), {
+    code: 'InvalidArg',
+    message: 'Value is undefined, expected a value or null',
+  })
+  t.is(nullableDouble(2), 4)
+  t.is(nullableDouble(null), null)
+  t.throws(() => nullableDouble(undefined as any), {
+    code: 'InvalidArg',
+    message: 'Value is undefined, expected a value or null',
+  })
+  const contact = normalizeContact({ name: ' Ann ', email: null })
+  t.deepEqual(contact, { name: 'Ann', email: null })
+  t.false('phone' in contact)
+  t.deepEqual(
+    normalizeContact({ name: 'Ann', email: 'ANN@X.IO', phone: '1' }),
+    { name: 'Ann', email: 'ann@x.io', phone: '1' },
+  )
+  t.throws(() => normalizeContact({ name: 'Ann' } as any), {
+    code: 'InvalidArg',
+    message: 'contact.email: Value is undefined, expected a value or null',
+  })
+  t.throws(
+    () => normalizeContact({ name: 'Ann', email: null, phone: null as any }),
+    {
+      code: 'InvalidArg',
+      message: 'contact.phone: Value is null, expected a value or undefined',
+    },
+  )
+})
+
+test('default value of argument', (t) => {
+  t.is(repeatWithDefault('a'), 'aa')
+  t.is(repeatWithDefault('a', 3), 'aaa')
+  t.is(repeatWithDefault('a', undefined, '-'), 'a-a')
+  t.is(repeatWithDefault('a', 3, '-'), 'a-a-a')
 })
 
 test('Result', (t) => {
   t.throws(() => throwError(), void 0, 'Manual Error')
 })
 
+test('convert panic into Error with catch_unwind', (t) => {
+  const err = t.throws(() => panicWithMessage('Something went wrong'))
+  t.regex(
+    err!.message,
+    /^Rust panicked at .*error\.rs:\d+:\d+: Something went wrong$/,
+  )
+})
+
+test('custom error type', (t) => {
+  t.is(checkConfigEntry('retries', 3), 3)
+  t.throws(() => checkConfigEntry('timeout', 1), {
+    instanceOf: Error,
+    code: 'ERR_UNKNOWN_KEY',
+    message: 'unknown config key `timeout`',
+  })
+  const err = t.throws<RangeError & { key: string; maxValue: number }>(
+    () => checkConfigEntry('retries', 11),
+    {
+      instanceOf: RangeError,
+      code: 'OutOfRange',
+      message: '`retries` must be at most 10',
+    },
+  )
+  t.is(err!.key, 'retries')
+  t.is(err!.maxValue, 10)
+  t.throws(() => checkConfigEntry('locked', 1), {
+    code: 'Locked',
+    message: 'config is locked',
+  })
+})
+
 test('function ts type override', (t) => {
   t.deepEqual(tsRename({ foo: 1, bar: 2, baz: 2 }), ['foo', 'bar', 'baz'])
 })
 
+test('override individual arg type', (t) => {
+  t.is(overrideIndividualArgOnFunction('width: ', '10px', 1), 'width: 10px1')
+})
+
+test('hand written type definitions', (t) => {
+  const yes: boolean = parseFlag('yes')
+  t.true(yes)
+  t.false(parseFlag('no'))
+  t.is(parseFlag('maybe'), null)
+  t.deepEqual(makeTagged('user'), { tag: 'user' })
+})
+
+test('interface implemented by js object', (t) => {
+  const progress: number[] = []
+  const reporter = {
+    report: (percent: number) => {
+      progress.push(percent)
+    },
+    isCancelled: () => progress.length >= 3,
+  }
+  t.is(runJob(4, reporter), 3)
+  t.deepEqual(progress, [25, 50, 75])
+  class Reporter {
+    reported = 0
+    report() {
+      this.reported++
+    }
+    isCancelled() {
+      return false
+    }
+  }
+  const instance = new Reporter()
+  t.is(runJob(2, instance), 2)
+  t.is(instance.reported, 2)
+  const err = new Error('cancel failed')
+  t.throws(
+    () =>
+      runJob(1, {
+        report: () => {},
+        isCancelled: () => {
+          throw err
+        },
+      }),
+    { is: err },
+  )
+  t.throws(
+    // @ts-expect-error
+    () => runJob(1, { report: () => {} }),
+    {
+      code: 'FunctionExpected',
+      message: 'ProgressReporter.isCancelled is not a function',
+    },
+  )
+})
+
+test('interface kept between calls', (t) => {
+  const progress: number[] = []
+  const job = new Job({
+    report: (step: number) => {
+      progress.push(step)
+    },
+    isCancelled: () => false,
+  })
+  t.is(job.step(), 1)
+  t.is(job.step(), 2)
+  t.deepEqual(progress, [1, 2])
+})
+
 test('option object', (t) => {
   t.notThrows(() => receiveAllOptionalObject())
   t.notThrows(() => receiveAllOptionalObject({}))
@@ -286,12 +1148,89 @@ test('option object', (t) => {
 test('should throw if object type is not matched', (t) => {
   // @ts-expect-error
   const err1 = t.throws(() => receiveStrictObject({ name: 1 }))
-  t.is(err1!.message, 'Failed to convert napi `string` into rust type `String`')
+  t.is(
+    err1!.message,
+    'strictObject.name: Failed to convert napi `string` into rust type `String`',
+  )
   // @ts-expect-error
   const err2 = t.throws(() => receiveStrictObject({ bar: 1 }))
   t.is(err2!.message, 'Missing field `name`')
 })
 
+test('property path of conversion errors', (t) => {
+  const options = {
+    endpoint: 'x',
+    retry: { maxAttempts: 3 },
+    timeouts: [1, 2],
+  }
+  t.is(totalTimeout(options), 9)
+  t.throws(
+    () => totalTimeout({ ...options, retry: { maxAttempts: '3' as any } }),
+    { instanceOf: TypeError, message: /^options\.retry\.maxAttempts: / },
+  )
+  t.throws(() => totalTimeout({ ...options, timeouts: [1, '2' as any] }), {
+    instanceOf: TypeError,
+    message: /^options\.timeouts\[1\]: /,
+  })
+  t.throws(() => totalTimeout({ ...options, retry: {} as any }), {
+    instanceOf: TypeError,
+    message: 'options.retry: Missing field `maxAttempts`',
+  })
+
+  const config = { port: 80, routes: [{ path: '/', methods: ['GET'] }] }
+  t.is(countRouteMethods(config), 1)
+  t.throws(
+    () =>
+      countRouteMethods({
+        ...config,
+        routes: [...config.routes, { path: '/a', methods: [1 as any] }],
+      }),
+    {
+      instanceOf: TypeError,
+      message: /^routes\[1\]\.methods\[0\]: invalid type/,
+    },
+  )
+  t.throws(
+    () => countRouteMethods({ port: 80, routes: [{ path: '/' } as any] }),
+    { instanceOf: TypeError, message: 'routes[0]: missing field `methods`' },
+  )
+})
+
+test('aggregated object conversion errors', (t) => {
+  t.is(
+    describeServer({ host: 'localhost', port: 80, retry: { maxAttempts: 3 } }),
+    'localhost:80 (3 attempts)',
+  )
+  const err = t.throws(
+    () =>
+      describeServer({
+        host: 1 as any,
+        port: 'x' as any,
+        retry: { maxAttempts: 'a' as any },
+      }),
+    { instanceOf: TypeError, code: 'InvalidArg', message: /^3 errors: / },
+  )
+  const errors: Error[] = (err as any).errors
+  t.is(errors.length, 3)
+  t.true(errors.every((e) => e instanceof TypeError))
+  t.regex(errors[0].message, /^options\.host: /)
+  t.regex(errors[1].message, /^options\.port: /)
+  t.regex(errors[2].message, /^options\.retry\.maxAttempts: /)
+  t.is(err!.message, `3 errors: ${errors.map((e) => e.message).join('; ')}`)
+
+  const missing = t.throws(() => describeServer({} as any), {
+    instanceOf: TypeError,
+  })
+  t.deepEqual(
+    (missing as any).errors.map((e: Error) => e.message),
+    [
+      'Missing field `host`',
+      'Missing field `port`',
+      'Missing field `retry`',
+    ],
+  )
+})
+
 test('aliased rust struct and enum', (t) => {
   const a: ALIAS = ALIAS.A
   const b: AliasedStruct = {
@@ -311,6 +1250,72 @@ test('serde-json', (t) => {
   t.is(getPackageJsonName(packageJson), 'napi-rs')
 })
 
+test('uuid interop', (t) => {
+  const id = '67e55044-10b1-426f-9247-bb680e5fe0c8'
+  t.is(uuidVersion(id), 4)
+  t.is(uuidVersion(id.toUpperCase().replace(/-/g, '')), 4)
+  t.throws(() => uuidVersion('not-a-uuid'), {
+    code: 'InvalidArg',
+    message: /^Invalid UUID `not-a-uuid`/,
+  })
+
+  const bytes = uuidToBytes(id)
+  t.true(Buffer.isBuffer(bytes))
+  t.is(Buffer.from(bytes).toString('hex'), id.replace(/-/g, ''))
+  t.is(uuidFromBytes(new Uint8Array(bytes)), id)
+  t.throws(() => uuidFromBytes(new Uint8Array(4)), {
+    code: 'InvalidArg',
+    message: 'Expected 16 bytes of UUID, but received 4 bytes',
+  })
+})
+
+test('url interop', (t) => {
+  const resolved = resolveUrl('https://napi.rs/docs/', '../concepts?a=1')
+  t.true(resolved instanceof URL)
+  t.is(resolved.href, 'https://napi.rs/concepts?a=1')
+  t.is(resolveUrl(new URL('file:///tmp/'), 'a b').href, 'file:///tmp/a%20b')
+  t.throws(() => resolveUrl('not a url', 'a'), {
+    code: 'InvalidArg',
+    message: /^Invalid URL `not a url`/,
+  })
+  t.throws(() => getSearchParams('https://napi.rs' as any), {
+    code: 'InvalidArg',
+    message: 'Expected a URL, but received String',
+  })
+
+  const params = getSearchParams(new URL('https://napi.rs/?q=a+b&tag=x&tag=y'))
+  t.true(params instanceof URLSearchParams)
+  t.deepEqual(
+    [...params],
+    [
+      ['q', 'a b'],
+      ['tag', 'x'],
+      ['tag', 'y'],
+    ],
+  )
+  const url = setSearchParams(
+    new URL('https://napi.rs/?old=1'),
+    new URLSearchParams([['q', '1 & 2']]),
+  )
+  t.is(url.search, '?q=1+%26+2')
+})
+
+test('engine JSON interop', (t) => {
+  t.is(stringifyWithEngine({ a: [1, 'b'] }), '{"a":[1,"b"]}')
+  t.is(stringifyWithEngine(undefined), null)
+  t.is(stringifyWithEngine(() => {}), null)
+  t.throws(() => stringifyWithEngine(BigInt(1)))
+
+  const bumped = bumpPackageVersion({
+    name: 'pkg',
+    version: '1.0.0',
+    dependencies: { toJSON: () => ({ napi: '2' }) } as any,
+  })
+  t.is(bumped.version, '1.0.0-next')
+  t.deepEqual(bumped.dependencies, { napi: '2' })
+  t.is(bumped.devDependencies, null)
+})
+
 test('serde-roundtrip', (t) => {
   t.is(testSerdeRoundtrip(1), 1)
   t.is(testSerdeRoundtrip(1.2), 1.2)
@@ -339,6 +1344,44 @@ test('serde-roundtrip', (t) => {
   t.is(err!.message, 'JS symbols cannot be represented as a serde_json::Value')
 })
 
+test('serde dates and large integers', (t) => {
+  const snapshot = createSnapshot()
+  t.true(snapshot.takenAt instanceof Date)
+  t.is(snapshot.takenAt.getTime(), 1650000000000)
+  t.is(snapshot.sequence, BigInt(2 ** 53) + BigInt(1))
+  t.is(snapshot.total, 42)
+  t.is(getSnapshotMillis(snapshot), 1650000000000)
+  t.is(
+    getSnapshotMillis({ takenAt: new Date(1000), sequence: 1, total: 2 }),
+    1000,
+  )
+  t.is(
+    getSnapshotMillis({
+      takenAt: new Date(2000),
+      sequence: BigInt(-7),
+      total: BigInt(0),
+    }),
+    2000,
+  )
+})
+
+test('serde typed arrays', (t) => {
+  t.is(
+    sumBinaryPayload({
+      header: new Uint8Array([1, 2, 3]),
+      samples: new Float64Array([0.5, 1.5]),
+    }),
+    8,
+  )
+  t.is(
+    sumBinaryPayload({
+      header: Buffer.from([255]),
+      samples: new Float64Array(new ArrayBuffer(16), 8, 1).fill(1),
+    }),
+    256,
+  )
+})
+
 test('buffer', (t) => {
   let buf = getBuffer()
   t.is(buf.toString('utf-8'), 'Hello world')
@@ -346,6 +1389,16 @@ test('buffer', (t) => {
   t.is(buf.toString('utf-8'), 'Hello world!')
 })
 
+test('pooled buffer', (t) => {
+  const pool = new ChunkPool(4)
+  t.deepEqual(pool.chunk(4, 7), Buffer.from([7, 7, 7, 7]))
+  t.deepEqual(pool.chunk(0, 7), Buffer.from([]))
+  const arrayBuffer = pool.arrayBufferChunk(8)
+  t.true(arrayBuffer instanceof ArrayBuffer)
+  t.deepEqual(new Uint8Array(arrayBuffer), new Uint8Array(8))
+  t.true(pool.idleCount <= 4)
+})
+
 test('convert typedarray to vec', (t) => {
   const input = new Uint32Array([1, 2, 3, 4, 5])
   t.deepEqual(convertU32Array(input), Array.from(input))
@@ -368,6 +1421,49 @@ test('deref uint8 array', (t) => {
   )
 })
 
+const BlobTest = typeof (global as any).Blob === 'function' ? test : test.skip
+
+BlobTest('create Blob from chunks', async (t) => {
+  const blob = createBlobFromChunks(['hello', ' ', 'world'], 'text/plain')
+  t.true(blob instanceof Blob)
+  t.is(blob.type, 'text/plain')
+  t.is(blob.size, 11)
+  t.is(await blob.text(), 'hello world')
+})
+
+test('receive Blob', async (t) => {
+  const blob = appendToBlob(new Blob(['hello'], { type: 'text/plain' }), '!')
+  t.is(blob.type, 'text/plain')
+  t.is(blob.size, 6)
+  t.is(await blob.text(), 'hello!')
+  t.throws(() => appendToBlob(Buffer.from('hello') as any, '!'), {
+    code: 'InvalidArg',
+    message: 'Expected a Blob, but received Object',
+  })
+})
+
+const FileTest = typeof (global as any).File === 'function' ? test : test.skip
+
+FileTest('create File', async (t) => {
+  const file = createFile('hello.txt', Buffer.from('hello'))
+  t.true(file instanceof File)
+  t.is(file.name, 'hello.txt')
+  t.is(file.type, 'text/plain')
+  t.is(file.lastModified, 0)
+  t.is(await file.text(), 'hello')
+})
+
+FileTest('receive File', async (t) => {
+  const file = renameFile(
+    new File(['hello'], 'hello.txt', { type: 'text/plain', lastModified: 42 }),
+    'world.txt',
+  )
+  t.is(file.name, 'world.txt')
+  t.is(file.type, 'text/plain')
+  t.is(file.lastModified, 42)
+  t.is(await file.text(), 'hello')
+})
+
 test('async', async (t) => {
   const bufPromise = readFileAsync(join(__dirname, '../package.json'))
   await t.notThrowsAsync(bufPromise)
@@ -415,6 +1511,31 @@ test('either4', (t) => {
   t.is(either4({ v: 'world' }), 'world'.length)
 })
 
+test('either in object fields', (t) => {
+  t.is(describeDrawing({ shape: { radius: 1 } }), 'circle of radius 1')
+  t.is(
+    describeDrawing({ shape: { side: 2 }, label: 'box' }),
+    'square of side 2 labelled box',
+  )
+  t.is(
+    describeDrawing({ shape: { side: 2 }, label: 3 }),
+    'square of side 2 numbered 3',
+  )
+  t.deepEqual(scaleDrawing({ shape: { side: 2 }, label: 'box' }, 2), {
+    shape: { side: 4 },
+    label: 'box',
+  })
+  t.throws(() => describeDrawing({ shape: {} as any }))
+  // a throwing getter only rules out the variant, the next one is still tried
+  const shape = {
+    get radius(): number {
+      throw new Error('no radius')
+    },
+    side: 3,
+  }
+  t.is(describeDrawing({ shape }), 'square of side 3')
+})
+
 test('external', (t) => {
   const FX = 42
   const ext = createExternal(FX)
@@ -457,6 +1578,28 @@ AbortSignalTest('abort resolved task', async (t) => {
   t.pass('should not throw')
 })
 
+test('async task in the cpu pool', async (t) => {
+  const [primes, sum] = await Promise.all([
+    countPrimesBelow(100),
+    withoutAbortController(1, 2),
+  ])
+  t.is(primes, 25)
+  t.is(sum, 3)
+})
+
+test('async task finally on the js thread', async (t) => {
+  const progress = { done: false }
+  const pending = trackedSum(1, 2, progress)
+  t.false(progress.done)
+  t.is(await pending, 3)
+  t.true(progress.done)
+  const failed = { done: false }
+  await t.throwsAsync(() => trackedSum(0xffffffff, 1, failed), {
+    message: 'Sum overflows u32',
+  })
+  t.true(failed.done)
+})
+
 const BigIntTest = typeof BigInt !== 'undefined' ? test : test.skip
 
 BigIntTest('BigInt add', (t) => {
@@ -523,6 +1666,56 @@ Napi4Test('resolve value from thread safe function fatal mode', async (t) => {
   t.true(await tsfnFatalMode)
 })
 
+Napi4Test('thread safe function with bounded queue', async (t) => {
+  let queueFullCount = 0
+  const called = new Promise<number[]>((resolve) => {
+    const values: number[] = []
+    queueFullCount = threadsafeFunctionQueueFull((value: number) => {
+      values.push(value)
+      setTimeout(() => resolve(values), 50)
+    })
+  })
+  t.is(queueFullCount, 2)
+  t.deepEqual(await called, [0])
+})
+
+Napi4Test('receive thread safe function as argument', async (t) => {
+  const calleeHandled = await new Promise((resolve) => {
+    tsfnCalleeHandledFromArg((err, value) => resolve([err, value]))
+  })
+  t.deepEqual(calleeHandled, [null, 'callee handled'])
+  const fatal = await new Promise<string>((resolve) => {
+    tsfnFatalFromArg(resolve)
+  })
+  t.is(fatal, 'fatal')
+})
+
+Napi4Test('thread safe function with many arguments', async (t) => {
+  const [few, many] = await Promise.all([
+    new Promise<number[]>((resolve) => {
+      tsfnSpreadArgs((...args: number[]) => resolve(args), 3)
+    }),
+    new Promise<number[]>((resolve) => {
+      tsfnSpreadArgs((...args: number[]) => resolve(args), 12)
+    }),
+  ])
+  t.deepEqual(few, [0, 1, 2])
+  t.deepEqual(many, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11])
+})
+
+Napi4Test('forward a channel into thread safe function', async (t) => {
+  const values = await new Promise<number[]>((resolve) => {
+    const received: number[] = []
+    tsfnForwardChannel((value: number) => {
+      received.push(value)
+      if (received.length === 10) {
+        resolve(received)
+      }
+    }, 10)
+  })
+  t.deepEqual(values, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9])
+})
+
 Napi4Test('throw error from thread safe function fatal mode', (t) => {
   const p = exec('node ./tsfn-error.js', {
     cwd: __dirname,
@@ -560,6 +1753,16 @@ Napi4Test('Promise should reject raw error in rust', async (t) => {
   t.is(err, fxError)
 })
 
+Napi4Test('resolve deferred from other thread', async (t) => {
+  t.is(await resolveDeferredFromThread(41, false), 42)
+  await t.throwsAsync(() => resolveDeferredFromThread(41, true), {
+    message: 'Rejected from thread',
+  })
+  await t.throwsAsync(() => dropDeferredFromThread(), {
+    message: 'JsDeferred was dropped without being resolved or rejected',
+  })
+})
+
 const Napi5Test = Number(process.versions.napi) >= 5 ? test : test.skip
 
 Napi5Test('Date test', (t) => {
@@ -575,3 +1778,64 @@ Napi5Test('Date to chrono test', (t) => {
     new Date(fixture.getTime() + 60 * 1000),
   )
 })
+
+Napi5Test('Date to time crate test', (t) => {
+  const fixture = new Date('2022-02-09T19:31:55.396Z')
+  t.is(timeDateToMillis(fixture), fixture.getTime())
+  t.deepEqual(
+    timeDateAdd1Hour(fixture),
+    new Date(fixture.getTime() + 60 * 60 * 1000),
+  )
+  t.throws(() => timeDateToMillis(new Date('invalid')), {
+    code: 'DateExpected',
+    message: 'Found invalid date',
+  })
+})
+
+test('bytes crate buffers', (t) => {
+  const [head, rest] = splitBytes(Buffer.from('hello world'), 5)
+  t.true(Buffer.isBuffer(head))
+  t.is(head.toString(), 'hello')
+  t.is(rest.toString(), ' world')
+  const [all, empty] = splitBytes(Buffer.from('abc'), 10)
+  t.is(all.toString(), 'abc')
+  t.is(empty.length, 0)
+
+  const [first, second] = cloneBytes(Buffer.from('shared'))
+  t.is(first.toString(), 'shared')
+  t.is(second.toString(), 'shared')
+
+  t.is(repeatBytes(Buffer.from('ab'), 3).toString(), 'ababab')
+  t.is(repeatBytes(Buffer.from('ab'), 0).length, 0)
+})
+
+test('ndarray tensors', (t) => {
+  const matrix = {
+    data: new Float64Array([1, 2, 3, 4, 5, 6]),
+    shape: [2, 3],
+  }
+  const transposed = transposeMatrix(matrix)
+  t.deepEqual(transposed.shape, [3, 2])
+  t.true(transposed.data instanceof Float64Array)
+  t.deepEqual([...transposed.data], [1, 4, 2, 5, 3, 6])
+  t.deepEqual([...matrix.data], [1, 2, 3, 4, 5, 6])
+  t.throws(() => transposeMatrix({ ...matrix, shape: [6] }), {
+    code: 'InvalidArg',
+  })
+  t.throws(() => transposeMatrix({ ...matrix, shape: [4, 2] }), {
+    code: 'InvalidArg',
+    message: 'ndarray of shape [4, 2] has 8 elements, but its data has 6',
+  })
+  t.throws(
+    () => transposeMatrix({ ...matrix, shape: [2 ** 32 - 1, 2 ** 32 - 1, 2] }),
+    {
+      code: 'InvalidArg',
+      message:
+        'ndarray of shape [4294967295, 4294967295, 2] has too many elements',
+    },
+  )
+
+  const tensor = { data: new Float32Array([1, 2, 3, 4]), shape: [2, 1, 2] }
+  scaleInPlace(tensor, 2)
+  t.deepEqual([...tensor.data], [2, 4, 6, 8])
+})
@@ -18,3 +18,18 @@ test('should be able to require in worker thread', (t) => {
       t.pass()
     })
 })
+
+test('should be able to require in multiple worker threads', async (t) => {
+  const expected = Animal.withKind(Kind.Cat).whoami() + DEFAULT_COST
+  const messages = await Promise.all(
+    Array.from({ length: 4 }).map(() => {
+      const w = new Worker(join(__dirname, 'worker.js'))
+      return new Promise<string>((resolve) => {
+        w.on('message', resolve)
+      }).then((msg) => w.terminate().then(() => msg))
+    }),
+  )
+  t.deepEqual(messages, Array.from({ length: 4 }).fill(expected))
+  // classes registered in the main thread are still usable after workers exit
+  t.is(Animal.withKind(Kind.Cat).whoami() + DEFAULT_COST, expected)
+})
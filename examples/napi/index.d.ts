@@ -3,57 +3,38 @@
 
 /* auto-generated by NAPI-RS */
 
+import { EventEmitter } from 'events'
+import { LoadAverage } from 'os'
 export class ExternalObject<T> {
   readonly '': {
     readonly '': unique symbol
     [K: symbol]: T
   }
 }
-/** This is a const */
-export const DEFAULT_COST: number
-export function getWords(): Array<string>
-/** Gets some numbers */
-export function getNums(): Array<number>
-export function sumNums(nums: Array<number>): number
-export function toJsObj(): object
-export function readFileAsync(path: string): Promise<Buffer>
-export function asyncMultiTwo(arg: number): Promise<number>
-export function bigintAdd(a: bigint, b: bigint): bigint
-export function createBigInt(): bigint
-export function createBigIntI64(): bigint
-export function bigintGetU64AsString(bi: bigint): string
-export function getCwd(callback: (arg0: string) => void): void
-export function optionEnd(callback: (arg0: string, arg1?: string | undefined | null) => void): void
-export function optionStart(callback: (arg0: string | undefined | null, arg1: string) => void): void
-export function optionStartEnd(callback: (arg0: string | undefined | null, arg1: string, arg2?: string | undefined | null) => void): void
-export function optionOnly(callback: (arg0?: string | undefined | null) => void): void
-/** napi = { version = 2, features = ["serde-json"] } */
-export function readFile(callback: (arg0: Error | undefined, arg1?: string | undefined | null) => void): void
-export function returnJsFunction(): (...args: any[]) => any
-export function dateToNumber(input: Date): number
-export function chronoDateToMillis(input: Date): number
-export function chronoDateAdd1Minute(input: Date): Date
-export interface Dates {
-  start: Date
-  end?: Date | undefined | null
+export const enum ALIAS {
+  A = 0,
+  B = 1
 }
-export function eitherStringOrNumber(input: string | number): number
-export function returnEither(input: number): string | number
-export function either3(input: string | number | boolean): number
-export interface Obj {
-  v: string | number
+export interface AliasedStruct {
+  a: ALIAS
+  b: number
 }
-export function either4(input: string | number | boolean | Obj): number
-export function receiveClassOrNumber(either: number | JsClassForEither): number
-export function receiveMutClassOrNumber(either: number | JsClassForEither): number
-/** default enum values are continuos i32s start from 0 */
-export const enum Kind {
-  /** Barks */
-  Dog = 0,
-  /** Kills birds */
-  Cat = 1,
-  /** Tasty */
-  Duck = 2
+export interface AllOptionalObject {
+  name?: string | undefined | null
+  age?: number | undefined | null
+}
+export interface Circle {
+  radius: number
+}
+export interface ClientOptions {
+  endpoint: string
+  retry: RetryOptions
+  timeouts: Array<number>
+}
+export interface Contact {
+  name: string
+  email: string | null
+  phone?: string | undefined
 }
 /** You could break the step and for an new continuous value. */
 export const enum CustomNumEnum {
@@ -66,52 +47,57 @@ export const enum CustomNumEnum {
   Nine = 9,
   Ten = 10
 }
-export function enumToI32(e: CustomNumEnum): number
-export function throwError(): void
-export function createExternal(size: number): ExternalObject<number>
-export function createExternalString(content: string): ExternalObject<string>
-export function getExternal(external: ExternalObject<number>): number
-export function mutateExternal(external: ExternalObject<number>, newVal: number): void
-export function tsRename(a: { foo: number }): string[]
-export function xxh64Alias(input: Buffer): bigint
-export function getMapping(): Record<string, number>
-export function sumMapping(nums: Record<string, number>): number
-export function mapOption(val?: number | undefined | null): number | undefined | null
-export function returnNull(): null
-export function returnUndefined(): void
-export function add(a: number, b: number): number
-export function fibonacci(n: number): number
-export function listObjKeys(obj: object): Array<string>
-export function createObj(): object
-export function getGlobal(): typeof global
-export function getUndefined(): void
-export function getNull(): JsNull
-export interface AllOptionalObject {
-  name?: string | undefined | null
-  age?: number | undefined | null
+/** This is a const */
+export const DEFAULT_COST: number
+export const DEFAULT_LIMITS: Limits
+export interface Dates {
+  start: Date
+  end?: Date | undefined | null
 }
-export function receiveAllOptionalObject(obj?: AllOptionalObject | undefined | null): void
-export const enum ALIAS {
-  A = 0,
-  B = 1
+export interface Drawing {
+  shape: Circle | Square
+  label?: string | number | undefined | null
 }
-export interface AliasedStruct {
-  a: ALIAS
-  b: number
+/** Byte range of a file, offsets beyond `Number.MAX_SAFE_INTEGER` throw a `RangeError` */
+export interface FileRange {
+  offset: number
+  len?: number | undefined | null
 }
-export function fnReceivedAliased(s: AliasedStruct, e: ALIAS): void
-export interface StrictObject {
+export interface Headers {
+  host: string
+  [key: string]: string
+}
+export interface Inventory {
   name: string
+  stock: Map<number, number>
 }
-export function receiveStrictObject(strictObject: StrictObject): void
-export function getStrFromObject(): void
-export interface TsTypeChanged {
-  typeOverride: object
-  typeOverrideOptional?: object
+/** default enum values are continuos i32s start from 0 */
+export const enum Kind {
+  /** Barks */
+  Dog = 0,
+  /** Kills birds */
+  Cat = 1,
+  /** Tasty */
+  Duck = 2
+}
+export interface Limits {
+  maxDepth: number
+  maxLength: number
+}
+export interface Measurements {
+  unit: string
+  precision?: number | undefined | null
+  [key: string]: number | string | undefined | null
+}
+export interface Obj {
+  v: string | number
+}
+/** Version of the addon */
+export const PACKAGE_VERSION: string
+export interface PackageInfo {
+  readonly name: string
+  version: string
 }
-export function createObjWithProperty(): { value: ArrayBuffer, get getter(): number }
-export function getterFromObj(): number
-export function asyncPlus100(p: Promise<number>): Promise<number>
 /** This is an interface for package.json */
 export interface PackageJson {
   name: string
@@ -120,28 +106,251 @@ export interface PackageJson {
   dependencies?: Record<string, any> | undefined | null
   devDependencies?: Record<string, any> | undefined | null
 }
-export function readPackageJson(): PackageJson
-export function getPackageJsonName(packageJson: PackageJson): string
-export function testSerdeRoundtrip(data: any): any
-export function contains(source: string, target: string): boolean
+/** Receives the progress of a long running job */
+export interface ProgressReporter {
+  /** Called with the percentage of finished work */
+  report(percent: number): void
+  isCancelled(): boolean
+}
+export interface RenamedFieldsObject {
+  MAX_RETRIES: number
+  timeoutMs: number
+}
+export interface RetryOptions {
+  maxAttempts: number
+}
+/** Bounds of the samples, `null` when there are none */
+export interface SampleRange {
+  min: number | null
+  max: number | null
+}
+export interface Segment {
+  start: [number, number]
+  end: [number, number]
+}
+export interface ServerOptions {
+  host: string
+  port: number
+  retry: RetryOptions
+}
+/** Enums with data carrying variants are passed as `{ type: 'Variant', ...fields }` objects. */
+export type Shape = { /** Circle around the origin */ type: 'Circle', /** Radius of the circle */ radius: number } | { type: 'Rectangle', width: number, height: number } | { type: 'Empty' }
+export interface Square {
+  side: number
+}
+export interface StrictObject {
+  name: string
+}
+export interface Tagged<T extends string = string> {
+  tag: T
+}
+/** String enums are passed to and from JavaScript as their variant names. */
+export type TaskStatus = 'pending' | 'in-progress' | 'done'
+export interface TsTypeChanged {
+  typeOverride: object
+  typeOverrideOptional?: object
+}
+export interface Viewport {
+  width: number
+  height: number
+  zoom?: number | undefined | null
+}
+export function add(a: number, b: number): number
+/** The price including the tax, rounded to cents */
+export function addTax(price: string, rate: number): string
+export function advanceTaskStatus(status: TaskStatus): TaskStatus
+export function appendBuffer(buf: Buffer): Buffer
+export function appendToBlob(blob: Blob, suffix: string): Blob
+export function asyncMultiTwo(arg: number): Promise<number>
+export function asyncPlus100(p: Promise<number>): Promise<number>
+export function bigintAdd(a: bigint, b: bigint): bigint
+export function bigintGetU64AsString(bi: bigint): string
+/** Converted from and into JavaScript through JSON text */
+export function bumpPackageVersion(packageJson: PackageJson): PackageJson
+export function callThreadsafeFunction(callback: (...args: any[]) => any): void
+export function callWithNoArgs(callback: () => void): void
+export function checkConfigEntry(key: string, value: number): number
+export function chronoDateAdd1Minute(input: Date): Date
+export function chronoDateToMillis(input: Date): number
+export function cloneBytes(bytes: Buffer): Array<Buffer>
+export function concatLatin1(s: string): string
+export function concatNums(a: Array<number>, b: Array<number>): number[]
 export function concatStr(s: string): string
 export function concatUtf16(s: string): string
-export function concatLatin1(s: string): string
+export function contains(source: string, target: string): boolean
+export function convertU32Array(input: Uint32Array): Array<number>
+export function countPrimesBelow(limit: number): Promise<number>
+export function countRouteMethods(config: { port: number, routes: Array<{ path: string, methods: Array<string> }> }): number
+export function createBigInt(): bigint
+export function createBigIntI64(): bigint
+export function createBlobFromChunks(chunks: Array<string>, mimeType: string): Blob
+export function createExternal(size: number): ExternalObject<number>
+export function createExternalString(content: string): ExternalObject<string>
+export function createExternalTypedArray(): Uint32Array
+export function createFile(name: string, content: Buffer): File
+export function createLabelledObj(id: number, label: string): { readonly id: number, label: string }
+export function createObj(): object
+export function createObjWithProperty(): { value: ArrayBuffer, get getter(): number }
+export function createSnapshot(): { takenAt: Date, sequence: number | bigint, total: number | bigint }
+/** The squares of the numbers below `limit`, computed when they are accessed */
+export function createSquares(limit: number): Record<number, number>
+export function createSymbol(): symbol
+export function dateToNumber(input: Date): number
+export function derefUint8Array(a: Uint8Array, b: Uint8ClampedArray): number
+export function describeDrawing(drawing: Drawing): string
+export function describeOwner(owner: string | null): string
+export function describePet(pet: object): string
+export function describeServer(options: ServerOptions): string
+export function divide(dividend: number, divisor: number): number
+export function doubleRenamedFields(obj: RenamedFieldsObject): RenamedFieldsObject
+export function dropDeferredFromThread(): Promise<number>
+export function either3(input: string | number | boolean): number
+export function either4(input: string | number | boolean | Obj): number
+export function eitherStringOrNumber(input: string | number): number
+export function elapsedNanos(start: number, end: number): number
+export function enumToI32(e: CustomNumEnum): number
+/** Attach it to a `Limits` object, e.g. `{ maxDepth, maxLength, exceedsLimits }` */
+export function exceedsLimits(this: Limits, depth: number, length: number): boolean
+export function fibonacci(n: number): number
+export function fileRangeEnd(range: FileRange): number
+export function findIndex(items: Array<string>, target: string): number | undefined
+export function firstElement<T>(items: Array<T>): T | undefined | null
+export function fnReceivedAliased(s: AliasedStruct, e: ALIAS): void
+export function formatMeasurements(measurements: Measurements): Array<string>
+export function fromCents(cents: bigint): string
+export function getAnimalNameFromThis(this: Animal): string
+export function getBuffer(): Buffer
+export function getCwd(callback: (arg0: string) => void): void
+export function getEnvVarFromJs(key: string): string | undefined | null
+export function getExternal(external: ExternalObject<number>): number
+export function getGlobal(): typeof global
+export function getMapping(): Record<string, number>
+/** Only exported if the runtime supports Node-API version 1, check that it's defined before calling it */
+export function getNodeApiVersion(): number
+export function getNull(): JsNull
+/** Gets some numbers */
+export function getNums(): Array<number>
+export function getPackageInfo(): PackageInfo
+export function getPackageJsonName(packageJson: PackageJson): string
+export function getRanks(): Map<number, string>
+export function getSearchParams(url: URL): URLSearchParams
+export function getSnapshotMillis(snapshot: { takenAt: Date, sequence: number | bigint, total: number | bigint }): number
+export function getSortedMapping(): Record<string, number>
+export function getSquares(count: number): number[]
+export function getStaticLabels(): Array<string>
+export function getStrFromObject(): void
+export function getThisProperty(this: object, key: string): string | undefined | null
+export function getUndefined(): void
+export function getUnitLabel(count: number): string
+export function getWords(): Array<string>
+export function getterFromObj(): number
+/** Define a non-enumerable `value` property, then switch the prototype of `obj` */
+export function hideAndReparent(obj: object, key: string, value: unknown, prototype?: object | undefined | null): Array<string | symbol>
+export function inspectValue(value: unknown): string
+export function isModuleInitialized(): boolean
+export function joinWith(separator: string, ...parts: (string | number)[]): string
+export function listObjKeys(obj: object): Array<string>
+export function makeTagged(tag: string): Tagged
+export function mapOption(val?: number | undefined | null): number | undefined | null
+export function mapWithCallback(values: Array<number>, mapper: (arg0: number, arg1: number) => string): Array<string>
+export function minMax(items: Array<number>): [number, number] | undefined | null
+export function mutateExternal(external: ExternalObject<number>, newVal: number): void
+export function mutateTypedArray(input: Float32Array): void
+export function normalizeContact(contact: Contact): Contact
+export function normalizeHeaders(headers: Headers): Headers
+export function nullableDouble(val: number | null): number | null
+export function numberedLabels(label: string, count: number): string[]
+export function optionEnd(callback: (arg0: string, arg1?: string | undefined | null) => void): void
+export function optionOnly(callback: (arg0?: string | undefined | null) => void): void
+export function optionStart(callback: (arg0: string | undefined | null, arg1: string) => void): void
+export function optionStartEnd(callback: (arg0: string | undefined | null, arg1: string, arg2?: string | undefined | null) => void): void
+export function overrideIndividualArgOnFunction(notOverridden: string, size: `${number}px`, notOverridden2: number): string
+export function padStart(s: string, width: number, fill?: string | undefined | null): string
+export function panicWithMessage(message: string): void
+export function parseFlag(value: 'yes' | 'no'): boolean
+export function parseFlag(value: string): boolean | null
+/** Parse the content of `/proc/loadavg` */
+export function parseLoadAverage(content: string): LoadAverage | undefined | null
+export function queueMicrotaskFromNative(callback: (...args: any[]) => any): void
+/** napi = { version = 2, features = ["serde-json"] } */
+export function readFile(callback: (arg0: Error | undefined, arg1?: string | undefined | null) => void): void
+export function readFileAsync(path: string): Promise<Buffer>
+export function readPackageJson(): PackageJson
+export function receiveAllOptionalObject(obj?: AllOptionalObject | undefined | null): void
+export function receiveClassOrNumber(either: number | JsClassForEither): number
+export function receiveMutClassOrNumber(either: number | JsClassForEither): number
+export function receiveStrictObject(strictObject: StrictObject): void
+export function renameAnimal(animal: Animal, name: string): Animal
+export function renameFile(file: File, name: string): File
+export function repeatBytes(chunk: Buffer, times: number): Buffer
+export function repeatWithDefault(text: string, times?: number, separator?: string | undefined | null): string
+export function requireFromNative(specifier: string): unknown
+export function resolveDeferredFromThread(value: number, reject: boolean): Promise<number>
+/** Resolve `path` relative to `base` */
+export function resolveUrl(base: string | URL, path: string): URL
+export function restock(inventory: Inventory, id: number, count: number): Inventory
+export function returnEither(input: number): string | number
+export function returnJsFunction(): (...args: any[]) => any
+export function returnNull(): null
+export function returnUndefined(): void
+export function reverseItems<T>(items: Array<T>): Array<T>
+export function rgbToHex(r: number, g: number, b: number): string
 export function roundtripStr(s: string): string
+export function runJob(steps: number, reporter: ProgressReporter): number
+/** The text with the lone surrogates replaced, and whether there were any */
+export function sampleRange(samples: Array<number>): SampleRange
+export function sanitizeText(s: string): [string, boolean]
+export function scaleDrawing(drawing: Drawing, factor: number): Drawing
+export function scaleInPlace(tensor: { data: Float32Array, shape: Array<number> }, factor: number): void
+export function scaleShape(shape: Shape, factor: number): Shape
+export function segmentLength(segment: Segment): number
+/** `NaN` and the infinities become `null` as in JSON, or fail with `rejectNonFinite` */
+export function serializeReadings(readings: Array<number>, rejectNonFinite: boolean): Array<number | null>
+export function setSearchParams(url: URL, params: URLSearchParams): URL
 export function setSymbolInObj(symbol: symbol): object
-export function createSymbol(): symbol
-export function withoutAbortController(a: number, b: number): Promise<number>
-export function withAbortController(a: number, b: number, signal: AbortSignal): Promise<number>
-export function callThreadsafeFunction(callback: (...args: any[]) => any): void
-export function threadsafeFunctionThrowError(cb: (...args: any[]) => any): void
+export function shapeArea(shape: Shape): number
+export function splitBytes(bytes: Buffer, at: number): Array<Buffer>
+export function stringifyWithEngine(value: unknown): string | null
+export function structuredCloneFromNative(value: unknown): unknown
+export function sumBinaryPayload(payload: { header: Uint8Array, samples: Float64Array }): number
+export function sumMap(nums: Map<string, number>): number
+export function sumMapping(nums: Record<string, number>): number
+export function sumNums(nums: Array<number>): number
+export function sumTypedFieldsOfObjects(objs: Array<object>): number
+export function sumTypedObjectFields(obj: object): number
+export function swapPair(pair: [string, number]): [number, string]
+export function testSerdeRoundtrip(data: any): any
 export function threadsafeFunctionFatalMode(cb: (...args: any[]) => any): void
 export function threadsafeFunctionFatalModeError(cb: (...args: any[]) => any): void
-export function getBuffer(): Buffer
-export function appendBuffer(buf: Buffer): Buffer
-export function convertU32Array(input: Uint32Array): Array<number>
-export function createExternalTypedArray(): Uint32Array
-export function mutateTypedArray(input: Float32Array): void
-export function derefUint8Array(a: Uint8Array, b: Uint8ClampedArray): number
+export function threadsafeFunctionQueueFull(cb: (...args: any[]) => any): number
+export function threadsafeFunctionThrowError(cb: (...args: any[]) => any): void
+export function throwError(): void
+export function timeDateAdd1Hour(input: Date): Date
+export function timeDateToMillis(input: Date): number
+export function toCents(price: string): bigint
+export function toJsObj(): object
+export function totalBytes(blocks: bigint, blockSize: bigint): bigint
+export function totalTimeout(options: ClientOptions): number
+export function trackedSum(a: number, b: number, progress: { done: boolean }): Promise<number>
+export function transposeMatrix(matrix: { data: Float64Array, shape: Array<number> }): { data: Float64Array, shape: Array<number> }
+export function tsRename(a: { foo: number }): string[]
+export function tsfnCalleeHandledFromArg(tsfn: (err: Error | null, value: string) => any): void
+export function tsfnFatalFromArg(tsfn: (value: string) => any): void
+export function tsfnForwardChannel(tsfn: (value: number) => any, count: number): void
+export function tsfnSpreadArgs(cb: (...args: any[]) => any, count: number): void
+/**
+ * Never exported, no Node.js supports this Node-API version
+ *
+ * Only exported if the runtime supports Node-API version 4294967295, check that it's defined before calling it
+ */
+export function unsupportedNapiVersionFn(): void
+export function uuidFromBytes(bytes: Uint8Array): string
+export function uuidToBytes(id: string): Uint8Array
+export function uuidVersion(id: string): number
+export function viewportArea(viewport: Viewport): number
+export function withAbortController(a: number, b: number, signal: AbortSignal): Promise<number>
+export function withoutAbortController(a: number, b: number): Promise<number>
+export function xxh64Alias(input: Buffer): bigint
 /**
  * `constructor` option for `struct` requires all fields to be public,
  * otherwise tag impl fn as constructor
@@ -172,9 +381,29 @@ export class Animal {
   returnOtherClass(): Dog
   returnOtherClassWithCustomConstructor(): Bird
 }
-export class Dog {
+export class AnimalWithDefaultConstructor {
   name: string
-  constructor(name: string)
+  kind: number
+  constructor(name: string, kind: number)
+}
+export type JsAsset = Asset
+export class Asset {
+  constructor()
+  get filePath(): number
+}
+export type JsAssets = Assets
+export class Assets {
+  constructor()
+  get(id: number): JsAsset | undefined | null
+}
+/**
+ * Methods of `Vehicle` are callable on `Bicycle` instances,
+ * they operate on the `Vehicle` in the first field.
+ */
+export class Bicycle extends Vehicle {
+  brand: string
+  constructor(brand: string)
+  ring(): string
 }
 export class Bird {
   name: string
@@ -185,19 +414,40 @@ export type Blake2bHasher = Blake2BHasher
 /** Smoking test for type generation */
 export class Blake2BHasher {
   static withKey(key: Blake2bKey): Blake2BHasher
+  update(data: Buffer): void
 }
 export type Blake2bKey = Blake2BKey
 export class Blake2BKey { }
+/** Hands out the chunks of a stream from a `BufferPool` */
+export class ChunkPool {
+  constructor(maxBuffers: number)
+  chunk(size: number, fill: number): Buffer
+  arrayBufferChunk(size: number): ArrayBuffer
+  get idleCount(): number
+}
+export class ClassWithFactory {
+  name: string
+  static withName(name: string): ClassWithFactory
+  setName(name: string): this
+}
 export class Context {
   maybeNeed?: boolean | undefined | null
   constructor()
   static withData(data: string): Context
+  static withDataAsync(data: string): Promise<Context>
   method(): string
 }
-export class AnimalWithDefaultConstructor {
+export class Dog {
   name: string
-  kind: number
-  constructor(name: string, kind: number)
+  constructor(name: string)
+}
+/** Keeps the reporter between calls */
+export class Job {
+  constructor(reporter: ProgressReporter)
+  step(): number
+}
+export class JsClassForEither {
+  constructor()
 }
 export class NinjaTurtle {
   name: string
@@ -206,35 +456,57 @@ export class NinjaTurtle {
   getMaskColor(): string
   getName(): string
 }
-export type JsAssets = Assets
-export class Assets {
-  constructor()
-  get(id: number): JsAsset | undefined | null
-}
-export type JsAsset = Asset
-export class Asset {
-  constructor()
-  get filePath(): number
-}
 export class Optional {
   static optionEnd(required: string, optional?: string | undefined | null): string
   static optionStart(optional: string | undefined | null, required: string): string
   static optionStartEnd(optional1: string | undefined | null, required: string, optional2?: string | undefined | null): string
   static optionOnly(optional?: string | undefined | null): string
 }
-export class ClassWithFactory {
-  name: string
-  static withName(name: string): ClassWithFactory
-  setName(name: string): this
+export class Snippet {
+  constructor(text: string)
+  firstLine(): string
+  expandTabs(): string
+  asBytes(): Buffer
 }
-export class JsClassForEither {
+/** Last in, first out, the values are returned as they were pushed */
+export class Stack<T> {
+  constructor()
+  /** Returns the new size */
+  push(item: T): number
+  pop(): T | undefined | null
+  peek(): T | undefined | null
+  get size(): number
+}
+export class Ticker extends EventEmitter {
   constructor()
+  tick(): number
+  /** Emits `tick` with the new count */
+  tickAndEmit(): boolean
+  /** Emits `tick` with the counts up to `count` from another thread */
+  tickInBackground(count: number): void
+}
+/** The `message` is passed on to `Error` with the other arguments of the constructor */
+export class ValidationError extends Error {
+  field: string
+  constructor(message: string, field: string)
+}
+export class Vehicle {
+  /** Wheels of a regular car */
+  static readonly CAR_WHEELS: number
+  static readonly DEFAULT_KIND: string
+  constructor(wheels: number)
+  getWheels(): number
+  static get maxWheels(): number
+}
+export namespace xxh2 {
+  export function xxh2Plus(a: number, b: number): number
+  export function xxh3Xxh64Alias(input: Buffer): bigint
 }
 export namespace xxh3 {
   export const ALIGNMENT: number
-  export function xxh3_64(input: Buffer): bigint
   /** xxh128 function */
   export function xxh128(input: Buffer): bigint
+  export function xxh3_64(input: Buffer): bigint
   /** Xxh3 class */
   export class Xxh3 {
     constructor()
@@ -243,7 +515,11 @@ export namespace xxh3 {
     digest(): bigint
   }
 }
-export namespace xxh2 {
-  export function xxh2Plus(a: number, b: number): number
-  export function xxh3Xxh64Alias(input: Buffer): bigint
+declare module 'os' {
+  /** Declared in the typings of the builtin `os` module */
+  export interface LoadAverage {
+    one: number
+    five: number
+    fifteen: number
+  }
 }
@@ -1,4 +1,7 @@
-use napi::{Env, JsObject};
+use napi::{
+  bindgen_prelude::{Array, FromNapiValue, ToNapiValue},
+  Env, JsObject,
+};
 
 #[napi]
 pub fn get_words() -> Vec<&'static str> {
@@ -16,6 +19,25 @@ fn sum_nums(nums: Vec<u32>) -> u32 {
   nums.iter().sum()
 }
 
+#[napi(ts_return_type = "number[]")]
+fn get_squares(env: Env, count: u32) -> napi::Result<Array> {
+  let squares: Vec<f64> = (0..count).map(|i| f64::from(i) * f64::from(i)).collect();
+  Array::from_numbers(&env, &squares)
+}
+
+#[napi(ts_return_type = "string[]")]
+fn numbered_labels(env: Env, label: String, count: u32) -> napi::Result<Array> {
+  Array::from_iter_with_len(&env, count, (1..=count).map(|i| format!("{}{}", label, i)))
+}
+
+#[napi(ts_return_type = "number[]")]
+fn concat_nums(env: Env, a: Vec<i32>, b: Vec<i32>) -> napi::Result<Array> {
+  let mut arr = env.create_array(0)?;
+  arr.set_elements(0, &a)?;
+  arr.set_elements(arr.len(), &b)?;
+  Ok(arr)
+}
+
 #[napi]
 fn to_js_obj(env: Env) -> napi::Result<JsObject> {
   let mut arr = env.create_array(0)?;
@@ -23,3 +45,42 @@ fn to_js_obj(env: Env) -> napi::Result<JsObject> {
   arr.insert(42)?;
   arr.coerce_to_object()
 }
+
+#[napi]
+fn first_element<T: FromNapiValue + ToNapiValue>(items: Vec<T>) -> Option<T> {
+  items.into_iter().next()
+}
+
+#[napi]
+fn reverse_items<T: FromNapiValue + ToNapiValue>(mut items: Vec<T>) -> Vec<T> {
+  items.reverse();
+  items
+}
+
+#[napi]
+fn min_max(items: Vec<f64>) -> Option<(f64, f64)> {
+  let first = *items.first()?;
+  Some(items.into_iter().fold((first, first), |(min, max), item| {
+    (min.min(item), max.max(item))
+  }))
+}
+
+#[napi]
+fn swap_pair(pair: (String, u32)) -> (u32, String) {
+  (pair.1, pair.0)
+}
+
+#[napi(object)]
+pub struct Segment {
+  pub start: (f64, f64),
+  pub end: (f64, f64),
+}
+
+#[napi]
+fn segment_length(segment: Segment) -> f64 {
+  let (dx, dy) = (
+    segment.end.0 - segment.start.0,
+    segment.end.1 - segment.start.1,
+  );
+  dx.hypot(dy)
+}
@@ -46,3 +46,20 @@ fn read_file_content() -> Result<String> {
 fn return_js_function() -> Result<JsFunction> {
   get_js_function(read_file_js_function)
 }
+
+#[napi]
+fn map_with_callback(
+  values: Vec<u32>,
+  mapper: Function<(u32, u32), String>,
+) -> Result<Vec<String>> {
+  values
+    .into_iter()
+    .enumerate()
+    .map(|(index, value)| mapper.call((value, index as u32)))
+    .collect()
+}
+
+#[napi]
+fn call_with_no_args(callback: Function<(), ()>) -> Result<()> {
+  callback.call(())
+}
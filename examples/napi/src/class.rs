@@ -1,4 +1,11 @@
-use napi::{bindgen_prelude::Buffer, Result};
+use std::thread;
+
+use napi::{
+  bindgen_prelude::{
+    Buffer, ClassInstance, EventEmitter, FromNapiValue, Object, This, ToNapiValue,
+  },
+  Error, Result, Status,
+};
 
 use crate::r#enum::Kind;
 
@@ -155,6 +162,20 @@ impl Context {
     })
   }
 
+  #[napi(factory)]
+  pub async fn with_data_async(data: String) -> Result<Self> {
+    if data.is_empty() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Context data must not be empty".to_owned(),
+      ));
+    }
+    Ok(Self {
+      data,
+      maybe_need: Some(true),
+    })
+  }
+
   #[napi]
   pub fn method(&self) -> String {
     self.data.clone()
@@ -281,3 +302,165 @@ impl Optional {
     }
   }
 }
+
+#[napi]
+pub struct Vehicle {
+  wheels: u32,
+}
+
+#[napi]
+impl Vehicle {
+  /// Wheels of a regular car
+  #[napi]
+  pub const CAR_WHEELS: u32 = 4;
+
+  #[napi(js_name = "DEFAULT_KIND")]
+  pub const KIND: &'static str = "vehicle";
+
+  #[napi(constructor)]
+  pub fn new(wheels: u32) -> Self {
+    Vehicle { wheels }
+  }
+
+  #[napi]
+  pub fn get_wheels(&self) -> u32 {
+    self.wheels
+  }
+
+  #[napi(getter)]
+  pub fn max_wheels() -> u32 {
+    18
+  }
+}
+
+/// Methods of `Vehicle` are callable on `Bicycle` instances,
+/// they operate on the `Vehicle` in the first field.
+#[napi(extends = Vehicle)]
+pub struct Bicycle {
+  vehicle: Vehicle,
+  pub brand: String,
+}
+
+#[napi]
+impl Bicycle {
+  #[napi(constructor)]
+  pub fn new(brand: String) -> Self {
+    Bicycle {
+      vehicle: Vehicle::new(2),
+      brand,
+    }
+  }
+
+  #[napi]
+  pub fn ring(&self) -> String {
+    format!(
+      "{} on {} wheels: ring ring",
+      self.brand, self.vehicle.wheels
+    )
+  }
+}
+
+#[napi(extends = "events:EventEmitter")]
+pub struct Ticker {
+  count: u32,
+}
+
+#[napi]
+impl Ticker {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Ticker { count: 0 }
+  }
+
+  #[napi]
+  pub fn tick(&mut self) -> u32 {
+    self.count += 1;
+    self.count
+  }
+
+  /// Emits `tick` with the new count
+  #[napi]
+  pub fn tick_and_emit(&mut self, this: This) -> Result<bool> {
+    let count = self.tick();
+    this.emit("tick", count)
+  }
+
+  /// Emits `tick` with the counts up to `count` from another thread
+  #[napi]
+  pub fn tick_in_background(&self, this: This, count: u32) -> Result<()> {
+    let emitter = this.threadsafe_emitter::<u32>()?;
+    thread::spawn(move || {
+      for n in 1..=count {
+        emitter.emit("tick", n);
+      }
+    });
+    Ok(())
+  }
+}
+
+/// The `message` is passed on to `Error` with the other arguments of the constructor
+#[napi(extends = "Error")]
+pub struct ValidationError {
+  pub field: String,
+}
+
+#[napi]
+impl ValidationError {
+  #[napi(constructor)]
+  pub fn new(_message: String, field: String) -> Self {
+    ValidationError { field }
+  }
+}
+
+/// Last in, first out, the values are returned as they were pushed
+#[napi]
+pub struct Stack<T> {
+  items: Vec<T>,
+}
+
+#[napi]
+impl<T: FromNapiValue + ToNapiValue + Clone> Stack<T> {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Stack { items: vec![] }
+  }
+
+  /// Returns the new size
+  #[napi]
+  pub fn push(&mut self, item: T) -> u32 {
+    self.items.push(item);
+    self.items.len() as u32
+  }
+
+  #[napi]
+  pub fn pop(&mut self) -> Option<T> {
+    self.items.pop()
+  }
+
+  #[napi]
+  pub fn peek(&self) -> Option<T> {
+    self.items.last().cloned()
+  }
+
+  #[napi(getter)]
+  pub fn size(&self) -> u32 {
+    self.items.len() as u32
+  }
+}
+
+#[napi]
+pub fn rename_animal(mut animal: ClassInstance<Animal>, name: String) -> ClassInstance<Animal> {
+  animal.name = name;
+  animal
+}
+
+#[napi]
+pub fn describe_pet(pet: Object) -> String {
+  if let Some(animal) = pet.downcast_ref::<Animal>() {
+    format!("animal {}", animal.name)
+  } else if let Some(dog) = pet.downcast_ref::<Dog>() {
+    format!("dog {}", dog.name)
+  } else {
+    "unknown".to_owned()
+  }
+}
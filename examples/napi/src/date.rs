@@ -16,6 +16,16 @@ fn chrono_date_add_1_minute(input: chrono::DateTime<Utc>) -> chrono::DateTime<Ut
   input + Duration::minutes(1)
 }
 
+#[napi]
+fn time_date_to_millis(input: time::OffsetDateTime) -> i64 {
+  (input.unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+#[napi]
+fn time_date_add_1_hour(input: time::PrimitiveDateTime) -> time::PrimitiveDateTime {
+  input + time::Duration::hours(1)
+}
+
 #[napi(object)]
 pub struct Dates {
   pub start: chrono::DateTime<Utc>,
@@ -82,3 +82,47 @@ fn receive_mut_class_or_number(either: Either<u32, &mut JsClassForEither>) -> u3
     Either::B(_) => 100,
   }
 }
+
+#[napi(object)]
+pub struct Circle {
+  pub radius: f64,
+}
+
+#[napi(object)]
+pub struct Square {
+  pub side: f64,
+}
+
+#[napi(object)]
+pub struct Drawing {
+  pub shape: Either<Circle, Square>,
+  pub label: Option<Either<String, u32>>,
+}
+
+#[napi]
+fn describe_drawing(drawing: Drawing) -> String {
+  let shape = match drawing.shape {
+    Either::A(circle) => format!("circle of radius {}", circle.radius),
+    Either::B(square) => format!("square of side {}", square.side),
+  };
+  match drawing.label {
+    Some(Either::A(label)) => format!("{} labelled {}", shape, label),
+    Some(Either::B(number)) => format!("{} numbered {}", shape, number),
+    None => shape,
+  }
+}
+
+#[napi]
+fn scale_drawing(drawing: Drawing, factor: f64) -> Drawing {
+  Drawing {
+    shape: match drawing.shape {
+      Either::A(circle) => Either::A(Circle {
+        radius: circle.radius * factor,
+      }),
+      Either::B(square) => Either::B(Square {
+        side: square.side * factor,
+      }),
+    },
+    label: drawing.label,
+  }
+}
@@ -1,5 +1,3 @@
-use napi::bindgen_prelude::*;
-
 /// default enum values are continuos i32s start from 0
 #[napi]
 pub enum Kind {
@@ -29,6 +27,60 @@ fn enum_to_i32(e: CustomNumEnum) -> i32 {
   e as i32
 }
 
+/// String enums are passed to and from JavaScript as their variant names.
+#[napi(string_enum = "kebab-case")]
+pub enum TaskStatus {
+  Pending,
+  InProgress,
+  Done,
+}
+
+#[napi]
+fn advance_task_status(status: TaskStatus) -> TaskStatus {
+  match status {
+    TaskStatus::Pending => TaskStatus::InProgress,
+    TaskStatus::InProgress | TaskStatus::Done => TaskStatus::Done,
+  }
+}
+
+/// Enums with data carrying variants are passed as `{ type: 'Variant', ...fields }` objects.
+#[napi]
+pub enum Shape {
+  /// Circle around the origin
+  Circle {
+    /// Radius of the circle
+    radius: f64,
+  },
+  Rectangle {
+    width: f64,
+    height: f64,
+  },
+  Empty,
+}
+
+#[napi]
+fn shape_area(shape: Shape) -> f64 {
+  match shape {
+    Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+    Shape::Rectangle { width, height } => width * height,
+    Shape::Empty => 0.0,
+  }
+}
+
+#[napi]
+fn scale_shape(shape: Shape, factor: f64) -> Shape {
+  match shape {
+    Shape::Circle { radius } => Shape::Circle {
+      radius: radius * factor,
+    },
+    Shape::Rectangle { width, height } => Shape::Rectangle {
+      width: width * factor,
+      height: height * factor,
+    },
+    Shape::Empty => Shape::Empty,
+  }
+}
+
 #[napi(skip_typescript)]
 pub enum SkippedEnums {
   One = 1,
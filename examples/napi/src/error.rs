@@ -4,3 +4,44 @@ use napi::bindgen_prelude::*;
 fn throw_error() -> Result<()> {
   Err(Error::new(Status::InvalidArg, "Manual Error".to_owned()))
 }
+
+#[napi(catch_unwind)]
+fn panic_with_message(message: String) {
+  panic!("{}", message);
+}
+
+#[napi(error)]
+pub enum ConfigError {
+  #[napi(code = "ERR_UNKNOWN_KEY")]
+  NotFound {
+    key: String,
+  },
+  #[napi(class = "RangeError")]
+  OutOfRange {
+    key: String,
+    max_value: u32,
+  },
+  Locked,
+}
+
+impl std::fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ConfigError::NotFound { key } => write!(f, "unknown config key `{}`", key),
+      ConfigError::OutOfRange { key, max_value } => {
+        write!(f, "`{}` must be at most {}", key, max_value)
+      }
+      ConfigError::Locked => write!(f, "config is locked"),
+    }
+  }
+}
+
+#[napi]
+fn check_config_entry(key: String, value: u32) -> std::result::Result<u32, ConfigError> {
+  match key.as_str() {
+    "locked" => Err(ConfigError::Locked),
+    "retries" if value > 10 => Err(ConfigError::OutOfRange { key, max_value: 10 }),
+    "retries" => Ok(value),
+    _ => Err(ConfigError::NotFound { key }),
+  }
+}
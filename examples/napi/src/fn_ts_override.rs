@@ -4,3 +4,39 @@ use napi::bindgen_prelude::{Object, Result};
 fn ts_rename(a: Object) -> Result<Object> {
   a.get_property_names()
 }
+
+#[napi]
+fn override_individual_arg_on_function(
+  not_overridden: String,
+  #[napi(ts_type = "`${number}px`")] size: String,
+  not_overridden2: u32,
+) -> String {
+  format!("{}{}{}", not_overridden, size, not_overridden2)
+}
+
+/// Overloads can't be generated, so the definitions are written by hand
+#[napi(
+  skip_typescript,
+  ts_extra = "export function parseFlag(value: 'yes' | 'no'): boolean\nexport function parseFlag(value: string): boolean | null"
+)]
+fn parse_flag(value: String) -> Option<bool> {
+  match value.as_str() {
+    "yes" => Some(true),
+    "no" => Some(false),
+    _ => None,
+  }
+}
+
+#[napi(
+  object,
+  skip_typescript,
+  ts_extra = "export interface Tagged<T extends string = string> {\n  tag: T\n}"
+)]
+pub struct Tagged {
+  pub tag: String,
+}
+
+#[napi]
+fn make_tagged(tag: String) -> Tagged {
+  Tagged { tag }
+}
@@ -0,0 +1,47 @@
+use napi::bindgen_prelude::*;
+
+/// Receives the progress of a long running job
+#[napi(interface)]
+pub trait ProgressReporter {
+  /// Called with the percentage of finished work
+  fn report(&self, percent: u32) -> Result<()>;
+  fn is_cancelled(&self) -> Result<bool>;
+}
+
+#[napi]
+fn run_job(steps: u32, reporter: Box<dyn ProgressReporter>) -> Result<u32> {
+  let mut finished = 0;
+  while finished < steps {
+    if reporter.is_cancelled()? {
+      break;
+    }
+    finished += 1;
+    reporter.report(finished * 100 / steps)?;
+  }
+  Ok(finished)
+}
+
+/// Keeps the reporter between calls
+#[napi]
+pub struct Job {
+  reporter: Box<dyn ProgressReporter>,
+  finished: u32,
+}
+
+#[napi]
+impl Job {
+  #[napi(constructor)]
+  pub fn new(reporter: Box<dyn ProgressReporter>) -> Self {
+    Job {
+      reporter,
+      finished: 0,
+    }
+  }
+
+  #[napi]
+  pub fn step(&mut self) -> Result<u32> {
+    self.finished += 1;
+    self.reporter.report(self.finished)?;
+    Ok(self.finished)
+  }
+}
@@ -9,6 +9,10 @@ extern crate serde_derive;
 /// This is a const
 pub const DEFAULT_COST: u32 = 12;
 
+#[napi(js_name = "PACKAGE_VERSION")]
+/// Version of the addon
+pub const VERSION: &str = "1.0.0";
+
 #[napi(skip_typescript)]
 pub const TYPE_SKIPPED_CONST: u32 = 12;
 
@@ -24,8 +28,10 @@ mod r#enum;
 mod error;
 mod external;
 mod fn_ts_override;
+mod interface;
 mod js_mod;
 mod map;
+mod module_init;
 mod nullable;
 mod number;
 mod object;
@@ -34,5 +40,8 @@ mod serde;
 mod string;
 mod symbol;
 mod task;
+mod tensor;
 mod threadsafe_function;
 mod typed_array;
+mod url;
+mod uuid;
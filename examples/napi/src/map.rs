@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[napi]
 fn get_mapping() -> HashMap<String, u32> {
@@ -12,3 +12,66 @@ fn get_mapping() -> HashMap<String, u32> {
 fn sum_mapping(nums: HashMap<String, u32>) -> u32 {
   nums.into_values().sum()
 }
+
+#[napi]
+fn get_sorted_mapping() -> BTreeMap<String, u32> {
+  BTreeMap::from([("b".to_string(), 102), ("a".to_string(), 101)])
+}
+
+#[napi(js_map)]
+fn get_ranks() -> BTreeMap<u32, String> {
+  BTreeMap::from([(1, "gold".to_string()), (2, "silver".to_string())])
+}
+
+#[napi]
+fn sum_map(#[napi(js_map)] nums: HashMap<String, u32>) -> u32 {
+  nums.into_values().sum()
+}
+
+#[napi(object)]
+pub struct Inventory {
+  pub name: String,
+  #[napi(js_map)]
+  pub stock: BTreeMap<u32, u32>,
+}
+
+#[napi]
+fn restock(mut inventory: Inventory, id: u32, count: u32) -> Inventory {
+  *inventory.stock.entry(id).or_default() += count;
+  inventory
+}
+
+#[napi(object)]
+pub struct Headers {
+  pub host: String,
+  #[napi(index_signature)]
+  pub extra: BTreeMap<String, String>,
+}
+
+#[napi]
+fn normalize_headers(mut headers: Headers) -> Headers {
+  headers.extra = headers
+    .extra
+    .into_iter()
+    .map(|(name, value)| (name.to_lowercase(), value.trim().to_owned()))
+    .collect();
+  headers
+}
+
+#[napi(object)]
+pub struct Measurements {
+  pub unit: String,
+  pub precision: Option<u32>,
+  #[napi(index_signature)]
+  pub values: BTreeMap<String, f64>,
+}
+
+#[napi]
+fn format_measurements(measurements: Measurements) -> Vec<String> {
+  let precision = measurements.precision.unwrap_or(0) as usize;
+  measurements
+    .values
+    .iter()
+    .map(|(name, value)| format!("{}: {:.*} {}", name, precision, value, measurements.unit))
+    .collect()
+}
@@ -0,0 +1,19 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use napi::bindgen_prelude::*;
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+#[napi(module_init)]
+fn init(env: Env, exports: Object) -> Result<()> {
+  let version = env.get_node_version()?;
+  assert!(version.major > 0);
+  assert!(exports.has_named_property("sumMapping")?);
+  INITIALIZED.store(true, Ordering::Relaxed);
+  Ok(())
+}
+
+#[napi]
+fn is_module_initialized() -> bool {
+  INITIALIZED.load(Ordering::Relaxed)
+}
@@ -5,6 +5,15 @@ fn map_option(val: Option<u32>) -> Option<u32> {
   val.map(|v| v + 1)
 }
 
+#[napi]
+fn repeat_with_default(
+  text: String,
+  #[napi(default = 2)] times: u32,
+  separator: Option<String>,
+) -> String {
+  vec![text; times as usize].join(&separator.unwrap_or_default())
+}
+
 #[napi]
 fn return_null() -> Null {
   Null
@@ -12,3 +21,38 @@ fn return_null() -> Null {
 
 #[napi]
 fn return_undefined() -> Undefined {}
+
+#[napi(none = "undefined")]
+fn find_index(items: Vec<String>, target: String) -> Option<u32> {
+  items
+    .iter()
+    .position(|item| item == &target)
+    .map(|index| index as u32)
+}
+
+#[napi]
+fn describe_owner(#[napi(none = "null")] owner: Option<String>) -> String {
+  owner.unwrap_or_else(|| "nobody".to_owned())
+}
+
+#[napi]
+fn nullable_double(val: Nullable<u32>) -> Nullable<u32> {
+  Nullable(val.0.map(|v| v * 2))
+}
+
+#[napi(object, none = "null")]
+pub struct Contact {
+  pub name: String,
+  pub email: Option<String>,
+  #[napi(none = "undefined")]
+  pub phone: Option<String>,
+}
+
+#[napi]
+fn normalize_contact(contact: Contact) -> Contact {
+  Contact {
+    name: contact.name.trim().to_owned(),
+    email: contact.email.map(|email| email.to_lowercase()),
+    phone: contact.phone,
+  }
+}
@@ -1,3 +1,6 @@
+use napi::bindgen_prelude::{DecimalNumber, ScaledDecimal};
+use rust_decimal::Decimal;
+
 #[napi]
 fn add(a: u32, b: u32) -> u32 {
   a + b
@@ -10,3 +13,84 @@ fn fibonacci(n: u32) -> u32 {
     _ => fibonacci(n - 1) + fibonacci(n - 2),
   }
 }
+
+/// The price including the tax, rounded to cents
+#[napi]
+fn add_tax(price: Decimal, rate: DecimalNumber) -> Decimal {
+  (price * (Decimal::ONE + rate.0)).round_dp(2)
+}
+
+#[napi]
+fn to_cents(price: Decimal) -> ScaledDecimal<2> {
+  ScaledDecimal(price)
+}
+
+#[napi]
+fn from_cents(cents: ScaledDecimal<2>) -> Decimal {
+  cents.0
+}
+
+/// Byte range of a file, offsets beyond `Number.MAX_SAFE_INTEGER` throw a `RangeError`
+#[napi(object, large_int = "checked")]
+pub struct FileRange {
+  pub offset: u64,
+  pub len: Option<u64>,
+}
+
+#[napi(large_int = "checked")]
+fn file_range_end(range: FileRange) -> u64 {
+  range.offset.saturating_add(range.len.unwrap_or(0))
+}
+
+#[napi(large_int = "saturate")]
+fn elapsed_nanos(start: i64, end: i64) -> i64 {
+  end.saturating_sub(start)
+}
+
+#[napi(large_int = "bigint")]
+fn total_bytes(blocks: u64, block_size: usize) -> u64 {
+  blocks.saturating_mul(block_size as u64)
+}
+
+#[napi(strict_numbers)]
+fn rgb_to_hex(r: u8, g: u8, b: u8) -> String {
+  format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+#[napi(object, strict_numbers)]
+pub struct Viewport {
+  pub width: u16,
+  pub height: u16,
+  pub zoom: Option<i8>,
+}
+
+#[napi(strict_numbers)]
+fn viewport_area(viewport: Viewport) -> u32 {
+  viewport.width as u32 * viewport.height as u32
+}
+
+#[napi(non_finite = "error")]
+fn divide(dividend: f64, divisor: f64) -> f64 {
+  dividend / divisor
+}
+
+/// Bounds of the samples, `null` when there are none
+#[napi(object, non_finite = "null")]
+pub struct SampleRange {
+  pub min: f64,
+  pub max: f64,
+}
+
+#[napi]
+fn sample_range(samples: Vec<f64>) -> SampleRange {
+  samples.into_iter().fold(
+    SampleRange {
+      min: f64::INFINITY,
+      max: f64::NEG_INFINITY,
+    },
+    |range, sample| SampleRange {
+      min: range.min.min(sample),
+      max: range.max.max(sample),
+    },
+  )
+}
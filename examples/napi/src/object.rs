@@ -1,4 +1,6 @@
-use napi::{bindgen_prelude::*, JsGlobal, JsNull, JsObject, JsUndefined, Property};
+use napi::{bindgen_prelude::*, JsGlobal, JsNull, JsObject, JsUndefined, JsUnknown, Property};
+
+use crate::class::Animal;
 
 #[napi]
 fn list_obj_keys(obj: Object) -> Vec<String> {
@@ -18,6 +20,35 @@ fn get_global(env: Env) -> Result<JsGlobal> {
   env.get_global()
 }
 
+#[napi]
+fn get_env_var_from_js(env: Env, key: String) -> Result<Option<String>> {
+  env.get_env_var(key)
+}
+
+#[napi(napi_version = 1)]
+fn get_node_api_version(env: Env) -> Result<u32> {
+  env.node_api_version()
+}
+
+#[napi(napi_version = 4294967295)]
+/// Never exported, no Node.js supports this Node-API version
+fn unsupported_napi_version_fn() {}
+
+#[napi]
+fn require_from_native(env: Env, specifier: String) -> Result<JsUnknown> {
+  env.require(specifier)
+}
+
+#[napi]
+fn queue_microtask_from_native(env: Env, callback: JsFunction) -> Result<()> {
+  env.get_global()?.queue_microtask(callback)
+}
+
+#[napi]
+fn structured_clone_from_native(env: Env, value: JsUnknown) -> Result<JsUnknown> {
+  env.get_global()?.structured_clone(value)
+}
+
 #[napi]
 fn get_undefined(env: Env) -> Result<JsUndefined> {
   env.get_undefined()
@@ -70,6 +101,38 @@ pub fn receive_strict_object(strict_object: StrictObject) {
   assert_eq!(strict_object.name, "strict");
 }
 
+#[napi(object)]
+pub struct RetryOptions {
+  pub max_attempts: u32,
+}
+
+#[napi(object)]
+pub struct ClientOptions {
+  pub endpoint: String,
+  pub retry: RetryOptions,
+  pub timeouts: Vec<u32>,
+}
+
+#[napi]
+pub fn total_timeout(options: ClientOptions) -> u32 {
+  options.timeouts.iter().sum::<u32>() * options.retry.max_attempts
+}
+
+#[napi(object, aggregate_errors)]
+pub struct ServerOptions {
+  pub host: String,
+  pub port: u32,
+  pub retry: RetryOptions,
+}
+
+#[napi]
+pub fn describe_server(options: ServerOptions) -> String {
+  format!(
+    "{}:{} ({} attempts)",
+    options.host, options.port, options.retry.max_attempts
+  )
+}
+
 #[napi]
 pub fn get_str_from_object(env: Env) {
   let mut obj = env.create_object().unwrap();
@@ -77,6 +140,65 @@ pub fn get_str_from_object(env: Env) {
   assert_eq!(obj.get("name").unwrap(), Some("value"));
 }
 
+#[napi]
+pub fn sum_typed_object_fields(obj: Object) -> Result<u32> {
+  let a: Option<u32> = obj.get("a")?;
+  let b: Option<u32> = obj.get("b")?;
+  Ok(a.unwrap_or_default() + b.unwrap_or_default())
+}
+
+const A: PropertyKey = PropertyKey::new("a");
+const B: PropertyKey = PropertyKey::new("b");
+
+#[napi]
+pub fn sum_typed_fields_of_objects(objs: Vec<Object>) -> Result<u32> {
+  objs.iter().try_fold(0, |sum, obj| {
+    let a: Option<u32> = obj.get_by_key(&A)?;
+    let b: Option<u32> = obj.get_by_key(&B)?;
+    Ok(sum + a.unwrap_or_default() + b.unwrap_or_default())
+  })
+}
+
+const ID: PropertyKey = PropertyKey::new("id");
+const LABEL: PropertyKey = PropertyKey::new("label");
+
+#[napi(ts_return_type = "{ readonly id: number, label: string }")]
+pub fn create_labelled_obj(env: Env, id: u32, label: String) -> Result<Object> {
+  let mut builder = ObjectBuilder::with_capacity(&env, 2);
+  builder.set_readonly(&ID, id)?.set(&LABEL, label)?;
+  builder.build()
+}
+
+#[napi(object, rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct RenamedFieldsObject {
+  pub max_retries: u32,
+  #[napi(js_name = "timeoutMs")]
+  pub timeout_ms: u32,
+}
+
+#[napi]
+pub fn double_renamed_fields(obj: RenamedFieldsObject) -> RenamedFieldsObject {
+  RenamedFieldsObject {
+    max_retries: obj.max_retries * 2,
+    timeout_ms: obj.timeout_ms * 2,
+  }
+}
+
+#[napi(object)]
+pub struct PackageInfo {
+  #[napi(readonly)]
+  pub name: String,
+  pub version: String,
+}
+
+#[napi]
+pub fn get_package_info() -> PackageInfo {
+  PackageInfo {
+    name: "napi-examples".to_owned(),
+    version: "1.0.0".to_owned(),
+  }
+}
+
 #[napi(object)]
 pub struct TsTypeChanged {
   #[napi(ts_type = "object")]
@@ -101,3 +223,111 @@ pub fn create_obj_with_property(env: Env) -> Result<JsObject> {
 fn getter_from_obj() -> u32 {
   42
 }
+
+#[napi(object)]
+pub struct Limits {
+  pub max_depth: u32,
+  pub max_length: u32,
+}
+
+#[napi]
+pub const DEFAULT_LIMITS: Limits = Limits {
+  max_depth: 8,
+  max_length: 1024,
+};
+
+/// Attach it to a `Limits` object, e.g. `{ maxDepth, maxLength, exceedsLimits }`
+#[napi]
+pub fn exceeds_limits(this: This<Limits>, depth: u32, length: u32) -> bool {
+  depth > this.max_depth || length > this.max_length
+}
+
+#[napi]
+pub fn get_this_property(this: This, key: String) -> Result<Option<String>> {
+  this.get(key)
+}
+
+#[napi]
+pub fn get_animal_name_from_this(this: This<&Animal>) -> String {
+  this.get_name().to_owned()
+}
+
+/// Declared in the typings of the builtin `os` module
+#[napi(object, ts_module = "os")]
+pub struct LoadAverage {
+  pub one: f64,
+  pub five: f64,
+  pub fifteen: f64,
+}
+
+/// Parse the content of `/proc/loadavg`
+#[napi]
+pub fn parse_load_average(content: String) -> Option<LoadAverage> {
+  let mut values = content.split_whitespace().map(|v| v.parse::<f64>().ok());
+  Some(LoadAverage {
+    one: values.next()??,
+    five: values.next()??,
+    fifteen: values.next()??,
+  })
+}
+
+#[napi]
+pub fn inspect_value(value: JsUnknown) -> Result<String> {
+  value.inspect()
+}
+
+/// The squares of the numbers below `limit`, computed when they are accessed
+#[napi(ts_return_type = "Record<number, number>")]
+pub fn create_squares(env: Env, limit: u32) -> Result<JsObject> {
+  let index = move |key: JsUnknown| -> Result<Option<u32>> {
+    if key.get_type()? != ValueType::String {
+      return Ok(None);
+    }
+    let key = key.coerce_to_string()?.into_utf8()?.into_owned()?;
+    Ok(key.parse::<u32>().ok().filter(|i| *i < limit))
+  };
+  let mut handler = ProxyHandler::new(&env)?;
+  handler
+    .get(move |_, _, key| Ok(index(key)?.map(|i| i * i)))?
+    .has(move |_, _, key| Ok(index(key)?.is_some()))?
+    .own_keys(move |_, _| Ok((0..limit).map(|i| i.to_string()).collect::<Vec<_>>()))?
+    .get_own_property_descriptor(move |env, _, key| {
+      let i = match index(key)? {
+        Some(i) => i,
+        None => return Ok(None),
+      };
+      let mut descriptor = env.create_object()?;
+      descriptor.set("value", i * i)?;
+      descriptor.set("enumerable", true)?;
+      descriptor.set("configurable", true)?;
+      Ok(Some(descriptor))
+    })?;
+  env.create_proxy(env.create_object()?, handler)
+}
+
+/// Define a non-enumerable `value` property, then switch the prototype of `obj`
+#[napi(ts_return_type = "Array<string | symbol>")]
+pub fn hide_and_reparent(
+  env: Env,
+  mut obj: JsObject,
+  key: String,
+  value: JsUnknown,
+  prototype: Option<JsObject>,
+) -> Result<JsObject> {
+  let mut descriptor = env.create_object()?;
+  descriptor.set_named_property("value", value)?;
+  descriptor.set("enumerable", false)?;
+  if !obj.define_property(env.create_string(&key)?, &descriptor)? {
+    return Err(Error::from_reason(format!("Can not define `{}`", key)));
+  }
+  let reparented = match prototype {
+    Some(prototype) => obj.set_prototype(prototype)?,
+    None => obj.set_prototype(env.get_null()?)?,
+  };
+  if !reparented {
+    return Err(Error::from_reason(
+      "Can not change the prototype".to_owned(),
+    ));
+  }
+  obj.own_keys()
+}
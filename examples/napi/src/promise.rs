@@ -1,7 +1,30 @@
-use napi::bindgen_prelude::*;
+use napi::{bindgen_prelude::*, JsObject};
 
 #[napi]
 pub async fn async_plus_100(p: Promise<u32>) -> Result<u32> {
   let v = p.await?;
   Ok(v + 100)
 }
+
+#[napi(ts_return_type = "Promise<number>")]
+pub fn resolve_deferred_from_thread(env: Env, value: u32, reject: bool) -> Result<JsObject> {
+  let (deferred, promise) = env.create_deferred::<u32>()?;
+  std::thread::spawn(move || {
+    if reject {
+      deferred.reject(Error::new(
+        Status::GenericFailure,
+        "Rejected from thread".to_owned(),
+      ));
+    } else {
+      deferred.resolve(value + 1);
+    }
+  });
+  Ok(promise)
+}
+
+#[napi(ts_return_type = "Promise<number>")]
+pub fn drop_deferred_from_thread(env: Env) -> Result<JsObject> {
+  let (deferred, promise) = env.create_deferred::<u32>()?;
+  std::thread::spawn(move || drop(deferred));
+  Ok(promise)
+}
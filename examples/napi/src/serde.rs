@@ -1,6 +1,7 @@
-use napi::bindgen_prelude::*;
+use napi::{bindgen_prelude::*, JsUnknown, LargeIntegerMode, NonFiniteMode, SerdeOptions};
 use serde_json::{Map, Value};
 use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[napi(object)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -30,3 +31,114 @@ fn get_package_json_name(package_json: PackageJson) -> String {
 fn test_serde_roundtrip(data: Value) -> Value {
   data
 }
+
+#[napi]
+fn stringify_with_engine(env: Env, value: JsUnknown) -> Result<Option<String>> {
+  env.json_stringify(value)
+}
+
+/// Converted from and into JavaScript through JSON text
+#[napi(
+  ts_args_type = "packageJson: PackageJson",
+  ts_return_type = "PackageJson"
+)]
+fn bump_package_version(env: Env, package_json: JsUnknown) -> Result<JsUnknown> {
+  let mut package_json: PackageJson = env.from_js_value_via_json(package_json)?;
+  package_json.version = format!("{}-next", package_json.version);
+  env.to_js_value_via_json(&package_json)
+}
+
+#[derive(Deserialize)]
+struct BinaryPayload {
+  header: Vec<u8>,
+  samples: Vec<f64>,
+}
+
+#[napi(ts_args_type = "payload: { header: Uint8Array, samples: Float64Array }")]
+fn sum_binary_payload(env: Env, payload: JsUnknown) -> Result<f64> {
+  let payload: BinaryPayload = env.from_js_value(payload)?;
+  Ok(payload.header.iter().map(|b| *b as f64).sum::<f64>() + payload.samples.iter().sum::<f64>())
+}
+
+#[derive(Deserialize)]
+struct Route {
+  #[allow(dead_code)]
+  path: String,
+  methods: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ServerConfig {
+  #[allow(dead_code)]
+  port: u16,
+  routes: Vec<Route>,
+}
+
+#[napi(
+  ts_args_type = "config: { port: number, routes: Array<{ path: string, methods: Array<string> }> }"
+)]
+fn count_route_methods(env: Env, config: JsUnknown) -> Result<u32> {
+  let config: ServerConfig = env.from_js_value(config)?;
+  Ok(
+    config
+      .routes
+      .iter()
+      .map(|route| route.methods.len() as u32)
+      .sum(),
+  )
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Snapshot {
+  #[serde(with = "napi::serde_date")]
+  taken_at: SystemTime,
+  sequence: i64,
+  total: u64,
+}
+
+#[napi(ts_return_type = "{ takenAt: Date, sequence: number | bigint, total: number | bigint }")]
+fn create_snapshot(env: Env) -> Result<JsUnknown> {
+  let snapshot = Snapshot {
+    taken_at: UNIX_EPOCH + Duration::from_millis(1_650_000_000_000),
+    sequence: (1 << 53) + 1,
+    total: 42,
+  };
+  env.to_js_value_with_options(
+    &snapshot,
+    SerdeOptions {
+      large_integers: LargeIntegerMode::Lossless,
+      ..Default::default()
+    },
+  )
+}
+
+#[napi(
+  ts_args_type = "snapshot: { takenAt: Date, sequence: number | bigint, total: number | bigint }"
+)]
+fn get_snapshot_millis(env: Env, snapshot: JsUnknown) -> Result<f64> {
+  let snapshot: Snapshot = env.from_js_value(snapshot)?;
+  Ok(
+    snapshot
+      .taken_at
+      .duration_since(UNIX_EPOCH)
+      .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))?
+      .as_millis() as f64,
+  )
+}
+
+/// `NaN` and the infinities become `null` as in JSON, or fail with `rejectNonFinite`
+#[napi(ts_return_type = "Array<number | null>")]
+fn serialize_readings(env: Env, readings: Vec<f64>, reject_non_finite: bool) -> Result<JsUnknown> {
+  env.to_js_value_with_options(
+    &readings,
+    SerdeOptions {
+      non_finite: if reject_non_finite {
+        NonFiniteMode::Error
+      } else {
+        NonFiniteMode::Null
+      },
+      ..Default::default()
+    },
+  )
+}
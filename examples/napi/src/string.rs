@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use napi::bindgen_prelude::*;
 
 #[napi]
@@ -16,6 +18,13 @@ fn concat_utf16(s: Utf16String) -> Utf16String {
   Utf16String::from(format!("{} + Rust 🦀 string!", s))
 }
 
+/// The text with the lone surrogates replaced, and whether there were any
+#[napi]
+fn sanitize_text(s: Utf16Lossy) -> (String, bool) {
+  let is_lossy = s.is_lossy();
+  (s.into_string(), is_lossy)
+}
+
 #[napi]
 fn concat_latin1(s: Latin1String) -> String {
   format!("{} + Rust 🦀 string!", s)
@@ -25,3 +34,71 @@ fn concat_latin1(s: Latin1String) -> String {
 pub fn roundtrip_str(s: String) -> String {
   s
 }
+
+#[napi]
+pub fn get_static_labels() -> Vec<StaticStr> {
+  vec!["pending".into(), "café".into(), "🦀 done".into()]
+}
+
+#[napi]
+fn join_with(separator: String, #[napi(variadic)] parts: Vec<Either<String, u32>>) -> String {
+  parts
+    .into_iter()
+    .map(|part| match part {
+      Either::A(s) => s,
+      Either::B(n) => n.to_string(),
+    })
+    .collect::<Vec<_>>()
+    .join(&separator)
+}
+
+#[napi(strict)]
+fn pad_start(s: String, width: u32, fill: Option<String>) -> String {
+  let fill = fill.unwrap_or_else(|| " ".to_owned());
+  let mut padded = String::new();
+  while padded.chars().count() + s.chars().count() < width as usize {
+    padded.push_str(&fill);
+  }
+  padded + &s
+}
+
+#[napi]
+pub fn get_unit_label(count: u32) -> &'static str {
+  if count == 1 {
+    "item"
+  } else {
+    "items"
+  }
+}
+
+#[napi]
+pub struct Snippet {
+  text: String,
+}
+
+#[napi]
+impl Snippet {
+  #[napi(constructor)]
+  pub fn new(text: String) -> Self {
+    Snippet { text }
+  }
+
+  #[napi]
+  pub fn first_line<'a>(&'a self) -> &'a str {
+    self.text.lines().next().unwrap_or_default()
+  }
+
+  #[napi]
+  pub fn expand_tabs(&self) -> Cow<'_, str> {
+    if self.text.contains('\t') {
+      Cow::Owned(self.text.replace('\t', "  "))
+    } else {
+      Cow::Borrowed(&self.text)
+    }
+  }
+
+  #[napi]
+  pub fn as_bytes(&self) -> &[u8] {
+    self.text.as_bytes()
+  }
+}
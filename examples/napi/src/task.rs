@@ -1,7 +1,7 @@
 use std::thread::sleep;
 
 use napi::bindgen_prelude::*;
-use napi::Task;
+use napi::{Env, JsObject, Ref, Task};
 
 struct DelaySum(u32, u32);
 
@@ -29,3 +29,70 @@ fn without_abort_controller(a: u32, b: u32) -> AsyncTask<DelaySum> {
 fn with_abort_controller(a: u32, b: u32, signal: AbortSignal) -> AsyncTask<DelaySum> {
   AsyncTask::with_signal(DelaySum(a, b), signal)
 }
+
+struct CountPrimes(u32);
+
+#[napi]
+impl Task for CountPrimes {
+  type Output = u32;
+  type JsValue = u32;
+
+  const POOL: TaskPool = TaskPool::Cpu;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(
+      (2..self.0)
+        .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+        .count() as u32,
+    )
+  }
+
+  fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+#[napi]
+fn count_primes_below(limit: u32) -> AsyncTask<CountPrimes> {
+  AsyncTask::new(CountPrimes(limit))
+}
+
+/// Keeps `progress` alive while computing and marks it as done on the JavaScript thread
+struct TrackedSum {
+  a: u32,
+  b: u32,
+  progress: Ref<()>,
+}
+
+#[napi]
+impl Task for TrackedSum {
+  type Output = u32;
+  type JsValue = u32;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self
+      .a
+      .checked_add(self.b)
+      .ok_or_else(|| Error::new(Status::InvalidArg, "Sum overflows u32".to_owned()))
+  }
+
+  fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn finally(&mut self, env: napi::Env) -> Result<()> {
+    let mut progress: JsObject = env.get_reference_value(&self.progress)?;
+    progress.set_named_property("done", env.get_boolean(true)?)?;
+    self.progress.unref(env)?;
+    Ok(())
+  }
+}
+
+#[napi(ts_args_type = "a: number, b: number, progress: { done: boolean }")]
+fn tracked_sum(env: Env, a: u32, b: u32, progress: JsObject) -> Result<AsyncTask<TrackedSum>> {
+  Ok(AsyncTask::new(TrackedSum {
+    a,
+    b,
+    progress: env.create_reference(progress)?,
+  }))
+}
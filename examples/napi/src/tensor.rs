@@ -0,0 +1,12 @@
+use napi::bindgen_prelude::NdArrayView;
+use ndarray::Array2;
+
+#[napi]
+fn transpose_matrix(matrix: Array2<f64>) -> Array2<f64> {
+  matrix.reversed_axes()
+}
+
+#[napi]
+fn scale_in_place(mut tensor: NdArrayView<f32>, factor: f64) {
+  tensor.view_mut().mapv_inplace(|x| x * factor as f32);
+}
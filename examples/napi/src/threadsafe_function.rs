@@ -1,8 +1,12 @@
+use std::sync::mpsc;
 use std::thread;
 
 use napi::{
   bindgen_prelude::*,
-  threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode},
+  threadsafe_function::{
+    Backpressure, ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction,
+    ThreadsafeFunctionCallMode,
+  },
   JsBoolean,
 };
 
@@ -61,3 +65,61 @@ pub fn threadsafe_function_fatal_mode_error(cb: JsFunction) -> Result<()> {
   });
   Ok(())
 }
+
+#[napi]
+pub fn threadsafe_function_queue_full(cb: JsFunction) -> Result<u32> {
+  let tsfn: ThreadsafeFunction<u32, ErrorStrategy::Fatal> =
+    cb.create_threadsafe_function(1, |ctx| ctx.env.create_uint32(ctx.value).map(|v| vec![v]))?;
+  let mut queue_full_count = 0;
+  // The queue can't be drained while the main thread is blocked here
+  for n in 0..3 {
+    if tsfn.call(n, ThreadsafeFunctionCallMode::NonBlocking) == Status::QueueFull {
+      queue_full_count += 1;
+    }
+  }
+  Ok(queue_full_count)
+}
+
+#[napi]
+pub fn tsfn_callee_handled_from_arg(tsfn: ThreadsafeFunction<String>) {
+  thread::spawn(move || {
+    tsfn.call(
+      Ok("callee handled".to_owned()),
+      ThreadsafeFunctionCallMode::NonBlocking,
+    );
+  });
+}
+
+#[napi]
+pub fn tsfn_fatal_from_arg(tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal>) {
+  thread::spawn(move || {
+    tsfn.call("fatal".to_owned(), ThreadsafeFunctionCallMode::NonBlocking);
+  });
+}
+
+#[napi]
+pub fn tsfn_spread_args(cb: JsFunction, count: u32) -> Result<()> {
+  let tsfn: ThreadsafeFunction<u32, ErrorStrategy::Fatal> =
+    cb.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<u32>| {
+      (0..ctx.value)
+        .map(|v| ctx.env.create_uint32(v))
+        .collect::<Result<Vec<_>>>()
+    })?;
+  thread::spawn(move || {
+    tsfn.call(count, ThreadsafeFunctionCallMode::NonBlocking);
+  });
+  Ok(())
+}
+
+#[napi]
+pub fn tsfn_forward_channel(tsfn: ThreadsafeFunction<u32, ErrorStrategy::Fatal>, count: u32) {
+  let (sender, receiver) = mpsc::sync_channel(2);
+  tsfn.forward(receiver, Backpressure::Block);
+  thread::spawn(move || {
+    for n in 0..count {
+      if sender.send(n).is_err() {
+        break;
+      }
+    }
+  });
+}
@@ -1,3 +1,4 @@
+use bytes::{BufMut, Bytes, BytesMut};
 use napi::bindgen_prelude::*;
 
 #[napi]
@@ -12,6 +13,39 @@ fn append_buffer(buf: Buffer) -> Buffer {
   buf.into()
 }
 
+/// Hands out the chunks of a stream from a `BufferPool`
+#[napi]
+pub struct ChunkPool {
+  pool: BufferPool,
+}
+
+#[napi]
+impl ChunkPool {
+  #[napi(constructor)]
+  pub fn new(max_buffers: u32) -> Self {
+    ChunkPool {
+      pool: BufferPool::new(max_buffers as usize, 64 * 1024),
+    }
+  }
+
+  #[napi]
+  pub fn chunk(&self, size: u32, fill: u32) -> PooledBuffer {
+    let mut chunk = self.pool.alloc(size as usize);
+    chunk.fill(fill as u8);
+    chunk
+  }
+
+  #[napi]
+  pub fn array_buffer_chunk(&self, size: u32) -> PooledArrayBuffer {
+    self.pool.alloc(size as usize).into_array_buffer()
+  }
+
+  #[napi(getter)]
+  pub fn idle_count(&self) -> u32 {
+    self.pool.idle_count() as u32
+  }
+}
+
 #[napi]
 fn convert_u32_array(input: Uint32Array) -> Vec<u32> {
   input.to_vec()
@@ -33,3 +67,57 @@ fn mutate_typed_array(mut input: Float32Array) {
 fn deref_uint8_array(a: Uint8Array, b: Uint8ClampedArray) -> u32 {
   (a.len() + b.len()) as u32
 }
+
+#[napi]
+fn create_blob_from_chunks(chunks: Vec<String>, mime_type: String) -> Blob {
+  chunks
+    .into_iter()
+    .map(String::into_bytes)
+    .collect::<Blob>()
+    .with_type(mime_type)
+}
+
+#[napi]
+fn append_to_blob(mut blob: Blob, suffix: String) -> Blob {
+  blob.push(suffix);
+  blob
+}
+
+#[napi]
+fn create_file(name: String, content: Buffer) -> FileBlob {
+  FileBlob::new(
+    name,
+    Blob::from(Vec::<u8>::from(content)).with_type("text/plain"),
+  )
+  .with_last_modified(0.0)
+}
+
+#[napi]
+fn rename_file(file: FileBlob, name: String) -> FileBlob {
+  let last_modified = file.last_modified();
+  let renamed = FileBlob::new(name, file.into_blob());
+  match last_modified {
+    Some(last_modified) => renamed.with_last_modified(last_modified),
+    None => renamed,
+  }
+}
+
+#[napi]
+fn split_bytes(mut bytes: Bytes, at: u32) -> Vec<Bytes> {
+  let head = bytes.split_to((at as usize).min(bytes.len()));
+  vec![head, bytes]
+}
+
+#[napi]
+fn clone_bytes(bytes: Bytes) -> Vec<Bytes> {
+  vec![bytes.clone(), bytes]
+}
+
+#[napi]
+fn repeat_bytes(chunk: Bytes, times: u32) -> BytesMut {
+  let mut out = BytesMut::with_capacity(chunk.len() * times as usize);
+  for _ in 0..times {
+    out.put_slice(&chunk);
+  }
+  out
+}
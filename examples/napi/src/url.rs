@@ -0,0 +1,21 @@
+use napi::bindgen_prelude::*;
+use url::Url;
+
+/// Resolve `path` relative to `base`
+#[napi]
+fn resolve_url(base: UrlOrString, path: String) -> Result<Url> {
+  base
+    .join(&path)
+    .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+}
+
+#[napi]
+fn get_search_params(url: Url) -> SearchParams {
+  SearchParams::from(&url)
+}
+
+#[napi]
+fn set_search_params(mut url: Url, params: SearchParams) -> Url {
+  url.query_pairs_mut().clear().extend_pairs(params.0);
+  url
+}
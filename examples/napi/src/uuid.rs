@@ -0,0 +1,17 @@
+use napi::bindgen_prelude::UuidBytes;
+use uuid::Uuid;
+
+#[napi]
+fn uuid_version(id: Uuid) -> u32 {
+  id.get_version_num() as u32
+}
+
+#[napi]
+fn uuid_to_bytes(id: Uuid) -> UuidBytes {
+  UuidBytes(id)
+}
+
+#[napi]
+fn uuid_from_bytes(bytes: UuidBytes) -> Uuid {
+  bytes.0
+}